@@ -0,0 +1,88 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::pagination_control::pagination_control;
+use crate::admin::structure::{page, split};
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use inventory::pagination::{KeysetPaginationOptions, KeysetPaginationResult};
+use inventory::customer::{CustomerSerial, CustomerStatus};
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Customer",
+    relative_path: "/admin/customer",
+    configurer,
+};
+
+const HEADINGS: [&str; 5] = ["id", "email_address ⏶", "display_name", "status", "created"];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/customer", web::get().to(render))
+    ;
+}
+
+async fn render(
+    query: web::Query<KeysetPaginationOptions<String>>,
+) -> Markup {
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(left(query.into_inner()).await, Markup::default()),
+    )
+}
+
+async fn left(pagination_options: KeysetPaginationOptions<String>) -> Markup {
+    let query_params = match serde_urlencoded::to_string(&pagination_options) {
+        Ok(pagination_options) => pagination_options,
+        Err(error) => return error_markup(error),
+    };
+
+    let (customer_vec, pagination_result) = unwrap_result_else_markup!(
+        wrapped_get::<(Vec<CustomerSerial>, KeysetPaginationResult<String>)>(
+            format!("/customer?{}", query_params).as_str(),
+        ).await
+    );
+
+    html! {
+        h2 { "Customers" }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+        @if customer_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (table(&customer_vec))
+        }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+    }
+}
+
+fn table(elements: &Vec<CustomerSerial>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.id) }
+                        td { (element.email_address) }
+                        td { (element.display_name) }
+                        td { (match CustomerStatus::try_from_repr(element.status) {
+                            Ok(variant) => format!("{:?}", variant),
+                            Err(error) => Markup::into_string(error_markup(error)),
+                        }) }
+                        td { (element.created) }
+                    }
+                }
+            }
+        }
+    }
+}
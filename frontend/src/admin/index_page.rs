@@ -1,5 +1,8 @@
 use crate::admin::structure::page;
-use crate::admin::{category_page, ebay_page, inventory_location_page, item_page, listing_page, marketplace_page, product_page};
+use crate::admin::{
+    category_page, customer_page, ebay_page, inventory_location_page, inventory_report_page, item_audit_page, item_page,
+    item_search_page, listing_index_page, listing_page, marketplace_page, product_page, profit_page, purchase_page,
+};
 use actix_web::web;
 use actix_web::web::ServiceConfig;
 use maud::{html, Markup};
@@ -12,10 +15,17 @@ pub async fn render() -> Markup {
 			div {
 				ol {
 					li { (product_page::PAGE.create_anchor()) }
+					li { (item_search_page::PAGE.create_anchor()) }
 					li { (category_page::PAGE.create_anchor()) }
 					li { (inventory_location_page::PAGE.create_anchor()) }
                     li { (marketplace_page::PAGE.create_anchor()) }
                     li { (ebay_page::PAGE.create_anchor()) }
+                    li { (listing_index_page::PAGE.create_anchor()) }
+                    li { (purchase_page::PAGE.create_anchor()) }
+                    li { (customer_page::PAGE.create_anchor()) }
+                    li { (item_audit_page::PAGE.create_anchor()) }
+                    li { (profit_page::PAGE.create_anchor()) }
+                    li { (inventory_report_page::PAGE.create_anchor()) }
 				}
 			}
 		},
@@ -34,6 +44,13 @@ pub fn configurer(config: &mut ServiceConfig) -> () {
             .configure(listing_page::PAGE.configurer)
             .configure(marketplace_page::PAGE.configurer)
             .configure(ebay_page::PAGE.configurer)
+            .configure(item_search_page::PAGE.configurer)
+            .configure(listing_index_page::PAGE.configurer)
+            .configure(purchase_page::PAGE.configurer)
+            .configure(customer_page::PAGE.configurer)
+            .configure(item_audit_page::PAGE.configurer)
+            .configure(profit_page::PAGE.configurer)
+            .configure(inventory_report_page::PAGE.configurer)
         )
     ;
 }
@@ -0,0 +1,262 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::{page, split};
+use crate::registry::REGISTRY;
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use inventory::inventory_report::{
+    AgingBucket, CapitalSummary, InventoryReportFormat, InventoryReportGrouping, InventoryReportParameters,
+    SellThroughSummary, StaleListing, DEFAULT_STALE_LISTING_DAYS,
+};
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Inventory report",
+    relative_path: "/admin/inventory_report",
+    configurer,
+};
+
+/// U+00A2 is the "cent" sign.
+const AGING_HEADINGS: [&str; 4] = ["days in inventory", "unsold items", "unsold capital (\u{00A2})", "sold items"];
+const SELL_THROUGH_HEADINGS: [&str; 5] = ["group", "items", "sold items", "sell-through (%)", "average days to sell"];
+const CAPITAL_HEADINGS: [&str; 7] = [
+    "group", "items", "acquisition (\u{00A2})", "refurbishment (\u{00A2})", "capital (\u{00A2})", "price (\u{00A2})",
+    "unknown acquisition price",
+];
+const STALE_LISTING_HEADINGS: [&str; 8] = [
+    "code", "product", "marketplaces", "listed since ⏶", "listed days", "days in inventory", "price (\u{00A2})",
+    "cost basis (\u{00A2})",
+];
+/// The query parameter value and display name of each grouping
+const GROUPINGS: [(InventoryReportGrouping, &str, &str); 3] = [
+    (InventoryReportGrouping::Category, "category", "Category"),
+    (InventoryReportGrouping::Marketplace, "marketplace", "Marketplace"),
+    (InventoryReportGrouping::InventoryLocation, "inventory_location", "Inventory location"),
+];
+/// The report path and display name of each report
+const REPORTS: [(&str, &str); 4] = [
+    ("/inventory_report/aging", "Aging"),
+    ("/inventory_report/sell_through", "Sell-through"),
+    ("/inventory_report/capital", "Capital"),
+    ("/inventory_report/stale_listing", "Stale listings"),
+];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/inventory_report", web::get().to(render))
+    ;
+}
+
+async fn render(parameters: web::Query<InventoryReportParameters>) -> Markup {
+    let mut parameters: InventoryReportParameters = parameters.into_inner();
+    parameters.format = InventoryReportFormat::Json;
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(left(&parameters).await, right(&parameters)),
+    )
+}
+
+async fn left(parameters: &InventoryReportParameters) -> Markup {
+    let query_params = match serde_urlencoded::to_string(parameters) {
+        Ok(query_params) => query_params,
+        Err(error) => return error_markup(error),
+    };
+
+    let aging_bucket_vec: Vec<AgingBucket> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<AgingBucket>>(format!("/inventory_report/aging?{}", query_params).as_str()).await
+    );
+    let sell_through_summary_vec: Vec<SellThroughSummary> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<SellThroughSummary>>(format!("/inventory_report/sell_through?{}", query_params).as_str()).await
+    );
+    let capital_summary_vec: Vec<CapitalSummary> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<CapitalSummary>>(format!("/inventory_report/capital?{}", query_params).as_str()).await
+    );
+    let stale_listing_vec: Vec<StaleListing> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<StaleListing>>(format!("/inventory_report/stale_listing?{}", query_params).as_str()).await
+    );
+
+    html! {
+        h2 { "Aging" }
+        (aging_table(&aging_bucket_vec))
+        h2 { "Sell-through" }
+        @if sell_through_summary_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (sell_through_table(&sell_through_summary_vec))
+        }
+        h2 { "Capital in unsold stock" }
+        @if capital_summary_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (capital_table(&capital_summary_vec))
+        }
+        h2 { (format!("Listed for {} days or more", parameters.listed_days.unwrap_or(DEFAULT_STALE_LISTING_DAYS))) }
+        @if stale_listing_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (stale_listing_table(&stale_listing_vec))
+        }
+    }
+}
+
+fn right(parameters: &InventoryReportParameters) -> Markup {
+    let csv_parameters = InventoryReportParameters {
+        format: InventoryReportFormat::Csv,
+        ..parameters.clone()
+    };
+    let csv_query_params = match serde_urlencoded::to_string(&csv_parameters) {
+        Ok(csv_query_params) => csv_query_params,
+        Err(error) => return error_markup(error),
+    };
+
+    html! {
+        h2 { "Filters" }
+        // Submitted natively so that the filters are reflected in this page's query string.
+        // Empty fields are disabled so that they are omitted rather than parsed as empty values.
+        form
+            action=(PAGE.relative_path)
+            method="get"
+            autocomplete="off"
+            onsubmit="for (const element of this.elements) { if (element.value === '') element.disabled = true; }"
+        {
+            label {
+                "Group by"
+                select name="grouping" {
+                    @for (grouping, value, display_name) in &GROUPINGS {
+                        option value=(value) selected[parameters.grouping == *grouping] { (display_name) }
+                    }
+                }
+            }
+            label {
+                "Listed for at least (days)"
+                input type="number" name="listed_days" min="0" placeholder=(DEFAULT_STALE_LISTING_DAYS) value=[parameters.listed_days];
+            }
+            input type="submit" value="Report";
+        }
+        hr {}
+        h2 { "Download" }
+        p { "Downloads each report with the current filters as CSV." }
+        @for (path, display_name) in REPORTS {
+            a href=(format!("{}{}?{}", REGISTRY.inventory_external_path, path, csv_query_params)) {
+                button { (display_name) }
+            }
+        }
+    }
+}
+
+fn aging_table(elements: &Vec<AgingBucket>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in AGING_HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td {
+                            @match element.max_days {
+                                Some(max_days) => (format!("{}–{}", element.min_days, max_days)),
+                                None => (format!("{}+", element.min_days)),
+                            }
+                        }
+                        td { (element.unsold_item_count) }
+                        td { (element.unsold_capital_cents) }
+                        td { (element.sold_item_count) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn sell_through_table(elements: &Vec<SellThroughSummary>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in SELL_THROUGH_HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.display_name) }
+                        td { (element.item_count) }
+                        td { (element.sold_item_count) }
+                        td { (format!("{:.1}", element.sell_through_percent)) }
+                        td {
+                            @if let Some(average_days_to_sell) = element.average_days_to_sell {
+                                (average_days_to_sell)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn capital_table(elements: &Vec<CapitalSummary>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in CAPITAL_HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.display_name) }
+                        td { (element.item_count) }
+                        td { (element.acquisition_cost_cents) }
+                        td { (element.refurbishment_cost_cents) }
+                        td { (element.capital_cents) }
+                        td { (element.price_cents) }
+                        td { (element.unknown_acquisition_price_count) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn stale_listing_table(elements: &Vec<StaleListing>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in STALE_LISTING_HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.item_code) }
+                        td { (element.product_display_name) }
+                        td { (element.marketplaces) }
+                        td { (element.listed_since) }
+                        td { (element.listed_days) }
+                        td { (element.days_in_inventory) }
+                        td { (element.price_cents) }
+                        td { (element.cost_basis_cents) }
+                    }
+                }
+            }
+        }
+    }
+}
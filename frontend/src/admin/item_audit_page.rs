@@ -0,0 +1,96 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::pagination_control::pagination_control;
+use crate::admin::structure::{page, split};
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use chrono::{DateTime, Utc};
+use inventory::pagination::{KeysetPaginationOptions, KeysetPaginationResult};
+use inventory::item::ItemStatus;
+use inventory::item_audit::ItemAuditSerial;
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Item audit",
+    relative_path: "/admin/item_audit",
+    configurer,
+};
+
+const HEADINGS: [&str; 7] = ["item_id", "status_before", "status_after", "deleted", "initiated_by_admin", "note", "created ⏷"];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/item_audit", web::get().to(render))
+    ;
+}
+
+async fn render(
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> Markup {
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(left(query.into_inner()).await, Markup::default()),
+    )
+}
+
+async fn left(pagination_options: KeysetPaginationOptions<DateTime<Utc>>) -> Markup {
+    let query_params = match serde_urlencoded::to_string(&pagination_options) {
+        Ok(pagination_options) => pagination_options,
+        Err(error) => return error_markup(error),
+    };
+
+    let (item_audit_vec, pagination_result) = unwrap_result_else_markup!(
+        wrapped_get::<(Vec<ItemAuditSerial>, KeysetPaginationResult<DateTime<Utc>>)>(
+            format!("/item_audit?{}", query_params).as_str(),
+        ).await
+    );
+
+    html! {
+        h2 { "Item audits" }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+        @if item_audit_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (table(&item_audit_vec))
+        }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+    }
+}
+
+fn table(elements: &Vec<ItemAuditSerial>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.item_id) }
+                        td { (status_markup(element.status_before)) }
+                        td { (status_markup(element.status_after)) }
+                        td { (element.deleted) }
+                        td { (element.initiated_by_admin) }
+                        td { (format!("{:?}", element.note)) }
+                        td { (element.created) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn status_markup(status: u8) -> String {
+    match ItemStatus::try_from_repr(status) {
+        Ok(variant) => format!("{}", variant),
+        Err(error) => Markup::into_string(error_markup(error)),
+    }
+}
@@ -8,7 +8,9 @@ use crate::unwrap_result_else_markup;
 use actix_web::web;
 use actix_web::web::ServiceConfig;
 use inventory::inventory_location::InventoryLocationSerial;
-use inventory::item::{ItemCondition, ItemSerial, ItemStatus};
+use inventory::item::{ItemCondition, ItemCostBasis, ItemSerial, ItemStatus};
+use inventory::item_grading::{GradingCompany, ItemGradingSerial};
+use inventory::item_refurbishment::{ItemRefurbishmentKind, ItemRefurbishmentSerial};
 use inventory::marketplace::MarketplaceSerial;
 use inventory::product::ProductSerial;
 use maud::{html, Markup};
@@ -20,10 +22,12 @@ pub const PAGE: Page = Page {
     relative_path: "/admin/product/{product_id}/item",
     configurer,
 };
-pub const ITEM_FIELDS: [&str; 13] = [
+pub const ITEM_FIELDS: [&str; 18] = [
     "id",
+    "code",
     "product_id",
     "inventory_location_id",
+    "storage_slot_id",
     "condition",
     "status",
     "price_cents",
@@ -32,15 +36,21 @@ pub const ITEM_FIELDS: [&str; 13] = [
     "acquisition_datetime",
     "acquisition_price_cents",
     "acquisition_location",
+    "consignor_id",
+    "commission_basis_points",
+    "commission_minimum_cents",
     "created",
     "updated",
 ];
 
 /// U+00A2 is the "cent" sign.
-const HEADINGS: [&str; 6] = ["id", "location", "condition", "status", "price (\u{00A2})", "actions"];
+const HEADINGS: [&str; 9] = ["id", "code", "location", "condition", "status", "grade", "price (\u{00A2})", "cost basis (\u{00A2})", "actions"];
+const REFURBISHMENT_HEADINGS: [&str; 7] = ["item", "date", "kind", "description", "cost (\u{00A2})", "disclosed", "photos"];
 const ITEM_DETAILS_CONTAINER_ID: &str = "item_details_container";
 const ITEM_DETAIL_ID_PREFIX: &str = "item_detail_";
 const CREATE_LISTING_FORM_CONTAINER_ID: &str = "create_listing_form_container";
+const ITEM_GRADING_FORM_CONTAINER_ID: &str = "item_grading_form_container";
+const ITEM_REFURBISHMENT_FORM_CONTAINER_ID: &str = "item_refurbishment_form_container";
 
 fn configurer(config: &mut ServiceConfig) {
     config
@@ -74,13 +84,28 @@ async fn left(product_id: &str) -> Markup {
     let item_vec: Vec<ItemSerial> = unwrap_result_else_markup!(
         wrapped_get::<Vec<ItemSerial>>(&format!("/product/{}/item", product_id)).await
     );
+    let item_grading_vec: Vec<ItemGradingSerial> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<ItemGradingSerial>>(&format!("/item_grading?product_id={}", product_id)).await
+    );
+    let item_cost_basis_vec: Vec<ItemCostBasis> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<ItemCostBasis>>(&format!("/product/{}/item_cost_basis", product_id)).await
+    );
+    let item_refurbishment_vec: Vec<ItemRefurbishmentSerial> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<ItemRefurbishmentSerial>>(&format!("/item_refurbishment?product_id={}", product_id)).await
+    );
 
     html!(
         h2 { (format!("Items for product \"{}\"", product.display_name)) }
         @if item_vec.is_empty() {
             p { "None" }
         } @else {
-            (table(&item_vec).await)
+            (table(&item_vec, &item_grading_vec, &item_cost_basis_vec).await)
+            h3 { "Refurbishment log" }
+            @if item_refurbishment_vec.is_empty() {
+                p { "None" }
+            } @else {
+                (refurbishment_table(&item_vec, &item_refurbishment_vec))
+            }
         }
     )
 }
@@ -89,10 +114,16 @@ async fn right() -> Markup {
     html! {
         (item_details())
         (create_listing_form().await)
+        (item_grading_form())
+        (item_refurbishment_form())
     }
 }
 
-async fn table(elements: &Vec<ItemSerial>) -> Markup {
+async fn table(
+    elements: &Vec<ItemSerial>,
+    item_grading_vec: &[ItemGradingSerial],
+    item_cost_basis_vec: &[ItemCostBasis],
+) -> Markup {
     let inventory_location_vec: Vec<InventoryLocationSerial> = unwrap_result_else_markup!(
         wrapped_get::<Vec<InventoryLocationSerial>>("/inventory_location").await
     );
@@ -108,6 +139,7 @@ async fn table(elements: &Vec<ItemSerial>) -> Markup {
                 @for element in elements {
                     tr {
                         td { (element.id) }
+                        td { (element.code) }
                         td { (inventory_location_markup(&inventory_location_vec, &element)) }
                         td { (match ItemCondition::try_from_repr(element.condition) {
                             Ok(variant) => format!("{}", variant),
@@ -117,7 +149,15 @@ async fn table(elements: &Vec<ItemSerial>) -> Markup {
                             Ok(variant) => format!("{}", variant),
                             Err(error) => Markup::into_string(error_markup(error)),
                         }) }
+                        td { (item_grading_vec.iter()
+                            .find(|item_grading| item_grading.item_id == element.id)
+                            .map(|item_grading| item_grading.summary.as_str())
+                            .unwrap_or("")) }
                         td { (element.price_cents) }
+                        td { (item_cost_basis_vec.iter()
+                            .find(|item_cost_basis| item_cost_basis.item_id == element.id)
+                            .map(|item_cost_basis| item_cost_basis.cost_basis_cents.to_string())
+                            .unwrap_or_default()) }
                         td {
                             a
                                 href=(listing_page::PAGE.relative_path
@@ -128,6 +168,8 @@ async fn table(elements: &Vec<ItemSerial>) -> Markup {
                             button onclick=(activate_item_details_script(element)) { "Details" }
                             x-item-images-button text="Images" item_id=(element.id) {}
                             button onclick=(activate_create_listing_script(element)) { "Create listing" }
+                            button onclick=(activate_item_grading_script(element)) { "Grade" }
+                            button onclick=(activate_item_refurbishment_script(element)) { "Log work" }
                         }
                     }
                 }
@@ -188,6 +230,111 @@ async fn create_listing_form() -> Markup {
     }
 }
 
+/// Replaces the item's grading. Subgrades are only set through the API and are cleared by this form.
+fn item_grading_form() -> Markup {
+    html! {
+        div #(ITEM_GRADING_FORM_CONTAINER_ID) style="display: none;" {
+            hr {}
+            (form::form(Some("Set grading"), "/item_grading", Method::PUT, html! {
+                label {
+                    "Item ID"
+                    input type="text" readonly[true] name="item_id";
+                }
+                label {
+                    "Grading company"
+                    select name="company" {
+                        (form::enum_options::<GradingCompany>())
+                    }
+                }
+                label {
+                    "Grade"
+                    input type="number" step="0.1" min="0" required[true] name="grade";
+                }
+                label {
+                    "Certificate number"
+                    input type="text" required[true] name="certificate_number";
+                }
+                label {
+                    "Grade date"
+                    input type="date" required[true] name="grade_date";
+                }
+                input type="submit";
+            }))
+            button onclick=(reactivity::hide_element_handler(ITEM_GRADING_FORM_CONTAINER_ID)) { "Close" }
+        }
+    }
+}
+
+fn refurbishment_table(item_vec: &[ItemSerial], item_refurbishment_vec: &[ItemRefurbishmentSerial]) -> Markup {
+    html! {
+        table {
+            thead {
+                @for heading in REFURBISHMENT_HEADINGS {
+                    th { (heading) }
+                }
+            }
+            tbody {
+                @for element in item_refurbishment_vec {
+                    tr {
+                        td { (item_vec.iter()
+                            .find(|item| item.id == element.item_id)
+                            .map(|item| item.code.as_str())
+                            .unwrap_or("")) }
+                        td { (element.performed_date) }
+                        td { (match ItemRefurbishmentKind::try_from_repr(element.kind) {
+                            Ok(variant) => format!("{}", variant),
+                            Err(error) => Markup::into_string(error_markup(error)),
+                        }) }
+                        td { (element.description) }
+                        td { (element.cost_cents) }
+                        td { (if element.disclose { "yes" } else { "no" }) }
+                        td { (element.item_image_ids.len()) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Logs new work on an item. Photos and corrections to existing entries are only made through the API.
+fn item_refurbishment_form() -> Markup {
+    html! {
+        div #(ITEM_REFURBISHMENT_FORM_CONTAINER_ID) style="display: none;" {
+            hr {}
+            (form::form(Some("Log refurbishment work"), "/item_refurbishment", Method::POST, html! {
+                label {
+                    "Item ID"
+                    input type="text" readonly[true] name="item_id";
+                }
+                label {
+                    "Kind"
+                    select name="kind" {
+                        (form::enum_options::<ItemRefurbishmentKind>())
+                    }
+                }
+                label {
+                    "Date performed"
+                    input type="date" required[true] name="performed_date";
+                }
+                label {
+                    "Description"
+                    input type="text" required[true] name="description";
+                }
+                label {
+                    "Cost (\u{00A2})"
+                    input type="number" min="0" required[true] name="cost_cents" value="0";
+                }
+                label {
+                    "Disclose in listings"
+                    input type="checkbox" name="disclose";
+                }
+                input type="submit";
+            }))
+            button onclick=(reactivity::hide_element_handler(ITEM_REFURBISHMENT_FORM_CONTAINER_ID)) { "Close" }
+        }
+    }
+}
+
 fn inventory_location_markup(inventory_location_vec: &Vec<InventoryLocationSerial>, item: &ItemSerial) -> Markup {
     let inventory_location: &InventoryLocationSerial = match inventory_location_vec
         .iter()
@@ -214,3 +361,21 @@ fn activate_create_listing_script(item: &ItemSerial) -> String {
     script.push_str(&reactivity::update_form_from_serialize("/listing", &json_map));
     script
 }
+
+fn activate_item_grading_script(item: &ItemSerial) -> String {
+    let mut json_map: Map<String, Value> = Map::with_capacity(1);
+    json_map.insert(String::from("item_id"), json!(item.id));
+
+    let mut script = reactivity::activate_element_handler(ITEM_GRADING_FORM_CONTAINER_ID);
+    script.push_str(&reactivity::update_form_from_serialize("/item_grading", &json_map));
+    script
+}
+
+fn activate_item_refurbishment_script(item: &ItemSerial) -> String {
+    let mut json_map: Map<String, Value> = Map::with_capacity(1);
+    json_map.insert(String::from("item_id"), json!(item.id));
+
+    let mut script = reactivity::activate_element_handler(ITEM_REFURBISHMENT_FORM_CONTAINER_ID);
+    script.push_str(&reactivity::update_form_from_serialize("/item_refurbishment", &json_map));
+    script
+}
@@ -0,0 +1,199 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::form;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::pagination_control::pagination_control_with_query;
+use crate::admin::structure::{page, split};
+use crate::admin::item_page;
+use crate::registry::REGISTRY;
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use chrono::{DateTime, Utc};
+use inventory::inventory_location::InventoryLocationSerial;
+use inventory::item::{ItemCondition, ItemSearchParameters, ItemSerial, ItemSortKey, ItemStatus};
+use inventory::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Item search",
+    relative_path: "/admin/item",
+    configurer,
+};
+
+/// U+00A2 is the "cent" sign.
+const HEADINGS: [&str; 8] = ["id", "code", "location", "condition", "status", "price (\u{00A2})", "created", "actions"];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/item", web::get().to(render))
+    ;
+}
+
+async fn render(
+    search_parameters: web::Query<ItemSearchParameters>,
+    pagination_options: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> Markup {
+    let mut search_parameters: ItemSearchParameters = search_parameters.into_inner();
+    // The PAGE key type is fixed by the sort key, so this worklist only orders by creation time.
+    search_parameters.sort_key = ItemSortKey::Created;
+
+    let inventory_location_vec: Vec<InventoryLocationSerial> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<InventoryLocationSerial>>("/inventory_location").await
+    );
+
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(
+            left(&search_parameters, pagination_options.into_inner(), &inventory_location_vec).await,
+            right(&search_parameters, &inventory_location_vec),
+        ),
+    )
+}
+
+async fn left(
+    search_parameters: &ItemSearchParameters,
+    pagination_options: KeysetPaginationOptions<DateTime<Utc>>,
+    inventory_location_vec: &Vec<InventoryLocationSerial>,
+) -> Markup {
+    let search_params = match serde_urlencoded::to_string(search_parameters) {
+        Ok(search_params) => search_params,
+        Err(error) => return error_markup(error),
+    };
+    let pagination_params = match serde_urlencoded::to_string(&pagination_options) {
+        Ok(pagination_params) => pagination_params,
+        Err(error) => return error_markup(error),
+    };
+
+    let (item_vec, pagination_result) = unwrap_result_else_markup!(
+        wrapped_get::<(Vec<ItemSerial>, KeysetPaginationResult<DateTime<Utc>>)>(
+            format!("/item?{}&{}", search_params, pagination_params).as_str(),
+        ).await
+    );
+
+    html! {
+        h2 { "Items" }
+        (pagination_control_with_query(PAGE.relative_path, &search_params, &pagination_options, &pagination_result))
+        @if item_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (table(&item_vec, inventory_location_vec))
+        }
+        (pagination_control_with_query(PAGE.relative_path, &search_params, &pagination_options, &pagination_result))
+    }
+}
+
+fn right(
+    search_parameters: &ItemSearchParameters,
+    inventory_location_vec: &Vec<InventoryLocationSerial>,
+) -> Markup {
+    html! {
+        h2 { "Filters" }
+        // Submitted natively so that the filters are reflected in this page's query string.
+        // Empty fields are disabled so that they are omitted rather than parsed as empty values.
+        form
+            action=(PAGE.relative_path)
+            method="get"
+            autocomplete="off"
+            onsubmit="for (const element of this.elements) { if (element.value === '') element.disabled = true; }"
+        {
+            label {
+                "Status"
+                select name="status" {
+                    option value="" { "_any" }
+                    (form::enum_options::<ItemStatus>())
+                }
+            }
+            label {
+                "Condition"
+                select name="condition" {
+                    option value="" { "_any" }
+                    (form::enum_options::<ItemCondition>())
+                }
+            }
+            label {
+                "Inventory location"
+                select name="inventory_location_id" {
+                    option value="" { "_any" }
+                    @for inventory_location in inventory_location_vec {
+                        option
+                            value=(inventory_location.id)
+                            selected[search_parameters.inventory_location_id == Some(inventory_location.id)]
+                            { (inventory_location.display_name) }
+                    }
+                }
+            }
+            label {
+                "Order"
+                select name="sort_direction" {
+                    option value="asc" selected[search_parameters.sort_direction == Direction::Ascending] { "Oldest first" }
+                    option value="desc" selected[search_parameters.sort_direction == Direction::Descending] { "Newest first" }
+                }
+            }
+            input type="submit" value="Search";
+        }
+        (export_links(search_parameters))
+    }
+}
+
+fn export_links(search_parameters: &ItemSearchParameters) -> Markup {
+    let search_params = match serde_urlencoded::to_string(search_parameters) {
+        Ok(search_params) => search_params,
+        Err(error) => return error_markup(error),
+    };
+
+    html! {
+        hr {}
+        h2 { "Export" }
+        p { "Exports every item matching the current filters." }
+        a href=(format!("{}/export/item?format=csv&{}", REGISTRY.inventory_external_path, search_params)) {
+            button { "CSV" }
+        }
+        a href=(format!("{}/export/item?format=jsonl&{}", REGISTRY.inventory_external_path, search_params)) {
+            button { "JSONL" }
+        }
+    }
+}
+
+fn table(elements: &Vec<ItemSerial>, inventory_location_vec: &Vec<InventoryLocationSerial>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.id) }
+                        td { (element.code) }
+                        td { (inventory_location_vec.iter()
+                            .find(|inventory_location| inventory_location.id == element.inventory_location_id)
+                            .map(|inventory_location| inventory_location.display_name.clone())
+                            .unwrap_or(element.inventory_location_id.to_string())) }
+                        td { (match ItemCondition::try_from_repr(element.condition) {
+                            Ok(variant) => format!("{}", variant),
+                            Err(error) => Markup::into_string(error_markup(error)),
+                        }) }
+                        td { (match ItemStatus::try_from_repr(element.status) {
+                            Ok(variant) => format!("{}", variant),
+                            Err(error) => Markup::into_string(error_markup(error)),
+                        }) }
+                        td { (element.price_cents) }
+                        td { (element.created) }
+                        td {
+                            a
+                                href=(item_page::PAGE.relative_path.replace("{product_id}", &element.product_id.to_string()))
+                                { button { "View product items" } }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
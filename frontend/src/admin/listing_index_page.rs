@@ -0,0 +1,90 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::pagination_control::pagination_control;
+use crate::admin::structure::{page, split};
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use chrono::{DateTime, Utc};
+use inventory::pagination::{KeysetPaginationOptions, KeysetPaginationResult};
+use inventory::listing::{ListingSerial, ListingStatus};
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Listing",
+    relative_path: "/admin/listing",
+    configurer,
+};
+
+const HEADINGS: [&str; 6] = ["id", "item_id", "bundle_id", "marketplace_id", "status", "updated ⏷"];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/listing", web::get().to(render))
+    ;
+}
+
+async fn render(
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> Markup {
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(left(query.into_inner()).await, Markup::default()),
+    )
+}
+
+async fn left(pagination_options: KeysetPaginationOptions<DateTime<Utc>>) -> Markup {
+    let query_params = match serde_urlencoded::to_string(&pagination_options) {
+        Ok(pagination_options) => pagination_options,
+        Err(error) => return error_markup(error),
+    };
+
+    let (listing_vec, pagination_result) = unwrap_result_else_markup!(
+        wrapped_get::<(Vec<ListingSerial>, KeysetPaginationResult<DateTime<Utc>>)>(
+            format!("/listing?{}", query_params).as_str(),
+        ).await
+    );
+
+    html! {
+        h2 { "Listings" }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+        @if listing_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (table(&listing_vec))
+        }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+    }
+}
+
+fn table(elements: &Vec<ListingSerial>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.id) }
+                        td { (element.item_id.map(|item_id| item_id.to_string()).unwrap_or_default()) }
+                        td { (element.bundle_id.map(|bundle_id| bundle_id.to_string()).unwrap_or_default()) }
+                        td { (element.marketplace_id) }
+                        td { (match ListingStatus::try_from_repr(element.status) {
+                            Ok(variant) => format!("{}", variant),
+                            Err(error) => Markup::into_string(error_markup(error)),
+                        }) }
+                        td { (element.updated) }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -11,3 +11,10 @@ mod inventory_location_page;
 mod listing_page;
 mod marketplace_page;
 mod ebay_page;
+mod item_search_page;
+mod listing_index_page;
+mod purchase_page;
+mod customer_page;
+mod item_audit_page;
+mod profit_page;
+mod inventory_report_page;
@@ -13,7 +13,7 @@ use admin::structure::pagination_control::pagination_control;
 use inventory::category::CategorySerial;
 use inventory::inventory_location::InventoryLocationSerial;
 use inventory::item::ItemCondition;
-use inventory::pagination::{pagination_guard, KeysetPaginationOptionsForString, KeysetPaginationResultForString};
+use inventory::pagination::{pagination_guard, KeysetPaginationOptions, KeysetPaginationResult};
 use inventory::product::ProductSerial;
 use maud::{html, Markup};
 use reqwest::Method;
@@ -45,12 +45,12 @@ async fn handle_unpaginated() -> Markup {
 }
 
 async fn handle_paginated(
-    query: web::Query<KeysetPaginationOptionsForString>,
+    query: web::Query<KeysetPaginationOptions<String>>,
 ) -> Markup {
     render(Some(query.into_inner())).await
 }
 
-async fn render(pagination_options: Option<KeysetPaginationOptionsForString>) -> Markup {
+async fn render(pagination_options: Option<KeysetPaginationOptions<String>>) -> Markup {
     page::page(
         &vec!(BreadcrumbItem::from(PAGE)),
         html! {
@@ -60,7 +60,7 @@ async fn render(pagination_options: Option<KeysetPaginationOptionsForString>) ->
     )
 }
 
-async fn left(pagination_options: Option<KeysetPaginationOptionsForString>) -> Markup {
+async fn left(pagination_options: Option<KeysetPaginationOptions<String>>) -> Markup {
     let pagination_options = pagination_options.unwrap_or_default();
     let query_params = match serde_urlencoded::to_string(&pagination_options) {
         Ok(pagination_options) => pagination_options,
@@ -68,7 +68,7 @@ async fn left(pagination_options: Option<KeysetPaginationOptionsForString>) -> M
     };
 
     let (product_vec, pagination_result) = unwrap_result_else_markup!(
-        wrapped_get::<(Vec<ProductSerial>, KeysetPaginationResultForString)>(
+        wrapped_get::<(Vec<ProductSerial>, KeysetPaginationResult<String>)>(
             format!("/product?{}", query_params).as_str(),
         ).await
     );
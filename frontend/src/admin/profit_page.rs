@@ -0,0 +1,189 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::{page, split};
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use inventory::profit::{ItemProfit, ProfitGrouping, ProfitReport, ProfitReportParameters, ProfitSummary};
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Profit",
+    relative_path: "/admin/profit",
+    configurer,
+};
+
+/// U+00A2 is the "cent" sign.
+const SUMMARY_HEADINGS: [&str; 10] = [
+    "group", "items", "units", "revenue (\u{00A2})", "marketplace fees (\u{00A2})", "shipping (\u{00A2})",
+    "acquisition (\u{00A2})", "refurbishment (\u{00A2})", "profit (\u{00A2})", "unknown acquisition price",
+];
+const ITEM_HEADINGS: [&str; 10] = [
+    "code", "product", "marketplace", "sold ⏷", "units", "revenue (\u{00A2})", "marketplace fees (\u{00A2})",
+    "shipping (\u{00A2})", "cost basis (\u{00A2})", "profit (\u{00A2})",
+];
+/// The query parameter value and display name of each grouping
+const GROUPINGS: [(ProfitGrouping, &str, &str); 8] = [
+    (ProfitGrouping::Product, "product", "Product"),
+    (ProfitGrouping::Category, "category", "Category"),
+    (ProfitGrouping::Label, "label", "Label"),
+    (ProfitGrouping::Marketplace, "marketplace", "Marketplace"),
+    (ProfitGrouping::Day, "day", "Day"),
+    (ProfitGrouping::Week, "week", "Week"),
+    (ProfitGrouping::Month, "month", "Month"),
+    (ProfitGrouping::Year, "year", "Year"),
+];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/profit", web::get().to(render))
+    ;
+}
+
+async fn render(parameters: web::Query<ProfitReportParameters>) -> Markup {
+    let parameters: ProfitReportParameters = parameters.into_inner();
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(left(&parameters).await, right(&parameters)),
+    )
+}
+
+async fn left(parameters: &ProfitReportParameters) -> Markup {
+    let query_params = match serde_urlencoded::to_string(parameters) {
+        Ok(query_params) => query_params,
+        Err(error) => return error_markup(error),
+    };
+
+    let profit_report: ProfitReport = unwrap_result_else_markup!(
+        wrapped_get::<ProfitReport>(format!("/profit/report?{}", query_params).as_str()).await
+    );
+    let item_profit_vec: Vec<ItemProfit> = unwrap_result_else_markup!(
+        wrapped_get::<Vec<ItemProfit>>(format!("/profit/item?{}", query_params).as_str()).await
+    );
+
+    html! {
+        h2 { "Profit report" }
+        @if profit_report.summaries.is_empty() {
+            p { "None" }
+        } @else {
+            (summary_table(&profit_report))
+        }
+        h2 { "Sold items" }
+        @if item_profit_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (item_table(&item_profit_vec))
+        }
+    }
+}
+
+fn right(parameters: &ProfitReportParameters) -> Markup {
+    html! {
+        h2 { "Filters" }
+        // Submitted natively so that the filters are reflected in this page's query string.
+        // Empty fields are disabled so that they are omitted rather than parsed as empty values.
+        form
+            action=(PAGE.relative_path)
+            method="get"
+            autocomplete="off"
+            onsubmit="for (const element of this.elements) { if (element.value === '') element.disabled = true; }"
+        {
+            label {
+                "Group by"
+                select name="grouping" {
+                    @for (grouping, value, display_name) in &GROUPINGS {
+                        option value=(value) selected[parameters.grouping == *grouping] { (display_name) }
+                    }
+                }
+            }
+            label {
+                "Sold on or after"
+                input type="date" name="sold_after" value=[parameters.sold_after];
+            }
+            label {
+                "Sold on or before"
+                input type="date" name="sold_before" value=[parameters.sold_before];
+            }
+            input type="submit" value="Report";
+        }
+    }
+}
+
+fn summary_table(profit_report: &ProfitReport) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in SUMMARY_HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for summary in &profit_report.summaries {
+                    (summary_row(summary))
+                }
+            }
+            tfoot {
+                (summary_row(&profit_report.total))
+            }
+        }
+    }
+}
+
+fn summary_row(summary: &ProfitSummary) -> Markup {
+    html! {
+        tr {
+            td { (summary.display_name) }
+            td { (summary.item_count) }
+            td { (summary.unit_count) }
+            td { (summary.revenue_cents) }
+            td { (summary.marketplace_fee_cents) }
+            td { (summary.shipping_cost_cents) }
+            td { (summary.acquisition_cost_cents) }
+            td { (summary.refurbishment_cost_cents) }
+            td { (summary.profit_cents) }
+            td { (summary.unknown_acquisition_price_count) }
+        }
+    }
+}
+
+fn item_table(elements: &Vec<ItemProfit>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in ITEM_HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    @if let Some(sale) = &element.sale {
+                        tr {
+                            td { (element.item_code) }
+                            td { (element.product_display_name) }
+                            td { (sale.marketplace_display_name) }
+                            td { (sale.sold) }
+                            td { (sale.quantity) }
+                            td { (sale.revenue_cents) }
+                            td { (sale.marketplace_fee_cents) }
+                            td { (sale.shipping_cost_cents) }
+                            td {
+                                (element.cost_basis_cents)
+                                @if element.acquisition_price_cents.is_none() {
+                                    " (unknown acquisition price)"
+                                }
+                            }
+                            td { (element.profit_cents.unwrap_or_default()) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
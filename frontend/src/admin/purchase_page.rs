@@ -0,0 +1,91 @@
+use crate::admin::api::wrapped_get;
+use crate::admin::structure::breadcrumb::BreadcrumbItem;
+use crate::admin::structure::error_text::error_markup;
+use crate::admin::structure::page::Page;
+use crate::admin::structure::pagination_control::pagination_control;
+use crate::admin::structure::{page, split};
+use crate::unwrap_result_else_markup;
+use actix_web::web;
+use actix_web::web::ServiceConfig;
+use chrono::{DateTime, Utc};
+use inventory::pagination::{KeysetPaginationOptions, KeysetPaginationResult};
+use inventory::purchase::{PurchaseSerial, PurchaseStatus};
+use maud::{html, Markup};
+
+pub const PAGE: Page = Page {
+    name: "Purchase",
+    relative_path: "/admin/purchase",
+    configurer,
+};
+
+/// U+00A2 is the "cent" sign.
+const HEADINGS: [&str; 6] = ["id", "listing_id", "contact_email_address", "status", "subtotal (\u{00A2})", "created ⏷"];
+
+fn configurer(config: &mut ServiceConfig) {
+    config
+        .route("/purchase", web::get().to(render))
+    ;
+}
+
+async fn render(
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> Markup {
+    page::page(
+        &vec!(BreadcrumbItem::from(PAGE)),
+        Markup::default(),
+        split::split(left(query.into_inner()).await, Markup::default()),
+    )
+}
+
+async fn left(pagination_options: KeysetPaginationOptions<DateTime<Utc>>) -> Markup {
+    let query_params = match serde_urlencoded::to_string(&pagination_options) {
+        Ok(pagination_options) => pagination_options,
+        Err(error) => return error_markup(error),
+    };
+
+    let (purchase_vec, pagination_result) = unwrap_result_else_markup!(
+        wrapped_get::<(Vec<PurchaseSerial>, KeysetPaginationResult<DateTime<Utc>>)>(
+            format!("/purchase?{}", query_params).as_str(),
+        ).await
+    );
+
+    html! {
+        h2 { "Purchases" }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+        @if purchase_vec.is_empty() {
+            p { "None" }
+        } @else {
+            (table(&purchase_vec))
+        }
+        (pagination_control(PAGE.relative_path, &pagination_options, &pagination_result))
+    }
+}
+
+fn table(elements: &Vec<PurchaseSerial>) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    @for heading in HEADINGS {
+                        th { (heading) }
+                    }
+                }
+            }
+            tbody {
+                @for element in elements {
+                    tr {
+                        td { (element.id) }
+                        td { (element.listing_id) }
+                        td { (element.contact_email_address) }
+                        td { (match PurchaseStatus::try_from_repr(element.status) {
+                            Ok(variant) => format!("{:?}", variant),
+                            Err(error) => Markup::into_string(error_markup(error)),
+                        }) }
+                        td { (element.cost_subtotal_cents) }
+                        td { (element.created) }
+                    }
+                }
+            }
+        }
+    }
+}
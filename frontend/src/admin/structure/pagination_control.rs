@@ -1,5 +1,6 @@
-use inventory::pagination::{Direction, KeysetPaginationOptionsForString, KeysetPaginationResultForString};
+use inventory::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
 use maud::{html, Markup};
+use serde::Serialize;
 
 macro_rules! url_encoded_pagination_options_else_err {
     ($options:expr$(,)?) => {
@@ -10,30 +11,50 @@ macro_rules! url_encoded_pagination_options_else_err {
     };
 }
 
-pub fn pagination_control(
+pub fn pagination_control<KeyT>(
     relative_path: &str,
-    pagination_options: &KeysetPaginationOptionsForString,
-    pagination_result: &KeysetPaginationResultForString,
-) -> Markup {
+    pagination_options: &KeysetPaginationOptions<KeyT>,
+    pagination_result: &KeysetPaginationResult<KeyT>,
+) -> Markup
+where
+    KeyT: Serialize + Clone,
+{
+    pagination_control_with_query(relative_path, "", pagination_options, pagination_result)
+}
+
+/// base_query: URL-encoded parameters (e.g. search filters) which are preserved across PAGE links.
+pub fn pagination_control_with_query<KeyT>(
+    relative_path: &str,
+    base_query: &str,
+    pagination_options: &KeysetPaginationOptions<KeyT>,
+    pagination_result: &KeysetPaginationResult<KeyT>,
+) -> Markup
+where
+    KeyT: Serialize + Clone,
+{
     let next_page_params = url_encoded_pagination_options_else_err!(
         pagination_result.create(&Direction::Ascending, &pagination_options.max_page_size)
     );
     let previous_page_params = url_encoded_pagination_options_else_err!(
         pagination_result.create(&Direction::Descending, &pagination_options.max_page_size)
     );
+    let base_query: String = match base_query.is_empty() {
+        true => String::new(),
+        false => format!("{}&", base_query),
+    };
 
     html! {
         div style=(concat!(
             "display: flex; flex-direction: row; justify-content: center; align-items: center;",
             "margin: 1rem 0;",
         )) {
-            a href=(format!("{}/?{}", relative_path, previous_page_params)) {
+            a href=(format!("{}/?{}{}", relative_path, base_query, previous_page_params)) {
                 button disabled[!pagination_result.has_lesser_value] { "<--" }
             }
             span style=(concat!("margin: 0 1rem;")) {
                 (format!("Showing {} entries", pagination_result.page_size))
             }
-            a href=(format!("{}/?{}", relative_path, next_page_params)) {
+            a href=(format!("{}/?{}{}", relative_path, base_query, next_page_params)) {
                 button disabled[!pagination_result.has_greater_value] { "-->" }
             }
         }
@@ -18,6 +18,12 @@ function submit_form(submit_event) {
     for (let entry of form_data.entries()) {
         let [key, value] = entry;
 
+        const boolean = as_boolean(form, key);
+        if (boolean !== undefined) {
+            form_data_as_object[key] = boolean;
+            continue;
+        }
+
         const number = as_number(form, key, value);
         if (number !== undefined) {
             form_data_as_object[key] = number;
@@ -136,6 +142,21 @@ function as_number(form, key, value) {
     return int_value;
 }
 
+/**
+ * Checked checkboxes should be converted to booleans. Unchecked checkboxes are absent from the form data entirely.
+ * @param form {HTMLFormElement}
+ * @param key {string}
+ * @return {(boolean | undefined)}
+ */
+function as_boolean(form, key) {
+    const form_control = form.elements.namedItem(key);
+    if (!form_control || form_control.type !== "checkbox") {
+        return undefined;
+    }
+
+    return form_control.checked;
+}
+
 /**
  * @type {ComponentFactory}
  * @param parameters
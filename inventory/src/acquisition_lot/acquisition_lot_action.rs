@@ -0,0 +1,141 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item::item_db;
+use crate::{ShopEntity, ShopModel};
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Ordered by item code. Includes each item's allocated acquisition price.
+pub async fn get_all_acquisition_lot_items(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_id: &Uuid,
+) -> Result<Vec<AcquisitionLotItem>, ShopError> {
+    acquisition_lot_db::get_all_acquisition_lot_items(pgexecutor, acquisition_lot_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn create_acquisition_lot(pgpool: &PgPool, mut acquisition_lot: AcquisitionLot) -> Result<AcquisitionLot, ShopError> {
+    acquisition_lot.created = Utc::now();
+    acquisition_lot.updated = acquisition_lot.created;
+    acquisition_lot_db::create_acquisition_lot(pgpool, &acquisition_lot.to_entity()).await?;
+    Ok(acquisition_lot)
+}
+
+/// Replaces every field of the current lot except for the creation time, then reallocates the lot's cost to its items.
+pub async fn update_acquisition_lot(
+    pgpool: &PgPool,
+    current: &AcquisitionLot,
+    mut acquisition_lot: AcquisitionLot,
+) -> Result<AcquisitionLot, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    acquisition_lot_db::get_acquisition_lot_for_update(&mut *transaction, &current.id).await?;
+    acquisition_lot.id = current.id;
+    acquisition_lot.created = current.created;
+    acquisition_lot.updated = Utc::now();
+    acquisition_lot_db::update_acquisition_lot(&mut *transaction, &acquisition_lot.to_entity()).await?;
+    allocate_in_transaction(&mut transaction, &acquisition_lot).await?;
+
+    transaction.commit().await?;
+    Ok(acquisition_lot)
+}
+
+/// The acquisition prices of the lot's items are cleared, since they were only shares of the lot's cost.
+/// False if the lot does not exist.
+pub async fn delete_acquisition_lot(pgpool: &PgPool, acquisition_lot_id: &Uuid) -> Result<bool, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    if acquisition_lot_db::get_acquisition_lot_for_update(&mut *transaction, acquisition_lot_id).await?.is_none() {
+        return Ok(false);
+    }
+    let updated: DateTime<Utc> = Utc::now();
+    for acquisition_lot_item in acquisition_lot_db::get_all_acquisition_lot_items(&mut *transaction, acquisition_lot_id).await? {
+        item_db::update_item_acquisition_price(&mut *transaction, &acquisition_lot_item.item_id, None, &updated).await?;
+    }
+    acquisition_lot_db::delete_acquisition_lot(&mut *transaction, acquisition_lot_id).await?;
+
+    transaction.commit().await?;
+    Ok(true)
+}
+
+/// Adds the item to the lot, or replaces its allocation values if it is already a member, and reallocates the lot's
+/// cost. An item in another lot is moved, and the other lot is reallocated as well.
+pub async fn create_or_replace_acquisition_lot_item(
+    pgpool: &PgPool,
+    mut acquisition_lot_item: AcquisitionLotItem,
+) -> Result<Vec<AcquisitionLotAllocation>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let acquisition_lot: AcquisitionLot = get_acquisition_lot_for_update(&mut transaction, &acquisition_lot_item.acquisition_lot_id).await?;
+    item_db::get_item_for_update(&mut *transaction, &acquisition_lot_item.item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", acquisition_lot_item.item_id)))?;
+
+    let previous: Option<AcquisitionLotItemEntity> =
+        acquisition_lot_db::get_acquisition_lot_item(&mut *transaction, &acquisition_lot_item.item_id).await?;
+    acquisition_lot_item.updated = Utc::now();
+    acquisition_lot_item.created = match &previous {
+        Some(previous) if previous.acquisition_lot_id == acquisition_lot.id => previous.created,
+        _ => acquisition_lot_item.updated,
+    };
+    acquisition_lot_db::create_or_replace_acquisition_lot_item(&mut *transaction, &acquisition_lot_item.to_entity()).await?;
+    let allocations: Vec<AcquisitionLotAllocation> = allocate_in_transaction(&mut transaction, &acquisition_lot).await?;
+
+    if let Some(previous) = previous.filter(|previous| previous.acquisition_lot_id != acquisition_lot.id) {
+        let previous_acquisition_lot: AcquisitionLot = get_acquisition_lot_for_update(&mut transaction, &previous.acquisition_lot_id).await?;
+        allocate_in_transaction(&mut transaction, &previous_acquisition_lot).await?;
+    }
+
+    transaction.commit().await?;
+    Ok(allocations)
+}
+
+/// Removes the item from the lot, clearing its acquisition price, and reallocates the lot's cost to the remaining items.
+/// Absent if the item is not in the lot.
+pub async fn delete_acquisition_lot_item(
+    pgpool: &PgPool,
+    acquisition_lot_id: &Uuid,
+    item_id: &Uuid,
+) -> Result<Option<Vec<AcquisitionLotAllocation>>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let acquisition_lot: AcquisitionLot = get_acquisition_lot_for_update(&mut transaction, acquisition_lot_id).await?;
+    let query_result = acquisition_lot_db::delete_acquisition_lot_item(&mut *transaction, acquisition_lot_id, item_id).await?;
+    if query_result.rows_affected() == 0 {
+        return Ok(None);
+    }
+    item_db::update_item_acquisition_price(&mut *transaction, item_id, None, &Utc::now()).await?;
+    let allocations: Vec<AcquisitionLotAllocation> = allocate_in_transaction(&mut transaction, &acquisition_lot).await?;
+
+    transaction.commit().await?;
+    Ok(Some(allocations))
+}
+
+async fn get_acquisition_lot_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    acquisition_lot_id: &Uuid,
+) -> Result<AcquisitionLot, ShopError> {
+    acquisition_lot_db::get_acquisition_lot_for_update(&mut **transaction, acquisition_lot_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Acquisition lot not found; [{}]", acquisition_lot_id)))?
+        .try_to_model()
+}
+
+/// Writes each item's share of the lot's cost to its acquisition price. The lot should be locked by the caller.
+async fn allocate_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    acquisition_lot: &AcquisitionLot,
+) -> Result<Vec<AcquisitionLotAllocation>, ShopError> {
+    let acquisition_lot_items: Vec<AcquisitionLotItem> = get_all_acquisition_lot_items(&mut **transaction, &acquisition_lot.id).await?;
+    let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items)?;
+
+    let updated: DateTime<Utc> = Utc::now();
+    for allocation in &allocations {
+        let acquisition_price_cents: i64 = allocation.allocated_cost_cents as i64;
+        item_db::update_item_acquisition_price(&mut **transaction, &allocation.item_id, Some(acquisition_price_cents), &updated).await?;
+    }
+    Ok(allocations)
+}
@@ -0,0 +1,164 @@
+use super::*;
+use crate::item::item_api::resolve_item_id;
+use crate::object::JsonHttpResponse;
+use crate::supplier::supplier_db;
+use crate::{try_return, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/acquisition_lot")
+            .route("", web::get().to(get_all_acquisition_lots))
+            .route("", web::post().to(create_acquisition_lot))
+            .route("/{acquisition_lot_id}", web::get().to(get_acquisition_lot))
+            .route("/{acquisition_lot_id}", web::put().to(update_acquisition_lot))
+            .route("/{acquisition_lot_id}", web::delete().to(delete_acquisition_lot))
+            .route("/{acquisition_lot_id}/item", web::get().to(get_all_acquisition_lot_items))
+            .route("/{acquisition_lot_id}/item/{item_id}", web::put().to(create_or_replace_acquisition_lot_item))
+            .route("/{acquisition_lot_id}/item/{item_id}", web::delete().to(delete_acquisition_lot_item))
+    );
+}
+
+async fn get_all_acquisition_lots(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let entities: Vec<AcquisitionLotEntity> = unwrap_result_else_500!(
+        acquisition_lot_db::get_all_acquisition_lots(pgpool.get_ref()).await
+    );
+    let acquisition_lots: Vec<AcquisitionLot> = unwrap_result_else_500!(
+        entities.iter().map(|entity| entity.try_to_model()).collect::<Result<Vec<_>, _>>()
+    );
+    acquisition_lots.iter()
+        .map(|acquisition_lot| acquisition_lot.to_serial())
+        .collect::<Vec<AcquisitionLotSerial>>()
+        .to_http_response()
+}
+
+async fn create_acquisition_lot(
+    pgpool: web::Data<PgPool>,
+    body: web::Json<AcquisitionLotSerial>,
+) -> HttpResponse {
+    let acquisition_lot: AcquisitionLot = match body.into_inner().try_to_model() {
+        Ok(acquisition_lot) => acquisition_lot,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    unwrap_option_else_404!(unwrap_result_else_500!(
+        supplier_db::get_supplier(pgpool.get_ref(), &acquisition_lot.supplier_id).await
+    ));
+
+    let acquisition_lot: AcquisitionLot = unwrap_result_else_500!(
+        acquisition_lot_action::create_acquisition_lot(&pgpool, acquisition_lot).await
+    );
+    acquisition_lot.to_serial().to_http_response()
+}
+
+async fn get_acquisition_lot(
+    pgpool: web::Data<PgPool>,
+    acquisition_lot_id: web::Path<String>,
+) -> HttpResponse {
+    let acquisition_lot_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&acquisition_lot_id.into_inner()));
+    let acquisition_lot: AcquisitionLot = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        acquisition_lot_db::get_acquisition_lot(pgpool.get_ref(), &acquisition_lot_id).await
+    )).try_to_model());
+    acquisition_lot.to_serial().to_http_response()
+}
+
+/// Reallocates the lot's cost to its items, so a change of cost, fees or allocation method takes effect immediately.
+async fn update_acquisition_lot(
+    pgpool: web::Data<PgPool>,
+    acquisition_lot_id: web::Path<String>,
+    body: web::Json<AcquisitionLotSerial>,
+) -> HttpResponse {
+    let acquisition_lot_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&acquisition_lot_id.into_inner()));
+    let acquisition_lot: AcquisitionLot = match body.into_inner().try_to_model() {
+        Ok(acquisition_lot) => acquisition_lot,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: AcquisitionLot = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        acquisition_lot_db::get_acquisition_lot(pgpool.get_ref(), &acquisition_lot_id).await
+    )).try_to_model());
+    unwrap_option_else_404!(unwrap_result_else_500!(
+        supplier_db::get_supplier(pgpool.get_ref(), &acquisition_lot.supplier_id).await
+    ));
+
+    match acquisition_lot_action::update_acquisition_lot(&pgpool, &current, acquisition_lot).await {
+        Ok(acquisition_lot) => acquisition_lot.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_acquisition_lot(
+    pgpool: web::Data<PgPool>,
+    acquisition_lot_id: web::Path<String>,
+) -> HttpResponse {
+    let acquisition_lot_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&acquisition_lot_id.into_inner()));
+    match unwrap_result_else_500!(acquisition_lot_action::delete_acquisition_lot(&pgpool, &acquisition_lot_id).await) {
+        false => HttpResponse::NotFound().finish(),
+        true => HttpResponse::NoContent().finish(),
+    }
+}
+
+async fn get_all_acquisition_lot_items(
+    pgpool: web::Data<PgPool>,
+    acquisition_lot_id: web::Path<String>,
+) -> HttpResponse {
+    let acquisition_lot_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&acquisition_lot_id.into_inner()));
+    unwrap_option_else_404!(unwrap_result_else_500!(
+        acquisition_lot_db::get_acquisition_lot(pgpool.get_ref(), &acquisition_lot_id).await
+    ));
+
+    let acquisition_lot_items: Vec<AcquisitionLotItem> = unwrap_result_else_500!(
+        acquisition_lot_action::get_all_acquisition_lot_items(pgpool.get_ref(), &acquisition_lot_id).await
+    );
+    acquisition_lot_items.iter()
+        .map(|acquisition_lot_item| acquisition_lot_item.to_serial())
+        .collect::<Vec<AcquisitionLotItemSerial>>()
+        .to_http_response()
+}
+
+/// Responds with the new allocation of every item in the lot. The allocation fails, and nothing is changed, if any
+/// item lacks the value the lot's allocation method requires.
+async fn create_or_replace_acquisition_lot_item(
+    pgpool: web::Data<PgPool>,
+    path: web::Path<(String, String)>,
+    body: web::Json<AcquisitionLotItemSerial>,
+) -> HttpResponse {
+    let (acquisition_lot_id, item_id) = path.into_inner();
+    let acquisition_lot_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&acquisition_lot_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id).await);
+    unwrap_option_else_404!(unwrap_result_else_500!(
+        acquisition_lot_db::get_acquisition_lot(pgpool.get_ref(), &acquisition_lot_id).await
+    ));
+
+    let mut acquisition_lot_item: AcquisitionLotItem = match body.into_inner().try_to_model() {
+        Ok(acquisition_lot_item) => acquisition_lot_item,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    acquisition_lot_item.acquisition_lot_id = acquisition_lot_id;
+    acquisition_lot_item.item_id = item_id;
+
+    match acquisition_lot_action::create_or_replace_acquisition_lot_item(&pgpool, acquisition_lot_item).await {
+        Ok(allocations) => allocations.to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Responds with the new allocation of every item remaining in the lot.
+async fn delete_acquisition_lot_item(
+    pgpool: web::Data<PgPool>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (acquisition_lot_id, item_id) = path.into_inner();
+    let acquisition_lot_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&acquisition_lot_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id).await);
+    unwrap_option_else_404!(unwrap_result_else_500!(
+        acquisition_lot_db::get_acquisition_lot(pgpool.get_ref(), &acquisition_lot_id).await
+    ));
+
+    match acquisition_lot_action::delete_acquisition_lot_item(&pgpool, &acquisition_lot_id, &item_id).await {
+        Ok(Some(allocations)) => allocations.to_http_response(),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
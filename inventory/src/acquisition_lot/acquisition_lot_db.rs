@@ -0,0 +1,212 @@
+use super::*;
+use crate::error::ShopError;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor};
+use uuid::Uuid;
+
+pub async fn get_acquisition_lot(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_id: &Uuid,
+) -> Result<Option<AcquisitionLotEntity>, ShopError> {
+    query_as!(AcquisitionLotEntity, "
+        select id, supplier_id, display_name, acquisition_date, total_cost_cents, fees_cents, allocation_method, note, created, updated
+        from shop.public.acquisition_lot
+        where id = $1
+    ",
+		acquisition_lot_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Locks the lot so that concurrent changes to its items are allocated one at a time.
+pub async fn get_acquisition_lot_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_id: &Uuid,
+) -> Result<Option<AcquisitionLotEntity>, ShopError> {
+    query_as!(AcquisitionLotEntity, "
+        select id, supplier_id, display_name, acquisition_date, total_cost_cents, fees_cents, allocation_method, note, created, updated
+        from shop.public.acquisition_lot
+        where id = $1
+        for update
+    ",
+		acquisition_lot_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recently acquired first
+pub async fn get_all_acquisition_lots(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<AcquisitionLotEntity>, ShopError> {
+    query_as!(AcquisitionLotEntity, "
+        select id, supplier_id, display_name, acquisition_date, total_cost_cents, fees_cents, allocation_method, note, created, updated
+        from shop.public.acquisition_lot
+        order by acquisition_date desc, created desc
+    ")
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recently acquired first
+pub async fn get_all_supplier_acquisition_lots(
+    pgexecutor: impl PgExecutor<'_>,
+    supplier_id: &Uuid,
+) -> Result<Vec<AcquisitionLotEntity>, ShopError> {
+    query_as!(AcquisitionLotEntity, "
+        select id, supplier_id, display_name, acquisition_date, total_cost_cents, fees_cents, allocation_method, note, created, updated
+        from shop.public.acquisition_lot
+        where supplier_id = $1
+        order by acquisition_date desc, created desc
+    ",
+		supplier_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_acquisition_lot(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot: &AcquisitionLotEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.acquisition_lot (id, supplier_id, display_name, acquisition_date, total_cost_cents, fees_cents, allocation_method, note, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+    ",
+		acquisition_lot.id,
+		acquisition_lot.supplier_id,
+		acquisition_lot.display_name,
+		acquisition_lot.acquisition_date,
+		acquisition_lot.total_cost_cents,
+		acquisition_lot.fees_cents,
+		acquisition_lot.allocation_method,
+		acquisition_lot.note,
+		acquisition_lot.created,
+		acquisition_lot.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The creation time is never changed.
+pub async fn update_acquisition_lot(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot: &AcquisitionLotEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.acquisition_lot
+        set (supplier_id, display_name, acquisition_date, total_cost_cents, fees_cents, allocation_method, note, updated)
+            = ($2, $3, $4, $5, $6, $7, $8, $9)
+        where id = $1
+    ",
+		acquisition_lot.id,
+		acquisition_lot.supplier_id,
+		acquisition_lot.display_name,
+		acquisition_lot.acquisition_date,
+		acquisition_lot.total_cost_cents,
+		acquisition_lot.fees_cents,
+		acquisition_lot.allocation_method,
+		acquisition_lot.note,
+		acquisition_lot.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Item memberships are deleted by cascade.
+pub async fn delete_acquisition_lot(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.acquisition_lot
+        where id = $1
+    ",
+		acquisition_lot_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_acquisition_lot_item(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<AcquisitionLotItemEntity>, ShopError> {
+    query_as!(AcquisitionLotItemEntity, "
+        select acquisition_lot_item.item_id, acquisition_lot_item.acquisition_lot_id, acquisition_lot_item.weight_grams, acquisition_lot_item.estimated_value_cents, item.acquisition_price_cents, acquisition_lot_item.created, acquisition_lot_item.updated
+        from shop.public.acquisition_lot_item
+            inner join shop.public.item on acquisition_lot_item.item_id = item.id
+        where acquisition_lot_item.item_id = $1
+    ",
+		item_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Ordered by item code
+pub async fn get_all_acquisition_lot_items(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_id: &Uuid,
+) -> Result<Vec<AcquisitionLotItemEntity>, ShopError> {
+    query_as!(AcquisitionLotItemEntity, "
+        select acquisition_lot_item.item_id, acquisition_lot_item.acquisition_lot_id, acquisition_lot_item.weight_grams, acquisition_lot_item.estimated_value_cents, item.acquisition_price_cents, acquisition_lot_item.created, acquisition_lot_item.updated
+        from shop.public.acquisition_lot_item
+            inner join shop.public.item on acquisition_lot_item.item_id = item.id
+        where acquisition_lot_item.acquisition_lot_id = $1
+        order by item.code
+    ",
+		acquisition_lot_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Moves the item into the lot if it already belongs to another.
+pub async fn create_or_replace_acquisition_lot_item(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_item: &AcquisitionLotItemEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.acquisition_lot_item (item_id, acquisition_lot_id, weight_grams, estimated_value_cents, created, updated)
+        values ($1, $2, $3, $4, $5, $6)
+        on conflict (item_id) do update
+            set (acquisition_lot_id, weight_grams, estimated_value_cents, updated)
+                = (excluded.acquisition_lot_id, excluded.weight_grams, excluded.estimated_value_cents, excluded.updated)
+    ",
+		acquisition_lot_item.item_id,
+		acquisition_lot_item.acquisition_lot_id,
+		acquisition_lot_item.weight_grams,
+		acquisition_lot_item.estimated_value_cents,
+		acquisition_lot_item.created,
+		acquisition_lot_item.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_acquisition_lot_item(
+    pgexecutor: impl PgExecutor<'_>,
+    acquisition_lot_id: &Uuid,
+    item_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.acquisition_lot_item
+        where acquisition_lot_id = $1 and item_id = $2
+    ",
+		acquisition_lot_id,
+		item_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
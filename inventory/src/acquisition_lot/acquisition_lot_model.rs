@@ -0,0 +1,482 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AcquisitionLotEntity {
+    pub id: Uuid,
+    pub supplier_id: Uuid,
+    pub display_name: String,
+    pub acquisition_date: NaiveDate,
+    pub total_cost_cents: i64,
+    pub fees_cents: i64,
+    pub allocation_method: i32,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for AcquisitionLotEntity {
+    type Model = AcquisitionLot;
+}
+
+/// A single purchase of any number of items from a supplier for one price, e.g. a lot of 30 figures. The cost of the
+/// lot is allocated across its items and written to each item's acquisition price.
+#[derive(Debug, Clone)]
+pub struct AcquisitionLot {
+    pub id: Uuid,
+    pub supplier_id: Uuid,
+    pub display_name: String,
+    pub acquisition_date: NaiveDate,
+    pub total_cost_cents: u32,
+    /// E.g. shipping and buyer's premium; allocated together with the total cost
+    pub fees_cents: u32,
+    pub allocation_method: AllocationMethod,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl AcquisitionLot {
+    pub fn get_allocated_cost_cents(&self) -> u64 {
+        u64::from(self.total_cost_cents) + u64::from(self.fees_cents)
+    }
+
+    /// Splits the total cost and fees across the items in proportion to the allocation method's weight of each item.
+    /// Shares are rounded down to the cent and the remaining cents go to the items with the largest remainders, so the
+    /// allocations always sum to the allocated cost.
+    pub fn allocate(&self, acquisition_lot_items: &[AcquisitionLotItem]) -> Result<Vec<AcquisitionLotAllocation>, ShopError> {
+        if acquisition_lot_items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let weights: Vec<u128> = acquisition_lot_items.iter()
+            .map(|acquisition_lot_item| self.allocation_method.get_weight(acquisition_lot_item).map(u128::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let weight_sum: u128 = weights.iter().sum();
+        if weight_sum == 0 {
+            return Err(ShopError::new(&format!(
+                "Cannot allocate a lot whose items have no total weight; [{}]; [{}]",
+                self.id,
+                self.allocation_method,
+            )));
+        }
+
+        let allocated_cost_cents: u128 = u128::from(self.get_allocated_cost_cents());
+        let mut shares: Vec<(u64, u128)> = weights.iter()
+            .map(|weight| ((allocated_cost_cents * weight / weight_sum) as u64, allocated_cost_cents * weight % weight_sum))
+            .collect();
+
+        let mut remaining_cents: u64 = self.get_allocated_cost_cents() - shares.iter().map(|(share, _)| share).sum::<u64>();
+        let mut indices_by_remainder: Vec<usize> = (0..shares.len()).collect();
+        indices_by_remainder.sort_by(|a, b| shares[*b].1.cmp(&shares[*a].1));
+        for index in indices_by_remainder {
+            if remaining_cents == 0 {
+                break;
+            }
+            shares[index].0 += 1;
+            remaining_cents -= 1;
+        }
+
+        Ok(acquisition_lot_items.iter()
+            .zip(shares)
+            .map(|(acquisition_lot_item, (share, _))| AcquisitionLotAllocation {
+                item_id: acquisition_lot_item.item_id,
+                allocated_cost_cents: share,
+            })
+            .collect())
+    }
+}
+
+impl ShopModel for AcquisitionLot {
+    type Entity = AcquisitionLotEntity;
+    type Serial = AcquisitionLotSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        AcquisitionLotSerial {
+            id: self.id.clone(),
+            supplier_id: self.supplier_id.clone(),
+            display_name: self.display_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            total_cost_cents: self.total_cost_cents.clone(),
+            fees_cents: self.fees_cents.clone(),
+            allocation_method: self.allocation_method.clone() as u8,
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let display_name: String = serial.display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err(ShopError::new("Acquisition lot display name must not be empty"));
+        }
+
+        Ok(AcquisitionLot {
+            id: object::random_uuid(),
+            supplier_id: serial.supplier_id.clone(),
+            display_name,
+            acquisition_date: serial.acquisition_date.clone(),
+            total_cost_cents: serial.total_cost_cents.clone(),
+            fees_cents: serial.fees_cents.clone(),
+            allocation_method: AllocationMethod::try_from_repr(serial.allocation_method)?,
+            note: serial.note.clone(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        AcquisitionLotEntity {
+            id: self.id.clone(),
+            supplier_id: self.supplier_id.clone(),
+            display_name: self.display_name.clone(),
+            acquisition_date: self.acquisition_date.clone(),
+            total_cost_cents: i64::from(self.total_cost_cents),
+            fees_cents: i64::from(self.fees_cents),
+            allocation_method: self.allocation_method.clone() as i32,
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(AcquisitionLot {
+            id: entity.id.clone(),
+            supplier_id: entity.supplier_id.clone(),
+            display_name: entity.display_name.clone(),
+            acquisition_date: entity.acquisition_date.clone(),
+            total_cost_cents: entity.total_cost_cents.clone() as u32,
+            fees_cents: entity.fees_cents.clone() as u32,
+            allocation_method: AllocationMethod::try_from_repr(entity.allocation_method.clone() as u8)?,
+            note: entity.note.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum AllocationMethod {
+    /// Every item receives an equal share
+    Even = 0,
+    /// In proportion to [AcquisitionLotItem::weight_grams]
+    Weight,
+    /// In proportion to [AcquisitionLotItem::estimated_value_cents]
+    EstimatedValue,
+}
+
+try_from_repr!(AllocationMethod<u8>);
+
+impl AllocationMethod {
+    pub fn get_display_name(&self) -> &'static str {
+        match self {
+            AllocationMethod::Even => "Even",
+            AllocationMethod::Weight => "By weight",
+            AllocationMethod::EstimatedValue => "By estimated value",
+        }
+    }
+
+    /// The item's share of the lot relative to the other items. Fails if the item lacks the value this method requires.
+    pub fn get_weight(&self, acquisition_lot_item: &AcquisitionLotItem) -> Result<u32, ShopError> {
+        let weight: Option<u32> = match self {
+            AllocationMethod::Even => Some(1),
+            AllocationMethod::Weight => acquisition_lot_item.weight_grams,
+            AllocationMethod::EstimatedValue => acquisition_lot_item.estimated_value_cents,
+        };
+        weight.ok_or_else(|| ShopError::new(&format!(
+            "Item has no value for the lot's allocation method; [{}]; [{}]",
+            acquisition_lot_item.item_id,
+            self,
+        )))
+    }
+}
+
+impl Display for AllocationMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AcquisitionLotSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub supplier_id: Uuid,
+    pub display_name: String,
+    pub acquisition_date: NaiveDate,
+    pub total_cost_cents: u32,
+    #[serde(default)]
+    pub fees_cents: u32,
+    pub allocation_method: u8,
+    pub note: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for AcquisitionLotSerial {
+    type Model = AcquisitionLot;
+}
+
+impl JsonHttpResponse for AcquisitionLotSerial {}
+impl JsonHttpResponse for Vec<AcquisitionLotSerial> {}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AcquisitionLotItemEntity {
+    pub item_id: Uuid,
+    pub acquisition_lot_id: Uuid,
+    pub weight_grams: Option<i32>,
+    pub estimated_value_cents: Option<i64>,
+    /// Selected from the item; never written through this entity
+    pub acquisition_price_cents: Option<i64>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for AcquisitionLotItemEntity {
+    type Model = AcquisitionLotItem;
+}
+
+/// Membership of an item in an acquisition lot. Each item belongs to at most one lot.
+#[derive(Debug, Clone)]
+pub struct AcquisitionLotItem {
+    pub item_id: Uuid,
+    pub acquisition_lot_id: Uuid,
+    /// Required by [AllocationMethod::Weight]
+    pub weight_grams: Option<u32>,
+    /// Required by [AllocationMethod::EstimatedValue]
+    pub estimated_value_cents: Option<u32>,
+    /// The item's share of the lot's cost as of the latest allocation. Ignored on input.
+    pub acquisition_price_cents: Option<u32>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopModel for AcquisitionLotItem {
+    type Entity = AcquisitionLotItemEntity;
+    type Serial = AcquisitionLotItemSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        AcquisitionLotItemSerial {
+            item_id: self.item_id.clone(),
+            acquisition_lot_id: self.acquisition_lot_id.clone(),
+            weight_grams: self.weight_grams.clone(),
+            estimated_value_cents: self.estimated_value_cents.clone(),
+            acquisition_price_cents: self.acquisition_price_cents.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        if serial.weight_grams.is_some_and(|weight_grams| weight_grams > i32::MAX as u32) {
+            return Err(ShopError::new(&format!("Weight is too large; [{:?}]", serial.weight_grams)));
+        }
+
+        Ok(AcquisitionLotItem {
+            item_id: serial.item_id.clone(),
+            acquisition_lot_id: serial.acquisition_lot_id.clone(),
+            weight_grams: serial.weight_grams.clone(),
+            estimated_value_cents: serial.estimated_value_cents.clone(),
+            acquisition_price_cents: None,
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        AcquisitionLotItemEntity {
+            item_id: self.item_id.clone(),
+            acquisition_lot_id: self.acquisition_lot_id.clone(),
+            weight_grams: self.weight_grams.map(|weight_grams| weight_grams as i32),
+            estimated_value_cents: self.estimated_value_cents.map(i64::from),
+            acquisition_price_cents: self.acquisition_price_cents.map(i64::from),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(AcquisitionLotItem {
+            item_id: entity.item_id.clone(),
+            acquisition_lot_id: entity.acquisition_lot_id.clone(),
+            weight_grams: entity.weight_grams.map(|weight_grams| weight_grams as u32),
+            estimated_value_cents: entity.estimated_value_cents.map(|estimated_value_cents| estimated_value_cents as u32),
+            acquisition_price_cents: entity.acquisition_price_cents.map(|acquisition_price_cents| acquisition_price_cents as u32),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+/// The item and lot IDs are taken from the request path when adding an item to a lot.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AcquisitionLotItemSerial {
+    #[serde(default)]
+    pub item_id: Uuid,
+    #[serde(default)]
+    pub acquisition_lot_id: Uuid,
+    pub weight_grams: Option<u32>,
+    pub estimated_value_cents: Option<u32>,
+    #[serde(default)]
+    pub acquisition_price_cents: Option<u32>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for AcquisitionLotItemSerial {
+    type Model = AcquisitionLotItem;
+}
+
+impl JsonHttpResponse for AcquisitionLotItemSerial {}
+impl JsonHttpResponse for Vec<AcquisitionLotItemSerial> {}
+
+/// An item's share of its lot's total cost and fees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcquisitionLotAllocation {
+    pub item_id: Uuid,
+    pub allocated_cost_cents: u64,
+}
+
+impl JsonHttpResponse for Vec<AcquisitionLotAllocation> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_acquisition_lot(total_cost_cents: u32, fees_cents: u32, allocation_method: AllocationMethod) -> AcquisitionLot {
+        AcquisitionLot {
+            id: Uuid::nil(),
+            supplier_id: Uuid::nil(),
+            display_name: String::from("Lot"),
+            acquisition_date: NaiveDate::default(),
+            total_cost_cents,
+            fees_cents,
+            allocation_method,
+            note: None,
+            created: DateTime::default(),
+            updated: DateTime::default(),
+        }
+    }
+
+    fn new_acquisition_lot_item(weight_grams: Option<u32>, estimated_value_cents: Option<u32>) -> AcquisitionLotItem {
+        AcquisitionLotItem {
+            item_id: object::random_uuid(),
+            acquisition_lot_id: Uuid::nil(),
+            weight_grams,
+            estimated_value_cents,
+            acquisition_price_cents: None,
+            created: DateTime::default(),
+            updated: DateTime::default(),
+        }
+    }
+
+    fn allocated_cost_cents(allocations: &[AcquisitionLotAllocation]) -> Vec<u64> {
+        allocations.iter().map(|allocation| allocation.allocated_cost_cents).collect()
+    }
+
+    #[test]
+    fn allocate_sums_exactly_to_total_cost_and_fees() {
+        let values: [u32; 7] = [7, 13, 29, 51, 1, 997, 3];
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = values.iter()
+            .map(|value| new_acquisition_lot_item(Some(*value), Some(*value)))
+            .collect();
+        for (total_cost_cents, fees_cents) in [(0, 0), (1, 0), (9999, 1), (12345, 678), (u32::MAX, u32::MAX)] {
+            for allocation_method in AllocationMethod::VARIANTS {
+                let acquisition_lot = new_acquisition_lot(total_cost_cents, fees_cents, allocation_method.clone());
+                let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items).unwrap();
+                assert_eq!(allocations.len(), acquisition_lot_items.len());
+                assert_eq!(allocated_cost_cents(&allocations).iter().sum::<u64>(), acquisition_lot.get_allocated_cost_cents());
+            }
+        }
+    }
+
+    #[test]
+    fn allocate_is_proportional_to_weight() {
+        let acquisition_lot = new_acquisition_lot(300, 100, AllocationMethod::Weight);
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = vec![
+            new_acquisition_lot_item(Some(100), None),
+            new_acquisition_lot_item(Some(300), None),
+            new_acquisition_lot_item(Some(0), None),
+        ];
+        let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items).unwrap();
+        assert_eq!(allocated_cost_cents(&allocations), vec![100, 300, 0]);
+        for (allocation, acquisition_lot_item) in allocations.iter().zip(&acquisition_lot_items) {
+            assert_eq!(allocation.item_id, acquisition_lot_item.item_id);
+        }
+    }
+
+    #[test]
+    fn allocate_gives_remaining_cents_to_largest_remainders() {
+        // Shares of 1000 are 142.86, 285.71, and 571.43
+        let acquisition_lot = new_acquisition_lot(1000, 0, AllocationMethod::EstimatedValue);
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = vec![
+            new_acquisition_lot_item(None, Some(1)),
+            new_acquisition_lot_item(None, Some(2)),
+            new_acquisition_lot_item(None, Some(4)),
+        ];
+        let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items).unwrap();
+        assert_eq!(allocated_cost_cents(&allocations), vec![143, 286, 571]);
+    }
+
+    #[test]
+    fn allocate_gives_tied_remaining_cents_to_earlier_items() {
+        let acquisition_lot = new_acquisition_lot(1000, 1, AllocationMethod::Even);
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = (0..3).map(|_| new_acquisition_lot_item(None, None)).collect();
+        let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items).unwrap();
+        assert_eq!(allocated_cost_cents(&allocations), vec![334, 334, 333]);
+
+        let acquisition_lot = new_acquisition_lot(2, 0, AllocationMethod::Even);
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = (0..5).map(|_| new_acquisition_lot_item(None, None)).collect();
+        let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items).unwrap();
+        assert_eq!(allocated_cost_cents(&allocations), vec![1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn allocate_rejects_zero_total_weight() {
+        let acquisition_lot = new_acquisition_lot(1000, 0, AllocationMethod::Weight);
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = vec![
+            new_acquisition_lot_item(Some(0), None),
+            new_acquisition_lot_item(Some(0), None),
+        ];
+        assert!(acquisition_lot.allocate(&acquisition_lot_items).is_err());
+    }
+
+    #[test]
+    fn allocate_rejects_missing_weight() {
+        let acquisition_lot = new_acquisition_lot(1000, 0, AllocationMethod::EstimatedValue);
+        let acquisition_lot_items: Vec<AcquisitionLotItem> = vec![
+            new_acquisition_lot_item(None, Some(10)),
+            new_acquisition_lot_item(Some(10), None),
+        ];
+        assert!(acquisition_lot.allocate(&acquisition_lot_items).is_err());
+    }
+
+    #[test]
+    fn allocate_gives_one_item_the_whole_cost() {
+        for allocation_method in AllocationMethod::VARIANTS {
+            let acquisition_lot = new_acquisition_lot(4999, 1, allocation_method.clone());
+            let acquisition_lot_items: Vec<AcquisitionLotItem> = vec![new_acquisition_lot_item(Some(3), Some(7))];
+            let allocations: Vec<AcquisitionLotAllocation> = acquisition_lot.allocate(&acquisition_lot_items).unwrap();
+            assert_eq!(allocated_cost_cents(&allocations), vec![5000]);
+        }
+    }
+
+    #[test]
+    fn allocate_nothing_to_no_items() {
+        let acquisition_lot = new_acquisition_lot(1000, 0, AllocationMethod::Even);
+        assert!(acquisition_lot.allocate(&[]).unwrap().is_empty());
+    }
+}
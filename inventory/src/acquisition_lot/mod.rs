@@ -0,0 +1,7 @@
+pub(crate) mod acquisition_lot_db;
+
+pub mod acquisition_lot_api;
+pub mod acquisition_lot_model;
+pub mod acquisition_lot_action;
+
+pub use acquisition_lot_model::*;
@@ -0,0 +1,307 @@
+use crate::barcode::{BarcodeMatrix, ItemLabel, LabelSheetDimensions, LabelSheetLayout, Symbology};
+use crate::error::ShopError;
+use crate::item::{item_db, Item};
+use crate::product::{product_db, Product};
+use crate::ShopEntity;
+use barcoders::sym::code128::Code128;
+use printpdf::{BuiltinFont, Color, Greyscale, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Rect};
+use qrcode::QrCode;
+use sqlx::PgPool;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Light modules on each side of a Code 128 symbol
+const CODE128_QUIET_ZONE: u32 = 10;
+/// Height of a Code 128 symbol in modules, when rendered as a standalone image
+const CODE128_ROW_HEIGHT: u32 = 50;
+/// Light modules on each side of a QR symbol
+const QR_QUIET_ZONE: u32 = 4;
+
+const LABEL_PADDING_MM: f32 = 2.0;
+const LABEL_FONT_SIZE_MAX: f32 = 9.0;
+/// Approximate average character width of Helvetica, relative to the font size
+const HELVETICA_CHARACTER_WIDTH: f32 = 0.55;
+const MM_PER_POINT: f32 = 0.3528;
+
+impl BarcodeMatrix {
+    pub fn is_dark(&self, column: u32, row: u32) -> bool {
+        self.modules[(row * self.columns + column) as usize]
+    }
+
+    /// Each (start column, length) run of consecutive dark modules in the row.
+    pub fn get_dark_runs(&self, row: u32) -> Vec<(u32, u32)> {
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+        let mut start: Option<u32> = None;
+        for column in 0..=self.columns {
+            let dark: bool = column < self.columns && self.is_dark(column, row);
+            match (dark, start) {
+                (true, None) => start = Some(column),
+                (false, Some(run_start)) => {
+                    runs.push((run_start, column - run_start));
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+        runs
+    }
+}
+
+/// The data encoded for an item. Code 128 encodes the short item code to keep the symbol narrow enough for small labels;
+/// QR codes encode the item ID. Both are accepted wherever an item is identified.
+pub fn get_sku_data(symbology: &Symbology, item_id: &Uuid, item_code: &str) -> String {
+    match symbology {
+        Symbology::Code128 => item_code.to_string(),
+        Symbology::Qr => item_id.hyphenated().to_string(),
+    }
+}
+
+pub fn encode(symbology: &Symbology, data: &str) -> Result<BarcodeMatrix, ShopError> {
+    match symbology {
+        Symbology::Code128 => encode_code128(data),
+        Symbology::Qr => encode_qr(data),
+    }
+}
+
+fn encode_code128(data: &str) -> Result<BarcodeMatrix, ShopError> {
+    // Character set B encodes all printable ASCII characters.
+    let code128: Code128 = Code128::new(format!("\u{0181}{}", data))
+        .map_err(|e| ShopError::new(&format!("Failed to encode Code 128 barcode; {}", e)))?;
+    let bars: Vec<u8> = code128.encode();
+
+    let quiet_zone: Vec<bool> = vec![false; CODE128_QUIET_ZONE as usize];
+    let mut modules: Vec<bool> = quiet_zone.clone();
+    modules.extend(bars.iter().map(|bar| *bar == 1));
+    modules.extend(quiet_zone);
+
+    Ok(BarcodeMatrix {
+        columns: modules.len() as u32,
+        rows: 1,
+        row_height: CODE128_ROW_HEIGHT,
+        modules,
+    })
+}
+
+fn encode_qr(data: &str) -> Result<BarcodeMatrix, ShopError> {
+    let qr_code: QrCode = QrCode::new(data.as_bytes())
+        .map_err(|e| ShopError::from_error("Failed to encode QR code", Box::new(e)))?;
+    let width: u32 = qr_code.width() as u32;
+    let colors: Vec<qrcode::Color> = qr_code.to_colors();
+
+    let size: u32 = width + 2 * QR_QUIET_ZONE;
+    let mut modules: Vec<bool> = vec![false; (size * size) as usize];
+    for row in 0..width {
+        for column in 0..width {
+            if colors[(row * width + column) as usize] == qrcode::Color::Dark {
+                modules[((row + QR_QUIET_ZONE) * size + column + QR_QUIET_ZONE) as usize] = true;
+            }
+        }
+    }
+
+    Ok(BarcodeMatrix {
+        columns: size,
+        rows: size,
+        row_height: 1,
+        modules,
+    })
+}
+
+/// The SVG is scaled in modules and may be resized freely by the client.
+pub fn render_svg(matrix: &BarcodeMatrix) -> String {
+    let height: u32 = matrix.rows * matrix.row_height;
+    let mut svg: String = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\
+        <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/><path fill=\"#000000\" d=\"",
+        matrix.columns,
+        height,
+    );
+    for row in 0..matrix.rows {
+        for (column, length) in matrix.get_dark_runs(row) {
+            _ = write!(svg, "M{} {}h{}v{}h-{}z", column, row * matrix.row_height, length, matrix.row_height, length);
+        }
+    }
+    svg.push_str("\"/></svg>");
+    svg
+}
+
+pub fn render_png(matrix: &BarcodeMatrix, module_size: u32) -> Result<Vec<u8>, ShopError> {
+    if module_size == 0 || module_size > 32 {
+        return Err(ShopError::new("Module size must be between 1 and 32;"));
+    }
+
+    let width: u32 = matrix.columns * module_size;
+    let height: u32 = matrix.rows * matrix.row_height * module_size;
+    let mut pixels: Vec<u8> = Vec::with_capacity((width * height) as usize);
+    for row in 0..matrix.rows {
+        let mut pixel_row: Vec<u8> = Vec::with_capacity(width as usize);
+        for column in 0..matrix.columns {
+            let value: u8 = if matrix.is_dark(column, row) { 0x00 } else { 0xff };
+            pixel_row.extend(std::iter::repeat_n(value, module_size as usize));
+        }
+        for _ in 0..(matrix.row_height * module_size) {
+            pixels.extend_from_slice(&pixel_row);
+        }
+    }
+
+    let mut png: Vec<u8> = Vec::new();
+    let mut encoder: png::Encoder<&mut Vec<u8>> = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()
+        .map_err(|e| ShopError::from_error("Failed to write PNG header", Box::new(e)))?;
+    writer.write_image_data(&pixels)
+        .map_err(|e| ShopError::from_error("Failed to write PNG data", Box::new(e)))?;
+    writer.finish()
+        .map_err(|e| ShopError::from_error("Failed to write PNG", Box::new(e)))?;
+    Ok(png)
+}
+
+/// Collects the printed contents of each item's label, in the given order.
+pub async fn get_item_labels(pgpool: &PgPool, item_ids: &[Uuid]) -> Result<Vec<ItemLabel>, ShopError> {
+    let mut item_labels: Vec<ItemLabel> = Vec::with_capacity(item_ids.len());
+    for item_id in item_ids {
+        let item: Item = item_db::get_item(pgpool, item_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+            .try_to_model()?;
+        let product: Product = product_db::get_product(pgpool, &item.product_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Product not found; [{}]", item.product_id)))?
+            .try_to_model()?;
+
+        item_labels.push(ItemLabel {
+            item_id: item.id,
+            item_code: item.code,
+            display_name: product.display_name,
+            condition: Into::<&'static str>::into(&item.condition).to_string(),
+            price: format!("${}.{:02}", item.price_cents / 100, item.price_cents % 100),
+        });
+    }
+    Ok(item_labels)
+}
+
+/// Renders one label per item as a PDF, filling each sheet left to right and then top to bottom.
+pub fn render_label_sheet(
+    item_labels: &[ItemLabel],
+    layout: &LabelSheetLayout,
+    symbology: &Symbology,
+    skip: u32,
+) -> Result<Vec<u8>, ShopError> {
+    let dimensions: LabelSheetDimensions = layout.get_dimensions();
+    let labels_per_page: u32 = dimensions.get_labels_per_sheet();
+
+    let (document, page, layer) = PdfDocument::new(
+        "Item labels",
+        Mm(dimensions.page_width),
+        Mm(dimensions.page_height),
+        "Labels",
+    );
+    let font: IndirectFontRef = document.add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| ShopError::from_error("Failed to add PDF font", Box::new(e)))?;
+    let font_bold: IndirectFontRef = document.add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| ShopError::from_error("Failed to add PDF font", Box::new(e)))?;
+
+    let mut layer: PdfLayerReference = document.get_page(page).get_layer(layer);
+    for (index, item_label) in item_labels.iter().enumerate() {
+        let position: u32 = skip + index as u32;
+        if position > 0 && position.is_multiple_of(labels_per_page) {
+            let (page, page_layer) = document.add_page(Mm(dimensions.page_width), Mm(dimensions.page_height), "Labels");
+            layer = document.get_page(page).get_layer(page_layer);
+        }
+
+        let position: u32 = position % labels_per_page;
+        let left: f32 = dimensions.margin_left + (position % dimensions.columns) as f32 * dimensions.pitch_horizontal;
+        let top: f32 = dimensions.page_height
+            - dimensions.margin_top
+            - (position / dimensions.columns) as f32 * dimensions.pitch_vertical;
+        render_label(&layer, (&font, &font_bold), &dimensions, symbology, item_label, (left, top))?;
+    }
+
+    save_document(document)
+}
+
+/// fonts: Regular and bold
+/// origin: The top left corner of the label in PDF coordinates, whose origin is the bottom left of the page.
+fn render_label(
+    layer: &PdfLayerReference,
+    (font, font_bold): (&IndirectFontRef, &IndirectFontRef),
+    dimensions: &LabelSheetDimensions,
+    symbology: &Symbology,
+    item_label: &ItemLabel,
+    (left, top): (f32, f32),
+) -> Result<(), ShopError> {
+    let matrix: BarcodeMatrix = encode(symbology, &get_sku_data(symbology, &item_label.item_id, &item_label.item_code))?;
+
+    let inner_left: f32 = left + LABEL_PADDING_MM;
+    let inner_top: f32 = top - LABEL_PADDING_MM;
+    let inner_width: f32 = dimensions.label_width - 2.0 * LABEL_PADDING_MM;
+    let inner_height: f32 = dimensions.label_height - 2.0 * LABEL_PADDING_MM;
+
+    // QR codes sit to the left of the text; linear barcodes span the label above the text.
+    let (text_left, text_top, text_width, text_height) = match symbology {
+        Symbology::Qr => {
+            let side: f32 = inner_height;
+            render_pdf_matrix(layer, &matrix, inner_left, inner_top, side, side);
+            (inner_left + side + LABEL_PADDING_MM, inner_top, inner_width - side - LABEL_PADDING_MM, inner_height)
+        }
+        Symbology::Code128 => {
+            let barcode_height: f32 = inner_height * 0.45;
+            render_pdf_matrix(layer, &matrix, inner_left, inner_top, inner_width, barcode_height);
+            let text_top: f32 = inner_top - barcode_height - LABEL_PADDING_MM / 2.0;
+            (inner_left, text_top, inner_width, inner_height - barcode_height - LABEL_PADDING_MM / 2.0)
+        }
+    };
+
+    let line_height: f32 = text_height / 4.0;
+    let font_size: f32 = (line_height / MM_PER_POINT / 1.25).min(LABEL_FONT_SIZE_MAX);
+    let lines: [(&str, f32, &IndirectFontRef); 4] = [
+        (&item_label.display_name, font_size, font_bold),
+        (&item_label.condition, font_size, font),
+        (&item_label.price, font_size, font_bold),
+        (&item_label.item_code, font_size, font_bold),
+    ];
+
+    layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+    for (index, (text, size, line_font)) in lines.into_iter().enumerate() {
+        let baseline: f32 = text_top - (index as f32 + 1.0) * line_height + (line_height - size * MM_PER_POINT) / 2.0;
+        layer.use_text(fit_text(text, size, text_width), size, Mm(text_left), Mm(baseline), line_font);
+    }
+    Ok(())
+}
+
+/// Draws the matrix, including its quiet zone, stretched to fill the given box.
+fn render_pdf_matrix(layer: &PdfLayerReference, matrix: &BarcodeMatrix, left: f32, top: f32, width: f32, height: f32) {
+    let module_width: f32 = width / matrix.columns as f32;
+    let module_height: f32 = height / (matrix.rows * matrix.row_height) as f32;
+
+    layer.set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+    for row in 0..matrix.rows {
+        let row_top: f32 = top - (row * matrix.row_height) as f32 * module_height;
+        let row_bottom: f32 = row_top - matrix.row_height as f32 * module_height;
+        for (column, length) in matrix.get_dark_runs(row) {
+            let run_left: f32 = left + column as f32 * module_width;
+            let run_right: f32 = run_left + length as f32 * module_width;
+            layer.add_rect(Rect::new(Mm(run_left), Mm(row_bottom), Mm(run_right), Mm(row_top)));
+        }
+    }
+}
+
+/// Truncates the text to the approximate number of characters which fit the width. Built-in PDF fonts cannot encode
+/// most non-ASCII characters, so these are replaced.
+fn fit_text(text: &str, font_size: f32, width: f32) -> String {
+    let max_characters: usize = (width / (font_size * MM_PER_POINT * HELVETICA_CHARACTER_WIDTH)) as usize;
+    let text: String = text.chars()
+        .map(|character| if character.is_ascii() && !character.is_ascii_control() { character } else { '?' })
+        .collect();
+    if text.len() <= max_characters {
+        return text;
+    }
+    let mut text: String = text[..max_characters.saturating_sub(3)].to_string();
+    text.push_str("...");
+    text
+}
+
+fn save_document(document: PdfDocumentReference) -> Result<Vec<u8>, ShopError> {
+    document.save_to_bytes()
+        .map_err(|e| ShopError::from_error("Failed to write PDF", Box::new(e)))
+}
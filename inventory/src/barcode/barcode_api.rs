@@ -0,0 +1,83 @@
+use crate::barcode::{barcode_action, BarcodeImageFormat, BarcodeMatrix, BarcodeParameters, ItemLabel, LabelSheetSerial, LABEL_SHEET_ITEM_COUNT_MAX};
+use crate::item::{item_api, item_db, Item, ItemEntity};
+use crate::{try_return, unwrap_option_else_404, unwrap_result_else_500, ShopEntity};
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/barcode")
+            .route("/item/{item_id}", web::get().to(get_item_barcode))
+            .route("/label_sheet", web::post().to(create_label_sheet))
+    );
+}
+
+/// Renders the item's SKU as a standalone barcode image.
+async fn get_item_barcode(
+    pgpool: web::Data<PgPool>,
+    item_id: web::Path<String>,
+    parameters: web::Query<BarcodeParameters>,
+) -> HttpResponse {
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    let parameters: BarcodeParameters = parameters.into_inner();
+    let item: ItemEntity = unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_id).await));
+    let item: Item = unwrap_result_else_500!(item.try_to_model());
+
+    let data: String = barcode_action::get_sku_data(&parameters.symbology, &item.id, &item.code);
+    let matrix: BarcodeMatrix = unwrap_result_else_500!(barcode_action::encode(&parameters.symbology, &data));
+    let body: Vec<u8> = match parameters.format {
+        BarcodeImageFormat::Svg => barcode_action::render_svg(&matrix).into_bytes(),
+        BarcodeImageFormat::Png => match barcode_action::render_png(&matrix, parameters.module_size) {
+            Ok(png) => png,
+            Err(error) => return HttpResponse::BadRequest().body(error.message),
+        },
+    };
+
+    HttpResponse::Ok()
+        .content_type(parameters.format.get_content_type())
+        .body(body)
+}
+
+/// Renders a printable PDF with one label per item.
+async fn create_label_sheet(
+    pgpool: web::Data<PgPool>,
+    label_sheet: web::Json<LabelSheetSerial>,
+) -> HttpResponse {
+    let label_sheet: LabelSheetSerial = label_sheet.into_inner();
+    if label_sheet.item_ids.is_empty() || label_sheet.item_ids.len() > LABEL_SHEET_ITEM_COUNT_MAX {
+        return HttpResponse::BadRequest()
+            .body(format!("Item count must be between 1 and {};", LABEL_SHEET_ITEM_COUNT_MAX));
+    }
+    let labels_per_sheet: u32 = label_sheet.layout.get_dimensions().get_labels_per_sheet();
+    if label_sheet.skip >= labels_per_sheet {
+        return HttpResponse::BadRequest()
+            .body(format!("Skip must be less than the {} labels per sheet;", labels_per_sheet));
+    }
+
+    let mut item_ids: Vec<Uuid> = Vec::with_capacity(label_sheet.item_ids.len());
+    for item_id in &label_sheet.item_ids {
+        item_ids.push(try_return!(item_api::resolve_item_id(&pgpool, item_id).await));
+    }
+
+    let item_labels: Vec<ItemLabel> = match barcode_action::get_item_labels(&pgpool, &item_ids).await {
+        Ok(item_labels) => item_labels,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let pdf: Vec<u8> = unwrap_result_else_500!(barcode_action::render_label_sheet(
+        &item_labels,
+        &label_sheet.layout,
+        &label_sheet.symbology,
+        label_sheet.skip,
+    ));
+
+    HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Inline,
+            parameters: vec![DispositionParam::Filename(String::from("labels.pdf"))],
+        })
+        .body(pdf)
+}
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const LABEL_SHEET_ITEM_COUNT_MAX: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Symbology {
+    #[serde(rename = "code128")]
+    Code128,
+    #[serde(rename = "qr")]
+    Qr,
+}
+
+impl Default for Symbology {
+    fn default() -> Self {
+        Symbology::Qr
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BarcodeImageFormat {
+    #[serde(rename = "svg")]
+    Svg,
+    #[serde(rename = "png")]
+    Png,
+}
+
+impl Default for BarcodeImageFormat {
+    fn default() -> Self {
+        BarcodeImageFormat::Svg
+    }
+}
+
+impl BarcodeImageFormat {
+    pub fn get_content_type(&self) -> &'static str {
+        match self {
+            BarcodeImageFormat::Svg => "image/svg+xml",
+            BarcodeImageFormat::Png => "image/png",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BarcodeParameters {
+    #[serde(default)]
+    pub symbology: Symbology,
+    #[serde(default)]
+    pub format: BarcodeImageFormat,
+    /// Width of the narrowest bar or module in pixels. Only applies to raster formats.
+    #[serde(default = "BarcodeParameters::default_module_size")]
+    pub module_size: u32,
+}
+
+impl BarcodeParameters {
+    fn default_module_size() -> u32 {
+        4
+    }
+}
+
+/// A grid of dark (true) and light (false) modules in row-major order, including the quiet zone.
+/// Linear symbologies have a single row, which is stretched to the row height when rendered.
+#[derive(Debug, Clone)]
+pub struct BarcodeMatrix {
+    pub columns: u32,
+    pub rows: u32,
+    /// Height of each row in modules
+    pub row_height: u32,
+    pub modules: Vec<bool>,
+}
+
+/// Common adhesive label stock. Dimensions are those published by the manufacturer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LabelSheetLayout {
+    /// US Letter; 30 labels of 2 5/8" x 1"
+    #[serde(rename = "avery_5160")]
+    Avery5160,
+    /// US Letter; 10 labels of 4" x 2"
+    #[serde(rename = "avery_5163")]
+    Avery5163,
+    /// A4; 21 labels of 63.5mm x 38.1mm
+    #[serde(rename = "avery_l7160")]
+    AveryL7160,
+}
+
+impl Default for LabelSheetLayout {
+    fn default() -> Self {
+        LabelSheetLayout::Avery5160
+    }
+}
+
+/// All lengths are in millimeters and are measured from the top left corner of the page.
+#[derive(Debug, Clone)]
+pub struct LabelSheetDimensions {
+    pub page_width: f32,
+    pub page_height: f32,
+    pub columns: u32,
+    pub rows: u32,
+    pub label_width: f32,
+    pub label_height: f32,
+    pub margin_left: f32,
+    pub margin_top: f32,
+    /// Distance between the left edges of horizontally adjacent labels
+    pub pitch_horizontal: f32,
+    /// Distance between the top edges of vertically adjacent labels
+    pub pitch_vertical: f32,
+}
+
+impl LabelSheetDimensions {
+    pub fn get_labels_per_sheet(&self) -> u32 {
+        self.columns * self.rows
+    }
+}
+
+impl LabelSheetLayout {
+    pub fn get_dimensions(&self) -> LabelSheetDimensions {
+        match self {
+            LabelSheetLayout::Avery5160 => LabelSheetDimensions {
+                page_width: 215.9,
+                page_height: 279.4,
+                columns: 3,
+                rows: 10,
+                label_width: 66.675,
+                label_height: 25.4,
+                margin_left: 4.7625,
+                margin_top: 12.7,
+                pitch_horizontal: 69.85,
+                pitch_vertical: 25.4,
+            },
+            LabelSheetLayout::Avery5163 => LabelSheetDimensions {
+                page_width: 215.9,
+                page_height: 279.4,
+                columns: 2,
+                rows: 5,
+                label_width: 101.6,
+                label_height: 50.8,
+                margin_left: 3.96875,
+                margin_top: 12.7,
+                pitch_horizontal: 104.775,
+                pitch_vertical: 50.8,
+            },
+            LabelSheetLayout::AveryL7160 => LabelSheetDimensions {
+                page_width: 210.0,
+                page_height: 297.0,
+                columns: 3,
+                rows: 7,
+                label_width: 63.5,
+                label_height: 38.1,
+                margin_left: 7.2,
+                margin_top: 15.15,
+                pitch_horizontal: 66.04,
+                pitch_vertical: 38.1,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelSheetSerial {
+    /// One label is printed per item, in the given order. Item IDs and item codes are both accepted.
+    pub item_ids: Vec<String>,
+    #[serde(default)]
+    pub layout: LabelSheetLayout,
+    #[serde(default)]
+    pub symbology: Symbology,
+    /// Number of label positions to leave blank at the start of the first sheet, so that partially used sheets can be
+    /// reused. Must be less than the number of labels per sheet.
+    #[serde(default)]
+    pub skip: u32,
+}
+
+/// The printed contents of a single label.
+#[derive(Debug, Clone)]
+pub struct ItemLabel {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub display_name: String,
+    pub condition: String,
+    pub price: String,
+}
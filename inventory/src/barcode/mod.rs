@@ -0,0 +1,5 @@
+pub mod barcode_api;
+pub mod barcode_model;
+pub mod barcode_action;
+
+pub use barcode_model::*;
@@ -0,0 +1,51 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use inventory::import::{import_action, ImportFormat, ImportReport};
+use inventory::environment;
+use sqlx::{Pool, Postgres};
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+
+#[actix_web::main]
+async fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let matches: ArgMatches = Command::new("shop-import")
+        .version("1.0")
+        .about("Import products, items, attributes, and labels from a CSV or JSONL file in a single transaction.")
+        .author("Zachary Siegel")
+        .arg(Arg::new("path")
+            .help("The file to import")
+            .required(true))
+        .arg(Arg::new("format")
+            .help("The file format; inferred from the file extension if omitted")
+            .short('f')
+            .long("format")
+            .value_parser(["csv", "jsonl"]))
+        .arg(Arg::new("dry_run")
+            .help("Validate every row and report errors without modifying the database")
+            .short('n')
+            .long("dry-run")
+            .action(ArgAction::SetTrue))
+        .get_matches();
+
+    let path: &String = matches.get_one("path")
+        .expect("path is required");
+    let format: ImportFormat = match matches.get_one::<String>("format").map(String::as_str) {
+        Some("jsonl") => ImportFormat::Jsonl,
+        Some(_) => ImportFormat::Csv,
+        None => ImportFormat::from_path(path),
+    };
+    let dry_run: bool = matches.get_flag("dry_run");
+
+    environment::load_env()?;
+    let pgpool: Pool<Postgres> = inventory::db::sqlx_connect().await?;
+
+    let bytes: Vec<u8> = fs::read(path)?;
+    let rows = import_action::parse_rows(&format, &bytes);
+    let report: ImportReport = import_action::import(&pgpool, rows, dry_run).await?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    match report.errors.is_empty() {
+        true => Ok(ExitCode::SUCCESS),
+        false => Ok(ExitCode::FAILURE),
+    }
+}
@@ -0,0 +1,352 @@
+use super::*;
+use crate::environment::RuntimeEnvironment;
+use crate::error::ShopError;
+use crate::item::{item_action, item_db, Item, ItemStatus};
+use crate::item_audit::ItemAudit;
+use crate::item_image::item_image_action;
+use crate::listing::{listing_db, Listing};
+use crate::{environment, object, ShopEntity, ShopModel};
+use actix_web::web::Payload;
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+impl BundleImage {
+    pub fn new(
+        bundle_id: Uuid,
+        alt_text: String,
+        original_file_name: String,
+    ) -> BundleImage {
+        BundleImage {
+            id: object::random_uuid(),
+            bundle_id,
+            alt_text,
+            priority: 0, // Priority mechanism is currently unused
+            original_file_name,
+        }
+    }
+
+    pub fn get_bundle_image_name(&self) -> String {
+        format!("bundle_{}_{}_{}", self.bundle_id, self.id, self.original_file_name)
+    }
+
+    pub fn get_bundle_image_path(&self) -> Result<PathBuf, ShopError> {
+        let images_directory = environment::images_directory_path()?;
+        Ok(images_directory.join(self.get_bundle_image_name()))
+    }
+
+    pub fn get_bundle_image_uri(&self) -> String {
+        let host: &str = RuntimeEnvironment::default().get_origin();
+        format!(
+            "{}/{}/{}",
+            host,
+            environment::images_directory_subpath(),
+            self.get_bundle_image_name()
+        )
+    }
+
+    pub fn delete_image_file(&self) -> Result<(), ShopError> {
+        fs::remove_file(self.get_bundle_image_path()?)
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))
+    }
+
+    /// If an error is returned, any created file will be deleted before returning.
+    pub async fn store_image_file(&self, payload: &mut Payload) -> Result<(), ShopError> {
+        item_image_action::store_image_file(&self.get_bundle_image_path()?, payload).await
+    }
+}
+
+/// Includes the IDs of the bundle's items.
+pub async fn get_bundle(pgpool: &PgPool, bundle_id: &Uuid) -> Result<Option<Bundle>, ShopError> {
+    let Some(entity) = bundle_db::get_bundle(pgpool, bundle_id).await? else {
+        return Ok(None);
+    };
+
+    let mut bundle: Bundle = entity.try_to_model()?;
+    bundle.item_ids = bundle_db::get_all_bundle_items(pgpool, &[*bundle_id]).await?
+        .iter()
+        .map(|bundle_item| bundle_item.item_id)
+        .collect();
+    Ok(Some(bundle))
+}
+
+/// Includes the IDs of each bundle's items.
+pub async fn get_all_bundles(pgpool: &PgPool) -> Result<Vec<Bundle>, ShopError> {
+    let mut bundles: Vec<Bundle> = bundle_db::get_all_bundles(pgpool).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bundle_ids: Vec<Uuid> = bundles.iter().map(|bundle| bundle.id).collect();
+    let bundle_item_entities: Vec<BundleItemEntity> = bundle_db::get_all_bundle_items(pgpool, &bundle_ids).await?;
+    for bundle in &mut bundles {
+        bundle.item_ids = bundle_item_entities.iter()
+            .filter(|bundle_item| bundle_item.bundle_id == bundle.id)
+            .map(|bundle_item| bundle_item.item_id)
+            .collect();
+    }
+    Ok(bundles)
+}
+
+/// The items of the bundle, in the order they were added
+pub async fn get_all_bundle_items(pgpool: &PgPool, bundle_id: &Uuid) -> Result<Vec<Item>, ShopError> {
+    let mut items: Vec<Item> = Vec::new();
+    for bundle_item in bundle_db::get_all_bundle_items(pgpool, &[*bundle_id]).await? {
+        let item: Item = item_db::get_item(pgpool, &bundle_item.item_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", bundle_item.item_id)))?
+            .try_to_model()?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+pub async fn get_all_bundle_images(pgpool: &PgPool, bundle_id: &Uuid) -> Result<Vec<BundleImage>, ShopError> {
+    bundle_db::get_all_bundle_images(pgpool, bundle_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn get_all_bundle_listings(pgexecutor: impl PgExecutor<'_>, bundle_id: &Uuid) -> Result<Vec<Listing>, ShopError> {
+    listing_db::get_all_bundle_listings(pgexecutor, bundle_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn create_bundle(pgpool: &PgPool, mut bundle: Bundle) -> Result<Bundle, ShopError> {
+    bundle.created = Utc::now();
+    bundle.updated = bundle.created;
+    bundle_db::create_bundle(pgpool, &bundle.to_entity()).await?;
+    Ok(bundle)
+}
+
+/// Replaces every field of the stored bundle except for its items and creation time. Fails if the bundle has an active
+/// listing, whose marketplace offer would no longer match the bundle.
+pub async fn update_bundle(pgpool: &PgPool, bundle_id: &Uuid, mut bundle: Bundle) -> Result<Bundle, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let current: Bundle = get_bundle_for_update(&mut transaction, bundle_id).await?;
+    validate_bundle_unlisted(&mut transaction, bundle_id).await?;
+
+    bundle.id = current.id;
+    bundle.created = current.created;
+    bundle.updated = Utc::now();
+    bundle_db::update_bundle(&mut *transaction, &bundle.to_entity()).await?;
+    bundle.item_ids = bundle_db::get_all_bundle_items(&mut *transaction, &[bundle.id]).await?
+        .iter()
+        .map(|bundle_item| bundle_item.item_id)
+        .collect();
+
+    transaction.commit().await?;
+    Ok(bundle)
+}
+
+/// Deletes the bundle with its inactive listings and images, releasing its items. Image files are removed only after
+/// the transaction commits. Fails if the bundle has any purchase, which must be retained, or any active listing, which
+/// must be withdrawn first. False if the bundle does not exist.
+pub async fn delete_bundle(pgpool: &PgPool, bundle_id: &Uuid) -> Result<bool, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    if bundle_db::get_bundle_for_update(&mut *transaction, bundle_id).await?.is_none() {
+        return Ok(false);
+    }
+    if !bundle_db::get_all_bundle_purchases(&mut *transaction, bundle_id).await?.is_empty() {
+        return Err(ShopError::new(&format!("Bundle has purchases, which must be retained; [{}]", bundle_id)));
+    }
+    validate_bundle_unlisted(&mut transaction, bundle_id).await?;
+
+    let bundle_images: Vec<BundleImage> = bundle_db::get_all_bundle_images(&mut *transaction, bundle_id).await?;
+    bundle_db::delete_all_bundle_listings(&mut *transaction, bundle_id).await?;
+    bundle_db::delete_bundle(&mut *transaction, bundle_id).await?;
+
+    transaction.commit().await?;
+
+    for bundle_image in bundle_images {
+        if let Err(error) = bundle_image.delete_image_file() {
+            log::warn!("Failed to delete bundle image file; [{}]; {}", bundle_image.id, error.message);
+        }
+    }
+    Ok(true)
+}
+
+/// An item can join a bundle only while neither is listed, and only if it is unsold, not consigned and in no other
+/// bundle.
+pub async fn create_bundle_item(pgpool: &PgPool, bundle_id: &Uuid, item_id: &Uuid) -> Result<Bundle, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    get_bundle_for_update(&mut transaction, bundle_id).await?;
+    validate_bundle_unlisted(&mut transaction, bundle_id).await?;
+
+    let item: Item = item_db::get_item_for_update(&mut *transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()?;
+    if item.status.is_sold() {
+        return Err(ShopError::new(&format!("Item has already been sold; [{}]", item_id)));
+    }
+    // A bundle's sale price cannot be attributed to a single consignor
+    if item.consignor_id.is_some() {
+        return Err(ShopError::new(&format!("Consigned items cannot be bundled; [{}]", item_id)));
+    }
+    if let Some(bundle_item) = bundle_db::get_bundle_item(&mut *transaction, item_id).await? {
+        return Err(ShopError::new(&format!("Item already belongs to a bundle; [{}]; [{}]", item_id, bundle_item.bundle_id)));
+    }
+    let listings: Vec<Listing> = item_action::get_all_item_listings(&mut *transaction, item_id).await?;
+    if listings.iter().any(|listing| listing.status.is_active()) {
+        return Err(ShopError::new(&format!("Item has active listings, which must be withdrawn; [{}]", item_id)));
+    }
+
+    let bundle_item = BundleItemEntity {
+        item_id: item.id,
+        bundle_id: *bundle_id,
+        created: Utc::now(),
+    };
+    bundle_db::create_bundle_item(&mut *transaction, &bundle_item).await?;
+
+    transaction.commit().await?;
+    get_bundle(pgpool, bundle_id).await?
+        .ok_or_else(|| ShopError::new(&format!("Bundle not found; [{}]", bundle_id)))
+}
+
+/// Fails if the bundle is listed or has purchases, which must keep the items they sold. None if the item does not
+/// belong to the bundle.
+pub async fn delete_bundle_item(pgpool: &PgPool, bundle_id: &Uuid, item_id: &Uuid) -> Result<Option<Bundle>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    get_bundle_for_update(&mut transaction, bundle_id).await?;
+    if !bundle_db::get_all_bundle_purchases(&mut *transaction, bundle_id).await?.is_empty() {
+        return Err(ShopError::new(&format!("Bundle has purchases, which must retain its items; [{}]", bundle_id)));
+    }
+    validate_bundle_unlisted(&mut transaction, bundle_id).await?;
+    if bundle_db::delete_bundle_item(&mut *transaction, bundle_id, item_id).await?.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    transaction.commit().await?;
+    get_bundle(pgpool, bundle_id).await
+}
+
+/// Moves every item of the bundle to the status, recording an [ItemAudit] for each item whose status changes. Either
+/// every item changes or, if any transition is not permitted, none does.
+pub async fn update_bundle_item_statuses(
+    pgpool: &PgPool,
+    bundle_id: &Uuid,
+    status: ItemStatus,
+    initiated_by_admin: bool,
+    audit_note: Option<String>,
+) -> Result<Vec<ItemAudit>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    get_bundle_for_update(&mut transaction, bundle_id).await?;
+    let items: Vec<Item> = get_all_bundle_items_for_update(&mut transaction, bundle_id).await?;
+    let mut item_audits: Vec<ItemAudit> = Vec::with_capacity(items.len());
+    for item in items.iter().filter(|item| item.status != status) {
+        item_audits.push(item_action::update_item_status_in_transaction(
+            &mut transaction,
+            item,
+            status.clone(),
+            initiated_by_admin,
+            audit_note.clone(),
+        ).await?);
+    }
+
+    transaction.commit().await?;
+    Ok(item_audits)
+}
+
+/// Marks every item of the bundle as purchased within the caller's transaction. Items are still listed unless the
+/// purchase was made while they were delisted, e.g. on hold for the buyer.
+/// A bundle is a single unit, so only a quantity of one can be sold.
+pub async fn record_sale_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    bundle_id: &Uuid,
+    quantity: u32,
+) -> Result<Vec<ItemAudit>, ShopError> {
+    if quantity != 1 {
+        return Err(ShopError::new(&format!("A bundle can only be sold as a single unit; [{}]", bundle_id)));
+    }
+
+    let items: Vec<Item> = get_all_bundle_items_for_update(transaction, bundle_id).await?;
+    if items.is_empty() {
+        return Err(ShopError::new(&format!("Bundle has no items; [{}]", bundle_id)));
+    }
+    let mut item_audits: Vec<ItemAudit> = Vec::with_capacity(items.len());
+    for item in &items {
+        if item.status.is_sold() {
+            return Err(ShopError::new(&format!("Bundle item has already been sold; [{}]", item.id)));
+        }
+        let status: ItemStatus = match item.status.get_successors().contains(&ItemStatus::PurchaseListed) {
+            true => ItemStatus::PurchaseListed,
+            false => ItemStatus::PurchasedDelisted,
+        };
+        item_audits.push(item_action::update_item_status_in_transaction(
+            transaction,
+            item,
+            status,
+            false,
+            Some(format!("Sold in bundle {}", bundle_id)),
+        ).await?);
+    }
+    Ok(item_audits)
+}
+
+/// A bundle can be listed only if it has items and none of them is listed on its own.
+pub async fn validate_bundle_listable(pgpool: &PgPool, bundle_id: &Uuid) -> Result<(), ShopError> {
+    let bundle_items: Vec<BundleItemEntity> = bundle_db::get_all_bundle_items(pgpool, &[*bundle_id]).await?;
+    if bundle_items.is_empty() {
+        return Err(ShopError::new(&format!("Bundle has no items; [{}]", bundle_id)));
+    }
+    for bundle_item in &bundle_items {
+        let listings: Vec<Listing> = item_action::get_all_item_listings(pgpool, &bundle_item.item_id).await?;
+        if listings.iter().any(|listing| listing.status.is_active()) {
+            return Err(ShopError::new(&format!("Bundle item is listed on its own; [{}]", bundle_item.item_id)));
+        }
+    }
+    Ok(())
+}
+
+/// An item can be listed on its own only if it does not belong to a listed bundle.
+pub async fn validate_item_listable(pgpool: &PgPool, item_id: &Uuid) -> Result<(), ShopError> {
+    let Some(bundle_item) = bundle_db::get_bundle_item(pgpool, item_id).await? else {
+        return Ok(());
+    };
+    let listings: Vec<Listing> = get_all_bundle_listings(pgpool, &bundle_item.bundle_id).await?;
+    if listings.iter().any(|listing| listing.status.is_active()) {
+        return Err(ShopError::new(&format!("Item belongs to a listed bundle; [{}]; [{}]", item_id, bundle_item.bundle_id)));
+    }
+    Ok(())
+}
+
+async fn get_bundle_for_update(transaction: &mut Transaction<'_, Postgres>, bundle_id: &Uuid) -> Result<Bundle, ShopError> {
+    bundle_db::get_bundle_for_update(&mut **transaction, bundle_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Bundle not found; [{}]", bundle_id)))?
+        .try_to_model()
+}
+
+async fn get_all_bundle_items_for_update(
+    transaction: &mut Transaction<'_, Postgres>,
+    bundle_id: &Uuid,
+) -> Result<Vec<Item>, ShopError> {
+    let mut items: Vec<Item> = Vec::new();
+    for bundle_item in bundle_db::get_all_bundle_items(&mut **transaction, &[*bundle_id]).await? {
+        let item: Item = item_db::get_item_for_update(&mut **transaction, &bundle_item.item_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", bundle_item.item_id)))?
+            .try_to_model()?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+async fn validate_bundle_unlisted(transaction: &mut Transaction<'_, Postgres>, bundle_id: &Uuid) -> Result<(), ShopError> {
+    let listings: Vec<Listing> = get_all_bundle_listings(&mut **transaction, bundle_id).await?;
+    if listings.iter().any(|listing| listing.status.is_active()) {
+        return Err(ShopError::new(&format!("Bundle has active listings, which must be withdrawn; [{}]", bundle_id)));
+    }
+    Ok(())
+}
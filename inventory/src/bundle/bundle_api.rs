@@ -0,0 +1,186 @@
+use super::*;
+use crate::item::item_api::resolve_item_id;
+use crate::item::ItemStatus;
+use crate::item_audit::ItemAudit;
+use crate::listing::{Listing, ListingSerial};
+use crate::object::JsonHttpResponse;
+use crate::{try_return, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/bundle")
+            .route("", web::get().to(get_all_bundles))
+            .route("", web::post().to(create_bundle))
+            .route("/{bundle_id}", web::get().to(get_bundle))
+            .route("/{bundle_id}", web::put().to(update_bundle))
+            .route("/{bundle_id}", web::delete().to(delete_bundle))
+            .route("/{bundle_id}/item/{item_id}", web::put().to(create_bundle_item))
+            .route("/{bundle_id}/item/{item_id}", web::delete().to(delete_bundle_item))
+            .route("/{bundle_id}/image", web::get().to(get_all_bundle_images))
+            .route("/{bundle_id}/image", web::post().to(create_bundle_image))
+            .route("/{bundle_id}/image/{bundle_image_id}", web::delete().to(delete_bundle_image))
+            .route("/{bundle_id}/listing", web::get().to(get_all_bundle_listings))
+            .route("/{bundle_id}/status", web::post().to(update_bundle_item_statuses))
+    );
+}
+
+async fn get_all_bundles(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let bundles: Vec<Bundle> = unwrap_result_else_500!(bundle_action::get_all_bundles(&pgpool).await);
+    bundles.iter()
+        .map(|bundle| bundle.to_serial())
+        .collect::<Vec<BundleSerial>>()
+        .to_http_response()
+}
+
+async fn create_bundle(pgpool: web::Data<PgPool>, body: web::Json<BundleSerial>) -> HttpResponse {
+    let bundle: Bundle = match body.into_inner().try_to_model() {
+        Ok(bundle) => bundle,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    let bundle: Bundle = unwrap_result_else_500!(bundle_action::create_bundle(&pgpool, bundle).await);
+    bundle.to_serial().to_http_response()
+}
+
+async fn get_bundle(pgpool: web::Data<PgPool>, bundle_id: web::Path<String>) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    let bundle: Bundle = unwrap_option_else_404!(unwrap_result_else_500!(bundle_action::get_bundle(&pgpool, &bundle_id).await));
+    bundle.to_serial().to_http_response()
+}
+
+async fn update_bundle(
+    pgpool: web::Data<PgPool>,
+    bundle_id: web::Path<String>,
+    body: web::Json<BundleSerial>,
+) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    let bundle: Bundle = match body.into_inner().try_to_model() {
+        Ok(bundle) => bundle,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    unwrap_option_else_404!(unwrap_result_else_500!(bundle_db::get_bundle(pgpool.get_ref(), &bundle_id).await));
+
+    match bundle_action::update_bundle(&pgpool, &bundle_id, bundle).await {
+        Ok(bundle) => bundle.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Purchased bundles are never deleted. The bundle's items are released, not deleted.
+async fn delete_bundle(pgpool: web::Data<PgPool>, bundle_id: web::Path<String>) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    match bundle_action::delete_bundle(&pgpool, &bundle_id).await {
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Responds with the bundle, including its new item.
+async fn create_bundle_item(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (bundle_id, item_id) = path.into_inner();
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id).await);
+    unwrap_option_else_404!(unwrap_result_else_500!(bundle_db::get_bundle(pgpool.get_ref(), &bundle_id).await));
+
+    match bundle_action::create_bundle_item(&pgpool, &bundle_id, &item_id).await {
+        Ok(bundle) => bundle.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Responds with the bundle, without the removed item.
+async fn delete_bundle_item(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (bundle_id, item_id) = path.into_inner();
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id).await);
+    unwrap_option_else_404!(unwrap_result_else_500!(bundle_db::get_bundle(pgpool.get_ref(), &bundle_id).await));
+
+    match bundle_action::delete_bundle_item(&pgpool, &bundle_id, &item_id).await {
+        Ok(Some(bundle)) => bundle.to_serial().to_http_response(),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_all_bundle_images(pgpool: web::Data<PgPool>, bundle_id: web::Path<String>) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    let bundle_images: Vec<BundleImage> = unwrap_result_else_500!(bundle_action::get_all_bundle_images(&pgpool, &bundle_id).await);
+    bundle_images.iter()
+        .map(|bundle_image| bundle_image.to_serial())
+        .collect::<Vec<BundleImageSerial>>()
+        .to_http_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateBundleImageParameters {
+    pub alt_text: String,
+    pub original_file_name: String,
+}
+
+async fn create_bundle_image(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<CreateBundleImageParameters>,
+    bundle_id: web::Path<String>,
+    mut payload: web::Payload,
+) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    unwrap_option_else_404!(unwrap_result_else_500!(bundle_db::get_bundle(pgpool.get_ref(), &bundle_id).await));
+    let bundle_image: BundleImage = BundleImage::new(
+        bundle_id,
+        parameters.alt_text.clone(),
+        parameters.original_file_name.clone(),
+    );
+
+    unwrap_result_else_500!(bundle_image.store_image_file(&mut payload).await);
+    unwrap_result_else_500!(bundle_db::create_bundle_image(pgpool.get_ref(), &bundle_image).await);
+
+    bundle_image.to_serial().to_http_response()
+}
+
+async fn delete_bundle_image(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (_, bundle_image_id): (String, String) = path.into_inner();
+    let bundle_image_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_image_id));
+    let bundle_image: BundleImage = unwrap_option_else_404!(unwrap_result_else_500!(
+        bundle_db::get_bundle_image(&pgpool, &bundle_image_id).await
+    ));
+
+    unwrap_result_else_500!(bundle_db::delete_bundle_image(pgpool.get_ref(), &bundle_image.id).await);
+    if let Err(error) = bundle_image.delete_image_file() {
+        log::warn!("Bundle image DB record was deleted, but the image file was not; [{}]; {}", bundle_image.id, error.message);
+    }
+    HttpResponse::NoContent().finish()
+}
+
+async fn get_all_bundle_listings(pgpool: web::Data<PgPool>, bundle_id: web::Path<String>) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    let listings: Vec<Listing> = unwrap_result_else_500!(bundle_action::get_all_bundle_listings(pgpool.get_ref(), &bundle_id).await);
+    listings.iter()
+        .map(|listing| listing.to_serial())
+        .collect::<Vec<ListingSerial>>()
+        .to_http_response()
+}
+
+/// Every item of the bundle moves to the status together, or none does.
+async fn update_bundle_item_statuses(
+    pgpool: web::Data<PgPool>,
+    bundle_id: web::Path<String>,
+    body: web::Json<BundleStatusChangeSerial>,
+) -> HttpResponse {
+    let bundle_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&bundle_id.into_inner()));
+    let body: BundleStatusChangeSerial = body.into_inner();
+    let status: ItemStatus = unwrap_result_else_400!(ItemStatus::try_from_repr(body.status));
+    unwrap_option_else_404!(unwrap_result_else_500!(bundle_db::get_bundle(pgpool.get_ref(), &bundle_id).await));
+
+    match bundle_action::update_bundle_item_statuses(&pgpool, &bundle_id, status, true, body.audit_note).await {
+        Ok(item_audits) => BundleStatusChangeReport {
+            item_audits: item_audits.iter().map(ItemAudit::to_serial).collect(),
+        }.to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
@@ -0,0 +1,276 @@
+use super::*;
+use crate::error::ShopError;
+use crate::purchase::PurchaseEntity;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_bundle(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+) -> Result<Option<BundleEntity>, ShopError> {
+    query_as!(BundleEntity, "
+        select id, display_name, description, condition, price_cents, note, created, updated
+        from shop.public.bundle
+        where id = $1
+    ",
+		bundle_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_bundle_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+) -> Result<Option<BundleEntity>, ShopError> {
+    query_as!(BundleEntity, "
+        select id, display_name, description, condition, price_cents, note, created, updated
+        from shop.public.bundle
+        where id = $1
+        for update
+    ",
+		bundle_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recently created bundles first
+pub async fn get_all_bundles(pgpool: &PgPool) -> Result<Vec<BundleEntity>, ShopError> {
+    query_as!(BundleEntity, "
+        select id, display_name, description, condition, price_cents, note, created, updated
+        from shop.public.bundle
+        order by created desc, id
+    ")
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_bundle(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle: &BundleEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.bundle (id, display_name, description, condition, price_cents, note, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8)
+    ",
+		bundle.id,
+		bundle.display_name,
+		bundle.description,
+		bundle.condition,
+		bundle.price_cents,
+		bundle.note,
+		bundle.created,
+		bundle.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_bundle(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle: &BundleEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.bundle
+        set (display_name, description, condition, price_cents, note, updated) = ($2, $3, $4, $5, $6, $7)
+        where id = $1
+    ",
+		bundle.id,
+		bundle.display_name,
+		bundle.description,
+		bundle.condition,
+		bundle.price_cents,
+		bundle.note,
+		bundle.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Item memberships and images are deleted by cascade.
+pub async fn delete_bundle(pgexecutor: impl PgExecutor<'_>, bundle_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.bundle
+        where id = $1
+    ",
+		bundle_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Ordered by bundle, then by the time each item was added
+pub async fn get_all_bundle_items(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_ids: &[Uuid],
+) -> Result<Vec<BundleItemEntity>, ShopError> {
+    query_as!(BundleItemEntity, "
+        select item_id, bundle_id, created
+        from shop.public.bundle_item
+        where bundle_id = any($1)
+        order by bundle_id, created, item_id
+    ",
+		bundle_ids
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The item's membership in a bundle, if any
+pub async fn get_bundle_item(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<BundleItemEntity>, ShopError> {
+    query_as!(BundleItemEntity, "
+        select item_id, bundle_id, created
+        from shop.public.bundle_item
+        where item_id = $1
+    ",
+		item_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_bundle_item(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_item: &BundleItemEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.bundle_item (item_id, bundle_id, created)
+        values ($1, $2, $3)
+    ",
+		bundle_item.item_id,
+		bundle_item.bundle_id,
+		bundle_item.created,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_bundle_item(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+    item_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.bundle_item
+        where bundle_id = $1 and item_id = $2
+    ",
+		bundle_id,
+		item_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_bundle_purchases(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+) -> Result<Vec<PurchaseEntity>, ShopError> {
+    query_as!(PurchaseEntity, "
+        select purchase.id, purchase.marketplace_id, purchase.external_id, purchase.customer_id, purchase.contact_email_address, purchase.listing_id, purchase.quantity, purchase.status, purchase.cost_subtotal_cents, purchase.cost_tax_cents, purchase.cost_shipping_cents, purchase.cost_discount_cents, purchase.seller_cost_total_cents, purchase.marketplace_fee_cents, purchase.shipping_method, purchase.payment_method, purchase.note, purchase.shipping_street_address, purchase.shipping_municipality, purchase.shipping_district, purchase.shipping_postal_area, purchase.shipping_country, purchase.billing_street_address, purchase.billing_municipality, purchase.billing_district, purchase.billing_postal_area, purchase.billing_country, purchase.created, purchase.updated
+        from shop.public.purchase
+            inner join shop.public.listing on listing.id = purchase.listing_id
+        where listing.bundle_id = $1
+    ",
+		bundle_id,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_all_bundle_listings(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.listing
+        where bundle_id = $1
+    ",
+		bundle_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_bundle_image(
+    pgpool: &PgPool,
+    bundle_image_id: &Uuid,
+) -> Result<Option<BundleImageEntity>, ShopError> {
+    query_as!(BundleImageEntity, "
+        select id, bundle_id, alt_text, priority, original_file_name
+        from shop.public.bundle_image
+        where id = $1
+    ",
+		bundle_image_id
+	)
+        .fetch_optional(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_bundle_images(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+) -> Result<Vec<BundleImageEntity>, ShopError> {
+    query_as!(BundleImageEntity, "
+        select id, bundle_id, alt_text, priority, original_file_name
+        from shop.public.bundle_image
+        where bundle_id = $1
+        order by priority, id
+    ",
+		bundle_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_bundle_image(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_image: &BundleImageEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.bundle_image (id, bundle_id, alt_text, priority, original_file_name)
+        values ($1, $2, $3, $4, $5)
+    ",
+		bundle_image.id,
+		bundle_image.bundle_id,
+		bundle_image.alt_text,
+		bundle_image.priority,
+		bundle_image.original_file_name,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_bundle_image(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_image_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.bundle_image
+        where id = $1
+    ",
+		bundle_image_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
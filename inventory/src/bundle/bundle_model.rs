@@ -0,0 +1,221 @@
+use crate::error::ShopError;
+use crate::item::ItemCondition;
+use crate::item_audit::ItemAuditSerial;
+use crate::object::JsonHttpResponse;
+use crate::{object, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct BundleEntity {
+    pub id: Uuid,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub condition: i32,
+    pub price_cents: i64,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for BundleEntity {
+    type Model = Bundle;
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct BundleItemEntity {
+    pub item_id: Uuid,
+    pub bundle_id: Uuid,
+    pub created: DateTime<Utc>,
+}
+
+/// Several items sold together as one unit, e.g. a figure lot. A bundle is listed, priced and sold as a whole, in
+/// place of its items. Each item belongs to at most one bundle.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    pub id: Uuid,
+    /// The title of the bundle's listings
+    pub display_name: String,
+    pub description: Option<String>,
+    /// The condition of the bundle as a whole, which is listed in place of the conditions of its items
+    pub condition: ItemCondition,
+    pub price_cents: u32,
+    pub note: Option<String>,
+    /// Stored separately from the bundle entity; empty when converted from an entity alone
+    pub item_ids: Vec<Uuid>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopModel for Bundle {
+    type Entity = BundleEntity;
+    type Serial = BundleSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        BundleSerial {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            description: self.description.clone(),
+            condition: self.condition.clone() as u8,
+            price_cents: self.price_cents.clone(),
+            note: self.note.clone(),
+            item_ids: self.item_ids.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    /// Items are added to and removed from the bundle individually, so the item IDs of the serial are ignored.
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let display_name: String = serial.display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err(ShopError::new("Bundle display name must not be empty"));
+        }
+
+        Ok(Bundle {
+            id: object::random_uuid(),
+            display_name,
+            description: serial.description.clone(),
+            condition: ItemCondition::try_from_repr(serial.condition)?,
+            price_cents: serial.price_cents.clone(),
+            note: serial.note.clone(),
+            item_ids: Vec::new(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        BundleEntity {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            description: self.description.clone(),
+            condition: self.condition.clone() as u8 as i32,
+            price_cents: i64::from(self.price_cents),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(Bundle {
+            id: entity.id.clone(),
+            display_name: entity.display_name.clone(),
+            description: entity.description.clone(),
+            condition: ItemCondition::try_from_repr(entity.condition as u8)?,
+            price_cents: entity.price_cents.clone() as u32,
+            note: entity.note.clone(),
+            item_ids: Vec::new(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub condition: u8,
+    pub price_cents: u32,
+    pub note: Option<String>,
+    /// Ignored on input
+    #[serde(default)]
+    pub item_ids: Vec<Uuid>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for BundleSerial {
+    type Model = Bundle;
+}
+
+impl JsonHttpResponse for BundleSerial {}
+impl JsonHttpResponse for Vec<BundleSerial> {}
+
+#[derive(Debug, Clone)]
+pub struct BundleImageEntity {
+    pub id: Uuid,
+    pub bundle_id: Uuid,
+    pub alt_text: String,
+    pub priority: i32,
+    pub original_file_name: String,
+}
+
+impl ShopEntity for BundleImageEntity {
+    type Model = BundleImage;
+}
+
+/// Stored alongside item images; see [crate::item_image::ItemImage]
+pub type BundleImage = BundleImageEntity;
+
+impl ShopModel for BundleImage {
+    type Entity = BundleImageEntity;
+    type Serial = BundleImageSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        BundleImageSerial {
+            id: self.id.clone(),
+            bundle_id: self.bundle_id.clone(),
+            alt_text: self.alt_text.clone(),
+            priority: self.priority.clone(),
+            original_file_name: self.original_file_name.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(BundleImageEntity {
+            id: object::random_uuid(),
+            bundle_id: serial.bundle_id.clone(),
+            alt_text: serial.alt_text.clone(),
+            priority: serial.priority.clone(),
+            original_file_name: serial.original_file_name.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        self.clone()
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(entity.clone())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleImageSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub bundle_id: Uuid,
+    pub alt_text: String,
+    pub priority: i32,
+    pub original_file_name: String,
+}
+
+impl ShopSerial for BundleImageSerial {
+    type Model = BundleImage;
+}
+
+impl JsonHttpResponse for BundleImageSerial {}
+impl JsonHttpResponse for Vec<BundleImageSerial> {}
+
+/// Moves every item of a bundle to the same status.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleStatusChangeSerial {
+    pub status: u8,
+    pub audit_note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleStatusChangeReport {
+    /// One audit for each item whose status changed
+    pub item_audits: Vec<ItemAuditSerial>,
+}
+
+impl JsonHttpResponse for BundleStatusChangeReport {}
@@ -0,0 +1,7 @@
+pub(crate) mod bundle_db;
+
+pub mod bundle_api;
+pub mod bundle_model;
+pub mod bundle_action;
+
+pub use bundle_model::*;
@@ -1,7 +1,7 @@
 use super::*;
 use crate::error::ShopError;
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool, Pool, Postgres};
+use sqlx::{query, query_as, PgExecutor, PgPool, Pool, Postgres};
 use uuid::Uuid;
 
 pub async fn get_all_categories(pool: &PgPool) -> Result<Vec<CategoryEntity>, ShopError> {
@@ -30,6 +30,22 @@ pub async fn get_category(
         .map_err(|e| ShopError::from(e))
 }
 
+pub async fn get_category_by_internal_name(
+    pgexecutor: impl PgExecutor<'_>,
+    internal_name: &str,
+) -> Result<Option<CategoryEntity>, ShopError> {
+    query_as!(CategoryEntity, "
+		select id, display_name, internal_name, parent_id, ebay_category_id
+		from shop.public.category
+		where internal_name = $1
+	",
+		internal_name
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
 pub async fn create_category(
     pool: &Pool<Postgres>,
     category: CategoryEntity,
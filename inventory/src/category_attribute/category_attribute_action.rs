@@ -0,0 +1,111 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item_attribute::{item_attribute_action, item_attribute_db, ItemAttribute};
+use crate::{ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+pub async fn get_all_category_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    category_id: &Uuid,
+) -> Result<Vec<CategoryAttribute>, ShopError> {
+    category_attribute_db::get_all_category_attributes(pgexecutor, category_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn get_item_category_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Vec<CategoryAttribute>, ShopError> {
+    category_attribute_db::get_item_category_attributes(pgexecutor, item_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn get_product_category_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    product_id: &Uuid,
+) -> Result<Vec<CategoryAttribute>, ShopError> {
+    category_attribute_db::get_product_category_attributes(pgexecutor, product_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+/// Existing item attributes matching the key are retyped; fails if any of them does not conform.
+pub async fn create_category_attribute(
+    pgpool: &PgPool,
+    mut category_attribute: CategoryAttribute,
+) -> Result<CategoryAttribute, ShopError> {
+    category_attribute.created = Utc::now();
+    category_attribute.updated = category_attribute.created;
+
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    category_attribute_db::create_category_attribute(&mut *transaction, &category_attribute.to_entity()).await?;
+    retype_item_attributes_in_transaction(
+        &mut transaction,
+        &category_attribute.category_id,
+        &[category_attribute.key.clone()],
+    ).await?;
+    transaction.commit().await?;
+    Ok(category_attribute)
+}
+
+/// Replaces every field of the current definition except for the category and the creation time. Existing item
+/// attributes matching the current or the new key are retyped; fails if any of them does not conform.
+pub async fn update_category_attribute(
+    pgpool: &PgPool,
+    current: &CategoryAttribute,
+    mut category_attribute: CategoryAttribute,
+) -> Result<CategoryAttribute, ShopError> {
+    category_attribute.id = current.id;
+    category_attribute.category_id = current.category_id;
+    category_attribute.created = current.created;
+    category_attribute.updated = Utc::now();
+
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    category_attribute_db::update_category_attribute(&mut *transaction, &category_attribute.to_entity()).await?;
+    retype_item_attributes_in_transaction(
+        &mut transaction,
+        &category_attribute.category_id,
+        &[current.key.clone(), category_attribute.key.clone()],
+    ).await?;
+    transaction.commit().await?;
+    Ok(category_attribute)
+}
+
+/// Existing item attributes matching the key lose the typed value of the definition.
+pub async fn delete_category_attribute(pgpool: &PgPool, current: &CategoryAttribute) -> Result<(), ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    category_attribute_db::delete_category_attribute(&mut *transaction, &current.id).await?;
+    retype_item_attributes_in_transaction(&mut transaction, &current.category_id, std::slice::from_ref(&current.key)).await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Types the attributes of the category's items matching any of the keys again according to the definitions as they
+/// stand in the transaction, replacing any stale typed values.
+async fn retype_item_attributes_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    category_id: &Uuid,
+    keys: &[String],
+) -> Result<(), ShopError> {
+    let item_attributes: Vec<ItemAttribute> =
+        item_attribute_db::get_category_item_attributes(&mut **transaction, category_id, keys).await?
+            .iter()
+            .map(|entity| entity.try_to_model())
+            .collect::<Result<Vec<_>, _>>()?;
+    for item_attribute in item_attributes {
+        let key: String = item_attribute.key.clone();
+        let item_id: Uuid = item_attribute.item_id;
+        let retyped: ItemAttribute = item_attribute_action::type_item_attribute_in_transaction(transaction, item_attribute)
+            .await
+            .map_err(|error| ShopError::new(&format!("Existing item attribute does not conform; [{}]; {}", item_id, error.message)))?;
+        item_attribute_db::update_item_attribute(&mut **transaction, &key, &retyped.to_entity()).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/category_attribute")
+            .route("", web::post().to(create_category_attribute))
+            .route("/category/{category_id}", web::get().to(get_all_category_attributes))
+            .route("/{category_attribute_id}", web::get().to(get_category_attribute))
+            .route("/{category_attribute_id}", web::put().to(update_category_attribute))
+            .route("/{category_attribute_id}", web::delete().to(delete_category_attribute))
+    );
+}
+
+async fn get_all_category_attributes(
+    pgpool: web::Data<PgPool>,
+    category_id: web::Path<String>,
+) -> HttpResponse {
+    let category_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&category_id.into_inner()));
+    let category_attributes: Vec<CategoryAttribute> = unwrap_result_else_500!(
+        category_attribute_action::get_all_category_attributes(pgpool.get_ref(), &category_id).await
+    );
+    category_attributes.iter()
+        .map(|category_attribute| category_attribute.to_serial())
+        .collect::<Vec<CategoryAttributeSerial>>()
+        .to_http_response()
+}
+
+async fn create_category_attribute(
+    pgpool: web::Data<PgPool>,
+    body: web::Json<CategoryAttributeSerial>,
+) -> HttpResponse {
+    let category_attribute: CategoryAttribute = match body.into_inner().try_to_model() {
+        Ok(category_attribute) => category_attribute,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    match category_attribute_action::create_category_attribute(&pgpool, category_attribute).await {
+        Ok(category_attribute) => category_attribute.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_category_attribute(
+    pgpool: web::Data<PgPool>,
+    category_attribute_id: web::Path<String>,
+) -> HttpResponse {
+    let category_attribute_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&category_attribute_id.into_inner()));
+    let category_attribute: CategoryAttribute = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        category_attribute_db::get_category_attribute(pgpool.get_ref(), &category_attribute_id).await
+    )).try_to_model());
+    category_attribute.to_serial().to_http_response()
+}
+
+async fn update_category_attribute(
+    pgpool: web::Data<PgPool>,
+    category_attribute_id: web::Path<String>,
+    body: web::Json<CategoryAttributeSerial>,
+) -> HttpResponse {
+    let category_attribute_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&category_attribute_id.into_inner()));
+    let category_attribute: CategoryAttribute = match body.into_inner().try_to_model() {
+        Ok(category_attribute) => category_attribute,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: CategoryAttribute = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        category_attribute_db::get_category_attribute(pgpool.get_ref(), &category_attribute_id).await
+    )).try_to_model());
+
+    match category_attribute_action::update_category_attribute(&pgpool, &current, category_attribute).await {
+        Ok(category_attribute) => category_attribute.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_category_attribute(
+    pgpool: web::Data<PgPool>,
+    category_attribute_id: web::Path<String>,
+) -> HttpResponse {
+    let category_attribute_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&category_attribute_id.into_inner()));
+    let current: CategoryAttribute = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        category_attribute_db::get_category_attribute(pgpool.get_ref(), &category_attribute_id).await
+    )).try_to_model());
+
+    match category_attribute_action::delete_category_attribute(&pgpool, &current).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
@@ -0,0 +1,143 @@
+use super::*;
+use crate::error::ShopError;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor};
+use uuid::Uuid;
+
+pub async fn get_category_attribute(
+    pgexecutor: impl PgExecutor<'_>,
+    category_attribute_id: &Uuid,
+) -> Result<Option<CategoryAttributeEntity>, ShopError> {
+    query_as!(CategoryAttributeEntity, "
+        select id, category_id, key, kind, allowed_values, unit, required, created, updated
+        from shop.public.category_attribute
+        where id = $1
+    ",
+		category_attribute_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Ordered by key
+pub async fn get_all_category_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    category_id: &Uuid,
+) -> Result<Vec<CategoryAttributeEntity>, ShopError> {
+    query_as!(CategoryAttributeEntity, "
+        select id, category_id, key, kind, allowed_values, unit, required, created, updated
+        from shop.public.category_attribute
+        where category_id = $1
+        order by lower(key), id
+    ",
+		category_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The definitions of every category directly associated with the item's product
+pub async fn get_item_category_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Vec<CategoryAttributeEntity>, ShopError> {
+    query_as!(CategoryAttributeEntity, "
+        select category_attribute.id, category_attribute.category_id, category_attribute.key, category_attribute.kind,
+            category_attribute.allowed_values, category_attribute.unit, category_attribute.required,
+            category_attribute.created, category_attribute.updated
+        from shop.public.category_attribute
+        inner join shop.public.product_category_association on category_attribute.category_id = product_category_association.category_id
+        inner join shop.public.item on product_category_association.product_id = item.product_id
+        where item.id = $1
+        order by lower(category_attribute.key), category_attribute.id
+    ",
+		item_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The definitions of every category directly associated with the product
+pub async fn get_product_category_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    product_id: &Uuid,
+) -> Result<Vec<CategoryAttributeEntity>, ShopError> {
+    query_as!(CategoryAttributeEntity, "
+        select category_attribute.id, category_attribute.category_id, category_attribute.key, category_attribute.kind,
+            category_attribute.allowed_values, category_attribute.unit, category_attribute.required,
+            category_attribute.created, category_attribute.updated
+        from shop.public.category_attribute
+        inner join shop.public.product_category_association on category_attribute.category_id = product_category_association.category_id
+        where product_category_association.product_id = $1
+        order by lower(category_attribute.key), category_attribute.id
+    ",
+		product_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_category_attribute(
+    pgexecutor: impl PgExecutor<'_>,
+    category_attribute: &CategoryAttributeEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.category_attribute (id, category_id, key, kind, allowed_values, unit, required, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    ",
+		category_attribute.id,
+		category_attribute.category_id,
+		category_attribute.key,
+		category_attribute.kind,
+		&category_attribute.allowed_values,
+		category_attribute.unit,
+		category_attribute.required,
+		category_attribute.created,
+		category_attribute.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The category and creation time are never changed.
+pub async fn update_category_attribute(
+    pgexecutor: impl PgExecutor<'_>,
+    category_attribute: &CategoryAttributeEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.category_attribute
+        set (key, kind, allowed_values, unit, required, updated) = ($2, $3, $4, $5, $6, $7)
+        where id = $1
+    ",
+		category_attribute.id,
+		category_attribute.key,
+		category_attribute.kind,
+		&category_attribute.allowed_values,
+		category_attribute.unit,
+		category_attribute.required,
+		category_attribute.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_category_attribute(
+    pgexecutor: impl PgExecutor<'_>,
+    category_attribute_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.category_attribute
+        where id = $1
+    ",
+		category_attribute_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -0,0 +1,341 @@
+use crate::error::ShopError;
+use crate::item_attribute::ItemAttribute;
+use crate::object::JsonHttpResponse;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+/// Dates are written as e.g. 1983-05-25
+pub const CATEGORY_ATTRIBUTE_DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct CategoryAttributeEntity {
+    pub id: Uuid,
+    pub category_id: Uuid,
+    pub key: String,
+    pub kind: i32,
+    pub allowed_values: Vec<String>,
+    pub unit: Option<String>,
+    pub required: bool,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for CategoryAttributeEntity {
+    type Model = CategoryAttribute;
+}
+
+/// Defines an attribute of the items whose product is directly associated with the category, e.g. figures have a
+/// numeric "Year". Attributes matching the key, compared case-insensitively, are validated and typed on creation and
+/// again whenever a definition of the key is created, changed, or deleted.
+#[derive(Debug, Clone)]
+pub struct CategoryAttribute {
+    pub id: Uuid,
+    pub category_id: Uuid,
+    /// The canonical spelling, which replaces the spelling of matching item attributes
+    pub key: String,
+    pub kind: CategoryAttributeKind,
+    /// Required by [CategoryAttributeKind::Enum]
+    pub allowed_values: Vec<String>,
+    /// e.g. "cm"; informational only
+    pub unit: Option<String>,
+    /// Items missing the attribute are not ready to be completed; see [crate::item_readiness::ItemReadiness]
+    pub required: bool,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl CategoryAttribute {
+    pub fn matches_key(&self, key: &str) -> bool {
+        self.key.eq_ignore_ascii_case(key.trim())
+    }
+
+    /// Canonicalizes the key and value of an item attribute matching this definition and sets its typed value.
+    /// Fails if the value does not conform to the kind.
+    pub fn apply(&self, item_attribute: &mut ItemAttribute) -> Result<(), ShopError> {
+        let value: &str = item_attribute.value.trim();
+        if value.is_empty() {
+            return Err(ShopError::new(&format!("Attribute value must not be empty; [{}]", self.key)));
+        }
+        let value: String = match self.kind {
+            CategoryAttributeKind::Text => value.to_string(),
+            CategoryAttributeKind::Number => {
+                let number: f64 = value.parse::<f64>()
+                    .ok()
+                    .filter(|number| number.is_finite())
+                    .ok_or_else(|| ShopError::new(&format!("Attribute value must be a number; [{}]; [{}]", self.key, value)))?;
+                item_attribute.value_number = Some(number);
+                value.to_string()
+            }
+            CategoryAttributeKind::Date => {
+                let date: NaiveDate = NaiveDate::parse_from_str(value, CATEGORY_ATTRIBUTE_DATE_FORMAT)
+                    .map_err(|_| ShopError::new(&format!("Attribute value must be a date like 1983-05-25; [{}]; [{}]", self.key, value)))?;
+                item_attribute.value_date = Some(date);
+                date.format(CATEGORY_ATTRIBUTE_DATE_FORMAT).to_string()
+            }
+            CategoryAttributeKind::Enum => self.allowed_values.iter()
+                .find(|allowed_value| allowed_value.eq_ignore_ascii_case(value))
+                .cloned()
+                .ok_or_else(|| ShopError::new(&format!(
+                    "Attribute value must be one of {:?}; [{}]; [{}]", self.allowed_values, self.key, value,
+                )))?,
+            CategoryAttributeKind::Boolean => {
+                let boolean: bool = match value.to_ascii_lowercase().as_str() {
+                    "true" | "yes" => true,
+                    "false" | "no" => false,
+                    _ => return Err(ShopError::new(&format!("Attribute value must be true or false; [{}]; [{}]", self.key, value))),
+                };
+                item_attribute.value_boolean = Some(boolean);
+                boolean.to_string()
+            }
+        };
+        item_attribute.key = self.key.clone();
+        item_attribute.value = value;
+        Ok(())
+    }
+
+    /// Applies every definition matching the item attribute's key. Attributes without a definition are kept as
+    /// free-form text.
+    pub fn apply_all(category_attributes: &[CategoryAttribute], mut item_attribute: ItemAttribute) -> Result<ItemAttribute, ShopError> {
+        let key: String = item_attribute.key.trim().to_string();
+        if key.is_empty() {
+            return Err(ShopError::new("Attribute key must not be empty"));
+        }
+        item_attribute.key = key.clone();
+        item_attribute.value_number = None;
+        item_attribute.value_date = None;
+        item_attribute.value_boolean = None;
+        for category_attribute in category_attributes.iter().filter(|definition| definition.matches_key(&key)) {
+            category_attribute.apply(&mut item_attribute)?;
+        }
+        Ok(item_attribute)
+    }
+}
+
+impl ShopModel for CategoryAttribute {
+    type Entity = CategoryAttributeEntity;
+    type Serial = CategoryAttributeSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        CategoryAttributeSerial {
+            id: self.id.clone(),
+            category_id: self.category_id.clone(),
+            key: self.key.clone(),
+            kind: self.kind.clone() as u8,
+            allowed_values: self.allowed_values.clone(),
+            unit: self.unit.clone(),
+            required: self.required.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    /// Allowed values are kept only for [CategoryAttributeKind::Enum].
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let key: String = serial.key.trim().to_string();
+        if key.is_empty() {
+            return Err(ShopError::new("Category attribute key must not be empty"));
+        }
+        let kind: CategoryAttributeKind = CategoryAttributeKind::try_from_repr(serial.kind)?;
+
+        let allowed_values: Vec<String> = match kind {
+            CategoryAttributeKind::Enum => {
+                let mut allowed_values: Vec<String> = Vec::new();
+                for allowed_value in serial.allowed_values.iter().map(|value| value.trim()) {
+                    if allowed_value.is_empty() {
+                        return Err(ShopError::new(&format!("Allowed values must not be empty; [{}]", key)));
+                    }
+                    if allowed_values.iter().any(|value| value.eq_ignore_ascii_case(allowed_value)) {
+                        return Err(ShopError::new(&format!("Allowed values must be distinct; [{}]; [{}]", key, allowed_value)));
+                    }
+                    allowed_values.push(allowed_value.to_string());
+                }
+                if allowed_values.is_empty() {
+                    return Err(ShopError::new(&format!("Enum attributes require allowed values; [{}]", key)));
+                }
+                allowed_values
+            }
+            _ => Vec::new(),
+        };
+        let unit: Option<String> = serial.unit.as_ref()
+            .map(|unit| unit.trim().to_string())
+            .filter(|unit| !unit.is_empty());
+
+        Ok(CategoryAttribute {
+            id: object::random_uuid(),
+            category_id: serial.category_id.clone(),
+            key,
+            kind,
+            allowed_values,
+            unit,
+            required: serial.required.clone(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        CategoryAttributeEntity {
+            id: self.id.clone(),
+            category_id: self.category_id.clone(),
+            key: self.key.clone(),
+            kind: self.kind.clone() as i32,
+            allowed_values: self.allowed_values.clone(),
+            unit: self.unit.clone(),
+            required: self.required.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(CategoryAttribute {
+            id: entity.id.clone(),
+            category_id: entity.category_id.clone(),
+            key: entity.key.clone(),
+            kind: CategoryAttributeKind::try_from_repr(entity.kind as u8)?,
+            allowed_values: entity.allowed_values.clone(),
+            unit: entity.unit.clone(),
+            required: entity.required.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum CategoryAttributeKind {
+    Text = 0,
+    /// Stored in [ItemAttribute::value_number]
+    Number,
+    /// Stored in [ItemAttribute::value_date]
+    Date,
+    /// One of [CategoryAttribute::allowed_values], compared case-insensitively
+    Enum,
+    /// "true", "false", "yes" or "no"; stored in [ItemAttribute::value_boolean]
+    Boolean,
+}
+
+try_from_repr!(CategoryAttributeKind<u8>);
+
+impl Display for CategoryAttributeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryAttributeSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub category_id: Uuid,
+    pub key: String,
+    pub kind: u8,
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+    pub unit: Option<String>,
+    pub required: bool,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for CategoryAttributeSerial {
+    type Model = CategoryAttribute;
+}
+
+impl JsonHttpResponse for CategoryAttributeSerial {}
+impl JsonHttpResponse for Vec<CategoryAttributeSerial> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category_attribute(key: &str, kind: CategoryAttributeKind, allowed_values: &[&str]) -> CategoryAttribute {
+        CategoryAttribute {
+            id: Uuid::nil(),
+            category_id: Uuid::nil(),
+            key: key.to_string(),
+            kind,
+            allowed_values: allowed_values.iter().map(|value| value.to_string()).collect(),
+            unit: None,
+            required: false,
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    fn attribute(key: &str, value: &str) -> ItemAttribute {
+        ItemAttribute {
+            item_id: Uuid::nil(),
+            key: key.to_string(),
+            value: value.to_string(),
+            visible: true,
+            priority: 0,
+            value_number: Some(1.0),
+            value_date: None,
+            value_boolean: Some(true),
+        }
+    }
+
+    #[test]
+    fn apply_all_keeps_undefined_attributes_as_text() {
+        let category_attributes: Vec<CategoryAttribute> = vec![category_attribute("Year", CategoryAttributeKind::Number, &[])];
+        let item_attribute: ItemAttribute = CategoryAttribute::apply_all(&category_attributes, attribute(" Height ", "12")).unwrap();
+        assert_eq!(item_attribute.key, "Height");
+        assert_eq!(item_attribute.value, "12");
+        assert_eq!(item_attribute.value_number, None);
+        assert_eq!(item_attribute.value_boolean, None);
+    }
+
+    #[test]
+    fn apply_all_types_numbers_with_canonical_key() {
+        let category_attributes: Vec<CategoryAttribute> = vec![category_attribute("Year", CategoryAttributeKind::Number, &[])];
+        let item_attribute: ItemAttribute = CategoryAttribute::apply_all(&category_attributes, attribute("year", " 1983 ")).unwrap();
+        assert_eq!(item_attribute.key, "Year");
+        assert_eq!(item_attribute.value, "1983");
+        assert_eq!(item_attribute.value_number, Some(1983.0));
+        assert_eq!(item_attribute.value_boolean, None);
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute("Year", "soon")).is_err());
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute("Year", "inf")).is_err());
+    }
+
+    #[test]
+    fn apply_all_types_dates() {
+        let category_attributes: Vec<CategoryAttribute> = vec![category_attribute("Released", CategoryAttributeKind::Date, &[])];
+        let item_attribute: ItemAttribute = CategoryAttribute::apply_all(&category_attributes, attribute("Released", "1983-05-25")).unwrap();
+        assert_eq!(item_attribute.value_date, NaiveDate::from_ymd_opt(1983, 5, 25));
+        assert_eq!(item_attribute.value, "1983-05-25");
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute("Released", "25.05.1983")).is_err());
+    }
+
+    #[test]
+    fn apply_all_canonicalizes_enum_values() {
+        let category_attributes: Vec<CategoryAttribute> = vec![category_attribute("Scale", CategoryAttributeKind::Enum, &["1/6", "1/12"])];
+        let item_attribute: ItemAttribute = CategoryAttribute::apply_all(&category_attributes, attribute("Scale", "1/12")).unwrap();
+        assert_eq!(item_attribute.value, "1/12");
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute("Scale", "1/18")).is_err());
+    }
+
+    #[test]
+    fn apply_all_types_booleans() {
+        let category_attributes: Vec<CategoryAttribute> = vec![category_attribute("Boxed", CategoryAttributeKind::Boolean, &[])];
+        let item_attribute: ItemAttribute = CategoryAttribute::apply_all(&category_attributes, attribute("Boxed", "No")).unwrap();
+        assert_eq!(item_attribute.value, "false");
+        assert_eq!(item_attribute.value_boolean, Some(false));
+        assert_eq!(item_attribute.value_number, None);
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute("Boxed", "maybe")).is_err());
+    }
+
+    #[test]
+    fn apply_all_rejects_empty_keys_and_values() {
+        let category_attributes: Vec<CategoryAttribute> = vec![category_attribute("Year", CategoryAttributeKind::Number, &[])];
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute(" ", "1983")).is_err());
+        assert!(CategoryAttribute::apply_all(&category_attributes, attribute("Year", " ")).is_err());
+    }
+}
@@ -0,0 +1,7 @@
+pub(crate) mod category_attribute_db;
+
+pub mod category_attribute_api;
+pub mod category_attribute_model;
+pub mod category_attribute_action;
+
+pub use category_attribute_model::*;
@@ -0,0 +1,116 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item::{item_db, Item};
+use crate::purchase::{Purchase, PurchaseStatus};
+use crate::{ShopEntity, ShopModel};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Records what is owed to the consignor of the item sold in the purchase, within the caller's transaction. Only a
+/// fulfilled purchase of a consigned item produces a payout.
+pub async fn record_sale_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    purchase: &Purchase,
+    item_id: &Uuid,
+) -> Result<Option<ConsignmentPayout>, ShopError> {
+    if !matches!(purchase.status, PurchaseStatus::Fulfilled) {
+        return Ok(None);
+    }
+
+    let item: Item = item_db::get_item_for_update(&mut **transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()?;
+    let Some(consignment_payout) = ConsignmentPayout::calculate(&item, purchase) else {
+        return Ok(None);
+    };
+
+    consignor_db::create_consignment_payout(&mut **transaction, &consignment_payout.to_entity()).await?;
+    log::info!(
+        "Recorded consignment payout; [{}]; [{}]; [{}]",
+        consignment_payout.consignor_id,
+        item_id,
+        consignment_payout.payout_cents,
+    );
+    Ok(Some(consignment_payout))
+}
+
+pub async fn get_all_consignor_consignment_payouts(
+    pgpool: &PgPool,
+    consignor_id: &Uuid,
+    parameters: &ConsignmentPayoutSearchParameters,
+) -> Result<Vec<ConsignmentPayout>, ShopError> {
+    consignor_db::get_all_consignor_consignment_payouts(pgpool, consignor_id, parameters.paid, None, None)
+        .await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+/// The payouts of the consignor's items sold within the period of the parameters
+pub async fn get_consignor_statement(
+    pgpool: &PgPool,
+    consignor: &Consignor,
+    parameters: &ConsignorStatementParameters,
+) -> Result<ConsignorStatement, ShopError> {
+    let sold_after: Option<DateTime<Utc>> = parameters.sold_after.map(start_of_day);
+    // The bound is inclusive of the whole day
+    let sold_before: Option<DateTime<Utc>> = parameters.sold_before.map(|sold_before| start_of_day(sold_before) + TimeDelta::days(1));
+    let consignment_payouts: Vec<ConsignmentPayout> = consignor_db::get_all_consignor_consignment_payouts(
+        pgpool,
+        &consignor.id,
+        None,
+        sold_after,
+        sold_before,
+    )
+        .await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ConsignorStatement::new(consignor, parameters, &consignment_payouts))
+}
+
+/// Fails without paying any payout if a listed payout is not an unpaid payout of the consignor.
+pub async fn pay_consignment_payouts(
+    pgpool: &PgPool,
+    consignor_id: &Uuid,
+    payment: &ConsignmentPaymentSerial,
+) -> Result<Vec<ConsignmentPayout>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let consignment_payouts: Vec<ConsignmentPayout> = consignor_db::update_consignment_payouts_paid(
+        &mut *transaction,
+        consignor_id,
+        payment.consignment_payout_ids.as_deref(),
+        &Utc::now(),
+        payment.payment_reference.as_deref(),
+    )
+        .await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(consignment_payout_ids) = &payment.consignment_payout_ids {
+        let unpayable: Option<&Uuid> = consignment_payout_ids.iter()
+            .find(|id| !consignment_payouts.iter().any(|consignment_payout| consignment_payout.id == **id));
+        if let Some(id) = unpayable {
+            return Err(ShopError::new(&format!("Consignment payout is not an unpaid payout of the consignor; [{}]", id)));
+        }
+    }
+
+    transaction.commit().await?;
+    Ok(consignment_payouts)
+}
+
+pub async fn get_all_consignor_balances(pgpool: &PgPool) -> Result<Vec<ConsignorBalance>, ShopError> {
+    Ok(consignor_db::get_all_consignor_balances(pgpool).await?
+        .iter()
+        .map(ConsignorBalance::from)
+        .collect())
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
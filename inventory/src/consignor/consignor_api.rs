@@ -0,0 +1,147 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use sqlx::postgres::PgQueryResult;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/consignor")
+            .route("", web::get().to(get_all_consignors))
+            .route("", web::post().to(create_consignor))
+            .route("/ledger", web::get().to(get_all_consignor_balances))
+            .route("/{consignor_id}", web::get().to(get_consignor))
+            .route("/{consignor_id}", web::put().to(update_consignor))
+            .route("/{consignor_id}", web::delete().to(delete_consignor))
+            .route("/{consignor_id}/payout", web::get().to(get_all_consignor_consignment_payouts))
+            .route("/{consignor_id}/payment", web::post().to(pay_consignment_payouts))
+            .route("/{consignor_id}/statement", web::get().to(get_consignor_statement))
+    );
+}
+
+async fn get_all_consignors(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let entities: Vec<ConsignorEntity> = unwrap_result_else_500!(consignor_db::get_all_consignors(pgpool.get_ref()).await);
+    let consignors: Vec<Consignor> = unwrap_result_else_500!(
+        entities.iter().map(|entity| entity.try_to_model()).collect::<Result<Vec<_>, _>>()
+    );
+    consignors.iter()
+        .map(|consignor| consignor.to_serial())
+        .collect::<Vec<ConsignorSerial>>()
+        .to_http_response()
+}
+
+async fn create_consignor(pgpool: web::Data<PgPool>, body: web::Json<ConsignorSerial>) -> HttpResponse {
+    let mut consignor: Consignor = match body.into_inner().try_to_model() {
+        Ok(consignor) => consignor,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    consignor.created = Utc::now();
+    consignor.updated = consignor.created;
+
+    match consignor_db::create_consignor(pgpool.get_ref(), &consignor.to_entity()).await {
+        Ok(_) => consignor.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// What has been paid to each consignor, and what is still owed to them
+async fn get_all_consignor_balances(pgpool: web::Data<PgPool>) -> HttpResponse {
+    unwrap_result_else_500!(consignor_action::get_all_consignor_balances(&pgpool).await).to_http_response()
+}
+
+async fn get_consignor(pgpool: web::Data<PgPool>, consignor_id: web::Path<String>) -> HttpResponse {
+    let consignor_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&consignor_id.into_inner()));
+    let consignor: Consignor = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        consignor_db::get_consignor(pgpool.get_ref(), &consignor_id).await
+    )).try_to_model());
+    consignor.to_serial().to_http_response()
+}
+
+async fn update_consignor(
+    pgpool: web::Data<PgPool>,
+    consignor_id: web::Path<String>,
+    body: web::Json<ConsignorSerial>,
+) -> HttpResponse {
+    let consignor_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&consignor_id.into_inner()));
+    let mut consignor: Consignor = match body.into_inner().try_to_model() {
+        Ok(consignor) => consignor,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: Consignor = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        consignor_db::get_consignor(pgpool.get_ref(), &consignor_id).await
+    )).try_to_model());
+    consignor.id = current.id;
+    consignor.created = current.created;
+    consignor.updated = Utc::now();
+
+    match consignor_db::update_consignor(pgpool.get_ref(), &consignor.to_entity()).await {
+        Ok(_) => consignor.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Fails while any item or payout refers to the consignor.
+async fn delete_consignor(pgpool: web::Data<PgPool>, consignor_id: web::Path<String>) -> HttpResponse {
+    let consignor_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&consignor_id.into_inner()));
+    let query_result: PgQueryResult = match consignor_db::delete_consignor(pgpool.get_ref(), &consignor_id).await {
+        Ok(query_result) => query_result,
+        Err(error) => return HttpResponse::Conflict().body(error.message),
+    };
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
+
+async fn get_all_consignor_consignment_payouts(
+    pgpool: web::Data<PgPool>,
+    consignor_id: web::Path<String>,
+    parameters: web::Query<ConsignmentPayoutSearchParameters>,
+) -> HttpResponse {
+    let consignor_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&consignor_id.into_inner()));
+    unwrap_option_else_404!(unwrap_result_else_500!(consignor_db::get_consignor(pgpool.get_ref(), &consignor_id).await));
+
+    let consignment_payouts: Vec<ConsignmentPayout> = unwrap_result_else_500!(
+        consignor_action::get_all_consignor_consignment_payouts(&pgpool, &consignor_id, &parameters.into_inner()).await
+    );
+    consignment_payouts.iter()
+        .map(|consignment_payout| consignment_payout.to_serial())
+        .collect::<Vec<ConsignmentPayoutSerial>>()
+        .to_http_response()
+}
+
+/// Responds with the payouts which were paid.
+async fn pay_consignment_payouts(
+    pgpool: web::Data<PgPool>,
+    consignor_id: web::Path<String>,
+    body: web::Json<ConsignmentPaymentSerial>,
+) -> HttpResponse {
+    let consignor_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&consignor_id.into_inner()));
+    unwrap_option_else_404!(unwrap_result_else_500!(consignor_db::get_consignor(pgpool.get_ref(), &consignor_id).await));
+
+    match consignor_action::pay_consignment_payouts(&pgpool, &consignor_id, &body.into_inner()).await {
+        Ok(consignment_payouts) => consignment_payouts.iter()
+            .map(|consignment_payout| consignment_payout.to_serial())
+            .collect::<Vec<ConsignmentPayoutSerial>>()
+            .to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_consignor_statement(
+    pgpool: web::Data<PgPool>,
+    consignor_id: web::Path<String>,
+    parameters: web::Query<ConsignorStatementParameters>,
+) -> HttpResponse {
+    let consignor_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&consignor_id.into_inner()));
+    let consignor: Consignor = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        consignor_db::get_consignor(pgpool.get_ref(), &consignor_id).await
+    )).try_to_model());
+
+    unwrap_result_else_500!(consignor_action::get_consignor_statement(&pgpool, &consignor, &parameters.into_inner()).await)
+        .to_http_response()
+}
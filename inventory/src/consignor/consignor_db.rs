@@ -0,0 +1,185 @@
+use super::*;
+use crate::error::ShopError;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor};
+use uuid::Uuid;
+
+pub async fn get_consignor(pgexecutor: impl PgExecutor<'_>, consignor_id: &Uuid) -> Result<Option<ConsignorEntity>, ShopError> {
+    query_as!(ConsignorEntity, "
+        select id, display_name, contact, note, created, updated
+        from shop.public.consignor
+        where id = $1
+    ",
+		consignor_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_consignors(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<ConsignorEntity>, ShopError> {
+    query_as!(ConsignorEntity, "
+        select id, display_name, contact, note, created, updated
+        from shop.public.consignor
+        order by display_name
+    ")
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_consignor(pgexecutor: impl PgExecutor<'_>, consignor: &ConsignorEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.consignor (id, display_name, contact, note, created, updated)
+        values ($1, $2, $3, $4, $5, $6)
+    ",
+		consignor.id,
+		consignor.display_name,
+		consignor.contact,
+		consignor.note,
+		consignor.created,
+		consignor.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The creation time is never changed.
+pub async fn update_consignor(pgexecutor: impl PgExecutor<'_>, consignor: &ConsignorEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.consignor
+        set (display_name, contact, note, updated) = ($2, $3, $4, $5)
+        where id = $1
+    ",
+		consignor.id,
+		consignor.display_name,
+		consignor.contact,
+		consignor.note,
+		consignor.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Fails while any item or payout refers to the consignor.
+pub async fn delete_consignor(pgexecutor: impl PgExecutor<'_>, consignor_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.consignor
+        where id = $1
+    ",
+		consignor_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_consignment_payout(
+    pgexecutor: impl PgExecutor<'_>,
+    consignment_payout: &ConsignmentPayoutEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.consignment_payout (id, consignor_id, item_id, purchase_id, sale_price_cents, fee_cents, commission_cents, payout_cents, paid, payment_reference, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+    ",
+		consignment_payout.id,
+		consignment_payout.consignor_id,
+		consignment_payout.item_id,
+		consignment_payout.purchase_id,
+		consignment_payout.sale_price_cents,
+		consignment_payout.fee_cents,
+		consignment_payout.commission_cents,
+		consignment_payout.payout_cents,
+		consignment_payout.paid,
+		consignment_payout.payment_reference,
+		consignment_payout.created,
+		consignment_payout.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Oldest first. Each present filter narrows the result set; the creation bounds are inclusive and exclusive.
+pub async fn get_all_consignor_consignment_payouts(
+    pgexecutor: impl PgExecutor<'_>,
+    consignor_id: &Uuid,
+    paid: Option<bool>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+) -> Result<Vec<ConsignmentPayoutEntity>, ShopError> {
+    query_as!(ConsignmentPayoutEntity, "
+        select id, consignor_id, item_id, purchase_id, sale_price_cents, fee_cents, commission_cents, payout_cents, paid, payment_reference, created, updated
+        from shop.public.consignment_payout
+        where consignor_id = $1
+            and ($2::boolean is null or (paid is not null) = $2)
+            and ($3::timestamptz is null or created >= $3)
+            and ($4::timestamptz is null or created < $4)
+        order by created, id
+    ",
+		consignor_id,
+		paid,
+		created_after,
+		created_before,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Marks the unpaid payouts of the consignor paid, either those listed or, if none are, every one of them. Payouts
+/// which are already paid or belong to another consignor are left unchanged and not returned.
+pub async fn update_consignment_payouts_paid(
+    pgexecutor: impl PgExecutor<'_>,
+    consignor_id: &Uuid,
+    consignment_payout_ids: Option<&[Uuid]>,
+    paid: &DateTime<Utc>,
+    payment_reference: Option<&str>,
+) -> Result<Vec<ConsignmentPayoutEntity>, ShopError> {
+    query_as!(ConsignmentPayoutEntity, "
+        update shop.public.consignment_payout
+        set (paid, payment_reference, updated) = ($3, $4, $3)
+        where consignor_id = $1 and paid is null and ($2::uuid[] is null or id = any($2))
+        returning id, consignor_id, item_id, purchase_id, sale_price_cents, fee_cents, commission_cents, payout_cents, paid, payment_reference, created, updated
+    ",
+		consignor_id,
+		consignment_payout_ids,
+		paid,
+		payment_reference,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn item_has_consignment_payouts(pgexecutor: impl PgExecutor<'_>, item_id: &Uuid) -> Result<bool, ShopError> {
+    query!(r#"
+        select exists(select 1 from shop.public.consignment_payout where item_id = $1) as "exists!"
+    "#,
+		item_id
+	)
+        .fetch_one(pgexecutor)
+        .await
+        .map(|record| record.exists)
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Every consignor, including those without payouts, by display name
+pub async fn get_all_consignor_balances(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<ConsignorBalanceEntity>, ShopError> {
+    query_as!(ConsignorBalanceEntity, r#"
+        select consignor.id as consignor_id, consignor.display_name as consignor_display_name,
+            coalesce(sum(consignment_payout.payout_cents) filter (where consignment_payout.paid is not null), 0)::bigint as "paid_cents!",
+            coalesce(sum(consignment_payout.payout_cents) filter (where consignment_payout.paid is null), 0)::bigint as "unpaid_cents!",
+            count(consignment_payout.id) filter (where consignment_payout.paid is null) as "unpaid_payout_count!"
+        from shop.public.consignor
+            left join shop.public.consignment_payout on consignment_payout.consignor_id = consignor.id
+        group by consignor.id
+        order by consignor.display_name
+    "#)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
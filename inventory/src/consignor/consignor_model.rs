@@ -0,0 +1,538 @@
+use crate::error::ShopError;
+use crate::item::Item;
+use crate::object::JsonHttpResponse;
+use crate::purchase::Purchase;
+use crate::{object, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ConsignorEntity {
+    pub id: Uuid,
+    pub display_name: String,
+    pub contact: Option<String>,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for ConsignorEntity {
+    type Model = Consignor;
+}
+
+/// A person or business whose items are sold on their behalf in exchange for a commission. Consigned items refer to
+/// their consignor and carry their own commission terms; see [Item::consignor_id]
+#[derive(Debug, Clone)]
+pub struct Consignor {
+    pub id: Uuid,
+    pub display_name: String,
+    /// E.g. an email address or a phone number
+    pub contact: Option<String>,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopModel for Consignor {
+    type Entity = ConsignorEntity;
+    type Serial = ConsignorSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        ConsignorSerial {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            contact: self.contact.clone(),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let display_name: String = serial.display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err(ShopError::new("Consignor display name must not be empty"));
+        }
+
+        Ok(Consignor {
+            id: object::random_uuid(),
+            display_name,
+            contact: serial.contact.clone(),
+            note: serial.note.clone(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        ConsignorEntity {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            contact: self.contact.clone(),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(Consignor {
+            id: entity.id.clone(),
+            display_name: entity.display_name.clone(),
+            contact: entity.contact.clone(),
+            note: entity.note.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsignorSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub display_name: String,
+    pub contact: Option<String>,
+    pub note: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for ConsignorSerial {
+    type Model = Consignor;
+}
+
+impl JsonHttpResponse for ConsignorSerial {}
+impl JsonHttpResponse for Vec<ConsignorSerial> {}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ConsignmentPayoutEntity {
+    pub id: Uuid,
+    pub consignor_id: Uuid,
+    pub item_id: Uuid,
+    pub purchase_id: Uuid,
+    pub sale_price_cents: i64,
+    pub fee_cents: i64,
+    pub commission_cents: i64,
+    pub payout_cents: i64,
+    pub paid: Option<DateTime<Utc>>,
+    pub payment_reference: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for ConsignmentPayoutEntity {
+    type Model = ConsignmentPayout;
+}
+
+/// What is owed to a consignor for one of their items sold in a fulfilled purchase. The amounts are fixed at the time
+/// of the sale, so later changes to the item's commission terms do not affect them.
+#[derive(Debug, Clone)]
+pub struct ConsignmentPayout {
+    pub id: Uuid,
+    pub consignor_id: Uuid,
+    pub item_id: Uuid,
+    pub purchase_id: Uuid,
+    /// The purchase subtotal less discounts
+    pub sale_price_cents: i64,
+    /// The marketplace fees of the purchase
+    pub fee_cents: i64,
+    pub commission_cents: i64,
+    /// Sale price less fees and commission, and never negative
+    pub payout_cents: i64,
+    /// When the payout was paid to the consignor; None while unpaid
+    pub paid: Option<DateTime<Utc>>,
+    /// E.g. a check number or a transfer ID
+    pub payment_reference: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ConsignmentPayout {
+    /// The commission is the item's share of the sale price, but no less than its minimum and no more than the sale
+    /// price itself. None if the item is not consigned.
+    pub fn calculate(item: &Item, purchase: &Purchase) -> Option<ConsignmentPayout> {
+        let consignor_id: Uuid = item.consignor_id?;
+        let commission_basis_points: i64 = i64::from(item.commission_basis_points.unwrap_or(0));
+        let commission_minimum_cents: i64 = i64::from(item.commission_minimum_cents.unwrap_or(0));
+
+        let sale_price_cents: i64 = (purchase.cost_subtotal_cents - purchase.cost_discount_cents).max(0);
+        let fee_cents: i64 = purchase.marketplace_fee_cents;
+        // Rounded half up to the cent
+        let commission_cents: i64 = ((sale_price_cents * commission_basis_points + 5000) / 10000)
+            .max(commission_minimum_cents)
+            .min(sale_price_cents);
+        let payout_cents: i64 = (sale_price_cents - fee_cents - commission_cents).max(0);
+
+        Some(ConsignmentPayout {
+            id: object::random_uuid(),
+            consignor_id,
+            item_id: item.id,
+            purchase_id: purchase.id,
+            sale_price_cents,
+            fee_cents,
+            commission_cents,
+            payout_cents,
+            paid: None,
+            payment_reference: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+        })
+    }
+
+    pub fn is_paid(&self) -> bool {
+        self.paid.is_some()
+    }
+}
+
+impl ShopModel for ConsignmentPayout {
+    type Entity = ConsignmentPayoutEntity;
+    type Serial = ConsignmentPayoutSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        ConsignmentPayoutSerial {
+            id: self.id.clone(),
+            consignor_id: self.consignor_id.clone(),
+            item_id: self.item_id.clone(),
+            purchase_id: self.purchase_id.clone(),
+            sale_price_cents: self.sale_price_cents,
+            fee_cents: self.fee_cents,
+            commission_cents: self.commission_cents,
+            payout_cents: self.payout_cents,
+            paid: self.paid.clone(),
+            payment_reference: self.payment_reference.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(ConsignmentPayout {
+            id: object::random_uuid(),
+            consignor_id: serial.consignor_id.clone(),
+            item_id: serial.item_id.clone(),
+            purchase_id: serial.purchase_id.clone(),
+            sale_price_cents: serial.sale_price_cents,
+            fee_cents: serial.fee_cents,
+            commission_cents: serial.commission_cents,
+            payout_cents: serial.payout_cents,
+            paid: serial.paid.clone(),
+            payment_reference: serial.payment_reference.clone(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        ConsignmentPayoutEntity {
+            id: self.id.clone(),
+            consignor_id: self.consignor_id.clone(),
+            item_id: self.item_id.clone(),
+            purchase_id: self.purchase_id.clone(),
+            sale_price_cents: self.sale_price_cents,
+            fee_cents: self.fee_cents,
+            commission_cents: self.commission_cents,
+            payout_cents: self.payout_cents,
+            paid: self.paid.clone(),
+            payment_reference: self.payment_reference.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(ConsignmentPayout {
+            id: entity.id.clone(),
+            consignor_id: entity.consignor_id.clone(),
+            item_id: entity.item_id.clone(),
+            purchase_id: entity.purchase_id.clone(),
+            sale_price_cents: entity.sale_price_cents,
+            fee_cents: entity.fee_cents,
+            commission_cents: entity.commission_cents,
+            payout_cents: entity.payout_cents,
+            paid: entity.paid.clone(),
+            payment_reference: entity.payment_reference.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsignmentPayoutSerial {
+    pub id: Uuid,
+    pub consignor_id: Uuid,
+    pub item_id: Uuid,
+    pub purchase_id: Uuid,
+    pub sale_price_cents: i64,
+    pub fee_cents: i64,
+    pub commission_cents: i64,
+    pub payout_cents: i64,
+    pub paid: Option<DateTime<Utc>>,
+    pub payment_reference: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for ConsignmentPayoutSerial {
+    type Model = ConsignmentPayout;
+}
+
+impl JsonHttpResponse for ConsignmentPayoutSerial {}
+impl JsonHttpResponse for Vec<ConsignmentPayoutSerial> {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsignmentPayoutSearchParameters {
+    /// Only paid payouts if true, or only unpaid payouts if false
+    pub paid: Option<bool>,
+}
+
+/// Marks unpaid payouts of a consignor as paid. Either every listed payout is paid, or none is.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsignmentPaymentSerial {
+    /// Every unpaid payout of the consignor if absent
+    pub consignment_payout_ids: Option<Vec<Uuid>>,
+    pub payment_reference: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsignorStatementParameters {
+    /// Inclusive
+    pub sold_after: Option<NaiveDate>,
+    /// Inclusive
+    pub sold_before: Option<NaiveDate>,
+}
+
+/// The payouts of a consignor's items sold within a period, with their totals
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsignorStatement {
+    pub consignor: ConsignorSerial,
+    pub sold_after: Option<NaiveDate>,
+    pub sold_before: Option<NaiveDate>,
+    /// Oldest first
+    pub consignment_payouts: Vec<ConsignmentPayoutSerial>,
+    pub sale_price_cents: i64,
+    pub fee_cents: i64,
+    pub commission_cents: i64,
+    pub payout_cents: i64,
+    /// The part of the payout total already paid
+    pub paid_cents: i64,
+    /// The part of the payout total still owed
+    pub unpaid_cents: i64,
+}
+
+impl ConsignorStatement {
+    pub fn new(
+        consignor: &Consignor,
+        parameters: &ConsignorStatementParameters,
+        consignment_payouts: &[ConsignmentPayout],
+    ) -> ConsignorStatement {
+        let total = |amount: fn(&ConsignmentPayout) -> i64| -> i64 { consignment_payouts.iter().map(amount).sum() };
+        ConsignorStatement {
+            consignor: consignor.to_serial(),
+            sold_after: parameters.sold_after,
+            sold_before: parameters.sold_before,
+            consignment_payouts: consignment_payouts.iter().map(ConsignmentPayout::to_serial).collect(),
+            sale_price_cents: total(|payout| payout.sale_price_cents),
+            fee_cents: total(|payout| payout.fee_cents),
+            commission_cents: total(|payout| payout.commission_cents),
+            payout_cents: total(|payout| payout.payout_cents),
+            paid_cents: total(|payout| if payout.is_paid() { payout.payout_cents } else { 0 }),
+            unpaid_cents: total(|payout| if payout.is_paid() { 0 } else { payout.payout_cents }),
+        }
+    }
+}
+
+impl JsonHttpResponse for ConsignorStatement {}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ConsignorBalanceEntity {
+    pub consignor_id: Uuid,
+    pub consignor_display_name: String,
+    pub paid_cents: i64,
+    pub unpaid_cents: i64,
+    pub unpaid_payout_count: i64,
+}
+
+/// One line of the consignment ledger: what has been paid to a consignor, and what is still owed to them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsignorBalance {
+    pub consignor_id: Uuid,
+    pub consignor_display_name: String,
+    pub paid_cents: i64,
+    pub unpaid_cents: i64,
+    pub unpaid_payout_count: u32,
+}
+
+impl From<&ConsignorBalanceEntity> for ConsignorBalance {
+    fn from(entity: &ConsignorBalanceEntity) -> Self {
+        ConsignorBalance {
+            consignor_id: entity.consignor_id,
+            consignor_display_name: entity.consignor_display_name.clone(),
+            paid_cents: entity.paid_cents,
+            unpaid_cents: entity.unpaid_cents,
+            unpaid_payout_count: entity.unpaid_payout_count as u32,
+        }
+    }
+}
+
+impl JsonHttpResponse for Vec<ConsignorBalance> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::{ItemCondition, ItemStatus};
+    use crate::purchase::{PaymentMethod, PurchaseStatus, ShippingMethod};
+
+    fn new_item(consignor_id: Option<Uuid>, commission_basis_points: Option<u32>, commission_minimum_cents: Option<u32>) -> Item {
+        Item {
+            id: object::random_uuid(),
+            code: String::from("AUS-00011"),
+            product_id: Uuid::nil(),
+            inventory_location_id: Uuid::nil(),
+            storage_slot_id: None,
+            condition: ItemCondition::Inapplicable,
+            status: ItemStatus::CompleteListed,
+            price_cents: 0,
+            priority: 0,
+            note: None,
+            acquisition_datetime: DateTime::default(),
+            acquisition_price_cents: None,
+            acquisition_location: None,
+            consignor_id,
+            commission_basis_points,
+            commission_minimum_cents,
+            created: DateTime::default(),
+            updated: DateTime::default(),
+        }
+    }
+
+    fn new_purchase(cost_subtotal_cents: i64, cost_discount_cents: i64, marketplace_fee_cents: i64) -> Purchase {
+        Purchase {
+            id: object::random_uuid(),
+            marketplace_id: Uuid::nil(),
+            external_id: None,
+            customer_id: None,
+            contact_email_address: String::from("a@example.com"),
+            listing_id: Uuid::nil(),
+            quantity: 1,
+            status: PurchaseStatus::Fulfilled,
+            cost_subtotal_cents,
+            cost_tax_cents: 0,
+            cost_shipping_cents: 0,
+            cost_discount_cents,
+            seller_cost_total_cents: 0,
+            marketplace_fee_cents,
+            shipping_method: ShippingMethod::Pickup,
+            payment_method: PaymentMethod::Cash,
+            note: None,
+            shipping_street_address: None,
+            shipping_municipality: None,
+            shipping_district: None,
+            shipping_postal_area: None,
+            shipping_country: None,
+            billing_street_address: None,
+            billing_municipality: None,
+            billing_district: None,
+            billing_postal_area: None,
+            billing_country: None,
+            created: DateTime::default(),
+            updated: DateTime::default(),
+        }
+    }
+
+    fn consigned_item(commission_basis_points: u32, commission_minimum_cents: u32) -> Item {
+        new_item(Some(object::random_uuid()), Some(commission_basis_points), Some(commission_minimum_cents))
+    }
+
+    #[test]
+    fn calculate_ignores_items_not_consigned() {
+        let item: Item = new_item(None, Some(2500), Some(100));
+        assert!(ConsignmentPayout::calculate(&item, &new_purchase(1000, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn calculate_records_item_purchase_and_consignor() {
+        let item: Item = consigned_item(2500, 0);
+        let purchase: Purchase = new_purchase(1000, 0, 0);
+        let consignment_payout: ConsignmentPayout = ConsignmentPayout::calculate(&item, &purchase).unwrap();
+        assert_eq!(Some(consignment_payout.consignor_id), item.consignor_id);
+        assert_eq!(consignment_payout.item_id, item.id);
+        assert_eq!(consignment_payout.purchase_id, purchase.id);
+        assert!(!consignment_payout.is_paid());
+    }
+
+    #[test]
+    fn calculate_pays_sale_price_less_fees_and_commission() {
+        let consignment_payout: ConsignmentPayout =
+            ConsignmentPayout::calculate(&consigned_item(2500, 0), &new_purchase(10000, 1000, 900)).unwrap();
+        assert_eq!(consignment_payout.sale_price_cents, 9000);
+        assert_eq!(consignment_payout.fee_cents, 900);
+        assert_eq!(consignment_payout.commission_cents, 2250);
+        assert_eq!(consignment_payout.payout_cents, 5850);
+    }
+
+    #[test]
+    fn calculate_excludes_shipping_and_tax_from_sale_price() {
+        let mut purchase: Purchase = new_purchase(1000, 0, 0);
+        purchase.cost_shipping_cents = 500;
+        purchase.cost_tax_cents = 80;
+        let consignment_payout: ConsignmentPayout = ConsignmentPayout::calculate(&consigned_item(1000, 0), &purchase).unwrap();
+        assert_eq!(consignment_payout.sale_price_cents, 1000);
+        assert_eq!(consignment_payout.commission_cents, 100);
+        assert_eq!(consignment_payout.payout_cents, 900);
+    }
+
+    #[test]
+    fn calculate_rounds_commission_half_up() {
+        let commission_cents = |cost_subtotal_cents: i64| {
+            ConsignmentPayout::calculate(&consigned_item(1250, 0), &new_purchase(cost_subtotal_cents, 0, 0))
+                .unwrap()
+                .commission_cents
+        };
+        assert_eq!(commission_cents(1003), 125);
+        assert_eq!(commission_cents(1004), 126);
+        assert_eq!(commission_cents(1005), 126);
+    }
+
+    #[test]
+    fn calculate_keeps_commission_minimum() {
+        let consignment_payout: ConsignmentPayout =
+            ConsignmentPayout::calculate(&consigned_item(1000, 300), &new_purchase(1000, 0, 0)).unwrap();
+        assert_eq!(consignment_payout.commission_cents, 300);
+        assert_eq!(consignment_payout.payout_cents, 700);
+    }
+
+    #[test]
+    fn calculate_limits_commission_to_sale_price() {
+        let consignment_payout: ConsignmentPayout =
+            ConsignmentPayout::calculate(&consigned_item(1000, 300), &new_purchase(200, 0, 0)).unwrap();
+        assert_eq!(consignment_payout.commission_cents, 200);
+        assert_eq!(consignment_payout.payout_cents, 0);
+    }
+
+    #[test]
+    fn calculate_never_pays_less_than_zero() {
+        let consignment_payout: ConsignmentPayout =
+            ConsignmentPayout::calculate(&consigned_item(2000, 0), &new_purchase(1000, 0, 900)).unwrap();
+        assert_eq!(consignment_payout.commission_cents, 200);
+        assert_eq!(consignment_payout.payout_cents, 0);
+
+        let consignment_payout: ConsignmentPayout =
+            ConsignmentPayout::calculate(&consigned_item(2000, 100), &new_purchase(500, 800, 50)).unwrap();
+        assert_eq!(consignment_payout.sale_price_cents, 0);
+        assert_eq!(consignment_payout.commission_cents, 0);
+        assert_eq!(consignment_payout.payout_cents, 0);
+    }
+
+    #[test]
+    fn calculate_treats_missing_terms_as_zero() {
+        let item: Item = new_item(Some(object::random_uuid()), None, None);
+        let consignment_payout: ConsignmentPayout = ConsignmentPayout::calculate(&item, &new_purchase(1000, 0, 100)).unwrap();
+        assert_eq!(consignment_payout.commission_cents, 0);
+        assert_eq!(consignment_payout.payout_cents, 900);
+    }
+}
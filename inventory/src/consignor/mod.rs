@@ -0,0 +1,7 @@
+pub(crate) mod consignor_db;
+
+pub mod consignor_action;
+pub mod consignor_api;
+pub mod consignor_model;
+
+pub use consignor_model::*;
@@ -1,5 +1,6 @@
 use crate::customer::{customer_db, CustomerSerial};
 use crate::object::JsonHttpResponse;
+use crate::pagination::KeysetPaginationOptions;
 use crate::{
     unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel,
     ShopSerial,
@@ -12,6 +13,7 @@ use uuid::Uuid;
 pub fn configurer(config: &mut ServiceConfig) {
     config.service(
         web::scope("/customer")
+            .route("", web::get().to(get_all_customers_paged))
             .route("", web::post().to(create_customer))
             .route("/{customer_id}", web::get().to(get_customer))
             .route(
@@ -57,3 +59,19 @@ async fn get_all_customer_purchases(
     }
     purchase_serial_vec.to_http_response()
 }
+
+async fn get_all_customers_paged(
+    pgpool: web::Data<PgPool>,
+    query: web::Query<KeysetPaginationOptions<String>>,
+) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        customer_db::get_all_customers_paged_email_address(&pgpool, &pagination_options).await
+    );
+
+    let mut customer_serial_vec = Vec::with_capacity(entities.len());
+    for entity in entities {
+        customer_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (customer_serial_vec, pagination_result).to_http_response()
+}
@@ -1,7 +1,9 @@
 use super::*;
 use crate::purchase::PurchaseEntity;
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use sqlx::{query, query_as, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 use crate::error::ShopError;
 
@@ -56,7 +58,7 @@ pub async fn get_all_customer_purchases(
     customer_id: &Uuid,
 ) -> Result<Vec<PurchaseEntity>, ShopError> {
     query_as!(PurchaseEntity, "\
-        select id, marketplace_id, external_id, customer_id, contact_email_address, listing_id, status, cost_subtotal_cents, cost_tax_cents, cost_shipping_cents, cost_discount_cents, seller_cost_total_cents, shipping_method, payment_method, note, shipping_street_address, shipping_municipality, shipping_district, shipping_postal_area, shipping_country, billing_street_address, billing_municipality, billing_district, billing_postal_area, billing_country, created, updated \
+        select id, marketplace_id, external_id, customer_id, contact_email_address, listing_id, quantity, status, cost_subtotal_cents, cost_tax_cents, cost_shipping_cents, cost_discount_cents, seller_cost_total_cents, marketplace_fee_cents, shipping_method, payment_method, note, shipping_street_address, shipping_municipality, shipping_district, shipping_postal_area, shipping_country, billing_street_address, billing_municipality, billing_district, billing_postal_area, billing_country, created, updated \
         from shop.public.purchase \
         where customer_id = $1 \
     ",
@@ -66,3 +68,22 @@ pub async fn get_all_customer_purchases(
         .await
         .map_err(|e| ShopError::from(e))
 }
+
+pub async fn get_all_customers_paged_email_address(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<String>,
+) -> Result<(Vec<CustomerEntity>, KeysetPaginationResult<String>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, email_address, phone_number, password_hash, display_name, role, status, shipping_street_address, shipping_municipality, shipping_district, shipping_postal_area, shipping_country, billing_street_address, billing_municipality, billing_district, billing_postal_area, billing_country, created, updated \
+        from shop.public.customer \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "email_address",
+        &Direction::Ascending,
+        keyset_pagination_options,
+        |customer: &CustomerEntity| (customer.email_address.clone(), customer.id),
+    ).await
+}
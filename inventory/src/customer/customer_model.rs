@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use strum::FromRepr;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, sqlx::FromRow)]
 pub struct CustomerEntity {
     pub id: Uuid,
     pub email_address: String,
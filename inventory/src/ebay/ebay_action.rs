@@ -1,12 +1,17 @@
 use super::{ebay_category, ebay_client};
+use crate::bundle::{bundle_action, Bundle, BundleImage};
 use crate::category::Category;
+use crate::decrypt::master_decrypt;
 use crate::error::ShopError;
 use crate::inventory_location::InventoryLocation;
 use crate::item::Item;
+use crate::item_grading::{item_grading_action, ItemGrading};
 use crate::item_image::{item_image_db, ItemImage};
+use crate::item_refurbishment::{item_refurbishment_action, ItemRefurbishment};
+use crate::item_stock::item_stock_action;
 use crate::listing::{Listing, ListingStatus};
 use crate::marketplace::marketplace_db;
-use crate::product::Product;
+use crate::product::{product_db, Product};
 use crate::{listing, ShopEntity};
 use listing::listing_action;
 use serde_json::Value;
@@ -21,6 +26,8 @@ pub static NOMINAL_RETURN_POLICY_ID: OnceLock<String> = OnceLock::new();
 static MARKETPLACE_ID: OnceLock<Uuid> = OnceLock::new();
 
 const MARKETPLACE_INTERNAL_NAME: &str = "ebay";
+// This value pertains to my testing "zach" account, as does the client secret.
+const USER_REFRESH_TOKEN_SECRET_NAME: &str = "ebay__zach.sandbox.refresh_token";
 
 /// Should be called only once.
 pub async fn init(pgpool: &PgPool) {
@@ -46,33 +53,34 @@ pub async fn publish(
         return Err(ShopError::new("listing is not draft"));
     }
 
-    let (item, product): (Item, Product) = listing_action::get_item_and_product_for_listing(pgpool, listing).await?;
-    let item_images: Vec<ItemImage> = item.get_all_item_images(pgpool).await?;
-
     log::info!("Posting listing to {}; [listing_id: {}]; [marketplace_id: {}]", MARKETPLACE_INTERNAL_NAME, listing.id, MARKETPLACE_ID.get().unwrap());
 
-    ebay_client::create_or_replace_inventory_item(user_access_token, &item, &product, &item_images).await?;
+    let offer_terms: OfferTerms = match listing.bundle_id {
+        Some(bundle_id) => put_bundle_inventory_item(pgpool, user_access_token, &bundle_id).await?,
+        None => put_item_inventory_item(pgpool, user_access_token, listing).await?,
+    };
 
-    let mut offer: Option<Value> = get_offer(user_access_token, &item.id).await?;
+    let sku: Uuid = listing.get_sku();
+    let mut offer: Option<Value> = get_offer(user_access_token, &sku).await?;
     let offer_id: String;
     if let Some(offer) = &offer {
-        log::info!("Offer already exists; Skipping creation; Attempting to publish; [{}]", item.id);
+        log::info!("Offer already exists; Skipping creation; Attempting to publish; [{}]", sku);
         offer_id = offer["offerId"]
             .as_str()
             .ok_or_else(|| ShopError::default())?
             .to_string();
     } else {
         // Offers are immediately published here, so we don't bother to check if already published
-        offer_id = create_offer(pgpool, user_access_token, &item).await?
+        offer_id = create_offer(pgpool, user_access_token, &sku, &offer_terms).await?
             .to_string();
         log::info!("Created ebay offer [{}]", offer_id);
 
-        offer = get_offer(user_access_token, &item.id).await?;
+        offer = get_offer(user_access_token, &sku).await?;
     }
     let offer: Value = offer.ok_or_else(|| ShopError::default())?;
 
     if offer_published(&offer).await? {
-        log::info!("Offer already published; Cancelling publish; [{}]", item.id);
+        log::info!("Offer already published; Cancelling publish; [{}]", sku);
         return Ok(());
     }
 
@@ -93,7 +101,7 @@ pub async fn withdraw(
         return Err(ShopError::new("listing is not published"));
     }
 
-    let offer_id: Option<String> = get_offer_id(user_access_token, &listing.item_id).await?;
+    let offer_id: Option<String> = get_offer_id(user_access_token, &listing.get_sku()).await?;
     let Some(offer_id) = offer_id else {
         return Err(ShopError::new("offer ID lookup failed"))
     };
@@ -124,6 +132,110 @@ pub async fn publish_all_with_status(
     Ok(())
 }
 
+/// Moves the inventory item and the offer of a published listing to the item's current inventory location.
+pub async fn update_offer_location(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listing: &Listing,
+) -> Result<(), ShopError> {
+    validate_listing_marketplace(listing)?;
+    if listing.status != ListingStatus::Published {
+        return Err(ShopError::new("listing is not published"));
+    }
+
+    let (item, product): (Item, Product) = listing_action::get_item_and_product_for_listing(pgpool, listing).await?;
+    let item_images: Vec<ItemImage> = item.get_all_item_images(pgpool).await?;
+    let item_grading: Option<ItemGrading> = item_grading_action::get_item_grading(pgpool, &item.id).await?;
+    let item_refurbishments: Vec<ItemRefurbishment> = item_refurbishment_action::get_all_item_refurbishments(pgpool, &item.id).await?;
+    let quantity: u32 = item_stock_action::get_available_quantity(pgpool, &item.id).await?;
+    ebay_client::create_or_replace_inventory_item(user_access_token, &item, quantity, &product, &item_images, item_grading.as_ref(), &item_refurbishments).await?;
+
+    let (offer_id, mut offer): (String, Value) = get_updatable_offer(user_access_token, &item.id).await?;
+    offer.as_object_mut()
+        .ok_or_else(|| ShopError::default())?
+        .insert("merchantLocationKey".to_string(), Value::String(item.inventory_location_id.to_string()));
+
+    ebay_client::update_offer(user_access_token, &offer_id, &offer).await?;
+    log::info!("Moved ebay offer to inventory location; [{}]; [{}]", offer_id, item.inventory_location_id);
+    Ok(())
+}
+
+/// Sets the price of the offer of a published listing to the item's current price.
+pub async fn update_offer_price(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listing: &Listing,
+) -> Result<(), ShopError> {
+    validate_listing_marketplace(listing)?;
+    if listing.status != ListingStatus::Published {
+        return Err(ShopError::new("listing is not published"));
+    }
+
+    let (item, _): (Item, Product) = listing_action::get_item_and_product_for_listing(pgpool, listing).await?;
+    let (offer_id, mut offer): (String, Value) = get_updatable_offer(user_access_token, &item.id).await?;
+    ebay_client::set_offer_price(&mut offer, item.price_cents)?;
+
+    ebay_client::update_offer(user_access_token, &offer_id, &offer).await?;
+    log::info!("Updated ebay offer price; [{}]; [{}]", offer_id, item.price_cents);
+    Ok(())
+}
+
+/// Sets the available quantity of the inventory item of a published listing to its item's current available quantity.
+pub async fn update_offer_quantity(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listing: &Listing,
+) -> Result<(), ShopError> {
+    validate_listing_marketplace(listing)?;
+    if listing.status != ListingStatus::Published {
+        return Err(ShopError::new("listing is not published"));
+    }
+
+    let (item, _): (Item, Product) = listing_action::get_item_and_product_for_listing(pgpool, listing).await?;
+    let quantity: u32 = item_stock_action::get_available_quantity(pgpool, &item.id).await?;
+
+    ebay_client::update_inventory_item_quantity(user_access_token, &item, quantity).await?;
+    log::info!("Updated ebay inventory item quantity; [{}]; [{}]", item.id, quantity);
+    Ok(())
+}
+
+/// The ID and body of the item's offer, without the read-only fields returned by getOffers which updateOffer rejects
+async fn get_updatable_offer(
+    user_access_token: &str,
+    item_id: &Uuid,
+) -> Result<(String, Value), ShopError> {
+    let Some(mut offer) = get_offer(user_access_token, item_id).await? else {
+        return Err(ShopError::new("offer lookup failed"));
+    };
+    let offer_id: String = offer["offerId"]
+        .as_str()
+        .ok_or_else(|| ShopError::default())?
+        .to_string();
+
+    let offer_object = offer.as_object_mut()
+        .ok_or_else(|| ShopError::default())?;
+    for key in ["offerId", "status", "listing", "sku", "marketplaceId", "format"] {
+        offer_object.remove(key);
+    }
+    Ok((offer_id, offer))
+}
+
+/// A user access token obtained from the refresh token stored as a secret, for tasks which run without a user session,
+/// e.g. scheduled repricing.
+pub async fn get_stored_user_access_token() -> Result<String, ShopError> {
+    let refresh_token: String = String::from_utf8(master_decrypt(USER_REFRESH_TOKEN_SECRET_NAME)?)
+        .map_err(|error| ShopError::from_error("decoding refresh token", Box::new(error)))?;
+    Ok(ebay_client::refresh_user_token(&refresh_token).await?.access_token)
+}
+
+pub fn is_marketplace_listing(listing: &Listing) -> bool {
+    is_marketplace(&listing.marketplace_id)
+}
+
+pub fn is_marketplace(marketplace_id: &Uuid) -> bool {
+    MARKETPLACE_ID.get().is_some_and(|ebay_marketplace_id| marketplace_id.eq(ebay_marketplace_id))
+}
+
 fn validate_listing_marketplace(listing: &Listing) -> Result<(), ShopError> {
     if listing.marketplace_id.ne(MARKETPLACE_ID.get().unwrap()) {
         Err(ShopError::new(&format!(
@@ -175,12 +287,77 @@ async fn get_offer_id(
     Ok(Some(first_offer_id))
 }
 
+/// What the offer of an inventory item is made of, besides its SKU
+struct OfferTerms {
+    inventory_location_id: Uuid,
+    price_cents: u32,
+    /// The product whose categories the offer is listed in
+    product_id: Uuid,
+}
+
+async fn put_item_inventory_item(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listing: &Listing,
+) -> Result<OfferTerms, ShopError> {
+    let (item, product): (Item, Product) = listing_action::get_item_and_product_for_listing(pgpool, listing).await?;
+    let item_images: Vec<ItemImage> = item.get_all_item_images(pgpool).await?;
+    let item_grading: Option<ItemGrading> = item_grading_action::get_item_grading(pgpool, &item.id).await?;
+    let item_refurbishments: Vec<ItemRefurbishment> = item_refurbishment_action::get_all_item_refurbishments(pgpool, &item.id).await?;
+    let quantity: u32 = item_stock_action::get_available_quantity(pgpool, &item.id).await?;
+    if quantity == 0 {
+        return Err(ShopError::new("item is out of stock"));
+    }
+
+    ebay_client::create_or_replace_inventory_item(user_access_token, &item, quantity, &product, &item_images, item_grading.as_ref(), &item_refurbishments).await?;
+    Ok(OfferTerms {
+        inventory_location_id: item.inventory_location_id,
+        price_cents: item.price_cents,
+        product_id: item.product_id,
+    })
+}
+
+/// The bundle is listed in the categories of its first item's product.
+async fn put_bundle_inventory_item(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    bundle_id: &Uuid,
+) -> Result<OfferTerms, ShopError> {
+    let bundle: Bundle = bundle_action::get_bundle(pgpool, bundle_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Bundle not found for listing; [{}]", bundle_id)))?;
+    let items: Vec<Item> = bundle_action::get_all_bundle_items(pgpool, bundle_id).await?;
+    let Some(first_item) = items.first() else {
+        return Err(ShopError::new("bundle has no items"));
+    };
+    if items.iter().any(|item| item.inventory_location_id != first_item.inventory_location_id) {
+        return Err(ShopError::new("bundle items are not all at the same inventory location"));
+    }
+
+    let mut products: Vec<Product> = Vec::with_capacity(items.len());
+    for item in &items {
+        let product: Product = product_db::get_product(pgpool, &item.product_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Product not found for item; [{}]", item.id)))?;
+        products.push(product);
+    }
+    let bundle_images: Vec<BundleImage> = bundle_action::get_all_bundle_images(pgpool, bundle_id).await?;
+
+    ebay_client::create_or_replace_bundle_inventory_item(user_access_token, &bundle, &first_item.inventory_location_id, &products, &bundle_images).await?;
+    Ok(OfferTerms {
+        inventory_location_id: first_item.inventory_location_id,
+        price_cents: bundle.price_cents,
+        product_id: first_item.product_id,
+    })
+}
+
 async fn create_offer(
     pgpool: &PgPool,
     user_token: &str,
-    item: &Item,
+    sku: &Uuid,
+    offer_terms: &OfferTerms,
 ) -> Result<String, ShopError> {
-    let categories: Vec<Category> = crate::product::product_db::get_product_categories(pgpool, &item.product_id)
+    let categories: Vec<Category> = crate::product::product_db::get_product_categories(pgpool, &offer_terms.product_id)
         .await?
         .iter()
         .map(|entity| entity.try_to_model())
@@ -195,7 +372,7 @@ async fn create_offer(
         ebay_categories.push(ebay_category);
     }
 
-    ebay_client::create_offer(user_token, item, &ebay_categories.iter().collect())
+    ebay_client::create_offer(user_token, sku, &offer_terms.inventory_location_id, offer_terms.price_cents, &ebay_categories.iter().collect())
         .await
 }
 
@@ -39,7 +39,7 @@ pub fn configurer(config: &mut ServiceConfig) {
     );
 }
 
-fn extract_user_token(request: &HttpRequest) -> Result<Cookie, HttpResponse> {
+pub fn extract_user_token(request: &HttpRequest) -> Result<Cookie<'_>, HttpResponse> {
     match request.cookie(EBAY_USER_ACCESS_TOKEN_COOKIE_NAME) {
         Some(value) => Ok(value),
         None => Err(HttpResponse::build(StatusCode::UNAUTHORIZED)
@@ -186,7 +186,7 @@ async fn get_listing(
     ));
     let listing: Listing = unwrap_result_else_500!(listing.try_to_model());
 
-    let json: Value = unwrap_result_else_500!(ebay_client::get_inventory_item(&user_access_token.value(), &listing.item_id.to_string()).await);
+    let json: Value = unwrap_result_else_500!(ebay_client::get_inventory_item(user_access_token.value(), &listing.get_sku().to_string()).await);
     HttpResponse::Ok().json(json)
 }
 
@@ -1,13 +1,16 @@
 use super::super::ebay_category::ebay_category_model::Category;
 use super::ebay_client_shared;
 use crate::ebay::ebay_client::ebay_client_shared::EBAY_BASE_URL;
+use crate::bundle::{Bundle, BundleImage};
 use crate::environment::RuntimeEnvironment;
 use crate::error::ShopError;
 use crate::http;
 use crate::http::{WithBearer, HTTP_CLIENT};
 use crate::inventory_location::InventoryLocation;
 use crate::item::Item;
+use crate::item_grading::ItemGrading;
 use crate::item_image::ItemImage;
+use crate::item_refurbishment::ItemRefurbishment;
 use crate::product::Product;
 use reqwest::header::{CONTENT_LANGUAGE, CONTENT_TYPE};
 use reqwest::{Request, Response};
@@ -17,17 +20,22 @@ use uuid::Uuid;
 
 const INVENTORY_API_BASE_PATH: &str = "/sell/inventory/v1";
 
+/// The quantity is one for a unique item, or the available units of a stock record.
 pub async fn create_or_replace_inventory_item(
     user_access_token: &str,
     item: &Item,
+    quantity: u32,
     product: &Product,
     item_images: &Vec<ItemImage>,
+    item_grading: Option<&ItemGrading>,
+    item_refurbishments: &[ItemRefurbishment],
 ) -> Result<(), ShopError> {
     let condition: &str = super::ebay_condition::Condition::from(&item.condition).to_serial();
     let item_image_uris: Vec<String> = item_images
         .iter()
         .map(|element| element.get_item_image_uri())
         .collect::<Vec<_>>();
+    let description: String = inventory_item_description(product, item_grading, item_refurbishments);
 
     let mut body: Value = json!({
         "availability": {
@@ -35,16 +43,16 @@ pub async fn create_or_replace_inventory_item(
                 "availabilityDistributions": [
                     {
                         "merchantLocationKey": item.inventory_location_id,
-                        "quantity": 1,
+                        "quantity": quantity,
                     }
                 ],
-                "quantity": 1,
+                "quantity": quantity,
             }
         },
         "condition": condition,
         "product": {
             "title": product.display_name,
-            "description": product.display_name,
+            "description": description,
             "upc": [],
         },
     });
@@ -58,6 +66,13 @@ pub async fn create_or_replace_inventory_item(
         upc_array.push(json!(upc))
     }
 
+    if let Some(item_grading) = item_grading {
+        body.index_mut("product")
+            .as_object_mut()
+            .ok_or_else(|| ShopError::default())?
+            .insert("aspects".to_string(), Value::Object(super::ebay_grading::grading_aspects(item_grading)));
+    }
+
     // Do not include images when running locally because the image URIs are not publicly available
     if RuntimeEnvironment::default() != RuntimeEnvironment::Local {
         let uris: Vec<Value> = item_image_uris
@@ -70,13 +85,71 @@ pub async fn create_or_replace_inventory_item(
             .insert("imageUrls".to_string(), Value::Array(uris));
     }
 
-    let body: String = serde_json::to_string(&body)
+    put_inventory_item(user_access_token, &item.id, &body).await
+}
+
+/// A single inventory item offering every item of the bundle together, with the bundle's own title, price and images.
+/// The items must share the inventory location.
+pub async fn create_or_replace_bundle_inventory_item(
+    user_access_token: &str,
+    bundle: &Bundle,
+    inventory_location_id: &Uuid,
+    products: &[Product],
+    bundle_images: &[BundleImage],
+) -> Result<(), ShopError> {
+    let condition: &str = super::ebay_condition::Condition::from(&bundle.condition).to_serial();
+    let mut description: String = bundle.description.clone().unwrap_or_default();
+    let contents: Vec<String> = products.iter()
+        .map(|product| format!("- {}", product.display_name))
+        .collect();
+    description.push_str(&format!("\n\nIncludes:\n{}", contents.join("\n")));
+
+    let mut body: Value = json!({
+        "availability": {
+            "shipToLocationAvailability": {
+                "availabilityDistributions": [
+                    {
+                        "merchantLocationKey": inventory_location_id,
+                        "quantity": 1,
+                    }
+                ],
+                "quantity": 1,
+            }
+        },
+        "condition": condition,
+        "product": {
+            "title": bundle.display_name,
+            "description": description.trim(),
+        },
+    });
+
+    // Do not include images when running locally because the image URIs are not publicly available
+    if RuntimeEnvironment::default() != RuntimeEnvironment::Local {
+        let uris: Vec<Value> = bundle_images
+            .iter()
+            .map(|bundle_image| Value::String(bundle_image.get_bundle_image_uri()))
+            .collect::<Vec<_>>();
+        body.index_mut("product")
+            .as_object_mut()
+            .ok_or_else(|| ShopError::default())?
+            .insert("imageUrls".to_string(), Value::Array(uris));
+    }
+
+    put_inventory_item(user_access_token, &bundle.id, &body).await
+}
+
+async fn put_inventory_item(
+    user_access_token: &str,
+    sku: &Uuid,
+    body: &Value,
+) -> Result<(), ShopError> {
+    let body: String = serde_json::to_string(body)
         .map_err(|e|
             ShopError::from_error("serializing inventory item", Box::new(e))
         )?;
 
     let request: Request = HTTP_CLIENT
-        .put(format!("{}{}/inventory_item/{}", *EBAY_BASE_URL, INVENTORY_API_BASE_PATH, item.id))
+        .put(format!("{}{}/inventory_item/{}", *EBAY_BASE_URL, INVENTORY_API_BASE_PATH, sku))
         .header(CONTENT_LANGUAGE, ebay_client_shared::EBAY_CONTENT_LANGUAGE)
         .header(CONTENT_TYPE, "application/json")
         .with_bearer(user_access_token)
@@ -88,6 +161,73 @@ pub async fn create_or_replace_inventory_item(
     Ok(())
 }
 
+/// The product name, followed by the item's grading and any refurbishment work marked for disclosure
+fn inventory_item_description(
+    product: &Product,
+    item_grading: Option<&ItemGrading>,
+    item_refurbishments: &[ItemRefurbishment],
+) -> String {
+    let mut description: String = product.display_name.clone();
+    if let Some(item_grading) = item_grading {
+        description.push_str(&format!("\n\n{}", item_grading.get_description()));
+    }
+
+    let disclosures: Vec<String> = item_refurbishments.iter()
+        .filter(|item_refurbishment| item_refurbishment.disclose)
+        .map(|item_refurbishment| format!("- {}", item_refurbishment.get_disclosure()))
+        .collect();
+    if !disclosures.is_empty() {
+        description.push_str(&format!("\n\nRefurbishment work:\n{}", disclosures.join("\n")));
+    }
+    description
+}
+
+/// Sets the available quantity of an existing inventory item without replacing the rest of it. A published offer for
+/// the inventory item is revised on its live listing, and ends once the quantity reaches zero.
+pub async fn update_inventory_item_quantity(
+    user_access_token: &str,
+    item: &Item,
+    quantity: u32,
+) -> Result<(), ShopError> {
+    let body: Value = json!({
+        "requests": [
+            {
+                "sku": item.id,
+                "shipToLocationAvailability": {
+                    "availabilityDistributions": [
+                        {
+                            "merchantLocationKey": item.inventory_location_id,
+                            "quantity": quantity,
+                        }
+                    ],
+                    "quantity": quantity,
+                },
+            }
+        ],
+    });
+    let body: String = serde_json::to_string(&body)
+        .map_err(|e| ShopError::from_error("serializing quantity update", Box::new(e)))?;
+
+    let request: Request = HTTP_CLIENT
+        .post(format!("{}{}/bulk_update_price_quantity", *EBAY_BASE_URL, INVENTORY_API_BASE_PATH))
+        .header(CONTENT_LANGUAGE, ebay_client_shared::EBAY_CONTENT_LANGUAGE)
+        .header(CONTENT_TYPE, "application/json")
+        .with_bearer(user_access_token)
+        .body(body)
+        .build()
+        .map_err(|e| ShopError::from_error("malformed request", Box::new(e)))?;
+
+    // Failures of the individual updates are reported in the body of an otherwise successful response
+    let response: Response = http::execute_checked(request).await?;
+    let response_body: Value = response.json().await
+        .map_err(|e| ShopError::from_error("deserializing quantity update response", Box::new(e)))?;
+    let status_code: Option<u64> = response_body["responses"][0]["statusCode"].as_u64();
+    if status_code != Some(200) {
+        return Err(ShopError::new(&format!("Failed to update inventory item quantity; [{}]; {}", item.id, response_body)));
+    }
+    Ok(())
+}
+
 pub async fn get_inventory_item(
     user_access_token: &str,
     item_id: &str,
@@ -253,15 +393,18 @@ pub async fn get_offers_fixed_price(
     Ok(Some(body))
 }
 
+/// The SKU is the ID of the item or bundle whose inventory item the offer sells.
 pub async fn create_offer(
     user_access_token: &str,
-    item: &Item,
+    sku: &Uuid,
+    inventory_location_id: &Uuid,
+    price_cents: u32,
     ebay_categories: &Vec<&Category>,
 ) -> Result<String, ShopError> {
     let category_0: &Category = *ebay_categories.get(0)
         .ok_or_else(|| ShopError::new("missing category"))?;
-    let price: String = dollar_string(u64::from(item.price_cents));
-    let price_div_2: String = dollar_string(u64::from(item.price_cents / 2));
+    let price: String = dollar_string(u64::from(price_cents));
+    let price_div_2: String = dollar_string(u64::from(price_cents / 2));
     let fulfillment_policy_id: &String = super::super::ebay_action::NOMINAL_FULFILLMENT_POLICY_ID
         .get()
         .ok_or_else(|| ShopError::default())?;
@@ -290,14 +433,14 @@ pub async fn create_offer(
             "returnPolicyId": return_policy_id,
         },
         "marketplaceId": ebay_client_shared::EBAY_MARKETPLACE_ID_US,
-        "merchantLocationKey": item.inventory_location_id,
+        "merchantLocationKey": inventory_location_id,
         "pricingSummary": {
             "price": {
                 "currency": "USD",
                 "value": price,
             }
         },
-        "sku": item.id,
+        "sku": sku,
         "tax": {
             "applyTax": false
         }
@@ -325,8 +468,55 @@ pub async fn create_offer(
     Ok(offer_id)
 }
 
+/// Replaces the offer with the given body. Published offers are revised on their live listing.
+pub async fn update_offer(
+    user_access_token: &str,
+    offer_id: &str,
+    body: &Value,
+) -> Result<(), ShopError> {
+    let body: String = serde_json::to_string(body)
+        .map_err(|e| ShopError::from_error("serializing offer", Box::new(e)))?;
+
+    let request: Request = HTTP_CLIENT
+        .put(format!("{}{}/offer/{}", *EBAY_BASE_URL, INVENTORY_API_BASE_PATH, offer_id))
+        .header(CONTENT_TYPE, "application/json")
+        .header(CONTENT_LANGUAGE, ebay_client_shared::EBAY_CONTENT_LANGUAGE)
+        .with_bearer(user_access_token)
+        .body(body)
+        .build()
+        .map_err(|e| ShopError::from_error("malformed request", Box::new(e)))?;
+    http::execute_checked(request).await?;
+    Ok(())
+}
+
 fn dollar_string(cents: u64) -> String {
-    format!("{}.{}", cents / 100, cents % 100)
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Sets the price of an offer body, along with the best offer auto-decline price derived from it in [create_offer].
+pub fn set_offer_price(offer: &mut Value, price_cents: u32) -> Result<(), ShopError> {
+    let price: String = dollar_string(u64::from(price_cents));
+    let price_div_2: String = dollar_string(u64::from(price_cents / 2));
+
+    let offer_object = offer.as_object_mut()
+        .ok_or_else(|| ShopError::default())?;
+    offer_object.entry("pricingSummary")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| ShopError::default())?
+        .insert("price".to_string(), json!({
+            "currency": "USD",
+            "value": price,
+        }));
+    if let Some(best_offer_terms) = offer_object.get_mut("listingPolicies")
+        .and_then(|listing_policies| listing_policies.get_mut("bestOfferTerms"))
+        .and_then(|best_offer_terms| best_offer_terms.as_object_mut()) {
+        best_offer_terms.insert("autoDeclinePrice".to_string(), json!({
+            "currency": "USD",
+            "value": price_div_2,
+        }));
+    }
+    Ok(())
 }
 
 pub async fn publish_offer(
@@ -0,0 +1,20 @@
+use crate::item_grading::{GradingCompany, ItemGrading};
+use serde_json::{json, Map, Value};
+
+/// Values of the "Professional Grader" item aspect
+fn grader_aspect_value(company: &GradingCompany) -> &'static str {
+    match company {
+        GradingCompany::Afa => "Action Figure Authority (AFA)",
+        GradingCompany::Cgc => "Certified Guaranty Company (CGC)",
+    }
+}
+
+/// Item aspects describing a certified grade. Each aspect value is an array, per the inventory item schema.
+pub fn grading_aspects(item_grading: &ItemGrading) -> Map<String, Value> {
+    let mut aspects: Map<String, Value> = Map::new();
+    aspects.insert("Graded".to_string(), json!(["Yes"]));
+    aspects.insert("Professional Grader".to_string(), json!([grader_aspect_value(&item_grading.company)]));
+    aspects.insert("Grade".to_string(), json!([item_grading.company.format_grade(item_grading.grade_tenths)]));
+    aspects.insert("Certification Number".to_string(), json!([item_grading.certificate_number]));
+    aspects
+}
@@ -3,6 +3,7 @@ mod ebay_client_auth;
 mod ebay_client_shared;
 mod ebay_client_trading;
 mod ebay_condition;
+mod ebay_grading;
 mod ebay_client_shared_xml;
 
 pub use ebay_client_inventory::*;
@@ -85,6 +85,12 @@ pub fn init_logger() -> Result<(), SetLoggerError> {
         .try_init()
 }
 
+/// Whether the enabled repricing rules are applied on a schedule; see
+/// [crate::repricing_rule::repricing_rule_action::spawn_scheduler]. Disabled unless `REPRICING_SCHEDULER_ENABLED=true`.
+pub fn repricing_scheduler_enabled() -> bool {
+    env::var("REPRICING_SCHEDULER_ENABLED").is_ok_and(|value| value == "true")
+}
+
 pub fn images_directory_path() -> Result<PathBuf, ShopError> {
     let path: PathBuf = if RuntimeEnvironment::default() == RuntimeEnvironment::Local {
         /* For local development, it is expected that the server is running on the host system.
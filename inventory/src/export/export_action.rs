@@ -0,0 +1,70 @@
+use crate::error::ShopError;
+use crate::export::{export_db, ItemExportEntity};
+use crate::import::ImportFormat;
+use crate::item::ItemSearchParameters;
+use actix_web::web::Bytes;
+use futures::channel::mpsc;
+use futures::SinkExt;
+use sqlx::PgPool;
+
+/// Number of serialized rows which may be buffered ahead of a slow client.
+const EXPORT_CHANNEL_CAPACITY: usize = 64;
+
+/// Streams the export on a separate task. The bounded channel applies backpressure, so rows are fetched only as fast
+/// as the client consumes them.
+pub fn stream_item_export(
+    pgpool: PgPool,
+    parameters: ItemSearchParameters,
+    format: ImportFormat,
+) -> mpsc::Receiver<Result<Bytes, actix_web::Error>> {
+    let (mut sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+
+    actix_web::rt::spawn(async move {
+        let mut is_first_row: bool = true;
+        let result: Result<(), ShopError> = export_db::stream_item_exports(&pgpool, &parameters, async |entity| {
+            let chunk: Result<Bytes, ShopError> = serialize_item_export(&format, &entity, is_first_row);
+            is_first_row = false;
+
+            let chunk = chunk.map_err(|error| {
+                log::error!("{}", error);
+                actix_web::error::ErrorInternalServerError(error.message)
+            });
+            let is_error: bool = chunk.is_err();
+            sender.send(chunk).await.is_ok() && !is_error
+        }).await;
+
+        if let Err(error) = result {
+            log::error!("{}", error);
+            let _ = sender.send(Err(actix_web::error::ErrorInternalServerError(error.message))).await;
+        }
+    });
+
+    receiver
+}
+
+/// The CSV header is written with the first row, so an empty export produces an empty file.
+fn serialize_item_export(
+    format: &ImportFormat,
+    entity: &ItemExportEntity,
+    is_first_row: bool,
+) -> Result<Bytes, ShopError> {
+    let serial = entity.try_to_serial()?;
+    match format {
+        ImportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(is_first_row)
+                .from_writer(Vec::new());
+            writer.serialize(&serial)
+                .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
+            let bytes: Vec<u8> = writer.into_inner()
+                .map_err(|e| ShopError::from_error("Failed to flush CSV row;", Box::new(e.into_error())))?;
+            Ok(Bytes::from(bytes))
+        }
+        ImportFormat::Jsonl => {
+            let mut bytes: Vec<u8> = serde_json::to_vec(&serial)
+                .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
+            bytes.push(b'\n');
+            Ok(Bytes::from(bytes))
+        }
+    }
+}
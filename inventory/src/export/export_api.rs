@@ -0,0 +1,44 @@
+use crate::export::{export_action, ExportParameters};
+use crate::import::ImportFormat;
+use crate::item::ItemSearchParameters;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::PgPool;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/export")
+            .route("/item", web::get().to(export_items))
+    );
+}
+
+/// Accepts the same filters and ordering as the item search.
+async fn export_items(
+    pgpool: web::Data<PgPool>,
+    search_parameters: web::Query<ItemSearchParameters>,
+    export_parameters: web::Query<ExportParameters>,
+) -> impl Responder {
+    let search_parameters: ItemSearchParameters = match search_parameters.into_inner().validated() {
+        Ok(search_parameters) => search_parameters,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let format: ImportFormat = export_parameters.into_inner().format;
+    let (content_type, file_name) = match format {
+        ImportFormat::Csv => ("text/csv; charset=utf-8", "items.csv"),
+        ImportFormat::Jsonl => ("application/jsonl", "items.jsonl"),
+    };
+
+    let stream = export_action::stream_item_export(
+        pgpool.get_ref().clone(),
+        search_parameters,
+        format,
+    );
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(String::from(file_name))],
+        })
+        .streaming(stream)
+}
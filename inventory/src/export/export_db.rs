@@ -0,0 +1,70 @@
+use crate::error::ShopError;
+use crate::export::ItemExportEntity;
+use crate::item::{item_db, ItemSearchParameters};
+use futures::TryStreamExt;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+/// Rows are fetched incrementally and passed to the consumer as they arrive, so the result set is never held in
+/// memory. The consumer returns false to stop the export early (e.g. when the client disconnects).
+pub async fn stream_item_exports(
+    pgpool: &PgPool,
+    parameters: &ItemSearchParameters,
+    mut consumer: impl AsyncFnMut(ItemExportEntity) -> bool,
+) -> Result<(), ShopError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select item.id as item_id, item.code as item_code, product.internal_name as product_internal_name, product.display_name as product_display_name, product.upc, product.release_date, \
+            (select string_agg(category.internal_name, ';' order by category.internal_name) \
+                from shop.public.product_category_association \
+                inner join shop.public.category on category.id = product_category_association.category_id \
+                where product_category_association.product_id = item.product_id) as categories, \
+            inventory_location.internal_name as inventory_location, \
+            item.condition, item.status, item.price_cents, item.priority, item.note, item.acquisition_datetime, item.acquisition_price_cents, item.acquisition_location, \
+            (select string_agg(item_attribute.key || '=' || item_attribute.value, ';' order by item_attribute.priority, item_attribute.key) \
+                from shop.public.item_attribute \
+                where item_attribute.item_id = item.id) as attributes, \
+            (select string_agg(label.internal_name, ';' order by label.internal_name) \
+                from shop.public.item_label_association \
+                inner join shop.public.label on label.id = item_label_association.label_id \
+                where item_label_association.item_id = item.id) as labels, \
+            (select string_agg(listing.id::text, ';' order by listing.created) \
+                from shop.public.listing \
+                where listing.item_id = item.id) as listing_ids, \
+            (select string_agg(distinct marketplace.internal_name, ';') \
+                from shop.public.listing \
+                inner join shop.public.marketplace on marketplace.id = listing.marketplace_id \
+                where listing.item_id = item.id) as listing_marketplaces, \
+            purchase_summary.purchase_ids, purchase_summary.purchase_subtotal_cents, purchase_summary.purchase_tax_cents, \
+            purchase_summary.purchase_shipping_cents, purchase_summary.purchase_discount_cents, purchase_summary.purchase_seller_cost_total_cents, \
+            item.created, item.updated \
+        from shop.public.item \
+        inner join shop.public.product on product.id = item.product_id \
+        inner join shop.public.inventory_location on inventory_location.id = item.inventory_location_id \
+        left join lateral ( \
+            select string_agg(purchase.id::text, ';' order by purchase.created) as purchase_ids, \
+                coalesce(sum(purchase.cost_subtotal_cents), 0)::bigint as purchase_subtotal_cents, \
+                coalesce(sum(purchase.cost_tax_cents), 0)::bigint as purchase_tax_cents, \
+                coalesce(sum(purchase.cost_shipping_cents), 0)::bigint as purchase_shipping_cents, \
+                coalesce(sum(purchase.cost_discount_cents), 0)::bigint as purchase_discount_cents, \
+                coalesce(sum(purchase.seller_cost_total_cents), 0)::bigint as purchase_seller_cost_total_cents \
+            from shop.public.purchase \
+            inner join shop.public.listing on listing.id = purchase.listing_id \
+            where listing.item_id = item.id \
+        ) as purchase_summary on true \
+        where true \
+    ");
+    item_db::push_item_search_filters(&mut builder, parameters);
+    builder.push(format!(
+        " order by item.{} {}, item.id {}",
+        parameters.sort_key.get_column_name(),
+        parameters.sort_direction.to_sql(),
+        parameters.sort_direction.to_sql(),
+    ));
+
+    let mut rows = builder.build_query_as::<ItemExportEntity>().fetch(pgpool);
+    while let Some(entity) = rows.try_next().await? {
+        if !consumer(entity).await {
+            break;
+        }
+    }
+    Ok(())
+}
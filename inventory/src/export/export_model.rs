@@ -0,0 +1,121 @@
+use crate::error::ShopError;
+use crate::import::ImportFormat;
+use crate::item::{ItemCondition, ItemStatus};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportParameters {
+    /// Exports share the import formats and column names, so an exported file may be re-imported. Rows are matched to
+    /// their items by the item ID and code columns, so items which still exist are skipped rather than duplicated; see
+    /// [crate::import::ImportRow].
+    #[serde(default)]
+    pub format: ImportFormat,
+}
+
+/// An item joined with its product, location, categories, attributes, labels, listings, and purchases.
+/// List-valued columns are separated by [crate::import::IMPORT_LIST_SEPARATOR].
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ItemExportEntity {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_internal_name: String,
+    pub product_display_name: String,
+    pub upc: Option<String>,
+    pub release_date: Option<NaiveDate>,
+    pub categories: Option<String>,
+    pub inventory_location: String,
+    pub condition: i32,
+    pub status: i32,
+    pub price_cents: i64,
+    pub priority: i32,
+    pub note: Option<String>,
+    pub acquisition_datetime: DateTime<Utc>,
+    pub acquisition_price_cents: Option<i64>,
+    pub acquisition_location: Option<String>,
+    pub attributes: Option<String>,
+    pub labels: Option<String>,
+    pub listing_ids: Option<String>,
+    pub listing_marketplaces: Option<String>,
+    pub purchase_ids: Option<String>,
+    pub purchase_subtotal_cents: i64,
+    pub purchase_tax_cents: i64,
+    pub purchase_shipping_cents: i64,
+    pub purchase_discount_cents: i64,
+    pub purchase_seller_cost_total_cents: i64,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ItemExportEntity {
+    pub fn try_to_serial(&self) -> Result<ItemExportSerial, ShopError> {
+        Ok(ItemExportSerial {
+            item_id: self.item_id,
+            item_code: self.item_code.clone(),
+            product_internal_name: self.product_internal_name.clone(),
+            product_display_name: self.product_display_name.clone(),
+            upc: self.upc.clone(),
+            release_date: self.release_date,
+            categories: self.categories.clone(),
+            inventory_location: self.inventory_location.clone(),
+            condition: ItemCondition::try_from_repr(u8::try_from(self.condition)
+                .map_err(|e| ShopError::from_error_default(Box::new(e)))?)?
+                .to_serial(),
+            status: ItemStatus::try_from_repr(u8::try_from(self.status)
+                .map_err(|e| ShopError::from_error_default(Box::new(e)))?)?
+                .into(),
+            price_cents: self.price_cents,
+            priority: self.priority,
+            note: self.note.clone(),
+            acquisition_datetime: self.acquisition_datetime,
+            acquisition_price_cents: self.acquisition_price_cents,
+            acquisition_location: self.acquisition_location.clone(),
+            attributes: self.attributes.clone(),
+            labels: self.labels.clone(),
+            listing_ids: self.listing_ids.clone(),
+            listing_marketplaces: self.listing_marketplaces.clone(),
+            purchase_ids: self.purchase_ids.clone(),
+            purchase_subtotal_cents: self.purchase_subtotal_cents,
+            purchase_tax_cents: self.purchase_tax_cents,
+            purchase_shipping_cents: self.purchase_shipping_cents,
+            purchase_discount_cents: self.purchase_discount_cents,
+            purchase_seller_cost_total_cents: self.purchase_seller_cost_total_cents,
+            created: self.created,
+            updated: self.updated,
+        })
+    }
+}
+
+/// Enumerations are written by name rather than by representation for the benefit of spreadsheets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemExportSerial {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_internal_name: String,
+    pub product_display_name: String,
+    pub upc: Option<String>,
+    pub release_date: Option<NaiveDate>,
+    pub categories: Option<String>,
+    pub inventory_location: String,
+    pub condition: &'static str,
+    pub status: &'static str,
+    pub price_cents: i64,
+    pub priority: i32,
+    pub note: Option<String>,
+    pub acquisition_datetime: DateTime<Utc>,
+    pub acquisition_price_cents: Option<i64>,
+    pub acquisition_location: Option<String>,
+    pub attributes: Option<String>,
+    pub labels: Option<String>,
+    pub listing_ids: Option<String>,
+    pub listing_marketplaces: Option<String>,
+    pub purchase_ids: Option<String>,
+    pub purchase_subtotal_cents: i64,
+    pub purchase_tax_cents: i64,
+    pub purchase_shipping_cents: i64,
+    pub purchase_discount_cents: i64,
+    pub purchase_seller_cost_total_cents: i64,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
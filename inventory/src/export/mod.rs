@@ -0,0 +1,7 @@
+pub(crate) mod export_db;
+
+pub mod export_api;
+pub mod export_model;
+pub mod export_action;
+
+pub use export_model::*;
@@ -66,6 +66,17 @@ pub fn header_set_cookie_secure(name: &str, token: &str, lifetime: u64) -> (&'st
     )
 }
 
+/// Set by the authenticating proxy on every request under /api
+const REMOTE_USER_HEADER_NAME: &str = "Remote-User";
+
+/// The user authenticated by the proxy, if any
+pub fn extract_remote_user(request: &actix_web::HttpRequest) -> Option<String> {
+    request.headers()
+        .get(REMOTE_USER_HEADER_NAME)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
 pub trait WithBearer {
     fn with_bearer(self, token: &str) -> Self;
 }
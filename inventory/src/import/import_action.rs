@@ -0,0 +1,363 @@
+use crate::category::category_db;
+use crate::error::ShopError;
+use crate::import::{ImportFormat, ImportReport, ImportRow, ImportRowError, IMPORT_LIST_SEPARATOR};
+use crate::inventory_location::{inventory_location_db, InventoryLocation};
+use crate::item::{item_action, item_db, normalize_item_code, Item, ItemCondition, ItemEntity, ItemStatus};
+use crate::item_attribute::{item_attribute_action, ItemAttribute};
+use crate::label::{label_db, Label};
+use crate::product::{product_db, Product, ProductEntity};
+use crate::{object, ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{Acquire, PgPool, Postgres, Transaction};
+use strum::VariantArray;
+use uuid::Uuid;
+
+/// Records which were written while importing a single row.
+#[derive(Debug, Default)]
+struct ImportRowOutcome {
+    product_created: bool,
+    item_created: bool,
+    item_skipped: bool,
+    labels_created: u32,
+}
+
+impl ImportRow {
+    fn has_item_columns(&self) -> bool {
+        self.item_id.is_some()
+            || self.item_code.is_some()
+            || self.condition.is_some()
+            || self.price_cents.is_some()
+            || self.priority.is_some()
+            || self.note.is_some()
+            || self.acquisition_datetime.is_some()
+            || self.acquisition_price_cents.is_some()
+            || self.acquisition_location.is_some()
+            || self.attributes.is_some()
+            || self.labels.is_some()
+    }
+}
+
+impl ImportReport {
+    fn record(&mut self, outcome: ImportRowOutcome) {
+        match outcome.product_created {
+            true => self.products_created += 1,
+            false => self.products_matched += 1,
+        }
+        if outcome.item_created {
+            self.items_created += 1;
+        }
+        if outcome.item_skipped {
+            self.items_skipped += 1;
+        }
+        self.labels_created += outcome.labels_created;
+    }
+}
+
+/// Each row is parsed independently so that one malformed row does not prevent the validation of the others.
+/// Row numbers are one-indexed and exclude the CSV header.
+pub fn parse_rows(format: &ImportFormat, bytes: &[u8]) -> Vec<(u32, Result<ImportRow, ShopError>)> {
+    match format {
+        ImportFormat::Csv => csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(bytes)
+            .into_deserialize::<ImportRow>()
+            .enumerate()
+            .map(|(index, result)| (
+                index as u32 + 1,
+                result.map_err(|e| ShopError::from_error(&format!("Malformed CSV row; [{}]", e), Box::new(e))),
+            ))
+            .collect(),
+        ImportFormat::Jsonl => bytes
+            .split(|byte| *byte == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.trim_ascii().is_empty())
+            .map(|(index, line)| (
+                index as u32 + 1,
+                serde_json::from_slice::<ImportRow>(line)
+                    .map_err(|e| ShopError::from_error(&format!("Malformed JSON row; [{}]", e), Box::new(e))),
+            ))
+            .collect(),
+    }
+}
+
+/// Imports all rows within a single transaction. The transaction is committed only if every row succeeds and this is
+/// not a dry run; otherwise the returned report lists the error for each failed row.
+pub async fn import(
+    pgpool: &PgPool,
+    rows: Vec<(u32, Result<ImportRow, ShopError>)>,
+    dry_run: bool,
+) -> Result<ImportReport, ShopError> {
+    let mut report: ImportReport = ImportReport {
+        dry_run,
+        row_count: rows.len() as u32,
+        ..ImportReport::default()
+    };
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    for (row_number, row) in rows {
+        let row: ImportRow = match row {
+            Ok(row) => row,
+            Err(error) => {
+                report.errors.push(ImportRowError::new(row_number, &error));
+                continue;
+            }
+        };
+
+        // A failed statement aborts the enclosing transaction, so each row is isolated within a savepoint.
+        let mut savepoint: Transaction<Postgres> = transaction.begin().await?;
+        match import_row(&mut savepoint, &row).await {
+            Ok(outcome) => {
+                savepoint.commit().await?;
+                report.record(outcome);
+            }
+            Err(error) => {
+                savepoint.rollback().await?;
+                report.errors.push(ImportRowError::new(row_number, &error));
+            }
+        }
+    }
+
+    if dry_run || !report.errors.is_empty() {
+        transaction.rollback().await?;
+    } else {
+        transaction.commit().await?;
+        report.committed = true;
+    }
+    Ok(report)
+}
+
+async fn import_row(
+    transaction: &mut Transaction<'_, Postgres>,
+    row: &ImportRow,
+) -> Result<ImportRowOutcome, ShopError> {
+    if row.product_internal_name.trim().is_empty() {
+        return Err(ShopError::new("Product internal name is required;"));
+    }
+    let mut outcome: ImportRowOutcome = ImportRowOutcome::default();
+
+    let product: Product = match find_product(transaction, row).await? {
+        Some(product) => product,
+        None => {
+            let product: Product = create_product(transaction, row).await?;
+            outcome.product_created = true;
+            product
+        }
+    };
+    associate_categories(transaction, &product, row).await?;
+
+    if let Some(item) = find_item(transaction, row).await? {
+        if item.product_id != product.id {
+            return Err(ShopError::new(&format!("Existing item belongs to a different product; [{}]", item.id)));
+        }
+        outcome.item_skipped = true;
+        return Ok(outcome);
+    }
+
+    let Some(inventory_location_name) = &row.inventory_location else {
+        if row.has_item_columns() {
+            return Err(ShopError::new("Item columns require an inventory location;"));
+        }
+        return Ok(outcome);
+    };
+    let item: Item = create_item(transaction, &product, inventory_location_name, row).await?;
+    outcome.item_created = true;
+
+    for (key, value) in split_attributes(&row.attributes)? {
+        let item_attribute: ItemAttribute = ItemAttribute {
+            item_id: item.id,
+            key: key.to_string(),
+            value: value.to_string(),
+            visible: true,
+            priority: 0,
+            value_number: None,
+            value_date: None,
+            value_boolean: None,
+        };
+        item_attribute_action::create_item_attribute_in_transaction(transaction, item_attribute).await?;
+    }
+
+    for label_name in split_list(&row.labels) {
+        let label: Label = match label_db::get_label_by_internal_name(&mut **transaction, label_name).await? {
+            Some(entity) => entity.try_to_model()?,
+            None => {
+                let label: Label = Label {
+                    id: object::random_uuid(),
+                    display_name: label_name.to_string(),
+                    internal_name: label_name.to_string(),
+                };
+                label_db::create_label(&mut **transaction, &label.to_entity()).await?;
+                outcome.labels_created += 1;
+                label
+            }
+        };
+        item_db::create_item_label_association(&mut **transaction, &item.id, &label.id).await?;
+    }
+
+    Ok(outcome)
+}
+
+/// Matches by UPC, then by internal name.
+async fn find_product(
+    transaction: &mut Transaction<'_, Postgres>,
+    row: &ImportRow,
+) -> Result<Option<Product>, ShopError> {
+    let product_by_upc: Option<ProductEntity> = match &row.upc {
+        Some(upc) => product_db::get_product_by_upc(&mut **transaction, upc).await?,
+        None => None,
+    };
+    if let Some(entity) = product_by_upc {
+        return Ok(Some(entity.try_to_model()?));
+    }
+
+    let Some(entity) = product_db::get_product_by_internal_name(&mut **transaction, &row.product_internal_name).await? else {
+        return Ok(None);
+    };
+    let product: Product = entity.try_to_model()?;
+    if row.upc.is_some() && product.upc.is_some() && row.upc != product.upc {
+        return Err(ShopError::new(&format!(
+            "Product matched by internal name has a different UPC; [{}, {:?}]",
+            product.internal_name,
+            product.upc,
+        )));
+    }
+    Ok(Some(product))
+}
+
+async fn create_product(
+    transaction: &mut Transaction<'_, Postgres>,
+    row: &ImportRow,
+) -> Result<Product, ShopError> {
+    let display_name: String = row.product_display_name.clone()
+        .ok_or_else(|| ShopError::new(&format!(
+            "Product display name is required to create a product; [{}]",
+            row.product_internal_name,
+        )))?;
+
+    let product: Product = Product {
+        id: object::random_uuid(),
+        display_name,
+        internal_name: row.product_internal_name.clone(),
+        upc: row.upc.clone(),
+        release_date: row.release_date,
+        created: Utc::now(),
+        updated: Utc::now(),
+    };
+    product_db::create_product(&mut **transaction, &product.to_entity()).await?;
+    Ok(product)
+}
+
+/// Categories which are already associated with the product are skipped.
+async fn associate_categories(
+    transaction: &mut Transaction<'_, Postgres>,
+    product: &Product,
+    row: &ImportRow,
+) -> Result<(), ShopError> {
+    let category_names: Vec<&str> = split_list(&row.categories);
+    if category_names.is_empty() {
+        return Ok(());
+    }
+
+    let mut category_ids: Vec<Uuid> = product_db::get_product_categories(&mut **transaction, &product.id).await?
+        .iter()
+        .map(|category| category.id)
+        .collect();
+    for category_name in category_names {
+        let category = category_db::get_category_by_internal_name(&mut **transaction, category_name).await?
+            .ok_or_else(|| ShopError::new(&format!("Category not found; [{}]", category_name)))?;
+        if category_ids.contains(&category.id) {
+            continue;
+        }
+        product_db::create_product_category_association(&mut **transaction, &product.id, &category.id).await?;
+        category_ids.push(category.id);
+    }
+    Ok(())
+}
+
+/// Matches by ID, then by code. A row without either never matches.
+async fn find_item(
+    transaction: &mut Transaction<'_, Postgres>,
+    row: &ImportRow,
+) -> Result<Option<ItemEntity>, ShopError> {
+    let item_id: Option<Uuid> = match (&row.item_id, &row.item_code) {
+        (Some(item_id), _) => Some(*item_id),
+        (None, Some(item_code)) => item_db::get_item_id_by_code(&mut **transaction, &normalize_item_code(item_code)?).await?,
+        (None, None) => None,
+    };
+    match item_id {
+        Some(item_id) => item_db::get_item_for_update(&mut **transaction, &item_id).await,
+        None => Ok(None),
+    }
+}
+
+async fn create_item(
+    transaction: &mut Transaction<'_, Postgres>,
+    product: &Product,
+    inventory_location_name: &str,
+    row: &ImportRow,
+) -> Result<Item, ShopError> {
+    let inventory_location: InventoryLocation = inventory_location_db::get_inventory_location_by_internal_name(
+        &mut **transaction,
+        inventory_location_name,
+    ).await?
+        .ok_or_else(|| ShopError::new(&format!("Inventory location not found; [{}]", inventory_location_name)))?
+        .try_to_model()?;
+    let condition: ItemCondition = match &row.condition {
+        Some(condition) => parse_item_condition(condition)?,
+        None => return Err(ShopError::new("Condition is required to create an item;")),
+    };
+    let price_cents: u32 = row.price_cents
+        .ok_or_else(|| ShopError::new("Price is required to create an item;"))?;
+
+    // Imported items lack images and listings, so they begin incomplete.
+    let item: Item = Item {
+        id: object::random_uuid(),
+        code: String::new(),
+        product_id: product.id,
+        inventory_location_id: inventory_location.id,
+        storage_slot_id: None,
+        condition,
+        status: ItemStatus::Incomplete,
+        price_cents,
+        priority: row.priority.unwrap_or(0),
+        note: row.note.clone(),
+        acquisition_datetime: row.acquisition_datetime.unwrap_or_else(Utc::now),
+        acquisition_price_cents: row.acquisition_price_cents,
+        acquisition_location: row.acquisition_location.clone(),
+        consignor_id: None,
+        commission_basis_points: None,
+        commission_minimum_cents: None,
+        created: Utc::now(),
+        updated: Utc::now(),
+    };
+    item_action::create_item_in_transaction(transaction, item).await
+}
+
+/// Accepts either the numeric representation or the serial name of the condition.
+fn parse_item_condition(value: &str) -> Result<ItemCondition, ShopError> {
+    if let Ok(discriminant) = value.parse::<u8>() {
+        return ItemCondition::try_from_repr(discriminant);
+    }
+    ItemCondition::VARIANTS.iter()
+        .find(|variant| variant.to_serial() == value)
+        .cloned()
+        .ok_or_else(|| ShopError::new(&format!("Unrecognized item condition; [{}]", value)))
+}
+
+fn split_list(value: &Option<String>) -> Vec<&str> {
+    match value {
+        Some(value) => value.split(IMPORT_LIST_SEPARATOR)
+            .map(|element| element.trim())
+            .filter(|element| !element.is_empty())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn split_attributes(value: &Option<String>) -> Result<Vec<(&str, &str)>, ShopError> {
+    split_list(value)
+        .into_iter()
+        .map(|pair| pair.split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .ok_or_else(|| ShopError::new(&format!("Attribute must be of the form key=value; [{}]", pair))))
+        .collect()
+}
@@ -0,0 +1,34 @@
+use crate::import::{import_action, ImportParameters, ImportReport};
+use crate::object::JsonHttpResponse;
+use crate::unwrap_result_else_500;
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse, Responder};
+use sqlx::PgPool;
+
+/// Import files are accepted in a single request body.
+const IMPORT_PAYLOAD_LIMIT: usize = 16 * 1024 * 1024;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/import")
+            .app_data(web::PayloadConfig::new(IMPORT_PAYLOAD_LIMIT))
+            .route("", web::post().to(import))
+    );
+}
+
+async fn import(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ImportParameters>,
+    body: web::Bytes,
+) -> impl Responder {
+    let parameters: ImportParameters = parameters.into_inner();
+    let rows = import_action::parse_rows(&parameters.format, &body);
+    let report: ImportReport = unwrap_result_else_500!(
+        import_action::import(&pgpool, rows, parameters.dry_run).await
+    );
+
+    if !report.errors.is_empty() {
+        return HttpResponse::BadRequest().json(report);
+    }
+    report.to_http_response()
+}
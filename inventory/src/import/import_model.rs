@@ -0,0 +1,113 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ImportFormat {
+    #[serde(rename = "csv")]
+    Csv,
+    /// One JSON object per line
+    #[serde(rename = "jsonl")]
+    Jsonl,
+}
+
+impl Default for ImportFormat {
+    fn default() -> Self {
+        ImportFormat::Csv
+    }
+}
+
+impl ImportFormat {
+    /// Infers the format from a file extension. Defaults to CSV.
+    pub fn from_path(path: &str) -> ImportFormat {
+        match path.rsplit_once('.') {
+            Some((_, "jsonl")) | Some((_, "ndjson")) => ImportFormat::Jsonl,
+            _ => ImportFormat::Csv,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportParameters {
+    #[serde(default)]
+    pub format: ImportFormat,
+    /// If true, every row is written and then rolled back, so the report reflects all constraint violations without
+    /// modifying the database.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A single row of an import file.
+///
+/// Products are matched by UPC, then by internal name, so repeated imports do not duplicate products. Likewise, a row
+/// which references an existing item by ID or code, as every exported row does, is skipped rather than duplicating the
+/// item. A new item is created for each other row which specifies an inventory location.
+/// List-valued columns are separated by [IMPORT_LIST_SEPARATOR] so that rows remain flat in CSV.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportRow {
+    pub product_internal_name: String,
+    /// Required only if the product does not yet exist.
+    pub product_display_name: Option<String>,
+    pub upc: Option<String>,
+    pub release_date: Option<NaiveDate>,
+    /// Internal names of existing categories, e.g. "video_game;star_wars"
+    pub categories: Option<String>,
+
+    /// An existing item, which is left unchanged. An item which does not exist is created under a new ID.
+    pub item_id: Option<Uuid>,
+    /// Used to find an existing item only if the item ID is absent
+    pub item_code: Option<String>,
+    /// Internal name of an existing inventory location. An item is created iff this is present and the row does not
+    /// reference an existing item.
+    pub inventory_location: Option<String>,
+    /// Either the numeric representation or the serial name of an [crate::item::ItemCondition], e.g. "like_new"
+    pub condition: Option<String>,
+    pub price_cents: Option<u32>,
+    pub priority: Option<i32>,
+    pub note: Option<String>,
+    /// Defaults to the time of import.
+    pub acquisition_datetime: Option<DateTime<Utc>>,
+    pub acquisition_price_cents: Option<u32>,
+    pub acquisition_location: Option<String>,
+    /// Key-value pairs, e.g. "region=NTSC;edition=collector"
+    pub attributes: Option<String>,
+    /// Internal names of labels. Labels which do not exist are created.
+    pub labels: Option<String>,
+}
+
+pub const IMPORT_LIST_SEPARATOR: char = ';';
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// True iff the import was written to the database. No rows are written if any row fails.
+    pub committed: bool,
+    pub row_count: u32,
+    pub products_created: u32,
+    pub products_matched: u32,
+    pub items_created: u32,
+    /// Rows referencing an existing item, whose item columns were ignored
+    pub items_skipped: u32,
+    pub labels_created: u32,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl JsonHttpResponse for ImportReport {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowError {
+    /// One-indexed, excluding any header line
+    pub row: u32,
+    pub message: String,
+}
+
+impl ImportRowError {
+    pub fn new(row: u32, error: &ShopError) -> ImportRowError {
+        ImportRowError {
+            row,
+            message: error.message.clone(),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod import_api;
+pub mod import_model;
+pub mod import_action;
+
+pub use import_model::*;
@@ -1,16 +1,19 @@
 use super::*;
 use crate::object::JsonHttpResponse;
-use crate::{unwrap_result_else_500, ShopModel};
+use crate::storage_slot::{storage_slot_action, StorageSlot, StorageSlotSerial};
+use crate::{unwrap_result_else_400, unwrap_result_else_500, ShopModel};
 use actix_web::http::StatusCode;
 use actix_web::{get, post, web, HttpResponseBuilder, Responder};
 use sqlx::postgres::PgQueryResult;
 use sqlx::{Pool, Postgres};
+use uuid::Uuid;
 
 pub fn configurer(config: &mut web::ServiceConfig) {
     config.service(
         web::scope("/inventory_location")
             .service(create_inventory_location)
-            .service(get_all_inventory_locations),
+            .service(get_all_inventory_locations)
+            .service(get_all_inventory_location_storage_slots),
     );
 }
 
@@ -43,3 +46,20 @@ async fn get_all_inventory_locations(pgpool: web::Data<Pool<Postgres>>) -> impl
         .collect::<Vec<InventoryLocationSerial>>()
         .to_http_response()
 }
+
+#[get("/{inventory_location_id}/storage_slot")]
+async fn get_all_inventory_location_storage_slots(
+    pgpool: web::Data<Pool<Postgres>>,
+    inventory_location_id: web::Path<String>,
+) -> impl Responder {
+    let inventory_location_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(inventory_location_id.into_inner().as_str()));
+    let storage_slots: Vec<StorageSlot> = unwrap_result_else_500!(
+        storage_slot_action::get_all_inventory_location_storage_slots(pgpool.get_ref(), &inventory_location_id).await
+    );
+
+    storage_slots
+        .iter()
+        .map(|storage_slot| storage_slot.to_serial())
+        .collect::<Vec<StorageSlotSerial>>()
+        .to_http_response()
+}
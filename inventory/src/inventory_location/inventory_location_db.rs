@@ -1,7 +1,8 @@
 use crate::error::ShopError;
 use crate::inventory_location::InventoryLocationEntity;
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
 
 pub async fn create_inventory_location(
     pgpool: &PgPool,
@@ -37,3 +38,35 @@ pub async fn get_all_inventory_locations(
         .await
         .map_err(|e| ShopError::from(e))
 }
+
+pub async fn get_inventory_location_by_internal_name(
+    pgexecutor: impl PgExecutor<'_>,
+    internal_name: &str,
+) -> Result<Option<InventoryLocationEntity>, ShopError> {
+    query_as!(InventoryLocationEntity, "
+    	select id, display_name, internal_name, time_zone_id, street_address, municipality, district, postal_area, country
+		from shop.public.inventory_location
+		where internal_name = $1
+	",
+		internal_name
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_inventory_location(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Option<InventoryLocationEntity>, ShopError> {
+    query_as!(InventoryLocationEntity, "
+    	select id, display_name, internal_name, time_zone_id, street_address, municipality, district, postal_area, country
+		from shop.public.inventory_location
+		where id = $1
+	",
+		inventory_location_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -0,0 +1,200 @@
+use super::*;
+use crate::category::category_db;
+use crate::error::ShopError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Every item with its listings, ordered by acquisition time
+pub async fn get_all_inventory_ages(pgpool: &PgPool) -> Result<Vec<InventoryAge>, ShopError> {
+    let listing_entities: Vec<InventoryAgeListingEntity> = inventory_report_db::get_all_inventory_age_listings(pgpool).await?;
+    inventory_report_db::get_all_inventory_ages(pgpool).await?
+        .iter()
+        .map(|entity| InventoryAge::try_from_entities(entity, &listing_entities))
+        .collect()
+}
+
+/// One bucket per [AGING_BUCKET_MIN_DAYS], plus the first, including empty buckets
+pub fn get_aging_report(inventory_ages: &[InventoryAge], now: &DateTime<Utc>) -> Vec<AgingBucket> {
+    let mut buckets: Vec<AgingBucket> = std::iter::once(0)
+        .chain(AGING_BUCKET_MIN_DAYS)
+        .map(|min_days| AgingBucket {
+            min_days,
+            max_days: AGING_BUCKET_MIN_DAYS.iter()
+                .find(|next_min_days| **next_min_days > min_days)
+                .map(|next_min_days| next_min_days - 1),
+            ..AgingBucket::default()
+        })
+        .collect();
+
+    for inventory_age in inventory_ages {
+        let days: u32 = inventory_age.get_days_in_inventory(now);
+        let Some(bucket) = buckets.iter_mut().rev().find(|bucket| bucket.min_days <= days) else {
+            continue;
+        };
+        if inventory_age.is_unsold_stock() {
+            bucket.unsold_item_count += 1;
+            bucket.unsold_capital_cents += inventory_age.get_cost_basis_cents();
+        } else if inventory_age.is_sold() {
+            bucket.sold_item_count += 1;
+        }
+    }
+    buckets
+}
+
+/// Ordered by display name. Items which are neither sold nor unsold stock, i.e. missing items, are excluded.
+pub async fn get_sell_through_report(
+    pgpool: &PgPool,
+    inventory_ages: &[InventoryAge],
+    grouping: &InventoryReportGrouping,
+    now: &DateTime<Utc>,
+) -> Result<Vec<SellThroughSummary>, ShopError> {
+    let category_display_names: HashMap<Uuid, String> = get_category_display_names(pgpool, grouping).await?;
+
+    // Summaries are accumulated alongside the total days to sell of their sold items
+    let mut summaries: BTreeMap<String, (SellThroughSummary, u64)> = BTreeMap::new();
+    for inventory_age in inventory_ages {
+        if !inventory_age.is_sold() && !inventory_age.is_unsold_stock() {
+            continue;
+        }
+        for (key, display_name, is_sold) in get_groups(grouping, inventory_age, &category_display_names) {
+            let (summary, total_days_to_sell) = summaries.entry(key.clone())
+                .or_insert_with(|| (SellThroughSummary { key, display_name, ..SellThroughSummary::default() }, 0));
+            summary.item_count += 1;
+            if is_sold {
+                summary.sold_item_count += 1;
+                *total_days_to_sell += u64::from(inventory_age.get_days_in_inventory(now));
+            }
+        }
+    }
+
+    let mut summaries: Vec<SellThroughSummary> = summaries.into_values()
+        .map(|(mut summary, total_days_to_sell)| {
+            summary.sell_through_percent = 100.0 * summary.sold_item_count as f64 / summary.item_count as f64;
+            summary.average_days_to_sell = (summary.sold_item_count > 0)
+                .then(|| (total_days_to_sell / summary.sold_item_count) as u32);
+            summary
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Ok(summaries)
+}
+
+/// Ordered by display name. Only unsold stock is counted.
+pub async fn get_capital_report(
+    pgpool: &PgPool,
+    inventory_ages: &[InventoryAge],
+    grouping: &InventoryReportGrouping,
+) -> Result<Vec<CapitalSummary>, ShopError> {
+    let category_display_names: HashMap<Uuid, String> = get_category_display_names(pgpool, grouping).await?;
+
+    let mut summaries: BTreeMap<String, CapitalSummary> = BTreeMap::new();
+    for inventory_age in inventory_ages.iter().filter(|inventory_age| inventory_age.is_unsold_stock()) {
+        let groups: Vec<(String, String, bool)> = get_groups(grouping, inventory_age, &category_display_names);
+        for (key, display_name, _) in groups {
+            let summary: &mut CapitalSummary = summaries.entry(key.clone())
+                .or_insert_with(|| CapitalSummary { key, display_name, ..CapitalSummary::default() });
+            summary.item_count += 1;
+            summary.acquisition_cost_cents += u64::from(inventory_age.acquisition_price_cents.unwrap_or(0));
+            summary.refurbishment_cost_cents += inventory_age.refurbishment_cost_cents;
+            summary.capital_cents += inventory_age.get_cost_basis_cents();
+            summary.price_cents += u64::from(inventory_age.price_cents);
+            if inventory_age.acquisition_price_cents.is_none() {
+                summary.unknown_acquisition_price_count += 1;
+            }
+        }
+    }
+
+    let mut summaries: Vec<CapitalSummary> = summaries.into_values().collect();
+    summaries.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Ok(summaries)
+}
+
+/// Unsold items whose earliest active listing is at least the given number of days old, longest listed first
+pub fn get_stale_listing_report(inventory_ages: &[InventoryAge], listed_days: u32, now: &DateTime<Utc>) -> Vec<StaleListing> {
+    let mut stale_listings: Vec<StaleListing> = inventory_ages.iter()
+        .filter(|inventory_age| inventory_age.is_unsold_stock())
+        .filter_map(|inventory_age| {
+            let listed_since: DateTime<Utc> = inventory_age.get_listed_since()?;
+            let days: u32 = (*now - listed_since).num_days().max(0) as u32;
+            (days >= listed_days).then(|| StaleListing {
+                item_id: inventory_age.item_id,
+                item_code: inventory_age.item_code.clone(),
+                product_display_name: inventory_age.product_display_name.clone(),
+                marketplaces: inventory_age.listings.iter()
+                    .filter(|listing| listing.status.is_active())
+                    .map(|listing| listing.marketplace_display_name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+                listed_since,
+                listed_days: days,
+                days_in_inventory: inventory_age.get_days_in_inventory(now),
+                price_cents: inventory_age.price_cents,
+                cost_basis_cents: inventory_age.get_cost_basis_cents(),
+            })
+        })
+        .collect();
+    stale_listings.sort_by(|a, b| a.listed_since.cmp(&b.listed_since).then_with(|| a.item_code.cmp(&b.item_code)));
+    stale_listings
+}
+
+/// The header is derived from the first row, so an empty report is an empty file.
+pub fn serialize_csv<T: Serialize>(rows: &[T]) -> Result<Vec<u8>, ShopError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
+    }
+    writer.into_inner()
+        .map_err(|e| ShopError::from_error("Failed to flush CSV rows;", Box::new(e.into_error())))
+}
+
+async fn get_category_display_names(
+    pgpool: &PgPool,
+    grouping: &InventoryReportGrouping,
+) -> Result<HashMap<Uuid, String>, ShopError> {
+    if *grouping != InventoryReportGrouping::Category {
+        return Ok(HashMap::new());
+    }
+    Ok(category_db::get_all_categories(pgpool).await?
+        .into_iter()
+        .map(|category| (category.id, category.display_name))
+        .collect())
+}
+
+/// The key and display name of every group to which the item belongs, and whether it counts as sold in that group
+fn get_groups(
+    grouping: &InventoryReportGrouping,
+    inventory_age: &InventoryAge,
+    category_display_names: &HashMap<Uuid, String>,
+) -> Vec<(String, String, bool)> {
+    let is_sold: bool = inventory_age.is_sold();
+    let groups: Vec<(String, String, bool)> = match grouping {
+        InventoryReportGrouping::Category => inventory_age.category_ids.iter()
+            .map(|category_id| (
+                category_id.to_string(),
+                category_display_names.get(category_id).cloned().unwrap_or_else(|| category_id.to_string()),
+                is_sold,
+            ))
+            .collect(),
+        InventoryReportGrouping::Marketplace => inventory_age.listings.iter()
+            .map(|listing| (
+                listing.marketplace_id.to_string(),
+                listing.marketplace_display_name.clone(),
+                is_sold && inventory_age.sold_marketplace_ids.contains(&listing.marketplace_id),
+            ))
+            .collect(),
+        InventoryReportGrouping::InventoryLocation => vec![(
+            inventory_age.inventory_location_id.to_string(),
+            inventory_age.inventory_location_display_name.clone(),
+            is_sold,
+        )],
+    };
+
+    if groups.is_empty() {
+        return vec![(UNGROUPED_KEY.to_string(), UNGROUPED_DISPLAY_NAME.to_string(), is_sold)];
+    }
+    groups
+}
@@ -0,0 +1,87 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::unwrap_result_else_500;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/inventory_report")
+            .route("/aging", web::get().to(get_aging_report))
+            .route("/sell_through", web::get().to(get_sell_through_report))
+            .route("/capital", web::get().to(get_capital_report))
+            .route("/stale_listing", web::get().to(get_stale_listing_report))
+    );
+}
+
+/// Days-in-inventory distribution of unsold stock and sold items
+async fn get_aging_report(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<InventoryReportParameters>,
+) -> HttpResponse {
+    let inventory_ages: Vec<InventoryAge> = unwrap_result_else_500!(inventory_report_action::get_all_inventory_ages(&pgpool).await);
+    let aging_buckets: Vec<AgingBucket> = inventory_report_action::get_aging_report(&inventory_ages, &Utc::now());
+    to_http_response(&parameters.format, aging_buckets, "aging.csv")
+}
+
+/// Sell-through rate by the grouping of the parameters
+async fn get_sell_through_report(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<InventoryReportParameters>,
+) -> HttpResponse {
+    let inventory_ages: Vec<InventoryAge> = unwrap_result_else_500!(inventory_report_action::get_all_inventory_ages(&pgpool).await);
+    let now: DateTime<Utc> = Utc::now();
+    let sell_through_summaries: Vec<SellThroughSummary> = unwrap_result_else_500!(
+        inventory_report_action::get_sell_through_report(&pgpool, &inventory_ages, &parameters.grouping, &now).await
+    );
+    to_http_response(&parameters.format, sell_through_summaries, "sell_through.csv")
+}
+
+/// Capital tied up in unsold stock by the grouping of the parameters
+async fn get_capital_report(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<InventoryReportParameters>,
+) -> HttpResponse {
+    let inventory_ages: Vec<InventoryAge> = unwrap_result_else_500!(inventory_report_action::get_all_inventory_ages(&pgpool).await);
+    let capital_summaries: Vec<CapitalSummary> = unwrap_result_else_500!(
+        inventory_report_action::get_capital_report(&pgpool, &inventory_ages, &parameters.grouping).await
+    );
+    to_http_response(&parameters.format, capital_summaries, "capital.csv")
+}
+
+/// Unsold items listed for at least the number of days of the parameters
+async fn get_stale_listing_report(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<InventoryReportParameters>,
+) -> HttpResponse {
+    let inventory_ages: Vec<InventoryAge> = unwrap_result_else_500!(inventory_report_action::get_all_inventory_ages(&pgpool).await);
+    let listed_days: u32 = parameters.listed_days.unwrap_or(DEFAULT_STALE_LISTING_DAYS);
+    let stale_listings: Vec<StaleListing> =
+        inventory_report_action::get_stale_listing_report(&inventory_ages, listed_days, &Utc::now());
+    to_http_response(&parameters.format, stale_listings, "stale_listing.csv")
+}
+
+/// CSV reports are downloaded as attachments with the given file name.
+fn to_http_response<T>(format: &InventoryReportFormat, rows: Vec<T>, file_name: &str) -> HttpResponse
+where
+    T: Serialize,
+    Vec<T>: JsonHttpResponse,
+{
+    match format {
+        InventoryReportFormat::Json => rows.to_http_response(),
+        InventoryReportFormat::Csv => {
+            let bytes: Vec<u8> = unwrap_result_else_500!(inventory_report_action::serialize_csv(&rows));
+            HttpResponse::Ok()
+                .content_type("text/csv; charset=utf-8")
+                .insert_header(ContentDisposition {
+                    disposition: DispositionType::Attachment,
+                    parameters: vec![DispositionParam::Filename(String::from(file_name))],
+                })
+                .body(bytes)
+        }
+    }
+}
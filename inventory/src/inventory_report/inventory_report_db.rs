@@ -0,0 +1,64 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item::ItemStatus;
+use crate::listing::ListingStatus;
+use crate::purchase::PurchaseStatus;
+use sqlx::{query_as, PgExecutor};
+
+/// Every item, with the time of its sale if it has sold. The sale is the latest of the fulfilled purchases of the item
+/// or its bundle or, lacking any, the first time the item entered a sold status.
+pub async fn get_all_inventory_ages(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<InventoryAgeEntity>, ShopError> {
+    let sold_statuses: Vec<i32> = vec![
+        ItemStatus::PurchaseListed as i32,
+        ItemStatus::PurchasedDelisted as i32,
+        ItemStatus::Shipped as i32,
+        ItemStatus::Received as i32,
+    ];
+    query_as!(InventoryAgeEntity, r#"
+        select item.id as item_id, item.code as item_code, product.display_name as product_display_name,
+            item.inventory_location_id, inventory_location.display_name as inventory_location_display_name,
+            item.status, item.price_cents, item.acquisition_datetime, item.acquisition_price_cents,
+            coalesce((select sum(item_refurbishment.cost_cents) from shop.public.item_refurbishment where item_refurbishment.item_id = item.id), 0)::bigint as "refurbishment_cost_cents!",
+            array(select product_category_association.category_id from shop.public.product_category_association where product_category_association.product_id = item.product_id) as "category_ids!",
+            coalesce(sale.created, (
+                select min(item_audit.created) from shop.public.item_audit
+                where item_audit.item_id = item.id and item_audit.status_after = any($2)
+            )) as sold,
+            coalesce(sale.marketplace_ids, '{}') as "sold_marketplace_ids!"
+        from shop.public.item
+            inner join shop.public.product on item.product_id = product.id
+            inner join shop.public.inventory_location on item.inventory_location_id = inventory_location.id
+            left join lateral (
+                select max(purchase.created) as created, array_agg(distinct purchase.marketplace_id) as marketplace_ids
+                from shop.public.purchase
+                    inner join shop.public.listing on purchase.listing_id = listing.id
+                where purchase.status = $1 and (listing.item_id = item.id or listing.bundle_id = (
+                    select bundle_item.bundle_id from shop.public.bundle_item where bundle_item.item_id = item.id
+                ))
+            ) as sale on true
+        order by item.acquisition_datetime, item.code
+    "#,
+		PurchaseStatus::Fulfilled as i32,
+		&sold_statuses,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Every listing other than drafts, which were never visible to buyers
+pub async fn get_all_inventory_age_listings(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<InventoryAgeListingEntity>, ShopError> {
+    query_as!(InventoryAgeListingEntity, r#"
+        select listing.item_id as "item_id!", listing.marketplace_id, marketplace.display_name as marketplace_display_name,
+            listing.status, listing.published
+        from shop.public.listing
+            inner join shop.public.marketplace on listing.marketplace_id = marketplace.id
+        where listing.status != $1 and listing.item_id is not null
+        order by listing.item_id, marketplace.display_name
+    "#,
+		ListingStatus::Draft as i32,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
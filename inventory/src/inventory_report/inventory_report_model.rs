@@ -0,0 +1,229 @@
+use crate::error::ShopError;
+use crate::item::ItemStatus;
+use crate::listing::ListingStatus;
+use crate::object::JsonHttpResponse;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The lower bound of each aging bucket after the first, in days. The first bucket starts at zero days and the last is
+/// unbounded.
+pub const AGING_BUCKET_MIN_DAYS: [u32; 5] = [30, 60, 90, 180, 365];
+pub const DEFAULT_STALE_LISTING_DAYS: u32 = 90;
+/// Key and display name of items belonging to none of the groups, e.g. items of uncategorized products
+pub const UNGROUPED_KEY: &str = "";
+pub const UNGROUPED_DISPLAY_NAME: &str = "(none)";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum InventoryReportFormat {
+    #[default]
+    #[serde(rename = "json")]
+    Json,
+    #[serde(rename = "csv")]
+    Csv,
+}
+
+/// The dimension by which the sell-through and capital reports are aggregated
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum InventoryReportGrouping {
+    /// An item counts toward every category directly associated with its product
+    #[default]
+    #[serde(rename = "category")]
+    Category,
+    /// An item counts toward every marketplace on which it has been listed
+    #[serde(rename = "marketplace")]
+    Marketplace,
+    #[serde(rename = "inventory_location")]
+    InventoryLocation,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InventoryReportParameters {
+    #[serde(default)]
+    pub format: InventoryReportFormat,
+    #[serde(default)]
+    pub grouping: InventoryReportGrouping,
+    /// The minimum age in days of a stale listing. Defaults to [DEFAULT_STALE_LISTING_DAYS].
+    pub listed_days: Option<u32>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct InventoryAgeEntity {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_display_name: String,
+    pub inventory_location_id: Uuid,
+    pub inventory_location_display_name: String,
+    pub status: i32,
+    pub price_cents: i64,
+    pub acquisition_datetime: DateTime<Utc>,
+    pub acquisition_price_cents: Option<i64>,
+    pub refurbishment_cost_cents: i64,
+    pub category_ids: Vec<Uuid>,
+    pub sold: Option<DateTime<Utc>>,
+    pub sold_marketplace_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct InventoryAgeListingEntity {
+    pub item_id: Uuid,
+    pub marketplace_id: Uuid,
+    pub marketplace_display_name: String,
+    pub status: i32,
+    pub published: Option<DateTime<Utc>>,
+}
+
+/// An item's age and cost, with the listings from which the reports are derived
+#[derive(Debug, Clone)]
+pub struct InventoryAge {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_display_name: String,
+    pub inventory_location_id: Uuid,
+    pub inventory_location_display_name: String,
+    pub status: ItemStatus,
+    pub price_cents: u32,
+    pub acquisition_datetime: DateTime<Utc>,
+    /// Counted as zero in the cost basis when unknown
+    pub acquisition_price_cents: Option<u32>,
+    pub refurbishment_cost_cents: u64,
+    pub category_ids: Vec<Uuid>,
+    /// The time of the latest fulfilled purchase, or else the time the item first entered a sold status
+    pub sold: Option<DateTime<Utc>>,
+    /// The marketplaces of the fulfilled purchases of the item or its bundle; empty if the item was sold without any
+    pub sold_marketplace_ids: Vec<Uuid>,
+    pub listings: Vec<InventoryAgeListing>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InventoryAgeListing {
+    pub marketplace_id: Uuid,
+    pub marketplace_display_name: String,
+    pub status: ListingStatus,
+    /// The time the listing last became visible to buyers; absent if it never has been
+    pub published: Option<DateTime<Utc>>,
+}
+
+impl InventoryAge {
+    pub fn try_from_entities(
+        entity: &InventoryAgeEntity,
+        listing_entities: &[InventoryAgeListingEntity],
+    ) -> Result<InventoryAge, ShopError> {
+        let listings: Vec<InventoryAgeListing> = listing_entities.iter()
+            .filter(|listing_entity| listing_entity.item_id == entity.item_id)
+            .map(|listing_entity| Ok(InventoryAgeListing {
+                marketplace_id: listing_entity.marketplace_id,
+                marketplace_display_name: listing_entity.marketplace_display_name.clone(),
+                status: ListingStatus::try_from_repr(listing_entity.status as u8)?,
+                published: listing_entity.published,
+            }))
+            .collect::<Result<Vec<_>, ShopError>>()?;
+
+        Ok(InventoryAge {
+            item_id: entity.item_id,
+            item_code: entity.item_code.clone(),
+            product_display_name: entity.product_display_name.clone(),
+            inventory_location_id: entity.inventory_location_id,
+            inventory_location_display_name: entity.inventory_location_display_name.clone(),
+            status: ItemStatus::try_from_repr(entity.status as u8)?,
+            price_cents: entity.price_cents as u32,
+            acquisition_datetime: entity.acquisition_datetime,
+            acquisition_price_cents: entity.acquisition_price_cents.map(|v| v as u32),
+            refurbishment_cost_cents: entity.refurbishment_cost_cents as u64,
+            category_ids: entity.category_ids.clone(),
+            sold: entity.sold,
+            sold_marketplace_ids: entity.sold_marketplace_ids.clone(),
+            listings,
+        })
+    }
+
+    /// Unsold items which are expected to be on hand. Missing items are excluded.
+    pub fn is_unsold_stock(&self) -> bool {
+        self.status.is_on_hand() && !self.status.is_sold()
+    }
+
+    pub fn is_sold(&self) -> bool {
+        self.status.is_sold()
+    }
+
+    pub fn get_cost_basis_cents(&self) -> u64 {
+        u64::from(self.acquisition_price_cents.unwrap_or(0)) + self.refurbishment_cost_cents
+    }
+
+    /// Days from acquisition until the sale, or until now if the item has not sold
+    pub fn get_days_in_inventory(&self, now: &DateTime<Utc>) -> u32 {
+        let until: DateTime<Utc> = match (self.is_sold(), self.sold) {
+            (true, Some(sold)) => sold,
+            _ => *now,
+        };
+        (until - self.acquisition_datetime).num_days().max(0) as u32
+    }
+
+    /// The earliest publication among the item's active listings. Absent if the item is not listed.
+    pub fn get_listed_since(&self) -> Option<DateTime<Utc>> {
+        self.listings.iter()
+            .filter(|listing| listing.status.is_active())
+            .filter_map(|listing| listing.published)
+            .min()
+    }
+}
+
+/// Unsold stock and sold items by their days in inventory. Sold items are counted by their days until the sale.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgingBucket {
+    pub min_days: u32,
+    /// Inclusive. Absent for the last bucket.
+    pub max_days: Option<u32>,
+    pub unsold_item_count: u64,
+    /// The cost basis of the unsold stock
+    pub unsold_capital_cents: u64,
+    pub sold_item_count: u64,
+}
+
+/// The share of the items in a group which have sold. For a marketplace, only items sold on that marketplace count as
+/// sold there.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SellThroughSummary {
+    pub key: String,
+    pub display_name: String,
+    /// Sold items and unsold stock
+    pub item_count: u64,
+    pub sold_item_count: u64,
+    pub sell_through_percent: f64,
+    pub average_days_to_sell: Option<u32>,
+}
+
+/// The cost basis of the unsold stock in a group
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CapitalSummary {
+    pub key: String,
+    pub display_name: String,
+    pub item_count: u64,
+    pub acquisition_cost_cents: u64,
+    pub refurbishment_cost_cents: u64,
+    pub capital_cents: u64,
+    /// Asking price of the stock
+    pub price_cents: u64,
+    /// Items whose acquisition price is unknown and counted as zero, understating the capital
+    pub unknown_acquisition_price_count: u64,
+}
+
+/// An unsold item which has been listed for at least the stale listing age. List-valued fields are joined so that the
+/// report may be written as CSV.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleListing {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_display_name: String,
+    pub marketplaces: String,
+    pub listed_since: DateTime<Utc>,
+    pub listed_days: u32,
+    pub days_in_inventory: u32,
+    pub price_cents: u32,
+    pub cost_basis_cents: u64,
+}
+
+impl JsonHttpResponse for Vec<AgingBucket> {}
+impl JsonHttpResponse for Vec<SellThroughSummary> {}
+impl JsonHttpResponse for Vec<CapitalSummary> {}
+impl JsonHttpResponse for Vec<StaleListing> {}
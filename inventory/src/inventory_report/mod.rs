@@ -0,0 +1,7 @@
+pub(crate) mod inventory_report_db;
+
+pub mod inventory_report_api;
+pub mod inventory_report_model;
+pub mod inventory_report_action;
+
+pub use inventory_report_model::*;
@@ -0,0 +1,107 @@
+use super::*;
+use crate::ebay::ebay_action;
+use crate::error::ShopError;
+use crate::inventory_location::inventory_location_db;
+use crate::item::{item_action, item_db, Item};
+use crate::listing::{Listing, ListingStatus};
+use crate::{object, ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Moves each item to the destination inventory location within a single transaction, recording an
+/// [InventoryTransfer] per moved item. Items already at the destination are skipped.
+pub async fn transfer_items(
+    pgpool: &PgPool,
+    item_ids: &[Uuid],
+    to_inventory_location_id: &Uuid,
+    initiated_by: Option<String>,
+    reason: Option<String>,
+) -> Result<Vec<InventoryTransfer>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    let transfers: Vec<InventoryTransfer> = transfer_items_in_transaction(
+        &mut transaction,
+        item_ids,
+        to_inventory_location_id,
+        initiated_by,
+        reason,
+    ).await?;
+    transaction.commit().await?;
+    Ok(transfers)
+}
+
+/// As [transfer_items], within the caller's transaction.
+pub async fn transfer_items_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item_ids: &[Uuid],
+    to_inventory_location_id: &Uuid,
+    initiated_by: Option<String>,
+    reason: Option<String>,
+) -> Result<Vec<InventoryTransfer>, ShopError> {
+    inventory_location_db::get_inventory_location(&mut **transaction, to_inventory_location_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Inventory location not found; [{}]", to_inventory_location_id)))?;
+
+    let now = Utc::now();
+    let mut transfers: Vec<InventoryTransfer> = Vec::with_capacity(item_ids.len());
+    for item_id in item_ids {
+        let item: Item = item_db::get_item_for_update(&mut **transaction, item_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+            .try_to_model()?;
+        if item.inventory_location_id == *to_inventory_location_id
+            || transfers.iter().any(|transfer| transfer.item_id == item.id) {
+            continue;
+        }
+
+        item_db::update_item_inventory_location(&mut **transaction, &item.id, to_inventory_location_id, &now).await?;
+        let transfer = InventoryTransfer {
+            id: object::random_uuid(),
+            item_id: item.id,
+            from_inventory_location_id: item.inventory_location_id,
+            to_inventory_location_id: *to_inventory_location_id,
+            initiated_by: initiated_by.clone(),
+            reason: reason.clone(),
+            created: now,
+        };
+        inventory_transfer_db::create_inventory_transfer(&mut **transaction, &transfer.to_entity()).await?;
+        transfers.push(transfer);
+    }
+
+    Ok(transfers)
+}
+
+/// Published eBay listings of the given items.
+pub async fn get_published_marketplace_listings(
+    pgpool: &PgPool,
+    item_ids: &[Uuid],
+) -> Result<Vec<Listing>, ShopError> {
+    let mut published_listings: Vec<Listing> = Vec::new();
+    for item_id in item_ids {
+        let listings: Vec<Listing> = item_action::get_all_item_listings(pgpool, item_id).await?;
+        published_listings.extend(listings.into_iter()
+            .filter(|listing| ebay_action::is_marketplace_listing(listing) && listing.status == ListingStatus::Published));
+    }
+    Ok(published_listings)
+}
+
+/// Moves the eBay offer of each published listing to its item's new inventory location.
+/// Failures do not revert the transfers and are returned per listing.
+pub async fn sync_marketplace_locations(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listings: &[Listing],
+) -> Vec<InventoryTransferMarketplaceError> {
+    let mut errors: Vec<InventoryTransferMarketplaceError> = Vec::new();
+    for listing in listings {
+        if let Err(error) = ebay_action::update_offer_location(pgpool, user_access_token, listing).await {
+            log::error!("Failed to move ebay offer to new inventory location; [{}]; {}", listing.id, error);
+            errors.push(InventoryTransferMarketplaceError {
+                item_id: listing.get_sku(),
+                listing_id: listing.id,
+                message: error.message,
+            });
+        }
+    }
+    errors
+}
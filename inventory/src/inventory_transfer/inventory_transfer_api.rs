@@ -0,0 +1,92 @@
+use super::*;
+use crate::ebay::ebay_api;
+use crate::item::item_api;
+use crate::listing::Listing;
+use crate::object::JsonHttpResponse;
+use crate::pagination::KeysetPaginationOptions;
+use crate::{http, try_return, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel};
+use actix_web::cookie::Cookie;
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/inventory_transfer")
+            .route("", web::get().to(get_all_inventory_transfers_paged))
+            .route("", web::post().to(transfer_items))
+    );
+}
+
+async fn get_all_inventory_transfers_paged(
+    pgpool: web::Data<PgPool>,
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        inventory_transfer_db::get_all_inventory_transfers_paged_created(&pgpool, &pagination_options).await
+    );
+
+    let mut inventory_transfer_serial_vec = Vec::with_capacity(entities.len());
+    for entity in entities {
+        inventory_transfer_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (inventory_transfer_serial_vec, pagination_result).to_http_response()
+}
+
+async fn transfer_items(
+    pgpool: web::Data<PgPool>,
+    request: HttpRequest,
+    body: web::Json<InventoryTransferBatchSerial>,
+) -> HttpResponse {
+    let body: InventoryTransferBatchSerial = body.into_inner();
+    if body.item_ids.is_empty() || body.item_ids.len() > INVENTORY_TRANSFER_ITEM_COUNT_MAX {
+        return HttpResponse::BadRequest()
+            .body(format!("Item count must be between 1 and {};", INVENTORY_TRANSFER_ITEM_COUNT_MAX));
+    }
+
+    let mut item_ids: Vec<Uuid> = Vec::with_capacity(body.item_ids.len());
+    for item_id in &body.item_ids {
+        item_ids.push(try_return!(item_api::resolve_item_id(&pgpool, item_id).await));
+    }
+
+    // Require the eBay token up front so that published offers are never left at the previous location
+    let published_listings: Vec<Listing> = unwrap_result_else_500!(
+        inventory_transfer_action::get_published_marketplace_listings(&pgpool, &item_ids).await
+    );
+    let user_access_token: Option<Cookie> = match published_listings.is_empty() {
+        true => None,
+        false => Some(try_return!(ebay_api::extract_user_token(&request))),
+    };
+
+    let initiated_by: Option<String> = http::extract_remote_user(&request);
+    let transfers: Vec<InventoryTransfer> = match inventory_transfer_action::transfer_items(
+        &pgpool,
+        &item_ids,
+        &body.to_inventory_location_id,
+        initiated_by,
+        body.reason,
+    ).await {
+        Ok(transfers) => transfers,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    let transferred_listings: Vec<Listing> = published_listings.into_iter()
+        .filter(|listing| transfers.iter().any(|transfer| listing.item_id == Some(transfer.item_id)))
+        .collect();
+    let marketplace_errors: Vec<InventoryTransferMarketplaceError> = match &user_access_token {
+        Some(user_access_token) => inventory_transfer_action::sync_marketplace_locations(
+            &pgpool,
+            user_access_token.value(),
+            &transferred_listings,
+        ).await,
+        None => Vec::new(),
+    };
+
+    InventoryTransferReport {
+        transfers: transfers.iter().map(|transfer| transfer.to_serial()).collect(),
+        marketplace_errors,
+    }.to_http_response()
+}
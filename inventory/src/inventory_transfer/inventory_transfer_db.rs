@@ -0,0 +1,67 @@
+use super::*;
+use crate::error::ShopError;
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// Oldest transfers first
+pub async fn get_all_item_inventory_transfers(
+    pgpool: &PgPool,
+    item_id: &Uuid,
+) -> Result<Vec<InventoryTransferEntity>, ShopError> {
+    query_as!(InventoryTransferEntity, "
+        select id, item_id, from_inventory_location_id, to_inventory_location_id, initiated_by, reason, created
+        from shop.public.inventory_transfer
+        where item_id = $1
+        order by created, id
+    ",
+		item_id
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_inventory_transfer(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_transfer: &InventoryTransferEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.inventory_transfer (id, item_id, from_inventory_location_id, to_inventory_location_id, initiated_by, reason, created)
+        values ($1, $2, $3, $4, $5, $6, $7)
+    ",
+		inventory_transfer.id,
+		inventory_transfer.item_id,
+		inventory_transfer.from_inventory_location_id,
+		inventory_transfer.to_inventory_location_id,
+		inventory_transfer.initiated_by,
+		inventory_transfer.reason,
+		inventory_transfer.created,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recent transfers first
+pub async fn get_all_inventory_transfers_paged_created(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<DateTime<Utc>>,
+) -> Result<(Vec<InventoryTransferEntity>, KeysetPaginationResult<DateTime<Utc>>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, item_id, from_inventory_location_id, to_inventory_location_id, initiated_by, reason, created \
+        from shop.public.inventory_transfer \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "created",
+        &Direction::Descending,
+        keyset_pagination_options,
+        |inventory_transfer: &InventoryTransferEntity| (inventory_transfer.created, inventory_transfer.id),
+    ).await
+}
@@ -0,0 +1,109 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{object, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const INVENTORY_TRANSFER_ITEM_COUNT_MAX: usize = 1000;
+
+/// Records the movement of an item from one inventory location to another.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct InventoryTransferEntity {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub from_inventory_location_id: Uuid,
+    pub to_inventory_location_id: Uuid,
+    /// The authenticated user who requested the transfer, if known
+    pub initiated_by: Option<String>,
+    pub reason: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopEntity for InventoryTransferEntity {
+    type Model = InventoryTransfer;
+}
+
+pub type InventoryTransfer = InventoryTransferEntity;
+
+impl ShopModel for InventoryTransfer {
+    type Entity = InventoryTransferEntity;
+    type Serial = InventoryTransferSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        InventoryTransferSerial {
+            id: self.id.clone(),
+            item_id: self.item_id.clone(),
+            from_inventory_location_id: self.from_inventory_location_id.clone(),
+            to_inventory_location_id: self.to_inventory_location_id.clone(),
+            initiated_by: self.initiated_by.clone(),
+            reason: self.reason.clone(),
+            created: self.created.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(InventoryTransfer {
+            id: object::random_uuid(),
+            item_id: serial.item_id.clone(),
+            from_inventory_location_id: serial.from_inventory_location_id.clone(),
+            to_inventory_location_id: serial.to_inventory_location_id.clone(),
+            initiated_by: serial.initiated_by.clone(),
+            reason: serial.reason.clone(),
+            created: serial.created.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        self.clone()
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(entity.clone())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryTransferSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub from_inventory_location_id: Uuid,
+    pub to_inventory_location_id: Uuid,
+    pub initiated_by: Option<String>,
+    pub reason: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopSerial for InventoryTransferSerial {
+    type Model = InventoryTransfer;
+}
+
+impl JsonHttpResponse for InventoryTransferSerial {}
+impl JsonHttpResponse for Vec<InventoryTransferSerial> {}
+
+/// Moves each item to the destination inventory location.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryTransferBatchSerial {
+    /// Item IDs and item codes are both accepted.
+    pub item_ids: Vec<String>,
+    pub to_inventory_location_id: Uuid,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryTransferReport {
+    /// One transfer per moved item. Items already at the destination are not transferred.
+    pub transfers: Vec<InventoryTransferSerial>,
+    /// Items whose published eBay offer could not be moved to the destination. The transfers themselves are retained.
+    pub marketplace_errors: Vec<InventoryTransferMarketplaceError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryTransferMarketplaceError {
+    pub item_id: Uuid,
+    pub listing_id: Uuid,
+    pub message: String,
+}
+
+impl JsonHttpResponse for InventoryTransferReport {}
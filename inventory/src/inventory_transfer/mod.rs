@@ -0,0 +1,7 @@
+pub(crate) mod inventory_transfer_db;
+
+pub mod inventory_transfer_api;
+pub mod inventory_transfer_model;
+pub mod inventory_transfer_action;
+
+pub use inventory_transfer_model::*;
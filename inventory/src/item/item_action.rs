@@ -1,8 +1,20 @@
+use crate::acquisition_lot::acquisition_lot_db;
+use crate::bundle::bundle_db;
+use crate::consignor::consignor_db;
+use crate::ebay::ebay_action;
 use crate::error::ShopError;
-use crate::item::Item;
+use crate::item::{item_db, Item, ItemCloneParameters, ItemCondition, ItemReference, ItemStatus};
+use crate::item_attribute::{item_attribute_db, ItemAttribute};
+use crate::item_audit::{item_audit_db, ItemAudit};
 use crate::item_image::{item_image_db, ItemImage};
-use crate::ShopEntity;
-use sqlx::PgPool;
+use crate::item_readiness::item_readiness_action;
+use crate::listing::{listing_action, listing_db, Listing, ListingStatus};
+use crate::label::Label;
+use crate::storage_slot::storage_slot_action;
+use crate::{object, ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
 
 impl Item {
     pub async fn get_all_item_images(&self, pgpool: &PgPool) -> Result<Vec<ItemImage>, ShopError> {
@@ -12,3 +24,318 @@ impl Item {
             .collect::<Result<Vec<_>, _>>()
     }
 }
+
+pub async fn get_all_item_listings(pgexecutor: impl PgExecutor<'_>, item_id: &Uuid) -> Result<Vec<Listing>, ShopError> {
+    item_db::get_all_item_listings(pgexecutor, item_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()
+}
+
+/// Returns the ID of the referenced item. An ID is returned as given, without verifying that the item exists.
+pub async fn resolve_item_reference(
+    pgexecutor: impl PgExecutor<'_>,
+    item_reference: &ItemReference,
+) -> Result<Option<Uuid>, ShopError> {
+    match item_reference {
+        ItemReference::Id(item_id) => Ok(Some(*item_id)),
+        ItemReference::Code(code) => item_db::get_item_id_by_code(pgexecutor, code).await,
+    }
+}
+
+/// Creates the item within the caller's transaction under the next code of its inventory location, replacing any code
+/// the item was given.
+pub async fn create_item_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    mut item: Item,
+) -> Result<Item, ShopError> {
+    item.code = item_db::next_item_code(&mut **transaction, &item.inventory_location_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Inventory location not found; [{}]", item.inventory_location_id)))?;
+    item_db::create_item(&mut **transaction, &item.to_entity()).await?;
+    Ok(item)
+}
+
+/// Overwrites the mutable fields of the stored item which shares the given item's ID.
+/// The product, creation time, and ID of the stored item are retained. The acquisition price of an item in an
+/// acquisition lot is retained as well, since it is allocated from the lot.
+/// Fails if the inventory location or price differs, since inventory locations change only through
+/// [crate::inventory_transfer::inventory_transfer_action::transfer_items] and prices only through
+/// [crate::price_history::price_history_action::change_item_price]. The storage slot must belong to the stored
+/// inventory location. Consignment terms are fixed once payouts have been recorded, and a bundled item cannot be
+/// consigned.
+/// A status change must be permitted by [crate::item::ItemStatus::validate_transition] and is recorded as an
+/// [ItemAudit] within the same transaction. An incomplete or missing item is completed only once it passes the blocking
+/// readiness rules; see [crate::item_readiness::ItemReadinessRule].
+pub async fn update_item(
+    pgpool: &PgPool,
+    mut item: Item,
+    initiated_by_admin: bool,
+    audit_note: Option<String>,
+) -> Result<Item, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let current: Item = item_db::get_item_for_update(&mut *transaction, &item.id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item.id)))?
+        .try_to_model()?;
+    current.status.validate_transition(&item.status)?;
+
+    if item.inventory_location_id != current.inventory_location_id {
+        return Err(ShopError::new(&format!("Inventory location changes require an inventory transfer; [{}]", item.id)));
+    }
+    if item.price_cents != current.price_cents {
+        return Err(ShopError::new(&format!("Price changes require a recorded price change; [{}]", item.id)));
+    }
+    item.product_id = current.product_id;
+    if acquisition_lot_db::get_acquisition_lot_item(&mut *transaction, &item.id).await?.is_some() {
+        item.acquisition_price_cents = current.acquisition_price_cents;
+    }
+    // Payouts already recorded were calculated from the current terms
+    let consignment_changed: bool = item.consignor_id != current.consignor_id
+        || item.commission_basis_points != current.commission_basis_points
+        || item.commission_minimum_cents != current.commission_minimum_cents;
+    if consignment_changed && consignor_db::item_has_consignment_payouts(&mut *transaction, &item.id).await? {
+        return Err(ShopError::new(&format!("Consignment terms cannot be changed once payouts have been recorded; [{}]", item.id)));
+    }
+    if item.consignor_id.is_some()
+        && let Some(bundle_item) = bundle_db::get_bundle_item(&mut *transaction, &item.id).await?
+    {
+        return Err(ShopError::new(&format!("Consigned items cannot be bundled; [{}]; [{}]", item.id, bundle_item.bundle_id)));
+    }
+    storage_slot_action::validate_item_storage_slot(&mut *transaction, &item.inventory_location_id, &item.storage_slot_id).await?;
+    item_readiness_action::validate_item_completion_in_transaction(&mut transaction, &current, &item.status).await?;
+    item.created = current.created;
+    item.updated = Utc::now();
+    item_db::update_item(&mut *transaction, &item.to_entity()).await?;
+
+    if current.status != item.status {
+        let item_audit: ItemAudit = ItemAudit::new(
+            item.id,
+            current.status.clone(),
+            item.status.clone(),
+            initiated_by_admin,
+            audit_note,
+        );
+        item_audit_db::create_item_audit(&mut *transaction, &item_audit.to_entity()).await?;
+    }
+
+    transaction.commit().await?;
+    Ok(item)
+}
+
+/// Changes the status of a locked item within the caller's transaction, recording the change as an [ItemAudit].
+/// The transition must be permitted by [crate::item::ItemStatus::validate_transition], and an incomplete or missing item
+/// is completed only once it is ready.
+pub async fn update_item_status_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item: &Item,
+    status: ItemStatus,
+    initiated_by_admin: bool,
+    audit_note: Option<String>,
+) -> Result<ItemAudit, ShopError> {
+    item.status.validate_transition(&status)?;
+    item_readiness_action::validate_item_completion_in_transaction(transaction, item, &status).await?;
+    item_db::update_item_status(&mut **transaction, &item.id, status.clone() as i32, &Utc::now()).await?;
+
+    let item_audit: ItemAudit = ItemAudit::new(item.id, item.status.clone(), status, initiated_by_admin, audit_note);
+    item_audit_db::create_item_audit(&mut **transaction, &item_audit.to_entity()).await?;
+    Ok(item_audit)
+}
+
+/// Withdraws each active listing of the item so that the item may be deleted.
+/// Published eBay listings are withdrawn through the eBay API. Other active listings are only cancelled locally and
+/// must be removed from their marketplace by hand.
+pub async fn withdraw_active_listings(
+    pgpool: &PgPool,
+    item_id: &Uuid,
+    ebay_user_access_token: Option<&str>,
+) -> Result<(), ShopError> {
+    let listings: Vec<Listing> = get_all_item_listings(pgpool, item_id).await?;
+    for listing in listings.iter().filter(|listing| listing.status.is_active()) {
+        if ebay_action::is_marketplace_listing(listing) && listing.status == ListingStatus::Published {
+            let user_access_token: &str = ebay_user_access_token
+                .ok_or_else(|| ShopError::new("An eBay user access token is required to withdraw an eBay listing;"))?;
+            ebay_action::withdraw(pgpool, user_access_token, listing).await?;
+        } else {
+            log::warn!(
+                "Cancelling listing locally; Remove it from the marketplace manually; [listing: {}] [marketplace: {}]",
+                listing.id,
+                listing.marketplace_id,
+            );
+            listing_action::update_listing_status(pgpool, listing, ListingStatus::Cancelled).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Deletes the item with its inactive listings, images, attributes, and label associations, and records a tombstone
+/// [ItemAudit] within the same transaction. Image files are removed only after the transaction commits.
+/// Fails if the item has any purchase, which must be retained, or any active listing, which must be withdrawn first.
+pub async fn delete_item(
+    pgpool: &PgPool,
+    item_id: &Uuid,
+    initiated_by_admin: bool,
+    audit_note: Option<String>,
+) -> Result<Item, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let item: Item = item_db::get_item_for_update(&mut *transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()?;
+
+    if !item_db::get_all_item_purchases(&mut *transaction, item_id).await?.is_empty() {
+        return Err(ShopError::new(&format!("Item has purchases, which must be retained; [{}]", item_id)));
+    }
+    let listings: Vec<Listing> = get_all_item_listings(&mut *transaction, item_id).await?;
+    if listings.iter().any(|listing| listing.status.is_active()) {
+        return Err(ShopError::new(&format!("Item has active listings, which must be withdrawn; [{}]", item_id)));
+    }
+    if let Some(bundle_item) = bundle_db::get_bundle_item(&mut *transaction, item_id).await? {
+        return Err(ShopError::new(&format!("Item belongs to a bundle, from which it must be removed; [{}]; [{}]", item_id, bundle_item.bundle_id)));
+    }
+
+    let item_images: Vec<ItemImage> = item_image_db::get_all_item_images(&mut *transaction, item_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Images, attributes, and label associations are removed by cascade.
+    listing_db::delete_all_item_listings(&mut *transaction, item_id).await?;
+    item_db::delete_item(&mut *transaction, item_id).await?;
+
+    let item_audit: ItemAudit = ItemAudit::new_tombstone(item.id, item.status.clone(), initiated_by_admin, audit_note);
+    item_audit_db::create_item_audit(&mut *transaction, &item_audit.to_entity()).await?;
+
+    transaction.commit().await?;
+
+    for item_image in item_images {
+        if let Err(error) = item_image.delete_image_file() {
+            log::warn!("Failed to delete item image file; [{}]; {}", item_image.id, error.message);
+        }
+    }
+    Ok(item)
+}
+
+/// Creates copies of the item, each with a new SKU and with the attributes, label associations, and images (including
+/// image files) of the source item. Copies are never listed, so a complete source item yields complete, unlisted copies,
+/// each of which must pass the item readiness rules. If any copy fails, no copy is retained.
+pub async fn clone_item(
+    pgpool: &PgPool,
+    item_id: &Uuid,
+    parameters: &ItemCloneParameters,
+) -> Result<Vec<Item>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    // Locked so that the copies match the source even if it is updated concurrently
+    let source: Item = item_db::get_item_for_update(&mut *transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()?;
+    let item_attributes: Vec<ItemAttribute> = item_attribute_db::get_all_item_attributes(&mut *transaction, item_id).await?;
+    let labels: Vec<Label> = item_db::get_all_item_labels(&mut *transaction, item_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    let item_images: Vec<ItemImage> = item_image_db::get_all_item_images(&mut *transaction, item_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut clones: Vec<Item> = Vec::new();
+    let mut copied_images: Vec<ItemImage> = Vec::new();
+    let result: Result<(), ShopError> = async {
+        for _ in 0..parameters.count {
+            let inventory_location_id: Uuid = parameters.inventory_location_id.unwrap_or(source.inventory_location_id);
+            let code: String = item_db::next_item_code(&mut *transaction, &inventory_location_id)
+                .await?
+                .ok_or_else(|| ShopError::new(&format!("Inventory location not found; [{}]", inventory_location_id)))?;
+            let clone: Item = clone_item_model(&source, parameters, code)?;
+            item_db::create_item(&mut *transaction, &clone.to_entity()).await?;
+
+            for item_attribute in &item_attributes {
+                let item_attribute: ItemAttribute = ItemAttribute {
+                    item_id: clone.id,
+                    ..item_attribute.clone()
+                };
+                item_attribute_db::create_item_attribute(&mut *transaction, &item_attribute.to_entity()).await?;
+            }
+            for label in &labels {
+                item_db::create_item_label_association(&mut *transaction, &clone.id, &label.id).await?;
+            }
+            for item_image in &item_images {
+                let copied_image: ItemImage = ItemImage {
+                    id: object::random_uuid(),
+                    item_id: clone.id,
+                    ..item_image.clone()
+                };
+                item_image_db::create_item_image(&mut *transaction, &copied_image.to_entity()).await?;
+                copied_image.copy_image_file(item_image)?;
+                copied_images.push(copied_image);
+            }
+            // Checked once everything is copied, since readiness rules may require attributes or images
+            if clone.status != ItemStatus::Incomplete {
+                item_readiness_action::validate_item_ready_in_transaction(&mut transaction, &clone).await?;
+            }
+
+            clones.push(clone);
+        }
+        Ok(())
+    }.await;
+
+    match result {
+        Ok(()) => {
+            transaction.commit().await?;
+            Ok(clones)
+        }
+        Err(error) => {
+            // The transaction is rolled back when dropped, but copied files must be removed manually.
+            for copied_image in copied_images {
+                if let Err(error) = copied_image.delete_image_file() {
+                    log::warn!("Failed to delete copied item image file; [{}]; {}", copied_image.id, error.message);
+                }
+            }
+            Err(error)
+        }
+    }
+}
+
+fn clone_item_model(source: &Item, parameters: &ItemCloneParameters, code: String) -> Result<Item, ShopError> {
+    let status: ItemStatus = match source.status {
+        ItemStatus::Incomplete => ItemStatus::Incomplete,
+        _ => ItemStatus::CompleteUnlisted,
+    };
+    let condition: ItemCondition = match parameters.condition {
+        Some(condition) => ItemCondition::try_from_repr(condition)?,
+        None => source.condition.clone(),
+    };
+
+    let inventory_location_id: Uuid = parameters.inventory_location_id.unwrap_or(source.inventory_location_id);
+    // A storage slot only applies within its own inventory location
+    let storage_slot_id: Option<Uuid> = match inventory_location_id == source.inventory_location_id {
+        true => source.storage_slot_id,
+        false => None,
+    };
+
+    Ok(Item {
+        id: object::random_uuid(),
+        code,
+        product_id: source.product_id,
+        inventory_location_id,
+        storage_slot_id,
+        condition,
+        status,
+        price_cents: parameters.price_cents.unwrap_or(source.price_cents),
+        priority: source.priority,
+        note: source.note.clone(),
+        acquisition_datetime: source.acquisition_datetime,
+        acquisition_price_cents: source.acquisition_price_cents,
+        acquisition_location: source.acquisition_location.clone(),
+        consignor_id: source.consignor_id,
+        commission_basis_points: source.commission_basis_points,
+        commission_minimum_cents: source.commission_minimum_cents,
+        created: Utc::now(),
+        updated: Utc::now(),
+    })
+}
@@ -1,13 +1,28 @@
 use super::*;
+use crate::acquisition_lot::{acquisition_lot_db, AcquisitionLotItem};
+use crate::bundle::bundle_db;
+use crate::ebay::{ebay_action, ebay_api};
 use crate::error::ShopError;
+use crate::inventory_transfer::{inventory_transfer_action, inventory_transfer_db, InventoryTransferSerial};
 use crate::item_audit::{item_audit_db, ItemAudit, ItemAuditSerial};
+use crate::item_grading::{item_grading_action, item_grading_db, ItemGrading};
+use crate::item_refurbishment::{item_refurbishment_action, ItemRefurbishment, ItemRefurbishmentSerial};
+use crate::item_stock::{item_stock_db, ItemStock};
 use crate::item_image::{item_image_action, item_image_db, ItemImage, ItemImageEntity, ItemImageSerial};
+use crate::item_readiness::{item_readiness_action, ItemReadiness};
 use crate::label::LabelSerial;
+use crate::listing::{Listing, ListingStatus};
 use crate::object::JsonHttpResponse;
-use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use crate::pagination::{KeysetKey, KeysetPaginationOptions};
+use crate::price_history::{price_history_action, price_history_db, PriceChangeMarketplaceError, PriceChangeReport, PriceChangeSerial, PriceHistory, PriceHistorySerial};
+use crate::profit::{profit_action, ItemProfit};
+use crate::storage_slot::storage_slot_action;
+use crate::{http, try_return, unwrap_option_else_400, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::cookie::Cookie;
 use actix_web::http::StatusCode;
-use actix_web::{web, HttpResponse, HttpResponseBuilder, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, HttpResponseBuilder, Responder};
 pub use futures::StreamExt;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use sqlx::postgres::PgQueryResult;
 use sqlx::PgPool;
@@ -16,8 +31,13 @@ use uuid::Uuid;
 pub fn configurer(config: &mut web::ServiceConfig) {
     config.service(
         web::scope("/item")
+            .route("", web::get().to(search_items))
             .route("", web::post().to(create_item))
+            .route("/by-code/{code}", web::get().to(get_item_by_code))
             .route("/{item_id}", web::get().to(get_item))
+            .route("/{item_id}", web::put().to(update_item))
+            .route("/{item_id}", web::delete().to(delete_item))
+            .route("/{item_id}/clone", web::post().to(clone_item))
             .route("/{item_id}/image", web::get().to(get_all_item_images))
             .route("/{item_id}/image", web::post().to(create_item_image))
             .route("/{item_id}/image/{item_image_id}", web::delete().to(delete_item_image))
@@ -34,6 +54,21 @@ pub fn configurer(config: &mut web::ServiceConfig) {
                 "/{item_id}/item_audit",
                 web::get().to(get_all_item_item_audits),
             )
+            .route(
+                "/{item_id}/inventory_transfer",
+                web::get().to(get_all_item_inventory_transfers),
+            )
+            .route("/{item_id}/grading", web::get().to(get_item_grading))
+            .route("/{item_id}/grading", web::delete().to(delete_item_grading))
+            .route("/{item_id}/stock", web::get().to(get_item_stock))
+            .route("/{item_id}/stock", web::delete().to(delete_item_stock))
+            .route("/{item_id}/refurbishment", web::get().to(get_all_item_refurbishments))
+            .route("/{item_id}/cost_basis", web::get().to(get_item_cost_basis))
+            .route("/{item_id}/acquisition_lot", web::get().to(get_item_acquisition_lot_item))
+            .route("/{item_id}/profit", web::get().to(get_item_profit))
+            .route("/{item_id}/readiness", web::get().to(get_item_readiness))
+            .route("/{item_id}/price", web::post().to(change_item_price))
+            .route("/{item_id}/price_history", web::get().to(get_all_item_price_histories))
             .route("/{item_id}/listing", web::get().to(get_all_item_listings)),
     )
         .route("/item_condition", web::get().to(get_all_item_conditions));
@@ -41,10 +76,22 @@ pub fn configurer(config: &mut web::ServiceConfig) {
 
 // todo: refactor to use unwrap_http macros
 
+/// Accepts either an item ID or an item code. Responds 400 if the value is neither, or 404 if no item has the code.
+pub async fn resolve_item_id(pgpool: &PgPool, value: &str) -> Result<Uuid, HttpResponse> {
+    let item_reference: ItemReference = ItemReference::parse(value)
+        .map_err(|error| HttpResponse::BadRequest().body(error.message))?;
+    match item_action::resolve_item_reference(pgpool, &item_reference).await {
+        Ok(Some(item_id)) => Ok(item_id),
+        Ok(None) => Err(HttpResponse::NotFound().finish()),
+        Err(error) => {
+            log::error!("{}", error);
+            Err(HttpResponse::InternalServerError().finish())
+        }
+    }
+}
+
 async fn get_item(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> impl Responder {
-    let Ok(item_id) = Uuid::try_parse(item_id.into_inner().as_str()) else {
-        return HttpResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR).finish();
-    };
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
 
     let item: Option<ItemEntity> = unwrap_result_else_500!(item_db::get_item(&pgpool, &item_id).await);
     let item: Result<Item, ShopError> = match item {
@@ -60,24 +107,197 @@ async fn get_item(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> impl
     }
 }
 
+/// Filters and pagination options are both read from the query string.
+/// The type of the pagination start value depends on the sort key.
+async fn search_items(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ItemSearchParameters>,
+    request: HttpRequest,
+) -> HttpResponse {
+    let parameters: ItemSearchParameters = match parameters.into_inner().validated() {
+        Ok(parameters) => parameters,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let query_string: &str = request.query_string();
+    match parameters.sort_key {
+        ItemSortKey::Price =>
+            search_items_paged(&pgpool, &parameters, query_string, |item| (item.price_cents, item.id)).await,
+        ItemSortKey::Priority =>
+            search_items_paged(&pgpool, &parameters, query_string, |item| (item.priority, item.id)).await,
+        ItemSortKey::Created =>
+            search_items_paged(&pgpool, &parameters, query_string, |item| (item.created, item.id)).await,
+        ItemSortKey::AcquisitionDatetime =>
+            search_items_paged(&pgpool, &parameters, query_string, |item| (item.acquisition_datetime, item.id)).await,
+    }
+}
+
+async fn search_items_paged<KeyT: KeysetKey + DeserializeOwned>(
+    pgpool: &PgPool,
+    parameters: &ItemSearchParameters,
+    query_string: &str,
+    getter: fn(&ItemEntity) -> (KeyT, Uuid),
+) -> HttpResponse {
+    let pagination_options: KeysetPaginationOptions<KeyT> = unwrap_result_else_400!(
+        web::Query::<KeysetPaginationOptions<KeyT>>::from_query(query_string)
+    ).into_inner();
+    let pagination_options: KeysetPaginationOptions<KeyT> = unwrap_result_else_400!(pagination_options.validated());
+
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        item_db::search_items(pgpool, parameters, &pagination_options, getter).await
+    );
+    let mut item_serial_vec: Vec<ItemSerial> = Vec::with_capacity(entities.len());
+    for entity in entities {
+        item_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (item_serial_vec, pagination_result).to_http_response()
+}
+
+async fn get_item_by_code(pgpool: web::Data<PgPool>, code: web::Path<String>) -> HttpResponse {
+    let code: String = match normalize_item_code(&code.into_inner()) {
+        Ok(code) => code,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let item: ItemEntity = unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item_by_code(&pgpool, &code).await));
+    let item: Item = unwrap_result_else_500!(item.try_to_model());
+    item.to_serial().to_http_response()
+}
+
 async fn create_item(pgpool: web::Data<PgPool>, item: web::Json<ItemSerial>) -> impl Responder {
-    let Ok(item) = item.into_inner().try_to_model() else {
+    let Ok(mut item) = item.into_inner().try_to_model() else {
         return HttpResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR).finish();
     };
+    item.code = unwrap_option_else_400!(unwrap_result_else_500!(
+        item_db::next_item_code(pgpool.get_ref(), &item.inventory_location_id).await
+    ));
+    unwrap_result_else_400!(
+        storage_slot_action::validate_item_storage_slot(pgpool.get_ref(), &item.inventory_location_id, &item.storage_slot_id).await
+    );
+    // A new item has no images or attributes yet, but may still be created past incomplete if no rule requires them
+    if item.status != ItemStatus::Incomplete
+        && let Err(error) = item_readiness_action::validate_item_ready(pgpool.get_ref(), &item).await
+    {
+        return HttpResponse::BadRequest().body(error.message);
+    }
 
-    let query_result: PgQueryResult = unwrap_result_else_500!(item_db::create_item(&pgpool, &item.to_entity()).await);
+    let query_result: PgQueryResult = unwrap_result_else_500!(item_db::create_item(pgpool.get_ref(), &item.to_entity()).await);
     HttpResponseBuilder::new(StatusCode::OK).body(query_result.rows_affected().to_string())
 }
 
+#[derive(Debug, Deserialize)]
+struct UpdateItemParameters {
+    /// Recorded in the item audit if the item status changes
+    pub audit_note: Option<String>,
+}
+
+async fn update_item(
+    pgpool: web::Data<PgPool>,
+    item_id: web::Path<String>,
+    parameters: web::Query<UpdateItemParameters>,
+    item: web::Json<ItemSerial>,
+) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    let mut item: Item = unwrap_result_else_400!(item.into_inner().try_to_model());
+    item.id = item_id; // Item ID is overridden with a random UUID in `try_to_model`.
+
+    unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_id).await));
+
+    // The status transition is validated against the locked item
+    match item_action::update_item(&pgpool, item, true, parameters.into_inner().audit_note).await {
+        Ok(item) => item.to_serial().to_http_response(),
+        Err(error) => {
+            log::info!("Rejecting item update; [{}]; {}", item_id, error.message);
+            HttpResponse::BadRequest().body(error.message)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteItemParameters {
+    /// Withdraw active listings before deletion instead of refusing
+    #[serde(default)]
+    pub force: bool,
+    /// Recorded in the item audit tombstone
+    pub audit_note: Option<String>,
+}
+
+/// Purchased items are never deleted, nor are bundled items until removed from their bundle. Active listings refuse
+/// deletion unless forced, in which case published eBay listings are withdrawn (requiring the eBay user access token)
+/// and others are cancelled locally.
+async fn delete_item(
+    pgpool: web::Data<PgPool>,
+    request: HttpRequest,
+    item_id: web::Path<String>,
+    parameters: web::Query<DeleteItemParameters>,
+) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    let parameters: DeleteItemParameters = parameters.into_inner();
+    unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_id).await));
+
+    let purchases = unwrap_result_else_500!(item_db::get_all_item_purchases(pgpool.get_ref(), &item_id).await);
+    if !purchases.is_empty() {
+        return HttpResponse::Conflict().body("Item has purchases, which must be retained;");
+    }
+    // Checked before any listing is withdrawn, since the deletion would otherwise fail afterward
+    if unwrap_result_else_500!(bundle_db::get_bundle_item(pgpool.get_ref(), &item_id).await).is_some() {
+        return HttpResponse::Conflict().body("Item belongs to a bundle, from which it must be removed;");
+    }
+
+    let listings: Vec<Listing> = unwrap_result_else_500!(item_action::get_all_item_listings(pgpool.get_ref(), &item_id).await);
+    let active_listings: Vec<&Listing> = listings.iter().filter(|listing| listing.status.is_active()).collect();
+    if !active_listings.is_empty() {
+        if !parameters.force {
+            return HttpResponse::Conflict().body("Item has active listings; Withdraw them or delete with force;");
+        }
+
+        let requires_ebay_token: bool = active_listings.iter()
+            .any(|listing| ebay_action::is_marketplace_listing(listing) && listing.status == ListingStatus::Published);
+        let user_access_token: Option<Cookie> = match requires_ebay_token {
+            true => Some(try_return!(ebay_api::extract_user_token(&request))),
+            false => None,
+        };
+        unwrap_result_else_500!(item_action::withdraw_active_listings(
+            &pgpool,
+            &item_id,
+            user_access_token.as_ref().map(|cookie| cookie.value()),
+        ).await);
+    }
+
+    unwrap_result_else_500!(item_action::delete_item(&pgpool, &item_id, true, parameters.audit_note).await);
+    HttpResponse::NoContent().finish()
+}
+
+async fn clone_item(
+    pgpool: web::Data<PgPool>,
+    item_id: web::Path<String>,
+    parameters: web::Json<ItemCloneParameters>,
+) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    let parameters: ItemCloneParameters = unwrap_result_else_400!(parameters.into_inner().validated());
+    let source: Item = unwrap_result_else_500!(
+        unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_id).await)).try_to_model()
+    );
+    // Copies of a complete item are complete themselves, so they must be ready like any other completed item
+    if source.status != ItemStatus::Incomplete
+        && let Err(error) = item_readiness_action::validate_item_ready(pgpool.get_ref(), &source).await
+    {
+        log::info!("Rejecting item clone; [{}]; {}", item_id, error.message);
+        return HttpResponse::BadRequest().body(error.message);
+    }
+
+    let clones: Vec<Item> = unwrap_result_else_500!(item_action::clone_item(&pgpool, &item_id, &parameters).await);
+    clones.iter()
+        .map(|item| item.to_serial())
+        .collect::<Vec<ItemSerial>>()
+        .to_http_response()
+}
+
 async fn get_all_item_images(
     pgpool: web::Data<PgPool>,
     item_id: web::Path<String>,
 ) -> impl Responder {
-    let Ok(item_id) = Uuid::try_parse(item_id.into_inner().as_str()) else {
-        return HttpResponse::BadRequest().finish();
-    };
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
 
-    let result = item_image_db::get_all_item_images(&pgpool, &item_id).await;
+    let result = item_image_db::get_all_item_images(pgpool.get_ref(), &item_id).await;
     let Ok(item_images) = result else {
         return HttpResponse::InternalServerError().finish();
     };
@@ -101,7 +321,7 @@ async fn create_item_image(
     item_id: web::Path<String>,
     mut payload: web::Payload,
 ) -> HttpResponse {
-    let item_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(item_id.into_inner().as_str()));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
     let item_image: ItemImage = ItemImage::new(
         item_id,
         parameters.alt_text.clone(),
@@ -109,7 +329,7 @@ async fn create_item_image(
     );
 
     unwrap_result_else_500!(item_image.store_image_file(&mut payload).await);
-    unwrap_result_else_500!(item_image_db::create_item_image(&pgpool, &item_image).await);
+    unwrap_result_else_500!(item_image_db::create_item_image(pgpool.get_ref(), &item_image).await);
 
     item_image.to_serial().to_http_response()
 }
@@ -132,8 +352,8 @@ async fn get_all_item_labels(
     pgpool: web::Data<PgPool>,
     item_id: web::Path<String>,
 ) -> impl Responder {
-    let item_id = unwrap_result_else_400!(Uuid::parse_str(&item_id.into_inner().as_str()));
-    unwrap_result_else_500!(item_db::get_all_item_labels(&pgpool, &item_id).await)
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    unwrap_result_else_500!(item_db::get_all_item_labels(pgpool.get_ref(), &item_id).await)
         .iter()
         .map(|label| label.to_serial())
         .collect::<Vec<LabelSerial>>()
@@ -145,11 +365,11 @@ async fn create_item_label_association(
     path: web::Path<(String, String)>,
 ) -> impl Responder {
     let (item_id, label_id) = path.into_inner();
-    let item_id = unwrap_result_else_400!(Uuid::parse_str(&item_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id).await);
     let label_id = unwrap_result_else_400!(Uuid::parse_str(&label_id));
 
     let query_result: PgQueryResult = unwrap_result_else_500!(
-		item_db::create_item_label_association(&pgpool, &item_id, &label_id).await
+		item_db::create_item_label_association(pgpool.get_ref(), &item_id, &label_id).await
 	);
     HttpResponse::Ok().body(query_result.rows_affected().to_string())
 }
@@ -159,7 +379,7 @@ async fn delete_item_label_association(
     path: web::Path<(String, String)>,
 ) -> impl Responder {
     let (item_id, label_id) = path.into_inner();
-    let item_id = unwrap_result_else_400!(Uuid::parse_str(&item_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id).await);
     let label_id = unwrap_result_else_400!(Uuid::parse_str(&label_id));
 
     let query_result: PgQueryResult = unwrap_result_else_500!(
@@ -172,7 +392,7 @@ async fn get_all_item_item_audits(
     pgpool: web::Data<PgPool>,
     item_id: web::Path<String>,
 ) -> impl Responder {
-    let item_id = unwrap_result_else_400!(Uuid::parse_str(&item_id));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
 
     let item_audit_entity_vec =
         unwrap_result_else_500!(item_audit_db::get_all_item_item_audits(&pgpool, &item_id).await);
@@ -188,13 +408,29 @@ async fn get_all_item_item_audits(
         .to_http_response()
 }
 
+async fn get_all_item_inventory_transfers(
+    pgpool: web::Data<PgPool>,
+    item_id: web::Path<String>,
+) -> impl Responder {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let inventory_transfer_entity_vec = unwrap_result_else_500!(
+        inventory_transfer_db::get_all_item_inventory_transfers(&pgpool, &item_id).await
+    );
+    let mut inventory_transfer_serial_vec: Vec<InventoryTransferSerial> = Vec::new();
+    for inventory_transfer_entity in inventory_transfer_entity_vec {
+        inventory_transfer_serial_vec.push(unwrap_result_else_500!(inventory_transfer_entity.try_to_model()).to_serial());
+    }
+    inventory_transfer_serial_vec.to_http_response()
+}
+
 async fn get_all_item_listings(
     pgpool: web::Data<PgPool>,
     item_id: web::Path<String>,
 ) -> impl Responder {
-    let item_id = unwrap_result_else_400!(Uuid::try_parse(item_id.into_inner().as_str()));
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
     let listing_vec =
-        unwrap_result_else_500!(item_db::get_all_item_listings(&pgpool, &item_id).await);
+        unwrap_result_else_500!(item_db::get_all_item_listings(pgpool.get_ref(), &item_id).await);
 
     let mut listing_serial_vec = Vec::new();
     for listing_entity in listing_vec {
@@ -209,3 +445,145 @@ async fn get_all_item_conditions() -> impl Responder {
     let body: String = ItemCondition::get_json_spec();
     HttpResponseBuilder::new(StatusCode::OK).body(body)
 }
+
+async fn get_item_grading(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let item_grading: ItemGrading = unwrap_option_else_404!(unwrap_result_else_500!(
+        item_grading_action::get_item_grading(&pgpool, &item_id).await
+    ));
+    item_grading.to_serial().to_http_response()
+}
+
+async fn delete_item_grading(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let query_result: PgQueryResult = unwrap_result_else_500!(item_grading_db::delete_item_grading(&pgpool, &item_id).await);
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
+
+async fn get_item_stock(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let item_stock: ItemStock = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        item_stock_db::get_item_stock(pgpool.get_ref(), &item_id).await
+    )).try_to_model());
+    item_stock.to_serial().to_http_response()
+}
+
+/// The item becomes a unique unit again. Published eBay offers retain their quantity until the listing is republished.
+async fn delete_item_stock(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let query_result: PgQueryResult = unwrap_result_else_500!(item_stock_db::delete_item_stock(&pgpool, &item_id).await);
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
+
+async fn get_all_item_refurbishments(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let item_refurbishments: Vec<ItemRefurbishment> = unwrap_result_else_500!(
+        item_refurbishment_action::get_all_item_refurbishments(&pgpool, &item_id).await
+    );
+    item_refurbishments.iter()
+        .map(|item_refurbishment| item_refurbishment.to_serial())
+        .collect::<Vec<ItemRefurbishmentSerial>>()
+        .to_http_response()
+}
+
+async fn get_item_cost_basis(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let entity: ItemCostBasisEntity = unwrap_option_else_404!(unwrap_result_else_500!(
+        item_db::get_item_cost_basis(pgpool.get_ref(), &item_id).await
+    ));
+    ItemCostBasis::from(&entity).to_http_response()
+}
+
+/// The item's membership in an acquisition lot, including its allocated acquisition price
+async fn get_item_acquisition_lot_item(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let acquisition_lot_item: AcquisitionLotItem = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        acquisition_lot_db::get_acquisition_lot_item(pgpool.get_ref(), &item_id).await
+    )).try_to_model());
+    acquisition_lot_item.to_serial().to_http_response()
+}
+
+/// The item's cost basis and, if it has sold, its sale proceeds and profit
+async fn get_item_profit(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let item_profit: ItemProfit = unwrap_option_else_404!(unwrap_result_else_500!(
+        profit_action::get_item_profit(&pgpool, &item_id).await
+    ));
+    item_profit.to_http_response()
+}
+
+/// The item's readiness score and the rules it fails; see [crate::item_readiness::ItemReadinessRule]
+async fn get_item_readiness(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    let item: Item = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        item_db::get_item(&pgpool, &item_id).await
+    )).try_to_model());
+
+    let item_readiness: ItemReadiness = unwrap_result_else_500!(item_readiness_action::get_item_readiness(&pgpool, &item).await);
+    item_readiness.to_http_response()
+}
+
+async fn change_item_price(
+    pgpool: web::Data<PgPool>,
+    request: HttpRequest,
+    item_id: web::Path<String>,
+    body: web::Json<PriceChangeSerial>,
+) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+    let body: PriceChangeSerial = body.into_inner();
+
+    // Require the eBay token up front so that published offers never drift from the recorded price
+    let published_listings: Vec<Listing> = unwrap_result_else_500!(
+        inventory_transfer_action::get_published_marketplace_listings(&pgpool, &[item_id]).await
+    );
+    let user_access_token: Option<Cookie> = match published_listings.is_empty() {
+        true => None,
+        false => Some(try_return!(ebay_api::extract_user_token(&request))),
+    };
+
+    let initiated_by: Option<String> = http::extract_remote_user(&request);
+    let price_history: Option<PriceHistory> = unwrap_result_else_500!(
+        price_history_action::change_item_price(&pgpool, &item_id, body.price_cents, initiated_by, body.reason).await
+    );
+
+    let marketplace_errors: Vec<PriceChangeMarketplaceError> = match (&price_history, &user_access_token) {
+        (Some(_), Some(user_access_token)) => price_history_action::sync_marketplace_prices(
+            &pgpool,
+            user_access_token.value(),
+            &published_listings,
+        ).await,
+        _ => Vec::new(),
+    };
+
+    PriceChangeReport {
+        price_history: price_history.map(|price_history| price_history.to_serial()),
+        marketplace_errors,
+    }.to_http_response()
+}
+
+async fn get_all_item_price_histories(pgpool: web::Data<PgPool>, item_id: web::Path<String>) -> HttpResponse {
+    let item_id: Uuid = try_return!(resolve_item_id(&pgpool, &item_id.into_inner()).await);
+
+    let price_history_entity_vec = unwrap_result_else_500!(
+        price_history_db::get_all_item_price_histories(&pgpool, &item_id).await
+    );
+    let mut price_history_serial_vec: Vec<PriceHistorySerial> = Vec::with_capacity(price_history_entity_vec.len());
+    for price_history_entity in price_history_entity_vec {
+        price_history_serial_vec.push(unwrap_result_else_500!(price_history_entity.try_to_model()).to_serial());
+    }
+    price_history_serial_vec.to_http_response()
+}
@@ -2,13 +2,17 @@ use super::*;
 use crate::error::ShopError;
 use crate::label::LabelEntity;
 use crate::listing::ListingEntity;
+use crate::pagination;
+use crate::pagination::{KeysetKey, KeysetPaginationOptions, KeysetPaginationResult};
+use crate::purchase::PurchaseEntity;
+use chrono::{DateTime, Utc};
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub async fn get_item(pgpool: &PgPool, item_id: &Uuid) -> Result<Option<ItemEntity>, ShopError> {
     query_as!(ItemEntity, "\
-		select id, product_id, inventory_location_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, created, updated \
+		select id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated \
 		from shop.public.item \
 		where id = $1 \
 		",
@@ -19,14 +23,80 @@ pub async fn get_item(pgpool: &PgPool, item_id: &Uuid) -> Result<Option<ItemEnti
         .map_err(|e| ShopError::from(e))
 }
 
-pub async fn create_item(pgpool: &PgPool, item: &ItemEntity) -> Result<PgQueryResult, ShopError> {
+/// Locks the item row until the enclosing transaction ends.
+pub async fn get_item_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<ItemEntity>, ShopError> {
+    query_as!(ItemEntity, "\
+		select id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated \
+		from shop.public.item \
+		where id = $1 \
+		for update \
+		",
+		item_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_item_by_code(pgpool: &PgPool, code: &str) -> Result<Option<ItemEntity>, ShopError> {
+    query_as!(ItemEntity, "\
+		select id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated \
+		from shop.public.item \
+		where code = $1 \
+		",
+		code
+	)
+        .fetch_optional(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_item_id_by_code(pgexecutor: impl PgExecutor<'_>, code: &str) -> Result<Option<Uuid>, ShopError> {
+    query!("
+		select id
+		from shop.public.item
+		where code = $1
+	",
+		code
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map(|record| record.map(|record| record.id))
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Draws the next number from the item code sequence and encodes it with the prefix of the given inventory location.
+/// Returns none if the inventory location does not exist.
+pub async fn next_item_code(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Option<String>, ShopError> {
+    query!(r#"
+		select internal_name, nextval('shop.public.item_code_sequence') as "number!"
+		from shop.public.inventory_location
+		where id = $1
+	"#,
+		inventory_location_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map(|record| record.map(|record| create_item_code(&record.internal_name, record.number as u64)))
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_item(pgexecutor: impl PgExecutor<'_>, item: &ItemEntity) -> Result<PgQueryResult, ShopError> {
     query!("
-		insert into shop.public.item (id, product_id, inventory_location_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, created, updated)
-		values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+		insert into shop.public.item (id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated)
+		values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
 	",
 		item.id,
+		item.code,
 		item.product_id,
 		item.inventory_location_id,
+		item.storage_slot_id,
 		item.condition,
 		item.status,
 		item.price_cents,
@@ -35,15 +105,182 @@ pub async fn create_item(pgpool: &PgPool, item: &ItemEntity) -> Result<PgQueryRe
 		item.acquisition_datetime,
 		item.acquisition_price_cents,
 		item.acquisition_location,
+		item.consignor_id,
+		item.commission_basis_points,
+		item.commission_minimum_cents,
 		item.created,
 		item.updated,
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_item(pgexecutor: impl PgExecutor<'_>, item: &ItemEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+		update shop.public.item
+		set (inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, updated)
+		    = ($2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+		where id = $1
+	",
+		item.id,
+		item.inventory_location_id,
+		item.storage_slot_id,
+		item.condition,
+		item.status,
+		item.price_cents,
+		item.priority,
+		item.note,
+		item.acquisition_datetime,
+		item.acquisition_price_cents,
+		item.acquisition_location,
+		item.consignor_id,
+		item.commission_basis_points,
+		item.commission_minimum_cents,
+		item.updated,
+	)
+        .execute(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
 
-pub async fn get_all_item_labels(pgpool: &PgPool, item_id: &Uuid) -> Result<Vec<LabelEntity>, ShopError> {
+/// Items currently assigned to the inventory location, in any status
+pub async fn get_all_inventory_location_items(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Vec<ItemEntity>, ShopError> {
+    query_as!(ItemEntity, "\
+		select id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated \
+		from shop.public.item \
+		where inventory_location_id = $1 \
+		order by code \
+		",
+		inventory_location_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_item_status(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    status: i32,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+		update shop.public.item
+		set (status, updated) = ($2, $3)
+		where id = $1
+	",
+		item_id,
+		status,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Inventory locations should only change through an [crate::inventory_transfer::InventoryTransfer].
+/// The item's storage slot belongs to the previous location and is cleared.
+pub async fn update_item_inventory_location(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    inventory_location_id: &Uuid,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+		update shop.public.item
+		set (inventory_location_id, storage_slot_id, updated) = ($2, null, $3)
+		where id = $1
+	",
+		item_id,
+		inventory_location_id,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Prices should only change through a [crate::price_history::PriceHistory].
+pub async fn update_item_price(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    price_cents: i64,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+		update shop.public.item
+		set (price_cents, updated) = ($2, $3)
+		where id = $1
+	",
+		item_id,
+		price_cents,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Acquisition prices of items in an acquisition lot should only change through its allocation; see
+/// [crate::acquisition_lot::AcquisitionLot::allocate].
+pub async fn update_item_acquisition_price(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    acquisition_price_cents: Option<i64>,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+		update shop.public.item
+		set (acquisition_price_cents, updated) = ($2, $3)
+		where id = $1
+	",
+		item_id,
+		acquisition_price_cents,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_item_storage_slot(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    storage_slot_id: Option<Uuid>,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+		update shop.public.item
+		set (storage_slot_id, updated) = ($2, $3)
+		where id = $1
+	",
+		item_id,
+		storage_slot_id,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Associated images, attributes, and labels are deleted by cascade. Listings must be deleted beforehand.
+pub async fn delete_item(pgexecutor: impl PgExecutor<'_>, item_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+		delete from shop.public.item
+		where id = $1
+	",
+		item_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_item_labels(pgexecutor: impl PgExecutor<'_>, item_id: &Uuid) -> Result<Vec<LabelEntity>, ShopError> {
     query_as!(LabelEntity, "
         select id, display_name, internal_name
 		from shop.public.label
@@ -52,13 +289,13 @@ pub async fn get_all_item_labels(pgpool: &PgPool, item_id: &Uuid) -> Result<Vec<
     ",
 		item_id,
 	)
-        .fetch_all(pgpool)
+        .fetch_all(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
 
 pub async fn create_item_label_association(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     item_id: &Uuid,
     label_id: &Uuid,
 ) -> Result<PgQueryResult, ShopError> {
@@ -69,7 +306,7 @@ pub async fn create_item_label_association(
 		item_id,
 		label_id,
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
@@ -92,17 +329,156 @@ pub async fn delete_item_label_association(
 }
 
 pub async fn get_all_item_listings(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     item_id: &Uuid,
 ) -> Result<Vec<ListingEntity>, ShopError> {
     query_as!(ListingEntity, "
-		select id, item_id, marketplace_id, status, created, updated
+		select id, item_id, bundle_id, marketplace_id, status, published, created, updated
 		from shop.public.listing
 		where item_id = $1
 	",
 		item_id,
 	)
-        .fetch_all(pgpool)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_item_purchases(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Vec<PurchaseEntity>, ShopError> {
+    query_as!(PurchaseEntity, "
+		select purchase.id, purchase.marketplace_id, purchase.external_id, purchase.customer_id, purchase.contact_email_address, purchase.listing_id, purchase.quantity, purchase.status, purchase.cost_subtotal_cents, purchase.cost_tax_cents, purchase.cost_shipping_cents, purchase.cost_discount_cents, purchase.seller_cost_total_cents, purchase.marketplace_fee_cents, purchase.shipping_method, purchase.payment_method, purchase.note, purchase.shipping_street_address, purchase.shipping_municipality, purchase.shipping_district, purchase.shipping_postal_area, purchase.shipping_country, purchase.billing_street_address, purchase.billing_municipality, purchase.billing_district, purchase.billing_postal_area, purchase.billing_country, purchase.created, purchase.updated
+		from shop.public.purchase
+		inner join shop.public.listing on listing.id = purchase.listing_id
+		where listing.item_id = $1
+	",
+		item_id,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_item_cost_basis(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<ItemCostBasisEntity>, ShopError> {
+    query_as!(ItemCostBasisEntity, r#"
+		select item.id as item_id, item.acquisition_price_cents, coalesce(sum(item_refurbishment.cost_cents), 0)::bigint as "refurbishment_cost_cents!"
+		from shop.public.item
+		left join shop.public.item_refurbishment on item_refurbishment.item_id = item.id
+		where item.id = $1
+		group by item.id
+	"#,
+		item_id,
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_product_item_cost_bases(
+    pgexecutor: impl PgExecutor<'_>,
+    product_id: &Uuid,
+) -> Result<Vec<ItemCostBasisEntity>, ShopError> {
+    query_as!(ItemCostBasisEntity, r#"
+		select item.id as item_id, item.acquisition_price_cents, coalesce(sum(item_refurbishment.cost_cents), 0)::bigint as "refurbishment_cost_cents!"
+		from shop.public.item
+		left join shop.public.item_refurbishment on item_refurbishment.item_id = item.id
+		where item.product_id = $1
+		group by item.id
+	"#,
+		product_id,
+	)
+        .fetch_all(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
+
+pub async fn search_items<KeyT: KeysetKey>(
+    pgpool: &PgPool,
+    parameters: &ItemSearchParameters,
+    pagination_options: &KeysetPaginationOptions<KeyT>,
+    getter: fn(&ItemEntity) -> (KeyT, Uuid),
+) -> Result<(Vec<ItemEntity>, KeysetPaginationResult<KeyT>), ShopError> {
+    let mut builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+		select id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated \
+		from shop.public.item \
+		where true \
+	");
+    push_item_search_filters(&mut builder, parameters);
+
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        parameters.sort_key.get_column_name(),
+        &parameters.sort_direction,
+        pagination_options,
+        getter,
+    ).await
+}
+
+pub fn push_item_search_filters(builder: &mut QueryBuilder<Postgres>, parameters: &ItemSearchParameters) {
+    if let Some(status) = parameters.status {
+        builder.push(" and status = ").push_bind(i32::from(status));
+    }
+    if let Some(condition) = parameters.condition {
+        builder.push(" and condition = ").push_bind(i32::from(condition));
+    }
+    if let Some(inventory_location_id) = parameters.inventory_location_id {
+        builder.push(" and inventory_location_id = ").push_bind(inventory_location_id);
+    }
+    if let Some(product_id) = parameters.product_id {
+        builder.push(" and product_id = ").push_bind(product_id);
+    }
+    if let Some(label_id) = parameters.label_id {
+        builder.push(" and exists (\
+            select 1 from shop.public.item_label_association \
+            where item_label_association.item_id = item.id and item_label_association.label_id = ")
+            .push_bind(label_id)
+            .push(")");
+    }
+    if let Some(price_cents_min) = parameters.price_cents_min {
+        builder.push(" and price_cents >= ").push_bind(i64::from(price_cents_min));
+    }
+    if let Some(price_cents_max) = parameters.price_cents_max {
+        builder.push(" and price_cents <= ").push_bind(i64::from(price_cents_max));
+    }
+    if let Some(acquired_after) = parameters.acquired_after {
+        builder.push(" and acquisition_datetime >= ").push_bind(acquired_after);
+    }
+    if let Some(acquired_before) = parameters.acquired_before {
+        builder.push(" and acquisition_datetime <= ").push_bind(acquired_before);
+    }
+    if let Some(consignor_id) = parameters.consignor_id {
+        builder.push(" and consignor_id = ").push_bind(consignor_id);
+    }
+    if let Some(attribute_key) = &parameters.attribute_key {
+        builder.push(" and exists (\
+            select 1 from shop.public.item_attribute \
+            where item_attribute.item_id = item.id and lower(item_attribute.key) = lower(")
+            .push_bind(attribute_key.trim().to_string())
+            .push(")");
+        if let Some(attribute_value) = &parameters.attribute_value {
+            builder.push(" and lower(item_attribute.value) = lower(").push_bind(attribute_value.trim().to_string()).push(")");
+        }
+        if let Some(attribute_number_min) = parameters.attribute_number_min {
+            builder.push(" and item_attribute.value_number >= ").push_bind(attribute_number_min);
+        }
+        if let Some(attribute_number_max) = parameters.attribute_number_max {
+            builder.push(" and item_attribute.value_number <= ").push_bind(attribute_number_max);
+        }
+        if let Some(attribute_date_min) = parameters.attribute_date_min {
+            builder.push(" and item_attribute.value_date >= ").push_bind(attribute_date_min);
+        }
+        if let Some(attribute_date_max) = parameters.attribute_date_max {
+            builder.push(" and item_attribute.value_date <= ").push_bind(attribute_date_max);
+        }
+        if let Some(attribute_boolean) = parameters.attribute_boolean {
+            builder.push(" and item_attribute.value_boolean = ").push_bind(attribute_boolean);
+        }
+        builder.push(")");
+    }
+}
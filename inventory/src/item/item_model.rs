@@ -1,19 +1,24 @@
 use crate::error::ShopError;
 use crate::object::JsonHttpResponse;
+use crate::pagination::Direction;
 use crate::{create_json_spec, object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 use strum::{FromRepr, IntoStaticStr, VariantArray};
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Item {
     /// SKU
     pub id: Uuid,
+    /// Short identifier for people and scanners; see [create_item_code]
+    pub code: String,
     pub product_id: Uuid,
     pub inventory_location_id: Uuid,
+    /// Where the item sits within its inventory location, if known
+    pub storage_slot_id: Option<Uuid>,
     pub condition: ItemCondition,
     pub status: ItemStatus,
     pub price_cents: u32,
@@ -22,10 +27,14 @@ pub struct Item {
     pub acquisition_datetime: DateTime<Utc>,
     pub acquisition_price_cents: Option<u32>,
     pub acquisition_location: Option<String>,
+    /// The owner of an item sold on consignment; see [crate::consignor::Consignor]
+    pub consignor_id: Option<Uuid>,
+    /// The share of a consigned item's sale price kept as commission; 10000 is the whole price
+    pub commission_basis_points: Option<u32>,
+    /// The least commission kept on a consigned item's sale
+    pub commission_minimum_cents: Option<u32>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
-    // Add grading information?
-    // Add refurbishment information?
 }
 
 /// Variants inspired by Ebay: https://www.ebay.com/help/selling/listings/creating-managing-listings/item-conditions-category.
@@ -65,7 +74,7 @@ impl Display for ItemCondition {
 create_json_spec!(ItemCondition<u8>);
 try_from_repr!(ItemCondition<u8>);
 
-#[derive(Debug, Clone, FromRepr, VariantArray, IntoStaticStr)]
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
 #[repr(u8)]
 pub enum ItemStatus {
     /// Item is only partially constructed and expects modifications before publishing
@@ -86,6 +95,8 @@ pub enum ItemStatus {
     Shipped,
     /// Item has been received by the customer (either via shipping or pickup)
     Received,
+    /// Item could not be found at its inventory location during a stock-take
+    Missing,
 }
 
 impl Display for ItemStatus {
@@ -96,6 +107,80 @@ impl Display for ItemStatus {
 
 try_from_repr!(ItemStatus<u8>);
 
+impl ItemStatus {
+    /// The statuses which may directly follow this status. Remaining in the same status is always permitted.
+    pub fn get_successors(&self) -> &'static [ItemStatus] {
+        match self {
+            ItemStatus::Incomplete => &[ItemStatus::CompleteUnlisted, ItemStatus::Missing],
+            ItemStatus::CompleteUnlisted => &[
+                ItemStatus::Incomplete,
+                ItemStatus::CompleteListed,
+                ItemStatus::CustomerHoldDelisted,
+                ItemStatus::PurchasedDelisted,
+                ItemStatus::Missing,
+            ],
+            ItemStatus::CompleteListed => &[
+                ItemStatus::CompleteUnlisted,
+                ItemStatus::CustomerHoldListed,
+                ItemStatus::PurchaseListed,
+            ],
+            ItemStatus::CustomerHoldListed => &[
+                ItemStatus::CompleteListed,
+                ItemStatus::CustomerHoldDelisted,
+                ItemStatus::PurchaseListed,
+            ],
+            ItemStatus::CustomerHoldDelisted => &[
+                ItemStatus::CompleteUnlisted,
+                ItemStatus::PurchasedDelisted,
+                ItemStatus::Missing,
+            ],
+            ItemStatus::PurchaseListed => &[ItemStatus::PurchasedDelisted],
+            ItemStatus::PurchasedDelisted => &[ItemStatus::Shipped, ItemStatus::Received],
+            ItemStatus::Shipped => &[ItemStatus::Received],
+            ItemStatus::Received => &[],
+            ItemStatus::Missing => &[ItemStatus::Incomplete, ItemStatus::CompleteUnlisted],
+        }
+    }
+
+    /// True iff the item is expected to be physically present at its inventory location.
+    pub fn is_on_hand(&self) -> bool {
+        match self {
+            ItemStatus::Incomplete
+            | ItemStatus::CompleteUnlisted
+            | ItemStatus::CompleteListed
+            | ItemStatus::CustomerHoldListed
+            | ItemStatus::CustomerHoldDelisted
+            | ItemStatus::PurchaseListed
+            | ItemStatus::PurchasedDelisted => true,
+            ItemStatus::Shipped | ItemStatus::Received | ItemStatus::Missing => false,
+        }
+    }
+
+    /// True iff the item has been purchased, whether or not it has left our hands.
+    pub fn is_sold(&self) -> bool {
+        match self {
+            ItemStatus::PurchaseListed
+            | ItemStatus::PurchasedDelisted
+            | ItemStatus::Shipped
+            | ItemStatus::Received => true,
+            ItemStatus::Incomplete
+            | ItemStatus::CompleteUnlisted
+            | ItemStatus::CompleteListed
+            | ItemStatus::CustomerHoldListed
+            | ItemStatus::CustomerHoldDelisted
+            | ItemStatus::Missing => false,
+        }
+    }
+
+    pub fn validate_transition(&self, next: &ItemStatus) -> Result<(), ShopError> {
+        if self == next || self.get_successors().contains(next) {
+            Ok(())
+        } else {
+            Err(ShopError::new(&format!("Illegal item status transition; [{} -> {}]", self, next)))
+        }
+    }
+}
+
 impl ShopModel for Item {
     type Entity = ItemEntity;
     type Serial = ItemSerial;
@@ -103,8 +188,10 @@ impl ShopModel for Item {
     fn to_serial(&self) -> Self::Serial {
         ItemSerial {
             id: self.id.clone(),
+            code: self.code.clone(),
             product_id: self.product_id.clone(),
             inventory_location_id: self.inventory_location_id.clone(),
+            storage_slot_id: self.storage_slot_id.clone(),
             condition: self.condition.clone() as u8,
             status: self.status.clone() as u8,
             price_cents: self.price_cents.clone(),
@@ -113,16 +200,23 @@ impl ShopModel for Item {
             acquisition_datetime: self.acquisition_datetime.clone(),
             acquisition_price_cents: self.acquisition_price_cents.clone(),
             acquisition_location: self.acquisition_location.clone(),
+            consignor_id: self.consignor_id.clone(),
+            commission_basis_points: self.commission_basis_points.clone(),
+            commission_minimum_cents: self.commission_minimum_cents.clone(),
             created: self.created.clone(),
             updated: self.updated.clone(),
         }
     }
 
     fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        validate_consignment_terms(serial.consignor_id.as_ref(), serial.commission_basis_points, serial.commission_minimum_cents)?;
+
         Ok(Item {
             id: object::random_uuid(),
+            code: serial.code.clone(),
             product_id: serial.product_id.clone(),
             inventory_location_id: serial.inventory_location_id.clone(),
+            storage_slot_id: serial.storage_slot_id.clone(),
             condition: ItemCondition::try_from_repr(serial.condition)?,
             status: ItemStatus::try_from_repr(serial.status)?,
             price_cents: serial.price_cents.clone(),
@@ -131,6 +225,9 @@ impl ShopModel for Item {
             acquisition_datetime: serial.acquisition_datetime.clone(),
             acquisition_price_cents: serial.acquisition_price_cents.clone(),
             acquisition_location: serial.acquisition_location.clone(),
+            consignor_id: serial.consignor_id.clone(),
+            commission_basis_points: serial.commission_basis_points.clone(),
+            commission_minimum_cents: serial.commission_minimum_cents.clone(),
             created: serial.created.clone(),
             updated: serial.updated.clone(),
         })
@@ -139,8 +236,10 @@ impl ShopModel for Item {
     fn to_entity(&self) -> Self::Entity {
         ItemEntity {
             id: self.id.clone(),
+            code: self.code.clone(),
             product_id: self.product_id.clone(),
             inventory_location_id: self.inventory_location_id.clone(),
+            storage_slot_id: self.storage_slot_id.clone(),
             condition: self.condition.clone() as u8 as i32,
             status: self.status.clone() as u8 as i32,
             price_cents: i64::from(self.price_cents),
@@ -149,6 +248,9 @@ impl ShopModel for Item {
             acquisition_datetime: self.acquisition_datetime.clone(),
             acquisition_price_cents: self.acquisition_price_cents.map(|v| i64::from(v)),
             acquisition_location: self.acquisition_location.clone(),
+            consignor_id: self.consignor_id.clone(),
+            commission_basis_points: self.commission_basis_points.map(|v| v as i32),
+            commission_minimum_cents: self.commission_minimum_cents.map(|v| i64::from(v)),
             created: self.created.clone(),
             updated: self.updated.clone(),
         }
@@ -157,8 +259,10 @@ impl ShopModel for Item {
     fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
         Ok(Item {
             id: entity.id.clone(),
+            code: entity.code.clone(),
             product_id: entity.product_id.clone(),
             inventory_location_id: entity.inventory_location_id.clone(),
+            storage_slot_id: entity.storage_slot_id.clone(),
             condition: ItemCondition::try_from_repr(entity.condition as u8)?,
             status: ItemStatus::try_from_repr(entity.status as u8)?,
             price_cents: entity.price_cents.clone() as u32,
@@ -167,17 +271,22 @@ impl ShopModel for Item {
             acquisition_datetime: entity.acquisition_datetime.clone(),
             acquisition_price_cents: entity.acquisition_price_cents.clone().map(|v| v as u32),
             acquisition_location: entity.acquisition_location.clone(),
+            consignor_id: entity.consignor_id.clone(),
+            commission_basis_points: entity.commission_basis_points.clone().map(|v| v as u32),
+            commission_minimum_cents: entity.commission_minimum_cents.clone().map(|v| v as u32),
             created: entity.created.clone(),
             updated: entity.updated.clone(),
         })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, sqlx::FromRow)]
 pub struct ItemEntity {
     pub id: Uuid,
+    pub code: String,
     pub product_id: Uuid,
     pub inventory_location_id: Uuid,
+    pub storage_slot_id: Option<Uuid>,
     pub condition: i32,
     pub status: i32,
     pub price_cents: i64,
@@ -186,6 +295,9 @@ pub struct ItemEntity {
     pub acquisition_datetime: DateTime<Utc>,
     pub acquisition_price_cents: Option<i64>,
     pub acquisition_location: Option<String>,
+    pub consignor_id: Option<Uuid>,
+    pub commission_basis_points: Option<i32>,
+    pub commission_minimum_cents: Option<i64>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
@@ -198,8 +310,13 @@ impl ShopEntity for ItemEntity {
 pub struct ItemSerial {
     #[serde(default)]
     pub id: Uuid,
+    /// Assigned on creation
+    #[serde(default)]
+    pub code: String,
     pub product_id: Uuid,
     pub inventory_location_id: Uuid,
+    #[serde(default)]
+    pub storage_slot_id: Option<Uuid>,
     pub condition: u8,
     pub status: u8,
     pub price_cents: u32,
@@ -208,6 +325,12 @@ pub struct ItemSerial {
     pub acquisition_datetime: DateTime<Utc>,
     pub acquisition_price_cents: Option<u32>,
     pub acquisition_location: Option<String>,
+    #[serde(default)]
+    pub consignor_id: Option<Uuid>,
+    #[serde(default)]
+    pub commission_basis_points: Option<u32>,
+    #[serde(default)]
+    pub commission_minimum_cents: Option<u32>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
@@ -215,5 +338,417 @@ pub struct ItemSerial {
 impl ShopSerial for ItemSerial {
     type Model = Item;
 }
+
+/// A consigned item has a commission rate of at most the whole price, and an unconsigned item has no commission terms.
+pub fn validate_consignment_terms(
+    consignor_id: Option<&Uuid>,
+    commission_basis_points: Option<u32>,
+    commission_minimum_cents: Option<u32>,
+) -> Result<(), ShopError> {
+    match (consignor_id, commission_basis_points) {
+        (Some(_), Some(commission_basis_points)) if commission_basis_points > 10000 => Err(ShopError::new(&format!(
+            "Commission must not exceed 10000 basis points; [{}]",
+            commission_basis_points,
+        ))),
+        (Some(_), Some(_)) => Ok(()),
+        (Some(consignor_id), None) => Err(ShopError::new(&format!("A consigned item requires a commission rate; [{}]", consignor_id))),
+        (None, _) if commission_basis_points.is_some() || commission_minimum_cents.is_some() => {
+            Err(ShopError::new("Commission terms apply only to consigned items"))
+        },
+        (None, _) => Ok(()),
+    }
+}
 impl JsonHttpResponse for ItemSerial {}
 impl JsonHttpResponse for Vec<ItemSerial> {}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemCostBasisEntity {
+    pub item_id: Uuid,
+    pub acquisition_price_cents: Option<i64>,
+    pub refurbishment_cost_cents: i64,
+}
+
+/// Everything spent on an item, against which its sale price is compared
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemCostBasis {
+    pub item_id: Uuid,
+    /// Counted as zero in the total when unknown
+    pub acquisition_price_cents: Option<u32>,
+    /// The sum of the costs of all refurbishment work performed on the item
+    pub refurbishment_cost_cents: u64,
+    pub cost_basis_cents: u64,
+}
+
+impl From<&ItemCostBasisEntity> for ItemCostBasis {
+    fn from(entity: &ItemCostBasisEntity) -> Self {
+        let acquisition_price_cents: Option<u32> = entity.acquisition_price_cents.map(|v| v as u32);
+        let refurbishment_cost_cents: u64 = entity.refurbishment_cost_cents as u64;
+        ItemCostBasis {
+            item_id: entity.item_id.clone(),
+            acquisition_price_cents,
+            refurbishment_cost_cents,
+            cost_basis_cents: u64::from(acquisition_price_cents.unwrap_or(0)) + refurbishment_cost_cents,
+        }
+    }
+}
+
+impl JsonHttpResponse for ItemCostBasis {}
+impl JsonHttpResponse for Vec<ItemCostBasis> {}
+
+/// Filters applied by the item search. Each present field narrows the result set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ItemSearchParameters {
+    pub status: Option<u8>,
+    pub condition: Option<u8>,
+    pub inventory_location_id: Option<Uuid>,
+    pub label_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    /// Inclusive
+    pub price_cents_min: Option<u32>,
+    /// Inclusive
+    pub price_cents_max: Option<u32>,
+    /// Inclusive
+    pub acquired_after: Option<DateTime<Utc>>,
+    /// Inclusive
+    pub acquired_before: Option<DateTime<Utc>>,
+    pub consignor_id: Option<Uuid>,
+    /// Narrows to items having an attribute of the key, compared case-insensitively. Required by the other attribute
+    /// filters, which apply to that attribute.
+    pub attribute_key: Option<String>,
+    /// Compared case-insensitively
+    pub attribute_value: Option<String>,
+    /// Inclusive; see [crate::category_attribute::CategoryAttributeKind::Number]
+    pub attribute_number_min: Option<f64>,
+    /// Inclusive
+    pub attribute_number_max: Option<f64>,
+    /// Inclusive; see [crate::category_attribute::CategoryAttributeKind::Date]
+    pub attribute_date_min: Option<NaiveDate>,
+    /// Inclusive
+    pub attribute_date_max: Option<NaiveDate>,
+    pub attribute_boolean: Option<bool>,
+    #[serde(default)]
+    pub sort_key: ItemSortKey,
+    /// The order in which items are presented.
+    #[serde(default)]
+    pub sort_direction: Direction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ItemSortKey {
+    #[serde(rename = "price")]
+    Price,
+    #[serde(rename = "priority")]
+    Priority,
+    #[serde(rename = "created")]
+    Created,
+    #[serde(rename = "acquisition_datetime")]
+    AcquisitionDatetime,
+}
+
+impl ItemSearchParameters {
+    pub fn validated(self) -> Result<Self, ShopError> {
+        let has_attribute_value_filter: bool = self.attribute_value.is_some()
+            || self.attribute_number_min.is_some()
+            || self.attribute_number_max.is_some()
+            || self.attribute_date_min.is_some()
+            || self.attribute_date_max.is_some()
+            || self.attribute_boolean.is_some();
+        let has_attribute_key: bool = self.attribute_key.as_ref().is_some_and(|key| !key.trim().is_empty());
+        if has_attribute_value_filter && !has_attribute_key {
+            return Err(ShopError::new("Attribute value filters require an attribute key;"));
+        }
+        Ok(self)
+    }
+}
+
+impl Default for ItemSortKey {
+    fn default() -> Self {
+        ItemSortKey::Created
+    }
+}
+
+impl ItemSortKey {
+    pub fn get_column_name(&self) -> &'static str {
+        match self {
+            ItemSortKey::Price => "price_cents",
+            ItemSortKey::Priority => "priority",
+            ItemSortKey::Created => "created",
+            ItemSortKey::AcquisitionDatetime => "acquisition_datetime",
+        }
+    }
+}
+
+pub const ITEM_CLONE_COUNT_MAX: u32 = 100;
+
+/// Requests copies of an existing item. Each present override replaces the corresponding field of every copy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemCloneParameters {
+    /// Number of copies to create
+    #[serde(default = "ItemCloneParameters::default_count")]
+    pub count: u32,
+    pub condition: Option<u8>,
+    pub price_cents: Option<u32>,
+    pub inventory_location_id: Option<Uuid>,
+}
+
+impl ItemCloneParameters {
+    fn default_count() -> u32 {
+        1
+    }
+
+    pub fn validated(self) -> Result<Self, ShopError> {
+        if self.count == 0 || self.count > ITEM_CLONE_COUNT_MAX {
+            return Err(ShopError::new(&format!("Clone count must be between 1 and {};", ITEM_CLONE_COUNT_MAX)));
+        }
+        if let Some(condition) = self.condition {
+            ItemCondition::try_from_repr(condition)?;
+        }
+        Ok(self)
+    }
+}
+
+/// Crockford base32 symbols, which exclude I, L, O, and U to avoid confusion when read aloud or handwritten.
+const ITEM_CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Crockford check symbols, indexed by the encoded number modulo 37.
+const ITEM_CODE_CHECK_ALPHABET: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+const ITEM_CODE_BODY_LENGTH_MIN: usize = 4;
+const ITEM_CODE_PREFIX_LENGTH_MAX: usize = 3;
+
+/// Creates an item code of the form `{prefix}-{number}{check}`, e.g. `AUS-00011`.
+/// The prefix is the first letters of the intake inventory location's internal name and is not updated if the item moves.
+/// The number is encoded in Crockford base32 and is followed by its Crockford check symbol.
+/// Uniqueness is provided by the number, which should be drawn from the `item_code_sequence`.
+///
+/// The V9 migration backfills existing items with an equivalent SQL implementation.
+pub fn create_item_code(inventory_location_internal_name: &str, number: u64) -> String {
+    let prefix: String = inventory_location_internal_name.chars()
+        .filter(|character| character.is_ascii_alphabetic())
+        .take(ITEM_CODE_PREFIX_LENGTH_MAX)
+        .collect::<String>()
+        .to_ascii_uppercase();
+    let prefix: &str = if prefix.is_empty() { "X" } else { &prefix };
+    format_item_code(prefix, number)
+}
+
+fn format_item_code(prefix: &str, number: u64) -> String {
+    let mut body: Vec<u8> = Vec::new();
+    let mut remainder: u64 = number;
+    while remainder > 0 {
+        body.push(ITEM_CODE_ALPHABET[(remainder % 32) as usize]);
+        remainder /= 32;
+    }
+    while body.len() < ITEM_CODE_BODY_LENGTH_MIN {
+        body.push(b'0');
+    }
+    body.reverse();
+
+    let check: char = ITEM_CODE_CHECK_ALPHABET[(number % 37) as usize] as char;
+    format!("{}-{}{}", prefix, String::from_utf8_lossy(&body), check)
+}
+
+/// Validates the check symbol of an item code and returns its canonical form.
+/// Case is ignored, as are hyphens and leading zeros within the number, and the Crockford aliases (O for 0; I and L for
+/// 1) are accepted.
+pub fn normalize_item_code(code: &str) -> Result<String, ShopError> {
+    let code: String = code.trim().to_ascii_uppercase();
+    let Some((prefix, body)) = code.split_once('-') else {
+        return Err(ShopError::new(&format!("Item code is missing its prefix; [{}]", code)));
+    };
+    if prefix.is_empty() || !prefix.chars().all(|character| character.is_ascii_alphabetic()) {
+        return Err(ShopError::new(&format!("Item code prefix is invalid; [{}]", code)));
+    }
+
+    let body: Vec<u8> = body.bytes()
+        .filter(|byte| *byte != b'-')
+        .map(|byte| match byte {
+            b'O' => b'0',
+            b'I' | b'L' => b'1',
+            _ => byte,
+        })
+        .collect();
+    let Some((check, digits)) = body.split_last() else {
+        return Err(ShopError::new(&format!("Item code is missing its number; [{}]", code)));
+    };
+    if digits.is_empty() || digits.len() > 12 {
+        return Err(ShopError::new(&format!("Item code number has an invalid length; [{}]", code)));
+    }
+
+    let mut number: u64 = 0;
+    for digit in digits {
+        let value: usize = ITEM_CODE_ALPHABET.iter()
+            .position(|symbol| symbol == digit)
+            .ok_or_else(|| ShopError::new(&format!("Item code contains an invalid symbol; [{}]", code)))?;
+        number = number * 32 + value as u64;
+    }
+    if ITEM_CODE_CHECK_ALPHABET[(number % 37) as usize] != *check {
+        return Err(ShopError::new(&format!("Item code check symbol does not match; [{}]", code)));
+    }
+
+    Ok(format_item_code(prefix, number))
+}
+
+/// An item may be referenced by either its ID or its code wherever an item is identified.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemReference {
+    Id(Uuid),
+    Code(String),
+}
+
+impl ItemReference {
+    pub fn parse(value: &str) -> Result<ItemReference, ShopError> {
+        match Uuid::try_parse(value) {
+            Ok(item_id) => Ok(ItemReference::Id(item_id)),
+            Err(_) => Ok(ItemReference::Code(normalize_item_code(value)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUMBERS: [u64; 9] = [0, 1, 31, 32, 36, 37, 1234, 32 * 32 * 32 * 32, u32::MAX as u64];
+
+    #[test]
+    fn validate_transition_permits_remaining_in_the_same_status() {
+        for status in ItemStatus::VARIANTS {
+            assert!(status.validate_transition(status).is_ok(), "{}", status);
+        }
+    }
+
+    #[test]
+    fn validate_transition_permits_exactly_the_successors() {
+        for status in ItemStatus::VARIANTS {
+            for next in ItemStatus::VARIANTS.iter().filter(|next| *next != status) {
+                assert_eq!(
+                    status.validate_transition(next).is_ok(),
+                    status.get_successors().contains(next),
+                    "{} -> {}", status, next,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validate_transition_follows_the_sale_of_an_item() {
+        let path: [ItemStatus; 6] = [
+            ItemStatus::Incomplete,
+            ItemStatus::CompleteUnlisted,
+            ItemStatus::CompleteListed,
+            ItemStatus::PurchaseListed,
+            ItemStatus::PurchasedDelisted,
+            ItemStatus::Shipped,
+        ];
+        for pair in path.windows(2) {
+            assert!(pair[0].validate_transition(&pair[1]).is_ok(), "{} -> {}", pair[0], pair[1]);
+        }
+        assert!(ItemStatus::Shipped.validate_transition(&ItemStatus::Received).is_ok());
+    }
+
+    #[test]
+    fn validate_transition_rejects_skipping_completion_and_leaving_received() {
+        assert!(ItemStatus::Incomplete.validate_transition(&ItemStatus::CompleteListed).is_err());
+        assert!(ItemStatus::Incomplete.validate_transition(&ItemStatus::PurchasedDelisted).is_err());
+        assert!(ItemStatus::Shipped.validate_transition(&ItemStatus::CompleteUnlisted).is_err());
+        for next in ItemStatus::VARIANTS.iter().filter(|next| **next != ItemStatus::Received) {
+            assert!(ItemStatus::Received.validate_transition(next).is_err(), "Received -> {}", next);
+        }
+    }
+
+    #[test]
+    fn validate_transition_names_both_statuses() {
+        let error: ShopError = ItemStatus::Received.validate_transition(&ItemStatus::Incomplete).unwrap_err();
+        assert!(error.message.contains("Received (8) -> Incomplete (0)"), "{}", error.message);
+    }
+
+    #[test]
+    fn create_item_code_formats_prefix_number_and_check_symbol() {
+        assert_eq!(create_item_code("austin", 1), "AUS-00011");
+        assert_eq!(create_item_code("austin", 0), "AUS-00000");
+        // 32 is "10" in base 32 and its check symbol is the 33rd
+        assert_eq!(create_item_code("austin", 32), "AUS-0010*");
+        assert_eq!(create_item_code("austin", 37), "AUS-00150");
+        assert_eq!(create_item_code("austin", 32 * 32 * 32 * 32), "AUS-10000~");
+    }
+
+    #[test]
+    fn create_item_code_prefix_uses_leading_letters() {
+        assert_eq!(create_item_code("1st-floor", 1), "STF-00011");
+        assert_eq!(create_item_code("la", 1), "LA-00011");
+        assert_eq!(create_item_code("2024", 1), "X-00011");
+    }
+
+    #[test]
+    fn normalize_item_code_round_trips() {
+        for number in NUMBERS {
+            let code: String = create_item_code("austin", number);
+            assert_eq!(normalize_item_code(&code).unwrap(), code);
+        }
+    }
+
+    #[test]
+    fn normalize_item_code_accepts_case_hyphens_leading_zeros_and_aliases() {
+        assert_eq!(normalize_item_code(" aus-00011 ").unwrap(), "AUS-00011");
+        assert_eq!(normalize_item_code("AUS-00-01-1").unwrap(), "AUS-00011");
+        assert_eq!(normalize_item_code("AUS-11").unwrap(), "AUS-00011");
+        assert_eq!(normalize_item_code("AUS-OOO1L").unwrap(), "AUS-00011");
+        assert_eq!(normalize_item_code("AUS-000I1").unwrap(), "AUS-00011");
+    }
+
+    #[test]
+    fn normalize_item_code_rejects_every_single_substitution() {
+        for number in NUMBERS {
+            let code: String = create_item_code("austin", number);
+            let (prefix, body) = code.split_once('-').unwrap();
+            for index in 0..body.len() {
+                for symbol in ITEM_CODE_CHECK_ALPHABET.iter().map(|symbol| *symbol as char) {
+                    let original: char = body.as_bytes()[index] as char;
+                    if symbol == original {
+                        continue;
+                    }
+                    let mut substituted: Vec<char> = body.chars().collect();
+                    substituted[index] = symbol;
+                    let substituted: String = format!("{}-{}", prefix, substituted.iter().collect::<String>());
+                    assert!(normalize_item_code(&substituted).is_err(), "{} was accepted for {}", substituted, code);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn normalize_item_code_rejects_every_adjacent_transposition() {
+        for number in NUMBERS {
+            let code: String = create_item_code("austin", number);
+            let (prefix, body) = code.split_once('-').unwrap();
+            let body: Vec<char> = body.chars().collect();
+            for index in 0..body.len() - 1 {
+                if body[index] == body[index + 1] {
+                    continue;
+                }
+                let mut transposed: Vec<char> = body.clone();
+                transposed.swap(index, index + 1);
+                let transposed: String = format!("{}-{}", prefix, transposed.iter().collect::<String>());
+                assert!(normalize_item_code(&transposed).is_err(), "{} was accepted for {}", transposed, code);
+            }
+        }
+    }
+
+    #[test]
+    fn normalize_item_code_rejects_malformed_codes() {
+        assert!(normalize_item_code("00011").is_err());
+        assert!(normalize_item_code("-00011").is_err());
+        assert!(normalize_item_code("A1-00011").is_err());
+        assert!(normalize_item_code("AUS-").is_err());
+        assert!(normalize_item_code("AUS-1").is_err());
+        assert!(normalize_item_code("AUS-0000000000001").is_err());
+        assert!(normalize_item_code("AUS-0U011").is_err());
+    }
+
+    #[test]
+    fn item_reference_parses_ids_and_codes() {
+        let item_id: Uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        assert_eq!(ItemReference::parse(&item_id.to_string()).unwrap(), ItemReference::Id(item_id));
+        assert_eq!(ItemReference::parse("aus-00011").unwrap(), ItemReference::Code("AUS-00011".to_string()));
+        assert!(ItemReference::parse("AUS-00012").is_err());
+    }
+}
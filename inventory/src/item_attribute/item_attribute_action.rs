@@ -0,0 +1,34 @@
+use super::*;
+use crate::category_attribute::{category_attribute_action, CategoryAttribute};
+use crate::error::ShopError;
+use crate::ShopModel;
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// Validates the item attribute against the definitions of the item's product's categories.
+pub async fn create_item_attribute_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item_attribute: ItemAttribute,
+) -> Result<ItemAttribute, ShopError> {
+    let item_attribute: ItemAttribute = type_item_attribute_in_transaction(transaction, item_attribute).await?;
+    item_attribute_db::create_item_attribute(&mut **transaction, &item_attribute.to_entity()).await?;
+    Ok(item_attribute)
+}
+
+/// Canonicalizes and types the item attribute according to the definitions of the item's product's categories.
+/// Fails if the value does not conform to a matching definition.
+pub async fn type_item_attribute(pgpool: &PgPool, item_attribute: ItemAttribute) -> Result<ItemAttribute, ShopError> {
+    let category_attributes: Vec<CategoryAttribute> =
+        category_attribute_action::get_item_category_attributes(pgpool, &item_attribute.item_id).await?;
+    CategoryAttribute::apply_all(&category_attributes, item_attribute)
+}
+
+/// Canonicalizes and types the item attribute according to the definitions of the item's product's categories.
+/// Fails if the value does not conform to a matching definition.
+pub async fn type_item_attribute_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item_attribute: ItemAttribute,
+) -> Result<ItemAttribute, ShopError> {
+    let category_attributes: Vec<CategoryAttribute> =
+        category_attribute_action::get_item_category_attributes(&mut **transaction, &item_attribute.item_id).await?;
+    CategoryAttribute::apply_all(&category_attributes, item_attribute)
+}
@@ -1,7 +1,8 @@
 use super::*;
+use crate::item::item_api;
 use crate::object::JsonHttpResponse;
 use crate::{
-    unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopModel, ShopSerial,
+    try_return, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopModel, ShopSerial,
 };
 use actix_web::web::ServiceConfig;
 use actix_web::{web, HttpResponse, Responder};
@@ -22,7 +23,7 @@ async fn get_item_attribute(
     path: web::Path<(String, String)>,
 ) -> impl Responder {
     let (item_id, key) = path.into_inner();
-    let item_id = unwrap_result_else_400! {Uuid::try_parse(item_id.as_str())};
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &item_id).await);
 
     let item_attribute = unwrap_result_else_500!(
 		item_attribute_db::get_item_attribute(&pgpool, &item_id, &key).await
@@ -37,8 +38,8 @@ async fn get_all_item_attributes(
     pgpool: web::Data<PgPool>,
     path: web::Path<String>,
 ) -> impl Responder {
-    let item_id = unwrap_result_else_400!(Uuid::try_parse(path.into_inner().as_str()));
-    unwrap_result_else_500!(item_attribute_db::get_all_item_attributes(&pgpool, &item_id).await)
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &path.into_inner()).await);
+    unwrap_result_else_500!(item_attribute_db::get_all_item_attributes(pgpool.get_ref(), &item_id).await)
         .iter()
         .map(|attr| attr.to_serial())
         .collect::<Vec<ItemAttributeSerial>>()
@@ -49,9 +50,13 @@ async fn create_item_attribute(
     pgpool: web::Data<PgPool>,
     path: web::Json<ItemAttributeSerial>,
 ) -> impl Responder {
-    let item_attribute = unwrap_result_else_400!(path.into_inner().try_to_model());
+    let item_attribute: ItemAttribute = unwrap_result_else_400!(path.into_inner().try_to_model());
+    let item_attribute: ItemAttribute = match item_attribute_action::type_item_attribute(pgpool.get_ref(), item_attribute).await {
+        Ok(item_attribute) => item_attribute,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
     let query_result = unwrap_result_else_500!(
-		item_attribute_db::create_item_attribute(&pgpool, &item_attribute).await
+		item_attribute_db::create_item_attribute(pgpool.get_ref(), &item_attribute.to_entity()).await
 	);
     HttpResponse::Ok().body(query_result.rows_affected().to_string())
 }
@@ -61,7 +66,7 @@ async fn delete_item_attribute(
     path: web::Path<(String, String)>,
 ) -> impl Responder {
     let (item_id, key) = path.into_inner();
-    let item_id = unwrap_result_else_400!(Uuid::try_parse(item_id.as_str()));
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &item_id).await);
     let query_result = unwrap_result_else_500!(
 		item_attribute_db::delete_item_attribute(&pgpool, &item_id, &key).await
 	);
@@ -1,6 +1,6 @@
 use crate::item_attribute::ItemAttributeEntity;
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool};
 use uuid::Uuid;
 use crate::error::ShopError;
 
@@ -10,7 +10,7 @@ pub async fn get_item_attribute(
     key: &str,
 ) -> Result<Option<ItemAttributeEntity>, ShopError> {
     query_as!(ItemAttributeEntity, "
-        select item_id, key, value, visible, priority
+        select item_id, key, value, visible, priority, value_number, value_date, value_boolean
         from shop.public.item_attribute
         where item_id = $1 and key = $2
     ",
@@ -23,36 +23,90 @@ pub async fn get_item_attribute(
 }
 
 pub async fn get_all_item_attributes(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     item_id: &Uuid,
 ) -> Result<Vec<ItemAttributeEntity>, ShopError> {
     query_as!(ItemAttributeEntity, "
-        select item_id, key, value, visible, priority
+        select item_id, key, value, visible, priority, value_number, value_date, value_boolean
         from shop.public.item_attribute
         where item_id = $1
     ",
 		item_id,
 	)
-        .fetch_all(pgpool)
+        .fetch_all(pgexecutor)
+        .await
+		.map_err(|e| ShopError::from(e))
+}
+
+/// The attributes of every item whose product is directly associated with the category and whose key matches one of
+/// the keys, compared case-insensitively
+pub async fn get_category_item_attributes(
+    pgexecutor: impl PgExecutor<'_>,
+    category_id: &Uuid,
+    keys: &[String],
+) -> Result<Vec<ItemAttributeEntity>, ShopError> {
+    query_as!(ItemAttributeEntity, "
+        select item_attribute.item_id, item_attribute.key, item_attribute.value, item_attribute.visible,
+            item_attribute.priority, item_attribute.value_number, item_attribute.value_date, item_attribute.value_boolean
+        from shop.public.item_attribute
+        inner join shop.public.item on item_attribute.item_id = item.id
+        inner join shop.public.product_category_association on item.product_id = product_category_association.product_id
+        where product_category_association.category_id = $1
+            and lower(trim(item_attribute.key)) = any(select lower(trim(key)) from unnest($2::text[]) as key)
+        order by item_attribute.item_id, item_attribute.key
+    ",
+		category_id,
+		keys,
+	)
+        .fetch_all(pgexecutor)
         .await
 		.map_err(|e| ShopError::from(e))
 }
 
 pub async fn create_item_attribute(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     item_attribute: &ItemAttributeEntity,
 ) -> Result<PgQueryResult, ShopError> {
     query!("
-        insert into shop.public.item_attribute (item_id, key, value, visible, priority)
-        values ($1, $2, $3, $4, $5)
+        insert into shop.public.item_attribute (item_id, key, value, visible, priority, value_number, value_date, value_boolean)
+        values ($1, $2, $3, $4, $5, $6, $7, $8)
     ",
 		item_attribute.item_id,
 		item_attribute.key,
 		item_attribute.value,
 		item_attribute.visible,
 		item_attribute.priority,
+		item_attribute.value_number,
+		item_attribute.value_date,
+		item_attribute.value_boolean,
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
+        .await
+		.map_err(|e| ShopError::from(e))
+}
+
+/// Replaces the attribute stored under the key, which may be respelled
+pub async fn update_item_attribute(
+    pgexecutor: impl PgExecutor<'_>,
+    key: &str,
+    item_attribute: &ItemAttributeEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.item_attribute
+        set (key, value, visible, priority, value_number, value_date, value_boolean) = ($3, $4, $5, $6, $7, $8, $9)
+        where item_id = $1 and key = $2
+    ",
+		item_attribute.item_id,
+		key,
+		item_attribute.key,
+		item_attribute.value,
+		item_attribute.visible,
+		item_attribute.priority,
+		item_attribute.value_number,
+		item_attribute.value_date,
+		item_attribute.value_boolean,
+	)
+        .execute(pgexecutor)
         .await
 		.map_err(|e| ShopError::from(e))
 }
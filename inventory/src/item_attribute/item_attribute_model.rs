@@ -1,6 +1,7 @@
 use crate::error::ShopError;
 use crate::object::JsonHttpResponse;
 use crate::{ShopEntity, ShopModel, ShopSerial};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -11,6 +12,12 @@ pub struct ItemAttributeEntity {
     pub value: String,
     pub visible: bool,
     pub priority: i32,
+    /// Set if the key matches a [crate::category_attribute::CategoryAttributeKind::Number] definition
+    pub value_number: Option<f64>,
+    /// Set if the key matches a [crate::category_attribute::CategoryAttributeKind::Date] definition
+    pub value_date: Option<NaiveDate>,
+    /// Set if the key matches a [crate::category_attribute::CategoryAttributeKind::Boolean] definition
+    pub value_boolean: Option<bool>,
     // constraint pk_item_attribute primary key (item_id, key)
 }
 
@@ -31,9 +38,13 @@ impl ShopModel for ItemAttribute {
             value: self.value.clone(),
             visible: self.visible.clone(),
             priority: self.priority.clone(),
+            value_number: self.value_number.clone(),
+            value_date: self.value_date.clone(),
+            value_boolean: self.value_boolean.clone(),
         }
     }
 
+    /// Typed values are never accepted; they are derived from the category attribute definitions on creation.
     fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
         Ok(ItemAttributeEntity {
             item_id: serial.item_id.clone(),
@@ -41,6 +52,9 @@ impl ShopModel for ItemAttribute {
             value: serial.value.clone(),
             visible: serial.visible.clone(),
             priority: serial.priority.clone(),
+            value_number: None,
+            value_date: None,
+            value_boolean: None,
         })
     }
 
@@ -60,6 +74,12 @@ pub struct ItemAttributeSerial {
     pub value: String,
     pub visible: bool,
     pub priority: i32,
+    #[serde(default)]
+    pub value_number: Option<f64>,
+    #[serde(default)]
+    pub value_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub value_boolean: Option<bool>,
 }
 
 impl ShopSerial for ItemAttributeSerial {
@@ -1,5 +1,6 @@
 pub(crate) mod item_attribute_db;
 
+pub mod item_attribute_action;
 pub mod item_attribute_api;
 pub mod item_attribute_model;
 
@@ -0,0 +1,45 @@
+use crate::item::ItemStatus;
+use crate::item_audit::ItemAudit;
+use crate::object;
+use chrono::Utc;
+use uuid::Uuid;
+
+impl ItemAudit {
+    pub fn new(
+        item_id: Uuid,
+        status_before: ItemStatus,
+        status_after: ItemStatus,
+        initiated_by_admin: bool,
+        note: Option<String>,
+    ) -> ItemAudit {
+        ItemAudit {
+            id: object::random_uuid(),
+            item_id,
+            status_before,
+            status_after,
+            initiated_by_admin,
+            note,
+            deleted: false,
+            created: Utc::now(),
+        }
+    }
+
+    /// Records the deletion of an item. The item's final status is retained as both the before and after status.
+    pub fn new_tombstone(
+        item_id: Uuid,
+        status: ItemStatus,
+        initiated_by_admin: bool,
+        note: Option<String>,
+    ) -> ItemAudit {
+        ItemAudit {
+            id: object::random_uuid(),
+            item_id,
+            status_before: status.clone(),
+            status_after: status,
+            initiated_by_admin,
+            note,
+            deleted: true,
+            created: Utc::now(),
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use super::*;
 use crate::error::ShopError;
-use sqlx::{query_as, PgPool};
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub async fn get_all_item_item_audits(
@@ -10,7 +14,7 @@ pub async fn get_all_item_item_audits(
     query_as!(
 		ItemAuditEntity,
 		"\
-        select id, item_id, status_before, status_after, initiated_by_admin, note, created \
+        select id, item_id, status_before, status_after, initiated_by_admin, note, deleted, created \
         from shop.public.item_audit \
         where item_audit.item_id = $1
     ",
@@ -20,3 +24,45 @@ pub async fn get_all_item_item_audits(
         .await
 		.map_err(|e| ShopError::from(e))
 }
+
+pub async fn create_item_audit(
+    pgexecutor: impl PgExecutor<'_>,
+    item_audit: &ItemAuditEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_audit (id, item_id, status_before, status_after, initiated_by_admin, note, deleted, created)
+        values ($1, $2, $3, $4, $5, $6, $7, $8)
+    ",
+		item_audit.id,
+		item_audit.item_id,
+		item_audit.status_before,
+		item_audit.status_after,
+		item_audit.initiated_by_admin,
+		item_audit.note,
+		item_audit.deleted,
+		item_audit.created,
+	)
+        .execute(pgexecutor)
+        .await
+		.map_err(|e| ShopError::from(e))
+}
+
+/// Most recent audits first
+pub async fn get_all_item_audits_paged_created(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<DateTime<Utc>>,
+) -> Result<(Vec<ItemAuditEntity>, KeysetPaginationResult<DateTime<Utc>>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, item_id, status_before, status_after, initiated_by_admin, note, deleted, created \
+        from shop.public.item_audit \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "created",
+        &Direction::Descending,
+        keyset_pagination_options,
+        |item_audit: &ItemAuditEntity| (item_audit.created, item_audit.id),
+    ).await
+}
@@ -6,7 +6,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, sqlx::FromRow)]
 pub struct ItemAuditEntity {
     pub id: Uuid,
     pub item_id: Uuid,
@@ -14,6 +14,8 @@ pub struct ItemAuditEntity {
     pub status_after: i32,
     pub initiated_by_admin: bool,
     pub note: Option<String>,
+    /// True iff this audit records the deletion of the item, which no longer exists.
+    pub deleted: bool,
     pub created: DateTime<Utc>,
 }
 
@@ -29,6 +31,8 @@ pub struct ItemAudit {
     pub status_after: ItemStatus,
     pub initiated_by_admin: bool,
     pub note: Option<String>,
+    /// True iff this audit records the deletion of the item, which no longer exists.
+    pub deleted: bool,
     pub created: DateTime<Utc>,
 }
 
@@ -44,6 +48,7 @@ impl ShopModel for ItemAudit {
             status_after: self.status_after.clone() as u8,
             initiated_by_admin: self.initiated_by_admin.clone(),
             note: self.note.clone(),
+            deleted: self.deleted.clone(),
             created: self.created.clone(),
         }
     }
@@ -56,6 +61,7 @@ impl ShopModel for ItemAudit {
             status_after: ItemStatus::try_from_repr(serial.status_after)?,
             initiated_by_admin: serial.initiated_by_admin.clone(),
             note: serial.note.clone(),
+            deleted: serial.deleted.clone(),
             created: serial.created.clone(),
         })
     }
@@ -68,6 +74,7 @@ impl ShopModel for ItemAudit {
             status_after: i32::from(self.status_after.clone() as u8),
             initiated_by_admin: self.initiated_by_admin.clone(),
             note: self.note.clone(),
+            deleted: self.deleted.clone(),
             created: self.created.clone(),
         }
     }
@@ -80,6 +87,7 @@ impl ShopModel for ItemAudit {
             status_after: ItemStatus::try_from_repr(entity.status_after as u8)?,
             initiated_by_admin: entity.initiated_by_admin.clone(),
             note: entity.note.clone(),
+            deleted: entity.deleted.clone(),
             created: entity.created.clone(),
         })
     }
@@ -94,6 +102,8 @@ pub struct ItemAuditSerial {
     pub status_after: u8,
     pub initiated_by_admin: bool,
     pub note: Option<String>,
+    #[serde(default)]
+    pub deleted: bool,
     pub created: DateTime<Utc>,
 }
 
@@ -1,5 +1,7 @@
 pub(crate) mod item_audit_db;
 
+pub mod item_audit_api;
 pub mod item_audit_model;
+pub mod item_audit_action;
 
 pub use item_audit_model::*;
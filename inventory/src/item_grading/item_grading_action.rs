@@ -0,0 +1,62 @@
+use super::*;
+use crate::error::ShopError;
+use crate::{ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Includes subgrades.
+pub async fn get_item_grading(pgpool: &PgPool, item_id: &Uuid) -> Result<Option<ItemGrading>, ShopError> {
+    let Some(entity) = item_grading_db::get_item_grading(pgpool, item_id).await? else {
+        return Ok(None);
+    };
+
+    let mut item_grading: ItemGrading = entity.try_to_model()?;
+    item_grading.subgrades = item_grading_db::get_all_item_grading_subgrades(pgpool, &[*item_id]).await?
+        .iter()
+        .map(ItemGradingSubgrade::from)
+        .collect();
+    Ok(Some(item_grading))
+}
+
+/// Gradings of every item of the product. Includes subgrades.
+pub async fn get_all_product_item_gradings(pgpool: &PgPool, product_id: &Uuid) -> Result<Vec<ItemGrading>, ShopError> {
+    let mut item_gradings: Vec<ItemGrading> = item_grading_db::get_all_product_item_gradings(pgpool, product_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let item_ids: Vec<Uuid> = item_gradings.iter().map(|item_grading| item_grading.item_id).collect();
+    let subgrade_entities: Vec<ItemGradingSubgradeEntity> = item_grading_db::get_all_item_grading_subgrades(pgpool, &item_ids).await?;
+    for item_grading in &mut item_gradings {
+        item_grading.subgrades = subgrade_entities.iter()
+            .filter(|entity| entity.item_id == item_grading.item_id)
+            .map(ItemGradingSubgrade::from)
+            .collect();
+    }
+    Ok(item_gradings)
+}
+
+/// Replaces any existing grading of the item, including all of its subgrades. The original creation time is retained.
+pub async fn create_or_replace_item_grading(pgpool: &PgPool, mut item_grading: ItemGrading) -> Result<ItemGrading, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    if let Some(current) = item_grading_db::get_item_grading(&mut *transaction, &item_grading.item_id).await? {
+        item_grading.created = current.created;
+    }
+    item_grading.updated = Utc::now();
+    item_grading_db::create_or_replace_item_grading(&mut *transaction, &item_grading.to_entity()).await?;
+
+    item_grading_db::delete_all_item_grading_subgrades(&mut *transaction, &item_grading.item_id).await?;
+    for subgrade in &item_grading.subgrades {
+        let entity = ItemGradingSubgradeEntity {
+            item_id: item_grading.item_id,
+            name: subgrade.name.clone(),
+            grade_tenths: i32::from(subgrade.grade_tenths),
+        };
+        item_grading_db::create_item_grading_subgrade(&mut *transaction, &entity).await?;
+    }
+
+    transaction.commit().await?;
+    Ok(item_grading)
+}
@@ -0,0 +1,45 @@
+use super::*;
+use crate::item::item_db;
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_500, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/item_grading")
+            .route("", web::get().to(get_all_product_item_gradings))
+            .route("", web::put().to(create_or_replace_item_grading))
+    );
+}
+
+async fn get_all_product_item_gradings(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ItemGradingSearchParameters>,
+) -> HttpResponse {
+    let item_gradings: Vec<ItemGrading> = unwrap_result_else_500!(
+        item_grading_action::get_all_product_item_gradings(&pgpool, &parameters.into_inner().product_id).await
+    );
+    item_gradings.iter()
+        .map(|item_grading| item_grading.to_serial())
+        .collect::<Vec<ItemGradingSerial>>()
+        .to_http_response()
+}
+
+/// The item ID is taken from the body so that a single admin form can grade any item.
+async fn create_or_replace_item_grading(
+    pgpool: web::Data<PgPool>,
+    body: web::Json<ItemGradingSerial>,
+) -> HttpResponse {
+    let item_grading: ItemGrading = match body.into_inner().try_to_model() {
+        Ok(item_grading) => item_grading,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_grading.item_id).await));
+
+    match item_grading_action::create_or_replace_item_grading(&pgpool, item_grading).await {
+        Ok(item_grading) => item_grading.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+use crate::error::ShopError;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_item_grading(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<ItemGradingEntity>, ShopError> {
+    query_as!(ItemGradingEntity, "
+        select item_id, company, grade_tenths, certificate_number, grade_date, created, updated
+        from shop.public.item_grading
+        where item_id = $1
+    ",
+		item_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_product_item_gradings(
+    pgpool: &PgPool,
+    product_id: &Uuid,
+) -> Result<Vec<ItemGradingEntity>, ShopError> {
+    query_as!(ItemGradingEntity, "
+        select item_grading.item_id, item_grading.company, item_grading.grade_tenths, item_grading.certificate_number, item_grading.grade_date, item_grading.created, item_grading.updated
+        from shop.public.item_grading
+            inner join shop.public.item on item_grading.item_id = item.id
+        where item.product_id = $1
+    ",
+		product_id
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_item_grading_subgrades(
+    pgexecutor: impl PgExecutor<'_>,
+    item_ids: &[Uuid],
+) -> Result<Vec<ItemGradingSubgradeEntity>, ShopError> {
+    query_as!(ItemGradingSubgradeEntity, "
+        select item_id, name, grade_tenths
+        from shop.public.item_grading_subgrade
+        where item_id = any($1)
+        order by item_id, name
+    ",
+		item_ids
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_or_replace_item_grading(
+    pgexecutor: impl PgExecutor<'_>,
+    item_grading: &ItemGradingEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_grading (item_id, company, grade_tenths, certificate_number, grade_date, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7)
+        on conflict (item_id) do update
+        set (company, grade_tenths, certificate_number, grade_date, updated)
+            = (excluded.company, excluded.grade_tenths, excluded.certificate_number, excluded.grade_date, excluded.updated)
+    ",
+		item_grading.item_id,
+		item_grading.company,
+		item_grading.grade_tenths,
+		item_grading.certificate_number,
+		item_grading.grade_date,
+		item_grading.created,
+		item_grading.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_item_grading_subgrade(
+    pgexecutor: impl PgExecutor<'_>,
+    item_grading_subgrade: &ItemGradingSubgradeEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_grading_subgrade (item_id, name, grade_tenths)
+        values ($1, $2, $3)
+    ",
+		item_grading_subgrade.item_id,
+		item_grading_subgrade.name,
+		item_grading_subgrade.grade_tenths,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_all_item_grading_subgrades(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.item_grading_subgrade
+        where item_id = $1
+    ",
+		item_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Subgrades are deleted by cascade.
+pub async fn delete_item_grading(pgpool: &PgPool, item_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.item_grading
+        where item_id = $1
+    ",
+		item_id
+	)
+        .execute(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
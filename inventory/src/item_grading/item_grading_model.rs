@@ -0,0 +1,300 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+/// Certified grades issued by the AFA (Action Figure Authority), whose overall scale runs from 10 to 100 in steps of 5
+const AFA_GRADE_TENTHS: [u16; 19] = [100, 150, 200, 250, 300, 350, 400, 450, 500, 550, 600, 650, 700, 750, 800, 850, 900, 950, 1000];
+const AFA_SUBGRADE_NAMES: [&str; 3] = ["card", "blister", "figure"];
+/// Certified grades issued by the CGC (Certified Guaranty Company) on its 10-point scale
+const CGC_GRADE_TENTHS: [u16; 25] = [5, 10, 15, 18, 20, 25, 30, 35, 40, 45, 50, 55, 60, 65, 70, 75, 80, 85, 90, 92, 94, 96, 98, 99, 100];
+const CGC_SUBGRADE_TENTHS: [u16; 19] = [10, 15, 20, 25, 30, 35, 40, 45, 50, 55, 60, 65, 70, 75, 80, 85, 90, 95, 100];
+const CGC_SUBGRADE_NAMES: [&str; 4] = ["centering", "corners", "edges", "surface"];
+
+/// A third-party grade certifying the condition of an encapsulated item
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemGradingEntity {
+    pub item_id: Uuid,
+    pub company: i32,
+    pub grade_tenths: i32,
+    pub certificate_number: String,
+    pub grade_date: Option<NaiveDate>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for ItemGradingEntity {
+    type Model = ItemGrading;
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemGradingSubgradeEntity {
+    pub item_id: Uuid,
+    pub name: String,
+    pub grade_tenths: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ItemGrading {
+    pub item_id: Uuid,
+    pub company: GradingCompany,
+    /// Tenths of a point, in order to represent both the AFA and CGC scales exactly
+    pub grade_tenths: u16,
+    /// Normalized by [GradingCompany::normalize_certificate_number]
+    pub certificate_number: String,
+    pub grade_date: Option<NaiveDate>,
+    /// Stored separately from the grading entity; empty when converted from an entity alone
+    pub subgrades: Vec<ItemGradingSubgrade>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemGradingSubgrade {
+    /// One of [GradingCompany::get_subgrade_names]
+    pub name: String,
+    pub grade_tenths: u16,
+}
+
+impl ItemGrading {
+    /// e.g. "AFA 85" or "CGC 9.8"
+    pub fn get_summary(&self) -> String {
+        format!("{} {}", self.company.get_abbreviation(), self.company.format_grade(self.grade_tenths))
+    }
+
+    /// A sentence suitable for a listing description, e.g. "Graded AFA 85 (card 85, blister 80, figure 85); Certificate number 12345678."
+    pub fn get_description(&self) -> String {
+        let mut description: String = format!("Graded {}", self.get_summary());
+        if !self.subgrades.is_empty() {
+            let subgrades: Vec<String> = self.subgrades.iter()
+                .map(|subgrade| format!("{} {}", subgrade.name, self.company.format_grade(subgrade.grade_tenths)))
+                .collect();
+            description.push_str(&format!(" ({})", subgrades.join(", ")));
+        }
+        description.push_str(&format!("; Certificate number {}.", self.certificate_number));
+        description
+    }
+}
+
+impl ShopModel for ItemGrading {
+    type Entity = ItemGradingEntity;
+    type Serial = ItemGradingSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        ItemGradingSerial {
+            item_id: self.item_id.clone(),
+            company: self.company.clone() as u8,
+            grade: f64::from(self.grade_tenths) / 10.0,
+            certificate_number: self.certificate_number.clone(),
+            grade_date: self.grade_date.clone(),
+            subgrades: self.subgrades.iter()
+                .map(|subgrade| ItemGradingSubgradeSerial {
+                    name: subgrade.name.clone(),
+                    grade: f64::from(subgrade.grade_tenths) / 10.0,
+                })
+                .collect(),
+            summary: self.get_summary(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    /// Validates the grade, subgrades, and certificate number against the grading company's scale.
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let company: GradingCompany = GradingCompany::try_from_repr(serial.company)?;
+
+        let grade_tenths: u16 = to_grade_tenths(serial.grade)?;
+        company.validate_grade(grade_tenths)?;
+
+        let mut subgrades: Vec<ItemGradingSubgrade> = Vec::with_capacity(serial.subgrades.len());
+        for subgrade in &serial.subgrades {
+            let name: String = subgrade.name.trim().to_lowercase();
+            if !company.get_subgrade_names().contains(&name.as_str()) {
+                return Err(ShopError::new(&format!(
+                    "Invalid {} subgrade; Expected one of {:?}; [{}]",
+                    company.get_abbreviation(),
+                    company.get_subgrade_names(),
+                    subgrade.name,
+                )));
+            }
+            if subgrades.iter().any(|existing: &ItemGradingSubgrade| existing.name == name) {
+                return Err(ShopError::new(&format!("Duplicate subgrade; [{}]", name)));
+            }
+            let grade_tenths: u16 = to_grade_tenths(subgrade.grade)?;
+            company.validate_subgrade(grade_tenths)?;
+            subgrades.push(ItemGradingSubgrade { name, grade_tenths });
+        }
+
+        Ok(ItemGrading {
+            item_id: serial.item_id.clone(),
+            company: company.clone(),
+            grade_tenths,
+            certificate_number: company.normalize_certificate_number(&serial.certificate_number)?,
+            grade_date: serial.grade_date.clone(),
+            subgrades,
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        ItemGradingEntity {
+            item_id: self.item_id.clone(),
+            company: self.company.clone() as i32,
+            grade_tenths: i32::from(self.grade_tenths),
+            certificate_number: self.certificate_number.clone(),
+            grade_date: self.grade_date.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(ItemGrading {
+            item_id: entity.item_id.clone(),
+            company: GradingCompany::try_from_repr(entity.company.clone() as u8)?,
+            grade_tenths: entity.grade_tenths as u16,
+            certificate_number: entity.certificate_number.clone(),
+            grade_date: entity.grade_date.clone(),
+            subgrades: Vec::new(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+impl From<&ItemGradingSubgradeEntity> for ItemGradingSubgrade {
+    fn from(entity: &ItemGradingSubgradeEntity) -> Self {
+        ItemGradingSubgrade {
+            name: entity.name.clone(),
+            grade_tenths: entity.grade_tenths as u16,
+        }
+    }
+}
+
+fn to_grade_tenths(grade: f64) -> Result<u16, ShopError> {
+    let tenths: f64 = (grade * 10.0).round();
+    if !grade.is_finite() || (grade * 10.0 - tenths).abs() > 1e-6 || !(0.0..=f64::from(u16::MAX)).contains(&tenths) {
+        return Err(ShopError::new(&format!("Grade must be a non-negative number with at most one decimal place; [{}]", grade)));
+    }
+    Ok(tenths as u16)
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum GradingCompany {
+    /// Action Figure Authority
+    Afa = 0,
+    /// Certified Guaranty Company
+    Cgc,
+}
+
+try_from_repr!(GradingCompany<u8>);
+
+impl GradingCompany {
+    pub fn get_abbreviation(&self) -> &'static str {
+        match self {
+            GradingCompany::Afa => "AFA",
+            GradingCompany::Cgc => "CGC",
+        }
+    }
+
+    pub fn get_subgrade_names(&self) -> &'static [&'static str] {
+        match self {
+            GradingCompany::Afa => &AFA_SUBGRADE_NAMES,
+            GradingCompany::Cgc => &CGC_SUBGRADE_NAMES,
+        }
+    }
+
+    pub fn validate_grade(&self, grade_tenths: u16) -> Result<(), ShopError> {
+        let grades: &[u16] = match self {
+            GradingCompany::Afa => &AFA_GRADE_TENTHS,
+            GradingCompany::Cgc => &CGC_GRADE_TENTHS,
+        };
+        match grades.contains(&grade_tenths) {
+            true => Ok(()),
+            false => Err(ShopError::new(&format!("Invalid {} grade; [{}]", self.get_abbreviation(), self.format_grade(grade_tenths)))),
+        }
+    }
+
+    /// AFA subgrades share the overall scale. CGC subgrades run from 1 to 10 in steps of 0.5.
+    pub fn validate_subgrade(&self, grade_tenths: u16) -> Result<(), ShopError> {
+        let grades: &[u16] = match self {
+            GradingCompany::Afa => &AFA_GRADE_TENTHS,
+            GradingCompany::Cgc => &CGC_SUBGRADE_TENTHS,
+        };
+        match grades.contains(&grade_tenths) {
+            true => Ok(()),
+            false => Err(ShopError::new(&format!("Invalid {} subgrade; [{}]", self.get_abbreviation(), self.format_grade(grade_tenths)))),
+        }
+    }
+
+    /// AFA grades are whole numbers. CGC grades always show one decimal place.
+    pub fn format_grade(&self, grade_tenths: u16) -> String {
+        match self {
+            GradingCompany::Afa if grade_tenths.is_multiple_of(10) => (grade_tenths / 10).to_string(),
+            _ => format!("{}.{}", grade_tenths / 10, grade_tenths % 10),
+        }
+    }
+
+    /// AFA serial numbers have 5 to 10 digits. CGC certification numbers have 10 digits and are often printed with a
+    /// hyphen before the final 3 digits, which is removed.
+    pub fn normalize_certificate_number(&self, certificate_number: &str) -> Result<String, ShopError> {
+        let normalized: String = certificate_number.trim().replace('-', "");
+        let valid: bool = normalized.chars().all(|c| c.is_ascii_digit()) && match self {
+            GradingCompany::Afa => (5..=10).contains(&normalized.len()),
+            GradingCompany::Cgc => normalized.len() == 10,
+        };
+        match valid {
+            true => Ok(normalized),
+            false => Err(ShopError::new(&format!("Invalid {} certificate number; [{}]", self.get_abbreviation(), certificate_number))),
+        }
+    }
+}
+
+impl Display for GradingCompany {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.get_abbreviation(), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemGradingSerial {
+    pub item_id: Uuid,
+    pub company: u8,
+    /// e.g. 85 for AFA or 9.8 for CGC
+    pub grade: f64,
+    pub certificate_number: String,
+    pub grade_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub subgrades: Vec<ItemGradingSubgradeSerial>,
+    /// See [ItemGrading::get_summary]; ignored on input
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemGradingSubgradeSerial {
+    pub name: String,
+    pub grade: f64,
+}
+
+impl ShopSerial for ItemGradingSerial {
+    type Model = ItemGrading;
+}
+
+impl JsonHttpResponse for ItemGradingSerial {}
+impl JsonHttpResponse for Vec<ItemGradingSerial> {}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemGradingSearchParameters {
+    pub product_id: Uuid,
+}
@@ -0,0 +1,7 @@
+pub(crate) mod item_grading_db;
+
+pub mod item_grading_api;
+pub mod item_grading_model;
+pub mod item_grading_action;
+
+pub use item_grading_model::*;
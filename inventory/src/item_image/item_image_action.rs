@@ -8,7 +8,7 @@ use sqlx::PgPool;
 use std::fs;
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 impl ItemImage {
@@ -45,48 +45,72 @@ impl ItemImage {
         )
     }
 
+    pub fn delete_image_file(&self) -> Result<(), ShopError> {
+        let path: PathBuf = self.get_item_image_path()?;
+        fs::remove_file(path)
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))
+    }
+
+    /// Copies the image file of the given image to the path of this image.
+    pub fn copy_image_file(&self, source: &ItemImage) -> Result<(), ShopError> {
+        fs::copy(source.get_item_image_path()?, self.get_item_image_path()?)
+            .map(|_| ())
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))
+    }
+
     /// If an error is returned, any created file will be deleted before returning.
     pub async fn store_image_file(&self, payload: &mut Payload) -> Result<(), ShopError> {
-        self.store_image_file_impl(payload).await
-            .inspect_err(|e| self.store_image_file_error_handler(e))
+        store_image_file(&self.get_item_image_path()?, payload).await
     }
+}
 
-    async fn store_image_file_impl(&self, payload: &mut Payload) -> Result<(), ShopError> {
-        let image_path: PathBuf = self.get_item_image_path()?;
-        let mut image_file: File = File::create_new(&image_path)
-            .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
+/// Writes the payload to a new image file at the path. Shared by item and [crate::bundle::BundleImage] images.
+/// If an error is returned, any created file will be deleted before returning.
+pub async fn store_image_file(image_path: &Path, payload: &mut Payload) -> Result<(), ShopError> {
+    store_image_file_impl(image_path, payload, None).await
+        .inspect_err(|e| store_image_file_error_handler(image_path, e))
+}
+
+/// As [store_image_file], but fails once the payload exceeds the given number of bytes.
+pub async fn store_image_file_limited(image_path: &Path, payload: &mut Payload, size_max: usize) -> Result<(), ShopError> {
+    store_image_file_impl(image_path, payload, Some(size_max)).await
+        .inspect_err(|e| store_image_file_error_handler(image_path, e))
+}
+
+async fn store_image_file_impl(image_path: &Path, payload: &mut Payload, size_max: Option<usize>) -> Result<(), ShopError> {
+    let mut image_file: File = File::create_new(image_path)
+        .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
 
-        while let Some(chunk) = payload.next().await {
-            let chunk: Bytes = chunk
-                .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
+    let mut size: usize = 0;
+    while let Some(chunk) = payload.next().await {
+        let chunk: Bytes = chunk
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
 
-            image_file.write_all(&chunk)
-                .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
+        size += chunk.len();
+        if let Some(size_max) = size_max
+            && size > size_max
+        {
+            return Err(ShopError::new(&format!("Image file exceeds the maximum size; [{} bytes]", size_max)));
         }
-        Ok(())
+
+        image_file.write_all(&chunk)
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))?;
     }
+    Ok(())
+}
 
-    fn store_image_file_error_handler(&self, e: &ShopError) {
-        log::error!("Failed to store image file; Attempting to delete the file; {}", e);
-        let path: PathBuf = match self.get_item_image_path() {
-            Ok(path) => path,
-            Err(e) => {
-                log::warn!("File not deleted; {}", e);
-                return;
-            }
-        };
-        let remove_result: std::io::Result<()> = fs::remove_file(&path);
-        if let Err(e) = remove_result {
-            log::warn!("FIle not deleted; {}", e);
-        }
+fn store_image_file_error_handler(image_path: &Path, e: &ShopError) {
+    log::error!("Failed to store image file; Attempting to delete the file; {}", e);
+    let remove_result: std::io::Result<()> = fs::remove_file(image_path);
+    if let Err(e) = remove_result {
+        log::warn!("FIle not deleted; {}", e);
     }
 }
 
 pub async fn delete_item_image(pgpool: &PgPool, item_image: &ItemImage) -> Result<(), ShopError> {
     item_image_db::delete_item_image(pgpool, &item_image.id).await?;
 
-    let path: PathBuf = item_image.get_item_image_path()?;
-    fs::remove_file(path)
+    item_image.delete_image_file()
         .map_err(|e| ShopError::from_error("Item image DB record was deleted, but the image file was not", Box::new(e)))?;
 
     Ok(())
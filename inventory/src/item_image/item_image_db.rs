@@ -1,7 +1,7 @@
 use super::*;
 use crate::error::ShopError;
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool};
 use uuid::Uuid;
 
 pub async fn get_item_image(
@@ -23,7 +23,7 @@ pub async fn get_item_image(
 }
 
 pub async fn create_item_image(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     item_image: &ItemImageEntity,
 ) -> Result<PgQueryResult, ShopError> {
     query!(
@@ -37,7 +37,7 @@ pub async fn create_item_image(
 		item_image.priority,
 		item_image.original_file_name,
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
@@ -59,7 +59,7 @@ pub async fn delete_item_image(
 }
 
 pub async fn get_all_item_images(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     item_id: &Uuid,
 ) -> Result<Vec<ItemImageEntity>, ShopError> {
     query_as!(ItemImageEntity, "
@@ -69,7 +69,7 @@ pub async fn get_all_item_images(
     ",
 		item_id
 	)
-        .fetch_all(pgpool)
+        .fetch_all(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
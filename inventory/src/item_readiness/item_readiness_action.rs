@@ -0,0 +1,123 @@
+use super::*;
+use crate::category::Category;
+use crate::category_attribute::{category_attribute_action, CategoryAttribute};
+use crate::error::ShopError;
+use crate::item::{Item, ItemStatus};
+use crate::item_attribute::{item_attribute_db, ItemAttribute};
+use crate::item_image::{item_image_db, ItemImage};
+use crate::product::{product_db, Product};
+use crate::{ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+
+pub async fn get_all_item_readiness_rules(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<ItemReadinessRule>, ShopError> {
+    item_readiness_db::get_all_item_readiness_rules(pgexecutor).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn create_item_readiness_rule(
+    pgpool: &PgPool,
+    mut item_readiness_rule: ItemReadinessRule,
+) -> Result<ItemReadinessRule, ShopError> {
+    item_readiness_rule.created = Utc::now();
+    item_readiness_rule.updated = item_readiness_rule.created;
+    item_readiness_db::create_item_readiness_rule(pgpool, &item_readiness_rule.to_entity()).await?;
+    Ok(item_readiness_rule)
+}
+
+/// Replaces every field of the current rule except for the creation time.
+pub async fn update_item_readiness_rule(
+    pgpool: &PgPool,
+    current: &ItemReadinessRule,
+    mut item_readiness_rule: ItemReadinessRule,
+) -> Result<ItemReadinessRule, ShopError> {
+    item_readiness_rule.id = current.id;
+    item_readiness_rule.created = current.created;
+    item_readiness_rule.updated = Utc::now();
+    item_readiness_db::update_item_readiness_rule(pgpool, &item_readiness_rule.to_entity()).await?;
+    Ok(item_readiness_rule)
+}
+
+pub async fn get_item_readiness(pgpool: &PgPool, item: &Item) -> Result<ItemReadiness, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    let item_readiness: ItemReadiness = evaluate_item_readiness_in_transaction(&mut transaction, item).await?;
+    transaction.commit().await?;
+    Ok(item_readiness)
+}
+
+/// Evaluates the item as given, which may differ from the stored item, against its stored product, categories, images,
+/// attributes, and the attribute definitions of the categories.
+pub async fn evaluate_item_readiness_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item: &Item,
+) -> Result<ItemReadiness, ShopError> {
+    let item_readiness_rules: Vec<ItemReadinessRule> = get_all_item_readiness_rules(&mut **transaction).await?;
+    let product: Product = product_db::get_product(&mut **transaction, &item.product_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Product not found; [{}]", item.product_id)))?
+        .try_to_model()?;
+    let categories: Vec<Category> = product_db::get_product_categories(&mut **transaction, &item.product_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    let item_images: Vec<ItemImage> = item_image_db::get_all_item_images(&mut **transaction, &item.id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    let item_attributes: Vec<ItemAttribute> = item_attribute_db::get_all_item_attributes(&mut **transaction, &item.id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    let category_attributes: Vec<CategoryAttribute> =
+        category_attribute_action::get_product_category_attributes(&mut **transaction, &item.product_id).await?;
+
+    let subject: ItemReadinessSubject = ItemReadinessSubject {
+        item,
+        product: &product,
+        categories: &categories,
+        item_images: &item_images,
+        item_attributes: &item_attributes,
+        category_attributes: &category_attributes,
+    };
+    Ok(ItemReadiness::evaluate(&subject, &item_readiness_rules))
+}
+
+/// Fails with every blocking issue unless the item is ready to be completed.
+pub async fn validate_item_ready(pgpool: &PgPool, item: &Item) -> Result<(), ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    validate_item_ready_in_transaction(&mut transaction, item).await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Fails with every blocking issue unless the item is ready to be completed.
+pub async fn validate_item_ready_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item: &Item,
+) -> Result<(), ShopError> {
+    let item_readiness: ItemReadiness = evaluate_item_readiness_in_transaction(transaction, item).await?;
+    if item_readiness.ready {
+        return Ok(());
+    }
+    let messages: Vec<&str> = item_readiness.blockers.iter()
+        .map(|issue| issue.message.as_str())
+        .collect();
+    Err(ShopError::new(&format!("Item is not ready to be completed; [{}]; {}", item.id, messages.join("; "))))
+}
+
+/// Completing an incomplete or missing item requires it to be ready, since a missing item may never have been
+/// completed; every other status transition is unaffected.
+pub async fn validate_item_completion_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item: &Item,
+    next: &ItemStatus,
+) -> Result<(), ShopError> {
+    match (&item.status, next) {
+        (ItemStatus::Incomplete | ItemStatus::Missing, ItemStatus::CompleteUnlisted) => {
+            validate_item_ready_in_transaction(transaction, item).await
+        }
+        _ => Ok(()),
+    }
+}
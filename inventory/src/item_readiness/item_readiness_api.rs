@@ -0,0 +1,89 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::postgres::PgQueryResult;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/item_readiness_rule")
+            .route("", web::get().to(get_all_item_readiness_rules))
+            .route("", web::post().to(create_item_readiness_rule))
+            .route("/{item_readiness_rule_id}", web::get().to(get_item_readiness_rule))
+            .route("/{item_readiness_rule_id}", web::put().to(update_item_readiness_rule))
+            .route("/{item_readiness_rule_id}", web::delete().to(delete_item_readiness_rule))
+    );
+}
+
+async fn get_all_item_readiness_rules(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let item_readiness_rules: Vec<ItemReadinessRule> = unwrap_result_else_500!(
+        item_readiness_action::get_all_item_readiness_rules(pgpool.get_ref()).await
+    );
+    item_readiness_rules.iter()
+        .map(|item_readiness_rule| item_readiness_rule.to_serial())
+        .collect::<Vec<ItemReadinessRuleSerial>>()
+        .to_http_response()
+}
+
+async fn create_item_readiness_rule(
+    pgpool: web::Data<PgPool>,
+    body: web::Json<ItemReadinessRuleSerial>,
+) -> HttpResponse {
+    let item_readiness_rule: ItemReadinessRule = match body.into_inner().try_to_model() {
+        Ok(item_readiness_rule) => item_readiness_rule,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    match item_readiness_action::create_item_readiness_rule(&pgpool, item_readiness_rule).await {
+        Ok(item_readiness_rule) => item_readiness_rule.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_item_readiness_rule(
+    pgpool: web::Data<PgPool>,
+    item_readiness_rule_id: web::Path<String>,
+) -> HttpResponse {
+    let item_readiness_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&item_readiness_rule_id.into_inner()));
+    let item_readiness_rule: ItemReadinessRule = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        item_readiness_db::get_item_readiness_rule(pgpool.get_ref(), &item_readiness_rule_id).await
+    )).try_to_model());
+    item_readiness_rule.to_serial().to_http_response()
+}
+
+async fn update_item_readiness_rule(
+    pgpool: web::Data<PgPool>,
+    item_readiness_rule_id: web::Path<String>,
+    body: web::Json<ItemReadinessRuleSerial>,
+) -> HttpResponse {
+    let item_readiness_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&item_readiness_rule_id.into_inner()));
+    let item_readiness_rule: ItemReadinessRule = match body.into_inner().try_to_model() {
+        Ok(item_readiness_rule) => item_readiness_rule,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: ItemReadinessRule = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        item_readiness_db::get_item_readiness_rule(pgpool.get_ref(), &item_readiness_rule_id).await
+    )).try_to_model());
+
+    match item_readiness_action::update_item_readiness_rule(&pgpool, &current, item_readiness_rule).await {
+        Ok(item_readiness_rule) => item_readiness_rule.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_item_readiness_rule(
+    pgpool: web::Data<PgPool>,
+    item_readiness_rule_id: web::Path<String>,
+) -> HttpResponse {
+    let item_readiness_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&item_readiness_rule_id.into_inner()));
+    let query_result: PgQueryResult = unwrap_result_else_500!(
+        item_readiness_db::delete_item_readiness_rule(pgpool.get_ref(), &item_readiness_rule_id).await
+    );
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
@@ -0,0 +1,97 @@
+use super::*;
+use crate::error::ShopError;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor};
+use uuid::Uuid;
+
+/// Oldest rules first
+pub async fn get_all_item_readiness_rules(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<ItemReadinessRuleEntity>, ShopError> {
+    query_as!(ItemReadinessRuleEntity, "
+        select id, display_name, enabled, kind, blocking, category_id, minimum_count, attribute_key, created, updated
+        from shop.public.item_readiness_rule
+        order by created, id
+    ")
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_item_readiness_rule(
+    pgexecutor: impl PgExecutor<'_>,
+    item_readiness_rule_id: &Uuid,
+) -> Result<Option<ItemReadinessRuleEntity>, ShopError> {
+    query_as!(ItemReadinessRuleEntity, "
+        select id, display_name, enabled, kind, blocking, category_id, minimum_count, attribute_key, created, updated
+        from shop.public.item_readiness_rule
+        where id = $1
+    ",
+		item_readiness_rule_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_item_readiness_rule(
+    pgexecutor: impl PgExecutor<'_>,
+    item_readiness_rule: &ItemReadinessRuleEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_readiness_rule (id, display_name, enabled, kind, blocking, category_id, minimum_count, attribute_key, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+    ",
+		item_readiness_rule.id,
+		item_readiness_rule.display_name,
+		item_readiness_rule.enabled,
+		item_readiness_rule.kind,
+		item_readiness_rule.blocking,
+		item_readiness_rule.category_id,
+		item_readiness_rule.minimum_count,
+		item_readiness_rule.attribute_key,
+		item_readiness_rule.created,
+		item_readiness_rule.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The creation time is never changed.
+pub async fn update_item_readiness_rule(
+    pgexecutor: impl PgExecutor<'_>,
+    item_readiness_rule: &ItemReadinessRuleEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.item_readiness_rule
+        set (display_name, enabled, kind, blocking, category_id, minimum_count, attribute_key, updated) = ($2, $3, $4, $5, $6, $7, $8, $9)
+        where id = $1
+    ",
+		item_readiness_rule.id,
+		item_readiness_rule.display_name,
+		item_readiness_rule.enabled,
+		item_readiness_rule.kind,
+		item_readiness_rule.blocking,
+		item_readiness_rule.category_id,
+		item_readiness_rule.minimum_count,
+		item_readiness_rule.attribute_key,
+		item_readiness_rule.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_item_readiness_rule(
+    pgexecutor: impl PgExecutor<'_>,
+    item_readiness_rule_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.item_readiness_rule
+        where id = $1
+    ",
+		item_readiness_rule_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
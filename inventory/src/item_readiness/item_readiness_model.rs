@@ -0,0 +1,529 @@
+use crate::category::Category;
+use crate::category_attribute::CategoryAttribute;
+use crate::error::ShopError;
+use crate::item::Item;
+use crate::item_attribute::ItemAttribute;
+use crate::item_image::ItemImage;
+use crate::object::JsonHttpResponse;
+use crate::product::Product;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemReadinessRuleEntity {
+    pub id: Uuid,
+    pub display_name: String,
+    pub enabled: bool,
+    pub kind: i32,
+    pub blocking: bool,
+    pub category_id: Option<Uuid>,
+    pub minimum_count: Option<i32>,
+    pub attribute_key: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for ItemReadinessRuleEntity {
+    type Model = ItemReadinessRule;
+}
+
+/// A check an item must pass before it is complete, e.g. "at least 3 images" or "figures need a Year attribute". An
+/// item can move from [crate::item::ItemStatus::Incomplete] or [crate::item::ItemStatus::Missing] to
+/// [crate::item::ItemStatus::CompleteUnlisted] only once it passes every enabled, blocking rule which applies to it.
+#[derive(Debug, Clone)]
+pub struct ItemReadinessRule {
+    pub id: Uuid,
+    pub display_name: String,
+    /// Disabled rules are never evaluated
+    pub enabled: bool,
+    pub kind: ItemReadinessRuleKind,
+    /// Failures of non-blocking rules only lower the readiness score
+    pub blocking: bool,
+    /// Limits the rule to items whose product is directly associated with the category
+    pub category_id: Option<Uuid>,
+    /// Required by [ItemReadinessRuleKind::ImageCount]
+    pub minimum_count: Option<u32>,
+    /// Required by [ItemReadinessRuleKind::Attribute]
+    pub attribute_key: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ItemReadinessRule {
+    pub fn applies_to(&self, subject: &ItemReadinessSubject) -> bool {
+        self.enabled && self.category_id.is_none_or(|category_id| {
+            subject.categories.iter().any(|category| category.id == category_id)
+        })
+    }
+
+    /// Describes why the item fails the rule. None if the item passes.
+    pub fn check(&self, subject: &ItemReadinessSubject) -> Option<String> {
+        match self.kind {
+            ItemReadinessRuleKind::ImageCount => {
+                let minimum_count: usize = self.minimum_count.unwrap_or(1) as usize;
+                match subject.item_images.len() < minimum_count {
+                    true => Some(format!("Item has {} of at least {} images", subject.item_images.len(), minimum_count)),
+                    false => None,
+                }
+            }
+            ItemReadinessRuleKind::Attribute => {
+                let attribute_key: &str = self.attribute_key.as_deref().unwrap_or_default();
+                let present: bool = subject.item_attributes.iter().any(|item_attribute| {
+                    item_attribute.key.trim().eq_ignore_ascii_case(attribute_key) && !item_attribute.value.trim().is_empty()
+                });
+                match present {
+                    true => None,
+                    false => Some(format!("Item has no value for the attribute; [{}]", attribute_key)),
+                }
+            }
+            ItemReadinessRuleKind::Price => match subject.item.price_cents {
+                0 => Some(String::from("Item has no price")),
+                _ => None,
+            },
+            ItemReadinessRuleKind::ProductIdentifier => {
+                let has_upc: bool = subject.product.upc.as_ref().is_some_and(|upc| !upc.trim().is_empty());
+                // Categories which were never mapped fall back to the root eBay category, whose ID is nil
+                let has_ebay_category: bool = subject.categories.iter().any(|category| !category.ebay_category_id.is_nil());
+                match has_upc || has_ebay_category {
+                    true => None,
+                    false => Some(format!("Product has neither a UPC nor an eBay category; [{}]", subject.product.internal_name)),
+                }
+            }
+            ItemReadinessRuleKind::ImageAltText => {
+                let missing_count: usize = subject.item_images.iter()
+                    .filter(|item_image| item_image.alt_text.trim().is_empty())
+                    .count();
+                match missing_count {
+                    0 => None,
+                    _ => Some(format!("{} of the item's images have no alt text", missing_count)),
+                }
+            }
+        }
+    }
+}
+
+impl ShopModel for ItemReadinessRule {
+    type Entity = ItemReadinessRuleEntity;
+    type Serial = ItemReadinessRuleSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        ItemReadinessRuleSerial {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            enabled: self.enabled.clone(),
+            kind: self.kind.clone() as u8,
+            blocking: self.blocking.clone(),
+            category_id: self.category_id.clone(),
+            minimum_count: self.minimum_count.clone(),
+            attribute_key: self.attribute_key.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    /// The minimum count and attribute key are kept only for the kinds which use them.
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let display_name: String = serial.display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err(ShopError::new("Item readiness rule display name must not be empty"));
+        }
+        let kind: ItemReadinessRuleKind = ItemReadinessRuleKind::try_from_repr(serial.kind)?;
+
+        let minimum_count: Option<u32> = match kind {
+            ItemReadinessRuleKind::ImageCount => match serial.minimum_count {
+                Some(minimum_count) if minimum_count > 0 && minimum_count <= i32::MAX as u32 => Some(minimum_count),
+                _ => return Err(ShopError::new(&format!("Minimum count must be positive; [{:?}]", serial.minimum_count))),
+            },
+            _ => None,
+        };
+        let attribute_key: Option<String> = match kind {
+            ItemReadinessRuleKind::Attribute => match serial.attribute_key.as_ref().map(|key| key.trim()) {
+                Some(attribute_key) if !attribute_key.is_empty() => Some(attribute_key.to_string()),
+                _ => return Err(ShopError::new("Attribute key must not be empty")),
+            },
+            _ => None,
+        };
+
+        Ok(ItemReadinessRule {
+            id: object::random_uuid(),
+            display_name,
+            enabled: serial.enabled.clone(),
+            kind,
+            blocking: serial.blocking.clone(),
+            category_id: serial.category_id.clone(),
+            minimum_count,
+            attribute_key,
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        ItemReadinessRuleEntity {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            enabled: self.enabled.clone(),
+            kind: self.kind.clone() as i32,
+            blocking: self.blocking.clone(),
+            category_id: self.category_id.clone(),
+            minimum_count: self.minimum_count.map(|v| v as i32),
+            attribute_key: self.attribute_key.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(ItemReadinessRule {
+            id: entity.id.clone(),
+            display_name: entity.display_name.clone(),
+            enabled: entity.enabled.clone(),
+            kind: ItemReadinessRuleKind::try_from_repr(entity.kind as u8)?,
+            blocking: entity.blocking.clone(),
+            category_id: entity.category_id.clone(),
+            minimum_count: entity.minimum_count.map(|v| v as u32),
+            attribute_key: entity.attribute_key.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum ItemReadinessRuleKind {
+    /// The item has at least [ItemReadinessRule::minimum_count] images
+    ImageCount = 0,
+    /// The item has a non-blank value for [ItemReadinessRule::attribute_key], compared case-insensitively
+    Attribute,
+    /// The item's price is not zero
+    Price,
+    /// The item's product has a UPC or a category mapped to an eBay category other than the root
+    ProductIdentifier,
+    /// Every image of the item has alt text
+    ImageAltText,
+}
+
+try_from_repr!(ItemReadinessRuleKind<u8>);
+
+impl Display for ItemReadinessRuleKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemReadinessRuleSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub display_name: String,
+    pub enabled: bool,
+    pub kind: u8,
+    pub blocking: bool,
+    pub category_id: Option<Uuid>,
+    pub minimum_count: Option<u32>,
+    pub attribute_key: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for ItemReadinessRuleSerial {
+    type Model = ItemReadinessRule;
+}
+
+impl JsonHttpResponse for ItemReadinessRuleSerial {}
+impl JsonHttpResponse for Vec<ItemReadinessRuleSerial> {}
+
+/// Everything the readiness evaluation inspects about an item
+#[derive(Debug)]
+pub struct ItemReadinessSubject<'a> {
+    pub item: &'a Item,
+    pub product: &'a Product,
+    /// The categories directly associated with the item's product
+    pub categories: &'a [Category],
+    pub item_images: &'a [ItemImage],
+    pub item_attributes: &'a [ItemAttribute],
+    /// The attribute definitions of the categories
+    pub category_attributes: &'a [CategoryAttribute],
+}
+
+impl ItemReadinessSubject<'_> {
+    /// Describes why the item lacks a required attribute. None if the item has a non-blank value for it.
+    pub fn check_required_attribute(&self, category_attribute: &CategoryAttribute) -> Option<String> {
+        let present: bool = self.item_attributes.iter().any(|item_attribute| {
+            category_attribute.matches_key(&item_attribute.key) && !item_attribute.value.trim().is_empty()
+        });
+        match present {
+            true => None,
+            false => Some(format!("Item has no value for the required attribute; [{}]", category_attribute.key)),
+        }
+    }
+}
+
+/// A failed rule or a missing required attribute
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemReadinessIssue {
+    pub item_readiness_rule_id: Option<Uuid>,
+    pub category_attribute_id: Option<Uuid>,
+    pub display_name: String,
+    pub message: String,
+}
+
+/// Why an item is or is not ready to be completed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemReadiness {
+    pub item_id: Uuid,
+    /// The percentage of the applicable rules and required attributes the item passes, rounded down; 100 if there are none
+    pub score: u8,
+    /// True if the item fails no blocking rule
+    pub ready: bool,
+    /// Failed blocking rules and missing required attributes, which prevent completion
+    pub blockers: Vec<ItemReadinessIssue>,
+    /// Failed non-blocking rules
+    pub warnings: Vec<ItemReadinessIssue>,
+}
+
+impl ItemReadiness {
+    pub fn evaluate(subject: &ItemReadinessSubject, item_readiness_rules: &[ItemReadinessRule]) -> ItemReadiness {
+        let mut applicable_count: usize = 0;
+        let mut blockers: Vec<ItemReadinessIssue> = Vec::new();
+        let mut warnings: Vec<ItemReadinessIssue> = Vec::new();
+        for item_readiness_rule in item_readiness_rules.iter().filter(|rule| rule.applies_to(subject)) {
+            applicable_count += 1;
+            let Some(message) = item_readiness_rule.check(subject) else {
+                continue;
+            };
+            let issue: ItemReadinessIssue = ItemReadinessIssue {
+                item_readiness_rule_id: Some(item_readiness_rule.id),
+                category_attribute_id: None,
+                display_name: item_readiness_rule.display_name.clone(),
+                message,
+            };
+            match item_readiness_rule.blocking {
+                true => blockers.push(issue),
+                false => warnings.push(issue),
+            }
+        }
+        // Categories may define the same key; it is required once
+        let mut required_keys: Vec<&str> = Vec::new();
+        for category_attribute in subject.category_attributes.iter().filter(|definition| definition.required) {
+            if required_keys.iter().any(|key| category_attribute.matches_key(key)) {
+                continue;
+            }
+            required_keys.push(&category_attribute.key);
+            applicable_count += 1;
+            let Some(message) = subject.check_required_attribute(category_attribute) else {
+                continue;
+            };
+            blockers.push(ItemReadinessIssue {
+                item_readiness_rule_id: None,
+                category_attribute_id: Some(category_attribute.id),
+                display_name: format!("Required attribute {}", category_attribute.key),
+                message,
+            });
+        }
+
+        let passed_count: usize = applicable_count - blockers.len() - warnings.len();
+        let score: u8 = match applicable_count {
+            0 => 100,
+            _ => (passed_count * 100 / applicable_count) as u8,
+        };
+        ItemReadiness {
+            item_id: subject.item.id,
+            score,
+            ready: blockers.is_empty(),
+            blockers,
+            warnings,
+        }
+    }
+}
+
+impl JsonHttpResponse for ItemReadiness {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::{ItemCondition, ItemStatus};
+
+    fn item(price_cents: u32) -> Item {
+        Item {
+            id: Uuid::nil(),
+            code: "AUS-00011".to_string(),
+            product_id: Uuid::nil(),
+            inventory_location_id: Uuid::nil(),
+            storage_slot_id: None,
+            condition: ItemCondition::Inapplicable,
+            status: ItemStatus::Incomplete,
+            price_cents,
+            priority: 0,
+            note: None,
+            acquisition_datetime: Utc::now(),
+            acquisition_price_cents: None,
+            acquisition_location: None,
+            consignor_id: None,
+            commission_basis_points: None,
+            commission_minimum_cents: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    fn product() -> Product {
+        Product {
+            id: Uuid::nil(),
+            display_name: "Product".to_string(),
+            internal_name: "product".to_string(),
+            upc: None,
+            release_date: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    fn item_image(alt_text: &str) -> ItemImage {
+        ItemImage {
+            id: object::random_uuid(),
+            item_id: Uuid::nil(),
+            alt_text: alt_text.to_string(),
+            priority: 0,
+            original_file_name: "image.png".to_string(),
+        }
+    }
+
+    fn item_readiness_rule(kind: ItemReadinessRuleKind, blocking: bool) -> ItemReadinessRule {
+        ItemReadinessRule {
+            id: object::random_uuid(),
+            display_name: format!("{}", kind),
+            enabled: true,
+            minimum_count: match kind {
+                ItemReadinessRuleKind::ImageCount => Some(2),
+                _ => None,
+            },
+            attribute_key: match kind {
+                ItemReadinessRuleKind::Attribute => Some("Year".to_string()),
+                _ => None,
+            },
+            kind,
+            blocking,
+            category_id: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn evaluate_scores_100_without_applicable_rules() {
+        let (item, product) = (item(0), product());
+        let subject: ItemReadinessSubject = ItemReadinessSubject {
+            item: &item,
+            product: &product,
+            categories: &[],
+            item_images: &[],
+            item_attributes: &[],
+            category_attributes: &[],
+        };
+        let mut disabled: ItemReadinessRule = item_readiness_rule(ItemReadinessRuleKind::Price, true);
+        disabled.enabled = false;
+        let mut other_category: ItemReadinessRule = item_readiness_rule(ItemReadinessRuleKind::Price, true);
+        other_category.category_id = Some(object::random_uuid());
+
+        for item_readiness_rules in [vec![], vec![disabled, other_category]] {
+            let item_readiness: ItemReadiness = ItemReadiness::evaluate(&subject, &item_readiness_rules);
+            assert_eq!(item_readiness.score, 100);
+            assert!(item_readiness.ready);
+            assert!(item_readiness.blockers.is_empty() && item_readiness.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn evaluate_scores_the_passed_share_of_applicable_rules_rounded_down() {
+        let (item, product) = (item(1000), product());
+        let item_images: [ItemImage; 1] = [item_image("")];
+        let subject: ItemReadinessSubject = ItemReadinessSubject {
+            item: &item,
+            product: &product,
+            categories: &[],
+            item_images: &item_images,
+            item_attributes: &[],
+            category_attributes: &[],
+        };
+        let item_readiness_rules: Vec<ItemReadinessRule> = vec![
+            item_readiness_rule(ItemReadinessRuleKind::Price, true),
+            item_readiness_rule(ItemReadinessRuleKind::ImageCount, false),
+            item_readiness_rule(ItemReadinessRuleKind::ImageAltText, false),
+        ];
+
+        let item_readiness: ItemReadiness = ItemReadiness::evaluate(&subject, &item_readiness_rules);
+        assert_eq!(item_readiness.score, 33);
+        assert!(item_readiness.ready);
+        assert_eq!(item_readiness.warnings.len(), 2);
+    }
+
+    #[test]
+    fn evaluate_is_not_ready_with_a_failed_blocking_rule() {
+        let (item, product) = (item(0), product());
+        let item_attributes: [ItemAttribute; 1] = [ItemAttribute {
+            item_id: Uuid::nil(),
+            key: " year ".to_string(),
+            value: "1999".to_string(),
+            visible: true,
+            priority: 0,
+            value_number: None,
+            value_date: None,
+            value_boolean: None,
+        }];
+        let subject: ItemReadinessSubject = ItemReadinessSubject {
+            item: &item,
+            product: &product,
+            categories: &[],
+            item_images: &[],
+            item_attributes: &item_attributes,
+            category_attributes: &[],
+        };
+        let price_rule: ItemReadinessRule = item_readiness_rule(ItemReadinessRuleKind::Price, true);
+        let price_rule_id: Uuid = price_rule.id;
+        let item_readiness_rules: Vec<ItemReadinessRule> = vec![
+            price_rule,
+            item_readiness_rule(ItemReadinessRuleKind::Attribute, true),
+            item_readiness_rule(ItemReadinessRuleKind::ProductIdentifier, false),
+        ];
+
+        let item_readiness: ItemReadiness = ItemReadiness::evaluate(&subject, &item_readiness_rules);
+        assert_eq!(item_readiness.score, 33);
+        assert!(!item_readiness.ready);
+        assert_eq!(item_readiness.blockers.len(), 1);
+        assert_eq!(item_readiness.blockers[0].item_readiness_rule_id, Some(price_rule_id));
+        assert_eq!(item_readiness.warnings.len(), 1);
+    }
+
+    #[test]
+    fn evaluate_scores_100_when_every_rule_passes() {
+        let (item, mut product) = (item(1000), product());
+        product.upc = Some("012345678905".to_string());
+        let item_images: [ItemImage; 2] = [item_image("Front"), item_image("Back")];
+        let subject: ItemReadinessSubject = ItemReadinessSubject {
+            item: &item,
+            product: &product,
+            categories: &[],
+            item_images: &item_images,
+            item_attributes: &[],
+            category_attributes: &[],
+        };
+        let item_readiness_rules: Vec<ItemReadinessRule> = vec![
+            item_readiness_rule(ItemReadinessRuleKind::Price, true),
+            item_readiness_rule(ItemReadinessRuleKind::ImageCount, true),
+            item_readiness_rule(ItemReadinessRuleKind::ImageAltText, false),
+            item_readiness_rule(ItemReadinessRuleKind::ProductIdentifier, false),
+        ];
+
+        let item_readiness: ItemReadiness = ItemReadiness::evaluate(&subject, &item_readiness_rules);
+        assert_eq!(item_readiness.score, 100);
+        assert!(item_readiness.ready);
+    }
+}
@@ -0,0 +1,7 @@
+pub(crate) mod item_readiness_db;
+
+pub mod item_readiness_api;
+pub mod item_readiness_model;
+pub mod item_readiness_action;
+
+pub use item_readiness_model::*;
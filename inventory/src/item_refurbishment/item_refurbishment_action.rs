@@ -0,0 +1,119 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item_image::item_image_db;
+use crate::{ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Includes images.
+pub async fn get_item_refurbishment(pgpool: &PgPool, item_refurbishment_id: &Uuid) -> Result<Option<ItemRefurbishment>, ShopError> {
+    let Some(entity) = item_refurbishment_db::get_item_refurbishment(pgpool, item_refurbishment_id).await? else {
+        return Ok(None);
+    };
+
+    let mut item_refurbishments: Vec<ItemRefurbishment> = vec![entity.try_to_model()?];
+    set_item_image_ids(pgpool, &mut item_refurbishments).await?;
+    Ok(item_refurbishments.pop())
+}
+
+/// Ordered by the date the work was performed. Includes images.
+pub async fn get_all_item_refurbishments(pgpool: &PgPool, item_id: &Uuid) -> Result<Vec<ItemRefurbishment>, ShopError> {
+    let mut item_refurbishments: Vec<ItemRefurbishment> = item_refurbishment_db::get_all_item_refurbishments(pgpool, item_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    set_item_image_ids(pgpool, &mut item_refurbishments).await?;
+    Ok(item_refurbishments)
+}
+
+/// Refurbishments of every item of the product, ordered by the date the work was performed. Includes images.
+pub async fn get_all_product_item_refurbishments(pgpool: &PgPool, product_id: &Uuid) -> Result<Vec<ItemRefurbishment>, ShopError> {
+    let mut item_refurbishments: Vec<ItemRefurbishment> = item_refurbishment_db::get_all_product_item_refurbishments(pgpool, product_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    set_item_image_ids(pgpool, &mut item_refurbishments).await?;
+    Ok(item_refurbishments)
+}
+
+pub async fn create_item_refurbishment(pgpool: &PgPool, mut item_refurbishment: ItemRefurbishment) -> Result<ItemRefurbishment, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    validate_item_image_ids(&mut *transaction, &item_refurbishment.item_id, &item_refurbishment.item_image_ids).await?;
+    item_refurbishment.created = Utc::now();
+    item_refurbishment.updated = item_refurbishment.created;
+    item_refurbishment_db::create_item_refurbishment(&mut *transaction, &item_refurbishment.to_entity()).await?;
+    create_item_refurbishment_images(&mut transaction, &item_refurbishment).await?;
+
+    transaction.commit().await?;
+    Ok(item_refurbishment)
+}
+
+/// Replaces every field of the current refurbishment, including its images, except for the item and the creation time.
+pub async fn update_item_refurbishment(
+    pgpool: &PgPool,
+    current: &ItemRefurbishment,
+    mut item_refurbishment: ItemRefurbishment,
+) -> Result<ItemRefurbishment, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    item_refurbishment.id = current.id;
+    item_refurbishment.item_id = current.item_id;
+    item_refurbishment.created = current.created;
+    item_refurbishment.updated = Utc::now();
+    validate_item_image_ids(&mut *transaction, &item_refurbishment.item_id, &item_refurbishment.item_image_ids).await?;
+    item_refurbishment_db::update_item_refurbishment(&mut *transaction, &item_refurbishment.to_entity()).await?;
+
+    item_refurbishment_db::delete_all_item_refurbishment_images(&mut *transaction, &item_refurbishment.id).await?;
+    create_item_refurbishment_images(&mut transaction, &item_refurbishment).await?;
+
+    transaction.commit().await?;
+    Ok(item_refurbishment)
+}
+
+async fn create_item_refurbishment_images(
+    transaction: &mut Transaction<'_, Postgres>,
+    item_refurbishment: &ItemRefurbishment,
+) -> Result<(), ShopError> {
+    for item_image_id in &item_refurbishment.item_image_ids {
+        let entity = ItemRefurbishmentImageEntity {
+            item_refurbishment_id: item_refurbishment.id,
+            item_image_id: *item_image_id,
+        };
+        item_refurbishment_db::create_item_refurbishment_image(&mut **transaction, &entity).await?;
+    }
+    Ok(())
+}
+
+/// Photos of refurbishment work must be images of the refurbished item.
+async fn validate_item_image_ids(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    item_image_ids: &[Uuid],
+) -> Result<(), ShopError> {
+    if item_image_ids.is_empty() {
+        return Ok(());
+    }
+
+    let item_image_entities = item_image_db::get_all_item_images(pgexecutor, item_id).await?;
+    for item_image_id in item_image_ids {
+        if !item_image_entities.iter().any(|entity| entity.id == *item_image_id) {
+            return Err(ShopError::new(&format!("Item image does not belong to the item; [{}]; [{}]", item_image_id, item_id)));
+        }
+    }
+    Ok(())
+}
+
+async fn set_item_image_ids(pgpool: &PgPool, item_refurbishments: &mut [ItemRefurbishment]) -> Result<(), ShopError> {
+    let item_refurbishment_ids: Vec<Uuid> = item_refurbishments.iter().map(|item_refurbishment| item_refurbishment.id).collect();
+    let image_entities: Vec<ItemRefurbishmentImageEntity> =
+        item_refurbishment_db::get_all_item_refurbishment_images(pgpool, &item_refurbishment_ids).await?;
+    for item_refurbishment in item_refurbishments {
+        item_refurbishment.item_image_ids = image_entities.iter()
+            .filter(|entity| entity.item_refurbishment_id == item_refurbishment.id)
+            .map(|entity| entity.item_image_id)
+            .collect();
+    }
+    Ok(())
+}
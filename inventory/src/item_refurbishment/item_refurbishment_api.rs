@@ -0,0 +1,96 @@
+use super::*;
+use crate::item::item_db;
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::postgres::PgQueryResult;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/item_refurbishment")
+            .route("", web::get().to(get_all_product_item_refurbishments))
+            .route("", web::post().to(create_item_refurbishment))
+            .route("/{item_refurbishment_id}", web::get().to(get_item_refurbishment))
+            .route("/{item_refurbishment_id}", web::put().to(update_item_refurbishment))
+            .route("/{item_refurbishment_id}", web::delete().to(delete_item_refurbishment))
+    );
+}
+
+async fn get_all_product_item_refurbishments(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ItemRefurbishmentSearchParameters>,
+) -> HttpResponse {
+    let item_refurbishments: Vec<ItemRefurbishment> = unwrap_result_else_500!(
+        item_refurbishment_action::get_all_product_item_refurbishments(&pgpool, &parameters.into_inner().product_id).await
+    );
+    item_refurbishments.iter()
+        .map(|item_refurbishment| item_refurbishment.to_serial())
+        .collect::<Vec<ItemRefurbishmentSerial>>()
+        .to_http_response()
+}
+
+/// The item ID is taken from the body so that a single admin form can log work on any item.
+async fn create_item_refurbishment(
+    pgpool: web::Data<PgPool>,
+    body: web::Json<ItemRefurbishmentSerial>,
+) -> HttpResponse {
+    let item_refurbishment: ItemRefurbishment = match body.into_inner().try_to_model() {
+        Ok(item_refurbishment) => item_refurbishment,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_refurbishment.item_id).await));
+
+    match item_refurbishment_action::create_item_refurbishment(&pgpool, item_refurbishment).await {
+        Ok(item_refurbishment) => item_refurbishment.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_item_refurbishment(
+    pgpool: web::Data<PgPool>,
+    item_refurbishment_id: web::Path<String>,
+) -> HttpResponse {
+    let item_refurbishment_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&item_refurbishment_id.into_inner()));
+    let item_refurbishment: ItemRefurbishment = unwrap_option_else_404!(unwrap_result_else_500!(
+        item_refurbishment_action::get_item_refurbishment(&pgpool, &item_refurbishment_id).await
+    ));
+    item_refurbishment.to_serial().to_http_response()
+}
+
+/// The item of an existing refurbishment cannot be changed; the item ID in the body is ignored.
+async fn update_item_refurbishment(
+    pgpool: web::Data<PgPool>,
+    item_refurbishment_id: web::Path<String>,
+    body: web::Json<ItemRefurbishmentSerial>,
+) -> HttpResponse {
+    let item_refurbishment_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&item_refurbishment_id.into_inner()));
+    let item_refurbishment: ItemRefurbishment = match body.into_inner().try_to_model() {
+        Ok(item_refurbishment) => item_refurbishment,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: ItemRefurbishment = unwrap_option_else_404!(unwrap_result_else_500!(
+        item_refurbishment_action::get_item_refurbishment(&pgpool, &item_refurbishment_id).await
+    ));
+
+    match item_refurbishment_action::update_item_refurbishment(&pgpool, &current, item_refurbishment).await {
+        Ok(item_refurbishment) => item_refurbishment.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_item_refurbishment(
+    pgpool: web::Data<PgPool>,
+    item_refurbishment_id: web::Path<String>,
+) -> HttpResponse {
+    let item_refurbishment_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&item_refurbishment_id.into_inner()));
+    let query_result: PgQueryResult = unwrap_result_else_500!(
+        item_refurbishment_db::delete_item_refurbishment(&pgpool, &item_refurbishment_id).await
+    );
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
@@ -0,0 +1,162 @@
+use super::*;
+use crate::error::ShopError;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_item_refurbishment(
+    pgexecutor: impl PgExecutor<'_>,
+    item_refurbishment_id: &Uuid,
+) -> Result<Option<ItemRefurbishmentEntity>, ShopError> {
+    query_as!(ItemRefurbishmentEntity, "
+        select id, item_id, kind, performed_date, description, cost_cents, disclose, created, updated
+        from shop.public.item_refurbishment
+        where id = $1
+    ",
+		item_refurbishment_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_item_refurbishments(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Vec<ItemRefurbishmentEntity>, ShopError> {
+    query_as!(ItemRefurbishmentEntity, "
+        select id, item_id, kind, performed_date, description, cost_cents, disclose, created, updated
+        from shop.public.item_refurbishment
+        where item_id = $1
+        order by performed_date, created
+    ",
+		item_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_product_item_refurbishments(
+    pgpool: &PgPool,
+    product_id: &Uuid,
+) -> Result<Vec<ItemRefurbishmentEntity>, ShopError> {
+    query_as!(ItemRefurbishmentEntity, "
+        select item_refurbishment.id, item_refurbishment.item_id, item_refurbishment.kind, item_refurbishment.performed_date, item_refurbishment.description, item_refurbishment.cost_cents, item_refurbishment.disclose, item_refurbishment.created, item_refurbishment.updated
+        from shop.public.item_refurbishment
+            inner join shop.public.item on item_refurbishment.item_id = item.id
+        where item.product_id = $1
+        order by item_refurbishment.performed_date, item_refurbishment.created
+    ",
+		product_id
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_item_refurbishment_images(
+    pgexecutor: impl PgExecutor<'_>,
+    item_refurbishment_ids: &[Uuid],
+) -> Result<Vec<ItemRefurbishmentImageEntity>, ShopError> {
+    query_as!(ItemRefurbishmentImageEntity, "
+        select item_refurbishment_id, item_image_id
+        from shop.public.item_refurbishment_image
+        where item_refurbishment_id = any($1)
+    ",
+		item_refurbishment_ids
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_item_refurbishment(
+    pgexecutor: impl PgExecutor<'_>,
+    item_refurbishment: &ItemRefurbishmentEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_refurbishment (id, item_id, kind, performed_date, description, cost_cents, disclose, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    ",
+		item_refurbishment.id,
+		item_refurbishment.item_id,
+		item_refurbishment.kind,
+		item_refurbishment.performed_date,
+		item_refurbishment.description,
+		item_refurbishment.cost_cents,
+		item_refurbishment.disclose,
+		item_refurbishment.created,
+		item_refurbishment.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The item and creation time are never changed.
+pub async fn update_item_refurbishment(
+    pgexecutor: impl PgExecutor<'_>,
+    item_refurbishment: &ItemRefurbishmentEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.item_refurbishment
+        set (kind, performed_date, description, cost_cents, disclose, updated) = ($2, $3, $4, $5, $6, $7)
+        where id = $1
+    ",
+		item_refurbishment.id,
+		item_refurbishment.kind,
+		item_refurbishment.performed_date,
+		item_refurbishment.description,
+		item_refurbishment.cost_cents,
+		item_refurbishment.disclose,
+		item_refurbishment.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Images are deleted by cascade.
+pub async fn delete_item_refurbishment(pgpool: &PgPool, item_refurbishment_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.item_refurbishment
+        where id = $1
+    ",
+		item_refurbishment_id
+	)
+        .execute(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_item_refurbishment_image(
+    pgexecutor: impl PgExecutor<'_>,
+    item_refurbishment_image: &ItemRefurbishmentImageEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_refurbishment_image (item_refurbishment_id, item_image_id)
+        values ($1, $2)
+    ",
+		item_refurbishment_image.item_refurbishment_id,
+		item_refurbishment_image.item_image_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_all_item_refurbishment_images(
+    pgexecutor: impl PgExecutor<'_>,
+    item_refurbishment_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.item_refurbishment_image
+        where item_refurbishment_id = $1
+    ",
+		item_refurbishment_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
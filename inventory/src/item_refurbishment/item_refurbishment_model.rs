@@ -0,0 +1,195 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+/// A unit of work performed on an item after acquisition, e.g. a repair or a cleaning
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemRefurbishmentEntity {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub kind: i32,
+    pub performed_date: NaiveDate,
+    pub description: String,
+    pub cost_cents: i64,
+    pub disclose: bool,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for ItemRefurbishmentEntity {
+    type Model = ItemRefurbishment;
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemRefurbishmentImageEntity {
+    pub item_refurbishment_id: Uuid,
+    pub item_image_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct ItemRefurbishment {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub kind: ItemRefurbishmentKind,
+    pub performed_date: NaiveDate,
+    pub description: String,
+    /// Added to the item's cost basis
+    pub cost_cents: u32,
+    /// Whether the work is described in marketplace listings
+    pub disclose: bool,
+    /// Photos of the work; each is an image of the same item. Stored separately from the refurbishment entity; empty
+    /// when converted from an entity alone.
+    pub item_image_ids: Vec<Uuid>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ItemRefurbishment {
+    /// A line suitable for a listing description, e.g. "2024-05-01: Cleaning; Removed surface dust from the blister."
+    pub fn get_disclosure(&self) -> String {
+        let description: &str = self.description.trim_end_matches('.');
+        format!("{}: {}; {}.", self.performed_date, self.kind.get_display_name(), description)
+    }
+}
+
+impl ShopModel for ItemRefurbishment {
+    type Entity = ItemRefurbishmentEntity;
+    type Serial = ItemRefurbishmentSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        ItemRefurbishmentSerial {
+            id: self.id.clone(),
+            item_id: self.item_id.clone(),
+            kind: self.kind.clone() as u8,
+            performed_date: self.performed_date.clone(),
+            description: self.description.clone(),
+            cost_cents: self.cost_cents.clone(),
+            disclose: self.disclose.clone(),
+            item_image_ids: self.item_image_ids.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let description: String = serial.description.trim().to_string();
+        if description.is_empty() {
+            return Err(ShopError::new("Refurbishment description must not be empty"));
+        }
+
+        let mut item_image_ids: Vec<Uuid> = Vec::with_capacity(serial.item_image_ids.len());
+        for item_image_id in &serial.item_image_ids {
+            if item_image_ids.contains(item_image_id) {
+                return Err(ShopError::new(&format!("Duplicate item image; [{}]", item_image_id)));
+            }
+            item_image_ids.push(item_image_id.clone());
+        }
+
+        Ok(ItemRefurbishment {
+            id: object::random_uuid(),
+            item_id: serial.item_id.clone(),
+            kind: ItemRefurbishmentKind::try_from_repr(serial.kind)?,
+            performed_date: serial.performed_date.clone(),
+            description,
+            cost_cents: serial.cost_cents.clone(),
+            disclose: serial.disclose.clone(),
+            item_image_ids,
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        ItemRefurbishmentEntity {
+            id: self.id.clone(),
+            item_id: self.item_id.clone(),
+            kind: self.kind.clone() as i32,
+            performed_date: self.performed_date.clone(),
+            description: self.description.clone(),
+            cost_cents: i64::from(self.cost_cents),
+            disclose: self.disclose.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(ItemRefurbishment {
+            id: entity.id.clone(),
+            item_id: entity.item_id.clone(),
+            kind: ItemRefurbishmentKind::try_from_repr(entity.kind.clone() as u8)?,
+            performed_date: entity.performed_date.clone(),
+            description: entity.description.clone(),
+            cost_cents: entity.cost_cents.clone() as u32,
+            disclose: entity.disclose.clone(),
+            item_image_ids: Vec::new(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum ItemRefurbishmentKind {
+    /// Fixing damage or a defect
+    Repair = 0,
+    /// Removing dirt, stains, or odors without altering the item
+    Cleaning,
+    /// Substituting a missing or broken part, e.g. a battery cover or an accessory
+    PartReplacement,
+}
+
+try_from_repr!(ItemRefurbishmentKind<u8>);
+
+impl ItemRefurbishmentKind {
+    pub fn get_display_name(&self) -> &'static str {
+        match self {
+            ItemRefurbishmentKind::Repair => "Repair",
+            ItemRefurbishmentKind::Cleaning => "Cleaning",
+            ItemRefurbishmentKind::PartReplacement => "Part replacement",
+        }
+    }
+}
+
+impl Display for ItemRefurbishmentKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemRefurbishmentSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub kind: u8,
+    pub performed_date: NaiveDate,
+    pub description: String,
+    pub cost_cents: u32,
+    #[serde(default)]
+    pub disclose: bool,
+    #[serde(default)]
+    pub item_image_ids: Vec<Uuid>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for ItemRefurbishmentSerial {
+    type Model = ItemRefurbishment;
+}
+
+impl JsonHttpResponse for ItemRefurbishmentSerial {}
+impl JsonHttpResponse for Vec<ItemRefurbishmentSerial> {}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemRefurbishmentSearchParameters {
+    pub product_id: Uuid,
+}
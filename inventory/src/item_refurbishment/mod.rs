@@ -0,0 +1,7 @@
+pub(crate) mod item_refurbishment_db;
+
+pub mod item_refurbishment_api;
+pub mod item_refurbishment_model;
+pub mod item_refurbishment_action;
+
+pub use item_refurbishment_model::*;
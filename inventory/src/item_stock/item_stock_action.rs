@@ -0,0 +1,95 @@
+use super::*;
+use crate::ebay::ebay_action;
+use crate::error::ShopError;
+use crate::item::item_db;
+use crate::listing::Listing;
+use crate::purchase::{PurchaseEntity, PurchaseStatus};
+use crate::{ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// The units of the item available for sale. A unique item without a stock record is a single unit.
+pub async fn get_available_quantity(pgexecutor: impl PgExecutor<'_>, item_id: &Uuid) -> Result<u32, ShopError> {
+    match item_stock_db::get_item_stock(pgexecutor, item_id).await? {
+        Some(entity) => Ok(entity.try_to_model()?.quantity),
+        None => Ok(1),
+    }
+}
+
+/// Replaces any existing stock record of the item. The original creation time is retained.
+pub async fn create_or_replace_item_stock(pgpool: &PgPool, mut item_stock: ItemStock) -> Result<ItemStock, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    if let Some(current) = item_stock_db::get_item_stock_for_update(&mut *transaction, &item_stock.item_id).await? {
+        item_stock.created = current.created;
+    }
+    item_stock.updated = Utc::now();
+    item_stock_db::create_or_replace_item_stock(&mut *transaction, &item_stock.to_entity()).await?;
+
+    transaction.commit().await?;
+    Ok(item_stock)
+}
+
+/// Removes the sold units from the item's stock within the caller's transaction. A unique item can only be sold as a
+/// single unit, and only if it has no purchase other than cancelled ones. It has no stock to update, in which case
+/// [None] is returned.
+pub async fn record_sale_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item_id: &Uuid,
+    quantity: u32,
+) -> Result<Option<ItemStock>, ShopError> {
+    let Some(entity) = item_stock_db::get_item_stock_for_update(&mut **transaction, item_id).await? else {
+        if quantity != 1 {
+            return Err(ShopError::new(&format!("A unique item can only be sold as a single unit; [{}]", item_id)));
+        }
+        // The item lock serializes concurrent purchases of the item
+        item_db::get_item_for_update(&mut **transaction, item_id)
+            .await?
+            .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?;
+        let purchases: Vec<PurchaseEntity> = item_db::get_all_item_purchases(&mut **transaction, item_id).await?;
+        if purchases.iter().any(|purchase| purchase.status != PurchaseStatus::Cancelled as i32) {
+            return Err(ShopError::new(&format!("Unique item has already been purchased; [{}]", item_id)));
+        }
+        return Ok(None);
+    };
+
+    let mut item_stock: ItemStock = entity.try_to_model()?;
+    if quantity > item_stock.quantity {
+        return Err(ShopError::new(&format!(
+            "Insufficient stock; [{}]; [available: {}]; [requested: {}]",
+            item_id,
+            item_stock.quantity,
+            quantity,
+        )));
+    }
+    item_stock.quantity -= quantity;
+    item_stock.updated = Utc::now();
+    item_stock_db::update_item_stock_quantity(&mut **transaction, item_id, item_stock.quantity as i32, &item_stock.updated).await?;
+
+    if item_stock.is_low_stock() {
+        log::warn!("Item stock is low; [{}]; [quantity: {}]", item_id, item_stock.quantity);
+    }
+    Ok(Some(item_stock))
+}
+
+/// Sets the quantity of the eBay offer of each published listing to its item's available quantity.
+/// Failures do not revert the stock changes and are returned per listing.
+pub async fn sync_marketplace_quantities(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listings: &[Listing],
+) -> Vec<ItemStockMarketplaceError> {
+    let mut errors: Vec<ItemStockMarketplaceError> = Vec::new();
+    for listing in listings {
+        if let Err(error) = ebay_action::update_offer_quantity(pgpool, user_access_token, listing).await {
+            log::error!("Failed to update ebay offer quantity; [{}]; {}", listing.id, error);
+            errors.push(ItemStockMarketplaceError {
+                item_id: listing.get_sku(),
+                listing_id: listing.id,
+                message: error.message,
+            });
+        }
+    }
+    errors
+}
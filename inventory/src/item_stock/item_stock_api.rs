@@ -0,0 +1,74 @@
+use super::*;
+use crate::ebay::ebay_api;
+use crate::inventory_transfer::inventory_transfer_action;
+use crate::item::item_db;
+use crate::listing::Listing;
+use crate::object::JsonHttpResponse;
+use crate::{try_return, unwrap_option_else_404, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::cookie::Cookie;
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpRequest, HttpResponse};
+use sqlx::PgPool;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/item_stock")
+            .route("", web::get().to(get_all_item_stocks))
+            .route("", web::put().to(create_or_replace_item_stock))
+    );
+}
+
+async fn get_all_item_stocks(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ItemStockSearchParameters>,
+) -> HttpResponse {
+    let entities: Vec<ItemStockEntity> = unwrap_result_else_500!(
+        item_stock_db::get_all_item_stocks(&pgpool, parameters.into_inner().low_stock).await
+    );
+    let mut item_stock_serial_vec: Vec<ItemStockSerial> = Vec::with_capacity(entities.len());
+    for entity in entities {
+        item_stock_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    item_stock_serial_vec.to_http_response()
+}
+
+/// The item ID is taken from the body so that a single admin form can stock any item.
+async fn create_or_replace_item_stock(
+    pgpool: web::Data<PgPool>,
+    request: HttpRequest,
+    body: web::Json<ItemStockSerial>,
+) -> HttpResponse {
+    let item_stock: ItemStock = match body.into_inner().try_to_model() {
+        Ok(item_stock) => item_stock,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    unwrap_option_else_404!(unwrap_result_else_500!(item_db::get_item(&pgpool, &item_stock.item_id).await));
+
+    // Require the eBay token up front so that published offers never drift from the recorded quantity
+    let published_listings: Vec<Listing> = unwrap_result_else_500!(
+        inventory_transfer_action::get_published_marketplace_listings(&pgpool, &[item_stock.item_id]).await
+    );
+    let user_access_token: Option<Cookie> = match published_listings.is_empty() {
+        true => None,
+        false => Some(try_return!(ebay_api::extract_user_token(&request))),
+    };
+
+    let item_stock: ItemStock = match item_stock_action::create_or_replace_item_stock(&pgpool, item_stock).await {
+        Ok(item_stock) => item_stock,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    let marketplace_errors: Vec<ItemStockMarketplaceError> = match &user_access_token {
+        Some(user_access_token) => item_stock_action::sync_marketplace_quantities(
+            &pgpool,
+            user_access_token.value(),
+            &published_listings,
+        ).await,
+        None => Vec::new(),
+    };
+
+    ItemStockReport {
+        item_stock: item_stock.to_serial(),
+        marketplace_errors,
+    }.to_http_response()
+}
@@ -0,0 +1,111 @@
+use super::*;
+use crate::error::ShopError;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_item_stock(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<ItemStockEntity>, ShopError> {
+    query_as!(ItemStockEntity, "
+        select item_id, quantity, low_stock_threshold, created, updated
+        from shop.public.item_stock
+        where item_id = $1
+    ",
+		item_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_item_stock_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<Option<ItemStockEntity>, ShopError> {
+    query_as!(ItemStockEntity, "
+        select item_id, quantity, low_stock_threshold, created, updated
+        from shop.public.item_stock
+        where item_id = $1
+        for update
+    ",
+		item_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Stock without a low stock threshold is never low.
+pub async fn get_all_item_stocks(
+    pgpool: &PgPool,
+    low_stock_only: bool,
+) -> Result<Vec<ItemStockEntity>, ShopError> {
+    query_as!(ItemStockEntity, "
+        select item_id, quantity, low_stock_threshold, created, updated
+        from shop.public.item_stock
+        where not $1 or quantity <= low_stock_threshold
+        order by quantity, item_id
+    ",
+		low_stock_only
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_or_replace_item_stock(
+    pgexecutor: impl PgExecutor<'_>,
+    item_stock: &ItemStockEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.item_stock (item_id, quantity, low_stock_threshold, created, updated)
+        values ($1, $2, $3, $4, $5)
+        on conflict (item_id) do update
+        set (quantity, low_stock_threshold, updated)
+            = (excluded.quantity, excluded.low_stock_threshold, excluded.updated)
+    ",
+		item_stock.item_id,
+		item_stock.quantity,
+		item_stock.low_stock_threshold,
+		item_stock.created,
+		item_stock.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_item_stock_quantity(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+    quantity: i32,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.item_stock
+        set (quantity, updated) = ($2, $3)
+        where item_id = $1
+    ",
+		item_id,
+		quantity,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_item_stock(pgpool: &PgPool, item_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.item_stock
+        where item_id = $1
+    ",
+		item_id
+	)
+        .execute(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
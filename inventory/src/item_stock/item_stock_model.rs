@@ -0,0 +1,135 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The available units of an item which represents many identical units of one product and condition, e.g. sealed stock
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemStockEntity {
+    pub item_id: Uuid,
+    pub quantity: i32,
+    pub low_stock_threshold: Option<i32>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for ItemStockEntity {
+    type Model = ItemStock;
+}
+
+/// Items without a stock record are unique units, whose quantity is always one.
+#[derive(Debug, Clone)]
+pub struct ItemStock {
+    pub item_id: Uuid,
+    /// Units available for sale
+    pub quantity: u32,
+    /// The stock is low when the quantity is at or below the threshold
+    pub low_stock_threshold: Option<u32>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ItemStock {
+    pub fn is_low_stock(&self) -> bool {
+        self.low_stock_threshold.is_some_and(|low_stock_threshold| self.quantity <= low_stock_threshold)
+    }
+}
+
+impl ShopModel for ItemStock {
+    type Entity = ItemStockEntity;
+    type Serial = ItemStockSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        ItemStockSerial {
+            item_id: self.item_id.clone(),
+            quantity: self.quantity,
+            low_stock_threshold: self.low_stock_threshold,
+            low_stock: self.is_low_stock(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        if serial.quantity > i32::MAX as u32 {
+            return Err(ShopError::new(&format!("Quantity is too large; [{}]", serial.quantity)));
+        }
+        if serial.low_stock_threshold.is_some_and(|low_stock_threshold| low_stock_threshold > i32::MAX as u32) {
+            return Err(ShopError::new("Low stock threshold is too large"));
+        }
+
+        Ok(ItemStock {
+            item_id: serial.item_id.clone(),
+            quantity: serial.quantity,
+            low_stock_threshold: serial.low_stock_threshold,
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        ItemStockEntity {
+            item_id: self.item_id.clone(),
+            quantity: self.quantity as i32,
+            low_stock_threshold: self.low_stock_threshold.map(|low_stock_threshold| low_stock_threshold as i32),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(ItemStock {
+            item_id: entity.item_id.clone(),
+            quantity: entity.quantity as u32,
+            low_stock_threshold: entity.low_stock_threshold.map(|low_stock_threshold| low_stock_threshold as u32),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemStockSerial {
+    pub item_id: Uuid,
+    pub quantity: u32,
+    pub low_stock_threshold: Option<u32>,
+    /// See [ItemStock::is_low_stock]; ignored on input
+    #[serde(default)]
+    pub low_stock: bool,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for ItemStockSerial {
+    type Model = ItemStock;
+}
+
+impl JsonHttpResponse for ItemStockSerial {}
+impl JsonHttpResponse for Vec<ItemStockSerial> {}
+
+#[derive(Debug, Deserialize)]
+pub struct ItemStockSearchParameters {
+    /// Only stock at or below its low stock threshold
+    #[serde(default)]
+    pub low_stock: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemStockReport {
+    pub item_stock: ItemStockSerial,
+    /// Published eBay offers which could not be updated to the new quantity. The stock change itself is retained.
+    pub marketplace_errors: Vec<ItemStockMarketplaceError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemStockMarketplaceError {
+    pub item_id: Uuid,
+    pub listing_id: Uuid,
+    pub message: String,
+}
+
+impl JsonHttpResponse for ItemStockReport {}
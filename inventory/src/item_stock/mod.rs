@@ -0,0 +1,7 @@
+pub(crate) mod item_stock_db;
+
+pub mod item_stock_api;
+pub mod item_stock_model;
+pub mod item_stock_action;
+
+pub use item_stock_model::*;
@@ -43,7 +43,7 @@ async fn create_label(pgpool: web::Data<PgPool>, label: web::Json<LabelSerial>)
         .into_inner()
         .try_to_model()
         .map(|label| label.to_entity()) // superfluous in this case
-        .map(async |label_entity| label_db::create_label(&pgpool, &label_entity).await);
+        .map(async |label_entity| label_db::create_label(pgpool.get_ref(), &label_entity).await);
 
     let Ok(db_result) = result else {
         return HttpResponse::InternalServerError().finish();
@@ -1,7 +1,7 @@
 use super::*;
 use crate::error::ShopError;
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool};
 use uuid::Uuid;
 
 pub async fn get_label(pgpool: &PgPool, label_id: Uuid) -> Result<Option<LabelEntity>, ShopError> {
@@ -19,7 +19,25 @@ pub async fn get_label(pgpool: &PgPool, label_id: Uuid) -> Result<Option<LabelEn
 		.map_err(|e| ShopError::from(e))
 }
 
-pub async fn create_label(pgpool: &PgPool, label: &LabelEntity) -> Result<PgQueryResult, ShopError> {
+pub async fn get_label_by_internal_name(
+    pgexecutor: impl PgExecutor<'_>,
+    internal_name: &str,
+) -> Result<Option<LabelEntity>, ShopError> {
+    query_as!(
+		LabelEntity,
+		"\
+        select id, display_name, internal_name \
+        from shop.public.label \
+        where internal_name = $1 \
+    ",
+		internal_name
+	)
+        .fetch_optional(pgexecutor)
+        .await
+		.map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_label(pgexecutor: impl PgExecutor<'_>, label: &LabelEntity) -> Result<PgQueryResult, ShopError> {
     query!(
 		"\
         insert into shop.public.label (id, display_name, internal_name) \
@@ -29,7 +47,7 @@ pub async fn create_label(pgpool: &PgPool, label: &LabelEntity) -> Result<PgQuer
 		label.display_name,
 		label.internal_name,
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
         .await
 		.map_err(|e| ShopError::from(e))
 }
@@ -2,19 +2,39 @@
 mod macros;
 
 // Models
+pub mod acquisition_lot;
+pub mod barcode;
+pub mod bundle;
 pub mod category;
+pub mod category_attribute;
+pub mod consignor;
 pub mod customer;
+pub mod export;
+pub mod import;
 pub mod inventory_location;
+pub mod inventory_report;
+pub mod inventory_transfer;
 pub mod item;
 pub mod item_attribute;
 pub mod item_audit;
+pub mod item_grading;
 pub mod item_image;
+pub mod item_readiness;
+pub mod item_refurbishment;
+pub mod item_stock;
 pub mod label;
 pub mod listing;
 pub mod marketplace;
 pub mod metric_counter;
+pub mod price_history;
 pub mod product;
+pub mod profit;
 pub mod purchase;
+pub mod repricing_rule;
+pub mod stock_take;
+pub mod storage_slot;
+pub mod supplier;
+pub mod trade_in;
 
 pub mod db;
 pub mod server;
@@ -3,13 +3,17 @@ use crate::item::{item_db, Item, ItemEntity};
 use crate::listing::{Listing, ListingStatus};
 use crate::product::{product_db, Product, ProductEntity};
 use crate::{listing, ShopEntity, ShopModel};
+use chrono::Utc;
 use sqlx::PgPool;
 
 pub async fn get_item_and_product_for_listing(
     pgpool: &PgPool,
     listing: &Listing,
 ) -> Result<(Item, Product), ShopError> {
-    let item: Option<ItemEntity> = item_db::get_item(pgpool, &listing.item_id).await?;
+    let Some(item_id) = &listing.item_id else {
+        return Err(ShopError::new(&format!("Listing is not of an item; [{}]", listing.id)));
+    };
+    let item: Option<ItemEntity> = item_db::get_item(pgpool, item_id).await?;
     let Some(item): Option<ItemEntity> = item else {
         return Err(ShopError::new(&format!("Item not found for listing; [{}]", listing.id)));
     };
@@ -30,6 +34,7 @@ pub async fn update_listing_status(
 ) -> Result<(), ShopError> {
     let mut updated_listing: Listing = listing.clone();
     updated_listing.status = new_status;
+    updated_listing.updated = Utc::now();
 
     listing::listing_db::update_listing(pgpool, &updated_listing.to_entity())
         .await?;
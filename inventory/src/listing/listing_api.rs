@@ -1,8 +1,12 @@
+use crate::bundle::bundle_action;
+use crate::error::ShopError;
 use crate::listing::{listing_db, Listing, ListingSerial};
 use crate::object::JsonHttpResponse;
+use crate::pagination::KeysetPaginationOptions;
 use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
 use actix_web::web::ServiceConfig;
 use actix_web::{web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
 use sqlx::postgres::PgQueryResult;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -10,6 +14,7 @@ use uuid::Uuid;
 pub fn configurer(config: &mut ServiceConfig) {
     config.service(
         web::scope("/listing")
+            .route("", web::get().to(get_all_listings_paged))
             .route("", web::post().to(create_listing))
             .route("/{listing_id}", web::get().to(get_listing))
             .route("/{listing_id}", web::put().to(update_listing))
@@ -22,6 +27,14 @@ async fn create_listing(
 ) -> impl Responder {
     let listing = listing.into_inner();
     let listing = unwrap_result_else_400!(listing.try_to_model());
+    let listable: Result<(), ShopError> = match (&listing.item_id, &listing.bundle_id) {
+        (_, Some(bundle_id)) => bundle_action::validate_bundle_listable(&pgpool, bundle_id).await,
+        (Some(item_id), None) => bundle_action::validate_item_listable(&pgpool, item_id).await,
+        (None, None) => Ok(()),
+    };
+    if let Err(error) = listable {
+        return HttpResponse::BadRequest().body(error.message);
+    }
 
     let query_result: PgQueryResult =
         unwrap_result_else_500!(listing_db::create_listing(&pgpool, &listing.to_entity()).await);
@@ -54,3 +67,19 @@ async fn update_listing(
         unwrap_result_else_500!(listing_db::update_listing(&pgpool, &listing.to_entity()).await);
     HttpResponse::Ok().body(query_result.rows_affected().to_string())
 }
+
+async fn get_all_listings_paged(
+    pgpool: web::Data<PgPool>,
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        listing_db::get_all_listings_paged_updated(&pgpool, &pagination_options).await
+    );
+
+    let mut listing_serial_vec = Vec::with_capacity(entities.len());
+    for entity in entities {
+        listing_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (listing_serial_vec, pagination_result).to_http_response()
+}
@@ -1,7 +1,10 @@
 use crate::error::ShopError;
 use crate::listing::{ListingEntity, ListingStatus};
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use chrono::{DateTime, Utc};
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub async fn create_listing(
@@ -9,13 +12,15 @@ pub async fn create_listing(
     listing: &ListingEntity,
 ) -> Result<PgQueryResult, ShopError> {
     query!("
-        insert into shop.public.listing (id, item_id, marketplace_id, status, created, updated)
-        values ($1, $2, $3, $4, $5, $6)
+        insert into shop.public.listing (id, item_id, bundle_id, marketplace_id, status, published, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8)
     ",
         listing.id,
         listing.item_id,
+        listing.bundle_id,
         listing.marketplace_id,
         listing.status,
+        listing.published,
         listing.created,
         listing.updated
     )
@@ -40,18 +45,25 @@ pub async fn get_listing(
         .map_err(|e| ShopError::from(e))
 }
 
+/// The publish time is set to the update time when the listing becomes active, and retained otherwise.
 pub async fn update_listing(
     pgpool: &PgPool,
     listing: &ListingEntity,
 ) -> Result<PgQueryResult, ShopError> {
+    let active_statuses: Vec<i32> = vec![ListingStatus::Published as i32, ListingStatus::Hold as i32];
     query!("
         update shop.public.listing
-        set (status, updated) = ($2, $3)
+        set (status, published, updated) = (
+            $2,
+            case when $2::int = any($4::int[]) and not status = any($4::int[]) then $3::timestamptz else published end,
+            $3
+        )
         where id = $1
     ",
         listing.id,
         listing.status,
         listing.updated,
+        &active_statuses,
     )
         .execute(pgpool)
         .await
@@ -75,3 +87,54 @@ pub async fn get_all_by_status_and_marketplace(
         .await
         .map_err(|e| ShopError::from(e))
 }
+
+/// Most recently updated listings first
+pub async fn get_all_listings_paged_updated(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<DateTime<Utc>>,
+) -> Result<(Vec<ListingEntity>, KeysetPaginationResult<DateTime<Utc>>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, item_id, bundle_id, marketplace_id, status, published, created, updated \
+        from shop.public.listing \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "updated",
+        &Direction::Descending,
+        keyset_pagination_options,
+        |listing: &ListingEntity| (listing.updated, listing.id),
+    ).await
+}
+
+pub async fn delete_all_item_listings(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.listing
+        where item_id = $1
+    ",
+        item_id,
+    )
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_bundle_listings(
+    pgexecutor: impl PgExecutor<'_>,
+    bundle_id: &Uuid,
+) -> Result<Vec<ListingEntity>, ShopError> {
+    query_as!(ListingEntity, "
+        select id, item_id, bundle_id, marketplace_id, status, published, created, updated
+        from shop.public.listing
+        where bundle_id = $1
+    ",
+        bundle_id,
+    )
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -7,12 +7,14 @@ use std::fmt::{Display, Formatter};
 use strum::{FromRepr, IntoStaticStr, VariantArray};
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, sqlx::FromRow)]
 pub struct ListingEntity {
     pub id: Uuid,
-    pub item_id: Uuid,
+    pub item_id: Option<Uuid>,
+    pub bundle_id: Option<Uuid>,
     pub marketplace_id: Uuid,
     pub status: i32,
+    pub published: Option<DateTime<Utc>>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
@@ -21,16 +23,27 @@ impl ShopEntity for ListingEntity {
     type Model = Listing;
 }
 
+/// A listing is of exactly one item or exactly one [crate::bundle::Bundle].
 #[derive(Debug, Clone)]
 pub struct Listing {
     pub id: Uuid,
-    pub item_id: Uuid,
+    pub item_id: Option<Uuid>,
+    pub bundle_id: Option<Uuid>,
     pub marketplace_id: Uuid,
     pub status: ListingStatus,
+    /// The time the listing last became active; see [ListingStatus::is_active]
+    pub published: Option<DateTime<Utc>>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
 
+impl Listing {
+    /// The ID of the listed item or bundle, which identifies the listing's inventory on its marketplace
+    pub fn get_sku(&self) -> Uuid {
+        self.item_id.or(self.bundle_id).unwrap_or_default()
+    }
+}
+
 impl ShopModel for Listing {
     type Entity = ListingEntity;
     type Serial = ListingSerial;
@@ -39,19 +52,27 @@ impl ShopModel for Listing {
         ListingSerial {
             id: self.id.clone(),
             item_id: self.item_id.clone(),
+            bundle_id: self.bundle_id.clone(),
             marketplace_id: self.marketplace_id.clone(),
             status: self.status.clone() as u8,
+            published: self.published.clone(),
             created: self.created.clone(),
             updated: self.updated.clone(),
         }
     }
 
     fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        if serial.item_id.is_some() == serial.bundle_id.is_some() {
+            return Err(ShopError::new("A listing must be of exactly one item or bundle"));
+        }
+
         Ok(Listing {
             id: object::random_uuid(),
             item_id: serial.item_id.clone(),
+            bundle_id: serial.bundle_id.clone(),
             marketplace_id: serial.marketplace_id.clone(),
             status: ListingStatus::default(),
+            published: None,
             created: serial.created.clone(),
             updated: serial.updated.clone(),
         })
@@ -61,8 +82,10 @@ impl ShopModel for Listing {
         ListingEntity {
             id: self.id.clone(),
             item_id: self.item_id.clone(),
+            bundle_id: self.bundle_id.clone(),
             marketplace_id: self.marketplace_id.clone(),
             status: self.status.clone() as i32,
+            published: self.published.clone(),
             created: self.created.clone(),
             updated: self.updated.clone(),
         }
@@ -72,8 +95,10 @@ impl ShopModel for Listing {
         Ok(Listing {
             id: entity.id.clone(),
             item_id: entity.item_id.clone(),
+            bundle_id: entity.bundle_id.clone(),
             marketplace_id: entity.marketplace_id.clone(),
             status: ListingStatus::try_from_repr(entity.status.clone() as u8)?,
+            published: entity.published.clone(),
             created: entity.created.clone(),
             updated: entity.updated.clone(),
         })
@@ -92,6 +117,16 @@ pub enum ListingStatus {
 
 try_from_repr!(ListingStatus<u8>);
 
+impl ListingStatus {
+    /// True iff the listing may currently be visible to buyers on its marketplace.
+    pub fn is_active(&self) -> bool {
+        match self {
+            ListingStatus::Published | ListingStatus::Hold => true,
+            ListingStatus::Draft | ListingStatus::Fulfilled | ListingStatus::Cancelled => false,
+        }
+    }
+}
+
 impl Default for ListingStatus {
     fn default() -> Self {
         ListingStatus::Draft
@@ -108,10 +143,16 @@ impl Display for ListingStatus {
 pub struct ListingSerial {
     #[serde(default)]
     pub id: Uuid,
-    pub item_id: Uuid,
+    #[serde(default)]
+    pub item_id: Option<Uuid>,
+    #[serde(default)]
+    pub bundle_id: Option<Uuid>,
     pub marketplace_id: Uuid,
     #[serde(default)]
     pub status: u8,
+    /// Ignored on input; set when the listing becomes active
+    #[serde(default)]
+    pub published: Option<DateTime<Utc>>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
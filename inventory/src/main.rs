@@ -1,4 +1,4 @@
-use inventory::{ebay, environment, server};
+use inventory::{ebay, environment, repricing_rule, server};
 use sqlx::{Pool, Postgres};
 use std::io;
 
@@ -14,6 +14,11 @@ async fn main() -> Result<(), io::Error> {
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
 
     ebay::ebay_action::init(&pgpool).await;
+    if environment::repricing_scheduler_enabled() {
+        repricing_rule::repricing_rule_action::spawn_scheduler(pgpool.clone());
+    } else {
+        log::info!("Repricing scheduler disabled; Set REPRICING_SCHEDULER_ENABLED=true to enable");
+    }
 
     server::open_server(pgpool).await
 }
@@ -1,8 +1,11 @@
 use crate::error::ShopError;
-use crate::ShopEntity;
+use crate::object::JsonHttpResponse;
 use actix_web::guard;
 use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{Encode, FromRow, PgPool, Postgres, QueryBuilder, Type};
 use std::cmp::PartialEq;
+use uuid::Uuid;
 
 pub fn pagination_guard(ctx: &guard::GuardContext) -> bool {
     ctx.head()
@@ -26,19 +29,48 @@ impl Default for Direction {
     }
 }
 
+impl Direction {
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            Direction::Ascending => "asc",
+            Direction::Descending => "desc",
+        }
+    }
+
+    pub fn reversed(&self) -> Direction {
+        match self {
+            Direction::Ascending => Direction::Descending,
+            Direction::Descending => Direction::Ascending,
+        }
+    }
+}
+
+/// Values which may be used as the ordered column of a keyset PAGE.
+/// Implemented for any type which can be bound to a Postgres query and passed through a query string.
+pub trait KeysetKey: Clone + Send + Serialize + for<'a> Encode<'a, Postgres> + Type<Postgres> + 'static {}
+
+impl<KeyT> KeysetKey for KeyT
+where
+    KeyT: Clone + Send + Serialize + for<'a> Encode<'a, Postgres> + Type<Postgres> + 'static,
+{}
+
+/// PAGE boundaries are (value, id) pairs so that elements which share a value are neither skipped nor repeated.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KeysetPaginationOptionsForString {
+#[serde(default)]
+pub struct KeysetPaginationOptions<KeyT> {
     /// Maximum number of elements in a returned PAGE.
     pub max_page_size: u32,
-    /// Ascend or descend in the relation from your current position.
+    /// Ascending moves forward through the relation order; Descending moves backward.
     pub direction: Direction,
-    /// If ascending, this is the preceding element to the desired PAGE.
-    /// If descending, this is the maximal element in the desired PAGE.
+    /// If ascending, this is the value of the element preceding the desired PAGE.
+    /// If descending, this is the value of the element following the desired PAGE.
     /// If none, returns the first PAGE.
-    pub start_value: Option<String>,
+    pub start_value: Option<KeyT>,
+    /// Identifies the start element among elements which share the start value.
+    pub start_id: Option<Uuid>,
 }
 
-impl KeysetPaginationOptionsForString {
+impl<KeyT> KeysetPaginationOptions<KeyT> {
     pub fn validated(self) -> Result<Self, ShopError> {
         if self.max_page_size.overflowing_add(1).1 {
             return Err(ShopError::new(&format!("Maximum PAGE size exceeds maximum value; [{}]", u32::MAX - 1)));
@@ -46,6 +78,9 @@ impl KeysetPaginationOptionsForString {
             return Err(ShopError::new("Maximum PAGE size cannot be zero;"));
         }
 
+        if self.start_value.is_some() != self.start_id.is_some() {
+            return Err(ShopError::new("Start value and start ID must be specified together;"));
+        }
         if self.start_value.is_none() && self.direction == Direction::Descending {
             return Err(ShopError::new("Unspecified start value cannot request a descending PAGE;"));
         }
@@ -54,127 +89,139 @@ impl KeysetPaginationOptionsForString {
     }
 }
 
-impl Default for KeysetPaginationOptionsForString {
+impl<KeyT> Default for KeysetPaginationOptions<KeyT> {
     fn default() -> Self {
-        KeysetPaginationOptionsForString {
+        KeysetPaginationOptions {
             max_page_size: 50,
             direction: Direction::Ascending,
             start_value: None,
+            start_id: None,
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct KeysetPaginationResultForString {
+pub struct KeysetPaginationResult<KeyT> {
     pub page_size: u32,
-    pub l_value: Option<String>,
-    pub r_value: Option<String>,
-    /// The maximum value for the entire table.
-    pub relation_max_value: Option<String>,
-    /// The minimum value for the entire table.
-    pub relation_min_value: Option<String>,
-    /// True iff min_value is less than the minimum value in the current PAGE.
-    /// This comparison should be performed by the DBMS.
+    /// Boundary of the first element in the PAGE.
+    pub l_value: Option<KeyT>,
+    pub l_id: Option<Uuid>,
+    /// Boundary of the last element in the PAGE.
+    pub r_value: Option<KeyT>,
+    pub r_id: Option<Uuid>,
+    /// True iff an element precedes the current PAGE in the relation order.
     pub has_lesser_value: bool,
-    /// True iff max_value is greater than the maximum value in the current PAGE.
-    /// This comparison should be performed by the DBMS.
+    /// True iff an element follows the current PAGE in the relation order.
     pub has_greater_value: bool,
 }
 
-impl KeysetPaginationResultForString {
-    pub fn create(&self, direction: &Direction, max_page_size: &u32) -> KeysetPaginationOptionsForString {
-        KeysetPaginationOptionsForString {
+impl<KeyT: Clone> KeysetPaginationResult<KeyT> {
+    pub fn create(&self, direction: &Direction, max_page_size: &u32) -> KeysetPaginationOptions<KeyT> {
+        let (start_value, start_id) = match direction {
+            Direction::Ascending => (self.r_value.clone(), self.r_id.clone()),
+            Direction::Descending => (self.l_value.clone(), self.l_id.clone()),
+        };
+        KeysetPaginationOptions {
             max_page_size: max_page_size.clone(),
             direction: direction.clone(),
-            start_value: match direction {
-                Direction::Ascending => self.r_value.clone(),
-                Direction::Descending => self.l_value.clone(),
-            },
+            start_value,
+            start_id,
         }
     }
 
-    /// entities: This vector should be retrieved from the DBMS in ascending sorted order.
+    /// entities: This vector should be in relation order and may contain one element beyond the maximum PAGE size
+    /// (in the requested direction), which indicates that a further PAGE exists.
     pub fn from_entities<EntityT>(
-        entities: Vec<EntityT>,
-        relation_min_entity: Option<EntityT>,
-        relation_max_entity: Option<EntityT>,
-        getter: fn(EntityT) -> String,
-        max_page_size: usize,
-    ) -> (Vec<EntityT>, KeysetPaginationResultForString)
-    where
-        EntityT: ShopEntity + Clone,
-    {
-        let relation_max_value: Option<String> = relation_max_entity.map(getter);
-        let relation_min_value: Option<String> = relation_min_entity.map(getter);
-        debug_assert!(
-            entities.len() == 0 && relation_max_value.is_none() && relation_min_value.is_none()
-                || entities.len() > 0 && relation_max_value.is_some() && relation_min_value.is_some()
-        );
-
-        let l_value: Option<String> = {
-            if entities.len() == 0 {
-                None
-            } else {
-                let first_value = entities.get(0)
-                    .map(|val| val.clone())
-                    .map(getter);
-                debug_assert!(first_value.is_some()); // Vector is not empty.
-
-                if entities.len() == max_page_size + 1 {
-                    first_value
-                } else {
-                    // Vector has been truncated. It is at the edge of the relation.
-                    if first_value == relation_min_value {
-                        None
-                    } else {
-                        first_value
-                    }
-                }
-            }
-        };
-        let r_value: Option<String> = {
-            if entities.len() == 0 {
-                None
-            } else {
-                let last_value = entities.get(entities.len() - 1)
-                    .map(|val| val.clone())
-                    .map(getter);
-                debug_assert!(last_value.is_some()); // Vector is not empty.
-
-                if last_value == relation_max_value {
-                    None
-                } else {
-                    last_value
-                }
+        mut entities: Vec<EntityT>,
+        options: &KeysetPaginationOptions<KeyT>,
+        getter: fn(&EntityT) -> (KeyT, Uuid),
+    ) -> (Vec<EntityT>, KeysetPaginationResult<KeyT>) {
+        let max_page_size: usize = options.max_page_size as usize;
+        let has_further_page: bool = entities.len() > max_page_size;
+        if has_further_page {
+            match options.direction {
+                Direction::Ascending => entities.truncate(max_page_size),
+                Direction::Descending => _ = entities.drain(..entities.len() - max_page_size),
             }
-        };
+        }
 
-        let has_greater_value: bool = r_value.is_some();
-        let has_lesser_value: bool = l_value.is_some();
-
-        let page: Vec<EntityT> = if entities.len() == max_page_size + 1 {
-            entities[1..].to_vec()
-        } else {
-            // Page is truncated
-            if l_value.is_some() {
-                entities[1..].to_vec()
-            } else {
-                entities[..].to_vec()
-            }
+        // The start element itself lies beyond the PAGE opposite the direction of travel.
+        let has_start: bool = options.start_value.is_some();
+        let (has_lesser_value, has_greater_value) = match options.direction {
+            Direction::Ascending => (has_start, has_further_page),
+            Direction::Descending => (has_further_page, has_start),
         };
-        let page_size: u32 = page.len() as u32;
+
+        let (l_value, l_id) = entities.first().map(getter).unzip();
+        let (r_value, r_id) = entities.last().map(getter).unzip();
+        let page_size: u32 = entities.len() as u32;
 
         (
-            page,
-            KeysetPaginationResultForString {
+            entities,
+            KeysetPaginationResult {
                 page_size,
                 l_value,
+                l_id,
                 r_value,
-                relation_max_value,
-                relation_min_value,
-                has_greater_value,
+                r_id,
                 has_lesser_value,
+                has_greater_value,
             }
         )
     }
 }
+
+impl<SerialT, KeyT> JsonHttpResponse for (Vec<SerialT>, KeysetPaginationResult<KeyT>)
+where
+    SerialT: Serialize,
+    KeyT: Serialize,
+{}
+
+/// Completes and executes a query for a single PAGE.
+///
+/// builder: A select statement over a relation with an `id` column whose where clause is open, e.g. `... where true`.
+/// key_column: The ordered column, which is compared together with `id` to resolve duplicate values.
+/// order: The order of the relation as presented to the client.
+pub async fn fetch_page<EntityT, KeyT>(
+    pgpool: &PgPool,
+    mut builder: QueryBuilder<'_, Postgres>,
+    key_column: &str,
+    order: &Direction,
+    options: &KeysetPaginationOptions<KeyT>,
+    getter: fn(&EntityT) -> (KeyT, Uuid),
+) -> Result<(Vec<EntityT>, KeysetPaginationResult<KeyT>), ShopError>
+where
+    EntityT: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    KeyT: KeysetKey,
+{
+    /* Moving backward through the relation is performed by reversing the comparison and ordering,
+        then reversing the fetched rows. */
+    let query_order: Direction = match options.direction {
+        Direction::Ascending => order.clone(),
+        Direction::Descending => order.reversed(),
+    };
+
+    if let (Some(start_value), Some(start_id)) = (&options.start_value, &options.start_id) {
+        let comparison: &str = match query_order {
+            Direction::Ascending => ">",
+            Direction::Descending => "<",
+        };
+        builder.push(format!(" and ({}, id) {} (", key_column, comparison))
+            .push_bind(start_value.clone())
+            .push(", ")
+            .push_bind(start_id.clone())
+            .push(")");
+    }
+    builder.push(format!(" order by {} {}, id {}", key_column, query_order.to_sql(), query_order.to_sql()))
+        .push(" limit ")
+        .push_bind(i64::from(options.max_page_size) + 1);
+
+    let mut entities: Vec<EntityT> = builder.build_query_as::<EntityT>()
+        .fetch_all(pgpool)
+        .await?;
+    if options.direction == Direction::Descending {
+        entities.reverse();
+    }
+
+    Ok(KeysetPaginationResult::from_entities(entities, options, getter))
+}
@@ -0,0 +1,7 @@
+pub(crate) mod price_history_db;
+
+pub mod price_history_api;
+pub mod price_history_model;
+pub mod price_history_action;
+
+pub use price_history_model::*;
@@ -0,0 +1,72 @@
+use super::*;
+use crate::ebay::ebay_action;
+use crate::error::ShopError;
+use crate::item::{item_db, Item};
+use crate::listing::Listing;
+use crate::{ShopEntity, ShopModel};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Sets the price of the item, recording the change as a [PriceHistory]. Nothing is recorded if the item already has
+/// the given price.
+pub async fn change_item_price(
+    pgpool: &PgPool,
+    item_id: &Uuid,
+    price_cents: u32,
+    initiated_by: Option<String>,
+    reason: Option<String>,
+) -> Result<Option<PriceHistory>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    let price_history: Option<PriceHistory> = change_item_price_in_transaction(
+        &mut transaction,
+        item_id,
+        price_cents,
+        initiated_by,
+        reason,
+    ).await?;
+    transaction.commit().await?;
+    Ok(price_history)
+}
+
+/// As [change_item_price], within the caller's transaction.
+pub async fn change_item_price_in_transaction(
+    transaction: &mut Transaction<'_, Postgres>,
+    item_id: &Uuid,
+    price_cents: u32,
+    initiated_by: Option<String>,
+    reason: Option<String>,
+) -> Result<Option<PriceHistory>, ShopError> {
+    let item: Item = item_db::get_item_for_update(&mut **transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()?;
+    if item.price_cents == price_cents {
+        return Ok(None);
+    }
+
+    let price_history = PriceHistory::new(item.id, item.price_cents, price_cents, initiated_by, reason);
+    item_db::update_item_price(&mut **transaction, &item.id, i64::from(price_cents), &price_history.created).await?;
+    price_history_db::create_price_history(&mut **transaction, &price_history.to_entity()).await?;
+    Ok(Some(price_history))
+}
+
+/// Sets the price of the eBay offer of each published listing to its item's current price.
+/// Failures do not revert the price changes and are returned per listing.
+pub async fn sync_marketplace_prices(
+    pgpool: &PgPool,
+    user_access_token: &str,
+    listings: &[Listing],
+) -> Vec<PriceChangeMarketplaceError> {
+    let mut errors: Vec<PriceChangeMarketplaceError> = Vec::new();
+    for listing in listings {
+        if let Err(error) = ebay_action::update_offer_price(pgpool, user_access_token, listing).await {
+            log::error!("Failed to update ebay offer price; [{}]; {}", listing.id, error);
+            errors.push(PriceChangeMarketplaceError {
+                item_id: listing.get_sku(),
+                listing_id: listing.id,
+                message: error.message,
+            });
+        }
+    }
+    errors
+}
@@ -0,0 +1,31 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::pagination::KeysetPaginationOptions;
+use crate::{unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, Responder};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/price_history")
+            .route("", web::get().to(get_all_price_histories_paged))
+    );
+}
+
+async fn get_all_price_histories_paged(
+    pgpool: web::Data<PgPool>,
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        price_history_db::get_all_price_histories_paged_created(&pgpool, &pagination_options).await
+    );
+
+    let mut price_history_serial_vec = Vec::with_capacity(entities.len());
+    for entity in entities {
+        price_history_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (price_history_serial_vec, pagination_result).to_http_response()
+}
@@ -0,0 +1,67 @@
+use super::*;
+use crate::error::ShopError;
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// Oldest changes first
+pub async fn get_all_item_price_histories(
+    pgpool: &PgPool,
+    item_id: &Uuid,
+) -> Result<Vec<PriceHistoryEntity>, ShopError> {
+    query_as!(PriceHistoryEntity, "
+        select id, item_id, old_price_cents, new_price_cents, initiated_by, reason, created
+        from shop.public.price_history
+        where item_id = $1
+        order by created, id
+    ",
+		item_id
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_price_history(
+    pgexecutor: impl PgExecutor<'_>,
+    price_history: &PriceHistoryEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.price_history (id, item_id, old_price_cents, new_price_cents, initiated_by, reason, created)
+        values ($1, $2, $3, $4, $5, $6, $7)
+    ",
+		price_history.id,
+		price_history.item_id,
+		price_history.old_price_cents,
+		price_history.new_price_cents,
+		price_history.initiated_by,
+		price_history.reason,
+		price_history.created,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recent changes first
+pub async fn get_all_price_histories_paged_created(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<DateTime<Utc>>,
+) -> Result<(Vec<PriceHistoryEntity>, KeysetPaginationResult<DateTime<Utc>>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, item_id, old_price_cents, new_price_cents, initiated_by, reason, created \
+        from shop.public.price_history \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "created",
+        &Direction::Descending,
+        keyset_pagination_options,
+        |price_history: &PriceHistoryEntity| (price_history.created, price_history.id),
+    ).await
+}
@@ -0,0 +1,150 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{object, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Records a change to the price of an item.
+#[derive(Debug, sqlx::FromRow)]
+pub struct PriceHistoryEntity {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub old_price_cents: i64,
+    pub new_price_cents: i64,
+    pub initiated_by: Option<String>,
+    pub reason: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopEntity for PriceHistoryEntity {
+    type Model = PriceHistory;
+}
+
+#[derive(Debug, Clone)]
+pub struct PriceHistory {
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub old_price_cents: u32,
+    pub new_price_cents: u32,
+    /// The authenticated user who changed the price, if known
+    pub initiated_by: Option<String>,
+    pub reason: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl PriceHistory {
+    pub fn new(
+        item_id: Uuid,
+        old_price_cents: u32,
+        new_price_cents: u32,
+        initiated_by: Option<String>,
+        reason: Option<String>,
+    ) -> PriceHistory {
+        PriceHistory {
+            id: object::random_uuid(),
+            item_id,
+            old_price_cents,
+            new_price_cents,
+            initiated_by,
+            reason,
+            created: Utc::now(),
+        }
+    }
+}
+
+impl ShopModel for PriceHistory {
+    type Entity = PriceHistoryEntity;
+    type Serial = PriceHistorySerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        PriceHistorySerial {
+            id: self.id.clone(),
+            item_id: self.item_id.clone(),
+            old_price_cents: self.old_price_cents.clone(),
+            new_price_cents: self.new_price_cents.clone(),
+            initiated_by: self.initiated_by.clone(),
+            reason: self.reason.clone(),
+            created: self.created.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(PriceHistory {
+            id: object::random_uuid(),
+            item_id: serial.item_id.clone(),
+            old_price_cents: serial.old_price_cents.clone(),
+            new_price_cents: serial.new_price_cents.clone(),
+            initiated_by: serial.initiated_by.clone(),
+            reason: serial.reason.clone(),
+            created: serial.created.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        PriceHistoryEntity {
+            id: self.id.clone(),
+            item_id: self.item_id.clone(),
+            old_price_cents: i64::from(self.old_price_cents),
+            new_price_cents: i64::from(self.new_price_cents),
+            initiated_by: self.initiated_by.clone(),
+            reason: self.reason.clone(),
+            created: self.created.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(PriceHistory {
+            id: entity.id.clone(),
+            item_id: entity.item_id.clone(),
+            old_price_cents: entity.old_price_cents.clone() as u32,
+            new_price_cents: entity.new_price_cents.clone() as u32,
+            initiated_by: entity.initiated_by.clone(),
+            reason: entity.reason.clone(),
+            created: entity.created.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceHistorySerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub item_id: Uuid,
+    pub old_price_cents: u32,
+    pub new_price_cents: u32,
+    pub initiated_by: Option<String>,
+    pub reason: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopSerial for PriceHistorySerial {
+    type Model = PriceHistory;
+}
+
+impl JsonHttpResponse for PriceHistorySerial {}
+impl JsonHttpResponse for Vec<PriceHistorySerial> {}
+
+/// Sets the price of an item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceChangeSerial {
+    pub price_cents: u32,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceChangeReport {
+    /// Absent if the item already had the requested price
+    pub price_history: Option<PriceHistorySerial>,
+    /// Published eBay offers which could not be updated to the new price. The price change itself is retained.
+    pub marketplace_errors: Vec<PriceChangeMarketplaceError>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceChangeMarketplaceError {
+    pub item_id: Uuid,
+    pub listing_id: Uuid,
+    pub message: String,
+}
+
+impl JsonHttpResponse for PriceChangeReport {}
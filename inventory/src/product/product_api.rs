@@ -1,9 +1,9 @@
 use super::*;
 use crate::category::CategorySerial;
 use crate::error::ShopError;
-use crate::item::{Item, ItemSerial};
+use crate::item::{item_db, Item, ItemCostBasis, ItemSerial};
 use crate::object::JsonHttpResponse;
-use crate::pagination::{pagination_guard, KeysetPaginationOptionsForString};
+use crate::pagination::{pagination_guard, KeysetPaginationOptions};
 use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopModel};
 use actix_web::guard::fn_guard;
 use actix_web::http::StatusCode;
@@ -24,7 +24,8 @@ pub fn configurer(config: &mut web::ServiceConfig) {
             .route("/{product_id}/category", web::post().to(create_product_category_association_body))
             .route("/{product_id}/category/{category_id}", web::post().to(create_product_category_association_path))
             .route("/{product_id}/category/{category_id}", web::delete().to(delete_product_category_association))
-            .route("/{product_id}/item", web::get().to(get_product_items)),
+            .route("/{product_id}/item", web::get().to(get_product_items))
+            .route("/{product_id}/item_cost_basis", web::get().to(get_all_product_item_cost_bases)),
     );
 }
 
@@ -52,11 +53,12 @@ async fn get_all_products(
 
 async fn get_all_products_paged_display_name(
     pgpool: web::Data<PgPool>,
-    query: web::Query<KeysetPaginationOptionsForString>,
+    query: web::Query<KeysetPaginationOptions<String>>,
 ) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
     let query_result = product_db::get_all_products_paged_display_name(
         &pgpool.into_inner(),
-        &query.into_inner(),
+        &pagination_options,
     ).await;
 
     let (entities, pagination_result) = unwrap_result_else_500!(query_result);
@@ -73,7 +75,7 @@ async fn get_product(pgpool: web::Data<PgPool>, product_id: web::Path<String>) -
     };
 
     let product = unwrap_result_else_500!(
-        product_db::get_product(&pgpool, &product_id).await
+        product_db::get_product(pgpool.get_ref(), &product_id).await
     );
     product
         .map(|product| product.to_serial().to_http_response())
@@ -98,7 +100,7 @@ async fn create_product(
     };
 
     let result = unwrap_result_else_500!(
-        product_db::create_product(&pgpool, &product).await
+        product_db::create_product(pgpool.get_ref(), &product).await
     );
     HttpResponseBuilder::new(StatusCode::CREATED)
         .body(result.rows_affected().to_string())
@@ -113,7 +115,7 @@ async fn get_product_categories(
     };
 
     let product_categories = unwrap_result_else_500!(
-        product_db::get_product_categories(&pgpool, &product_id).await
+        product_db::get_product_categories(pgpool.get_ref(), &product_id).await
     );
     product_categories
         .iter()
@@ -154,7 +156,7 @@ async fn create_product_category_association(
     };
 
     let result = unwrap_result_else_500!(
-        product_db::create_product_category_association(&pgpool, &product_id, &category_id).await
+        product_db::create_product_category_association(pgpool, &product_id, &category_id).await
     );
     HttpResponseBuilder::new(StatusCode::CREATED)
         .body(result.rows_affected().to_string())
@@ -197,3 +199,15 @@ async fn get_product_items(
         Err(_) => HttpResponseBuilder::new(StatusCode::INTERNAL_SERVER_ERROR).finish(),
     }
 }
+
+async fn get_all_product_item_cost_bases(
+    pgpool: web::Data<PgPool>,
+    product_id: web::Path<String>,
+) -> HttpResponse {
+    let product_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(&product_id.into_inner()));
+    unwrap_result_else_500!(item_db::get_all_product_item_cost_bases(pgpool.get_ref(), &product_id).await)
+        .iter()
+        .map(ItemCostBasis::from)
+        .collect::<Vec<ItemCostBasis>>()
+        .to_http_response()
+}
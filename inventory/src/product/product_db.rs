@@ -1,10 +1,10 @@
 use super::*;
 use crate::category::CategoryEntity;
 use crate::item::ItemEntity;
-use crate::pagination::{Direction, KeysetPaginationOptionsForString, KeysetPaginationResultForString};
-use sqlx::postgres::{PgArguments, PgQueryResult, PgRow};
-use sqlx::query::Map;
-use sqlx::{query, query_as, PgPool, Postgres};
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 use crate::error::ShopError;
 
@@ -20,70 +20,25 @@ pub async fn get_all_products(pgpool: &PgPool) -> Result<Vec<ProductEntity>, Sho
 
 pub async fn get_all_products_paged_display_name(
     pgpool: &PgPool,
-    keyset_pagination_options: &KeysetPaginationOptionsForString,
-) -> Result<(Vec<ProductEntity>, KeysetPaginationResultForString), ShopError> {
-    let limit: u32 = if keyset_pagination_options.start_value.is_none() {
-        keyset_pagination_options.max_page_size
-    } else {
-        keyset_pagination_options.max_page_size.saturating_add(1)
-    };
-    let start_value: String = keyset_pagination_options.start_value.clone().unwrap_or_default();
-
-    /* This function always returns records in ascending order on the ordered column, but we must alternate
-        between ascending and descending in order to move forward and backward between pages. */
-    let query: Map<Postgres, fn(PgRow) -> Result<ProductEntity, sqlx::Error>, PgArguments> = match keyset_pagination_options.direction {
-        Direction::Ascending => {
-            query_as!(ProductEntity, "\
-        		select id, display_name, internal_name, upc, release_date, created, updated
-        		from shop.public.product
-        		where display_name >= $1
-        		order by display_name asc
-                limit $2
-        	",
-                start_value,
-                i64::from(limit),
-            )
-        }
-        Direction::Descending => {
-            query_as!(ProductEntity, "\
-                with PAGE as (
-                    select id, display_name, internal_name, upc, release_date, created, updated
-                    from shop.public.product
-                    where display_name <= $1
-                    order by display_name desc
-                    limit $2
-                )
-                select id, display_name, internal_name, upc, release_date, created, updated
-                from PAGE
-                order by display_name asc
-        	",
-                start_value,
-                i64::from(limit),
-            )
-        }
-    };
-
-    let entities: Vec<ProductEntity> = query.fetch_all(pgpool).await?;
-    let relation_max_entity: Option<ProductEntity> = query_as!(ProductEntity,
-        "select * from shop.public.product order by display_name desc limit 1")
-        .fetch_optional(pgpool)
-        .await?;
-    let relation_min_entity: Option<ProductEntity> = query_as!(ProductEntity,
-        "select * from shop.public.product order by display_name asc limit 1")
-        .fetch_optional(pgpool)
-        .await?;
-
-    Ok(KeysetPaginationResultForString::from_entities(
-        entities,
-        relation_min_entity,
-        relation_max_entity,
-        |val| val.display_name,
-        keyset_pagination_options.max_page_size as usize,
-    ))
+    keyset_pagination_options: &KeysetPaginationOptions<String>,
+) -> Result<(Vec<ProductEntity>, KeysetPaginationResult<String>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, display_name, internal_name, upc, release_date, created, updated \
+        from shop.public.product \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "display_name",
+        &Direction::Ascending,
+        keyset_pagination_options,
+        |product: &ProductEntity| (product.display_name.clone(), product.id),
+    ).await
 }
 
 pub async fn get_product(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     product_id: &Uuid,
 ) -> Result<Option<ProductEntity>, ShopError> {
     query_as!(ProductEntity, "\
@@ -93,7 +48,39 @@ pub async fn get_product(
 	",
 		product_id
 	)
-        .fetch_optional(pgpool)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_product_by_upc(
+    pgexecutor: impl PgExecutor<'_>,
+    upc: &str,
+) -> Result<Option<ProductEntity>, ShopError> {
+    query_as!(ProductEntity, "\
+		select id, display_name, internal_name, upc, release_date, created, updated \
+		from shop.public.product \
+		where upc = $1 \
+	",
+		upc
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_product_by_internal_name(
+    pgexecutor: impl PgExecutor<'_>,
+    internal_name: &str,
+) -> Result<Option<ProductEntity>, ShopError> {
+    query_as!(ProductEntity, "\
+		select id, display_name, internal_name, upc, release_date, created, updated \
+		from shop.public.product \
+		where internal_name = $1 \
+	",
+		internal_name
+	)
+        .fetch_optional(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
@@ -114,7 +101,7 @@ pub async fn delete_product(
 }
 
 pub async fn get_product_categories(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     product_id: &Uuid,
 ) -> Result<Vec<CategoryEntity>, ShopError> {
     query_as!(CategoryEntity, "
@@ -123,13 +110,13 @@ pub async fn get_product_categories(
         inner join shop.public.product_category_association on category.id = product_category_association.category_id
         where product_category_association.product_id = $1
     ", product_id)
-        .fetch_all(pgpool)
+        .fetch_all(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
 
 pub async fn create_product(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     product: &ProductEntity,
 ) -> Result<PgQueryResult, ShopError> {
     query!(
@@ -145,13 +132,13 @@ pub async fn create_product(
 		product.created,
 		product.updated
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
 
 pub async fn create_product_category_association(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     product_id: &Uuid,
     category_id: &Uuid,
 ) -> Result<PgQueryResult, ShopError> {
@@ -163,7 +150,7 @@ pub async fn create_product_category_association(
 		category_id,
 		product_id,
 	)
-        .execute(pgpool)
+        .execute(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
@@ -193,7 +180,7 @@ pub async fn get_all_product_items(
     query_as!(
 		ItemEntity,
 		"\
-		select id, product_id, inventory_location_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, created, updated \
+		select id, code, product_id, inventory_location_id, storage_slot_id, condition, status, price_cents, priority, note, acquisition_datetime, acquisition_price_cents, acquisition_location, consignor_id, commission_basis_points, commission_minimum_cents, created, updated \
 		from shop.public.item \
 		where product_id = $1 \
 		",
@@ -1,13 +1,12 @@
 use crate::error::ShopError;
 use crate::object::JsonHttpResponse;
-use crate::pagination::KeysetPaginationResultForString;
 use crate::{object, ShopEntity, ShopModel, ShopSerial};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::types::chrono;
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, sqlx::FromRow)]
 pub struct ProductEntity {
     pub id: Uuid,
     pub display_name: String,
@@ -74,4 +73,3 @@ impl ShopSerial for ProductSerial {
 }
 impl JsonHttpResponse for ProductSerial {}
 impl JsonHttpResponse for Vec<ProductSerial> {}
-impl JsonHttpResponse for (Vec<ProductSerial>, KeysetPaginationResultForString) {}
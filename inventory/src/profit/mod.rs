@@ -0,0 +1,7 @@
+pub(crate) mod profit_db;
+
+pub mod profit_api;
+pub mod profit_model;
+pub mod profit_action;
+
+pub use profit_model::*;
@@ -0,0 +1,152 @@
+use super::*;
+use crate::category::category_db;
+use crate::error::ShopError;
+use crate::label::label_db;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Key and display name of items belonging to none of the categories or labels
+const UNGROUPED_KEY: &str = "";
+const UNGROUPED_DISPLAY_NAME: &str = "(none)";
+
+pub async fn get_item_profit(pgpool: &PgPool, item_id: &Uuid) -> Result<Option<ItemProfit>, ShopError> {
+    let entities: Vec<ItemProfitEntity> = profit_db::get_all_item_profit_lines(pgpool, Some(item_id), None, None).await?;
+    Ok(ItemProfit::from_entities(&entities))
+}
+
+/// Items sold within the range, with the totals of only the purchases made within it, most recently sold first
+pub async fn get_all_sold_item_profits(
+    pgpool: &PgPool,
+    sold_after: Option<NaiveDate>,
+    sold_before: Option<NaiveDate>,
+) -> Result<Vec<ItemProfit>, ShopError> {
+    Ok(get_all_sold_item_profit_lines(pgpool, sold_after, sold_before).await?
+        .chunk_by(|a, b| a.item_id == b.item_id)
+        .filter_map(ItemProfit::from_entities)
+        .collect())
+}
+
+async fn get_all_sold_item_profit_lines(
+    pgpool: &PgPool,
+    sold_after: Option<NaiveDate>,
+    sold_before: Option<NaiveDate>,
+) -> Result<Vec<ItemProfitEntity>, ShopError> {
+    let sold_after: Option<DateTime<Utc>> = sold_after.map(start_of_day);
+    // The bound is inclusive of the whole day
+    let sold_before: Option<DateTime<Utc>> = sold_before.map(|sold_before| start_of_day(sold_before) + TimeDelta::days(1));
+    profit_db::get_all_item_profit_lines(pgpool, None, sold_after, sold_before).await
+}
+
+/// Aggregates the items sold within the range of the parameters by their grouping. An item counts toward a period or
+/// marketplace with only its purchases made in it, and so may count toward several.
+pub async fn get_profit_report(pgpool: &PgPool, parameters: &ProfitReportParameters) -> Result<ProfitReport, ShopError> {
+    let entities: Vec<ItemProfitEntity> = get_all_sold_item_profit_lines(pgpool, parameters.sold_after, parameters.sold_before).await?;
+
+    let display_names: HashMap<Uuid, String> = match parameters.grouping {
+        ProfitGrouping::Category => category_db::get_all_categories(pgpool).await?
+            .into_iter()
+            .map(|category| (category.id, category.display_name))
+            .collect(),
+        ProfitGrouping::Label => label_db::get_all_labels(pgpool).await?
+            .into_iter()
+            .map(|label| (label.id, label.display_name))
+            .collect(),
+        _ => HashMap::new(),
+    };
+
+    let mut summaries: BTreeMap<String, ProfitSummary> = BTreeMap::new();
+    let mut total: ProfitSummary = ProfitSummary::new(String::new(), "Total".to_string());
+    for item_entities in entities.chunk_by(|a, b| a.item_id == b.item_id) {
+        let Some(item_profit) = ItemProfit::from_entities(item_entities) else {
+            continue;
+        };
+        total.add(&item_profit);
+
+        if !parameters.grouping.is_per_purchase() {
+            for (key, display_name) in get_group_keys(&parameters.grouping, &item_profit, &display_names) {
+                summaries.entry(key.clone())
+                    .or_insert_with(|| ProfitSummary::new(key, display_name))
+                    .add(&item_profit);
+            }
+            continue;
+        }
+
+        // Each purchase counts only toward its own period or marketplace
+        let mut group_entities: BTreeMap<(String, String), Vec<&ItemProfitEntity>> = BTreeMap::new();
+        for entity in item_entities {
+            if let Some(group_key) = get_purchase_group_key(&parameters.grouping, entity) {
+                group_entities.entry(group_key).or_default().push(entity);
+            }
+        }
+        for ((key, display_name), group_entities) in group_entities {
+            let Some(group_item_profit) = ItemProfit::from_entities(group_entities) else {
+                continue;
+            };
+            summaries.entry(key.clone())
+                .or_insert_with(|| ProfitSummary::new(key, display_name))
+                .add(&group_item_profit);
+        }
+    }
+
+    let mut summaries: Vec<ProfitSummary> = summaries.into_values().collect();
+    if !parameters.grouping.is_period() {
+        summaries.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    }
+
+    Ok(ProfitReport {
+        grouping: parameters.grouping.clone(),
+        sold_after: parameters.sold_after,
+        sold_before: parameters.sold_before,
+        summaries,
+        total,
+    })
+}
+
+/// The key and display name of the period or marketplace of the purchase. Absent for groupings which are not per
+/// purchase, and for rows without a purchase.
+fn get_purchase_group_key(grouping: &ProfitGrouping, entity: &ItemProfitEntity) -> Option<(String, String)> {
+    match grouping {
+        ProfitGrouping::Marketplace => Some((
+            entity.marketplace_id?.to_string(),
+            entity.marketplace_display_name.clone().unwrap_or_default(),
+        )),
+        _ => {
+            let period: String = grouping.get_period(entity.sold.as_ref()?)?;
+            Some((period.clone(), period))
+        }
+    }
+}
+
+/// The key and display name of every group to which the sold item belongs
+fn get_group_keys(
+    grouping: &ProfitGrouping,
+    item_profit: &ItemProfit,
+    display_names: &HashMap<Uuid, String>,
+) -> Vec<(String, String)> {
+    let Some(sale) = &item_profit.sale else {
+        return Vec::new();
+    };
+    let ids: &[Uuid] = match grouping {
+        ProfitGrouping::Product => return vec![(item_profit.product_id.to_string(), item_profit.product_display_name.clone())],
+        ProfitGrouping::Marketplace => return vec![(sale.marketplace_id.to_string(), sale.marketplace_display_name.clone())],
+        ProfitGrouping::Day | ProfitGrouping::Week | ProfitGrouping::Month | ProfitGrouping::Year => {
+            let period: String = grouping.get_period(&sale.sold).unwrap_or_default();
+            return vec![(period.clone(), period)];
+        }
+        ProfitGrouping::Category => &item_profit.category_ids,
+        ProfitGrouping::Label => &item_profit.label_ids,
+    };
+
+    if ids.is_empty() {
+        return vec![(UNGROUPED_KEY.to_string(), UNGROUPED_DISPLAY_NAME.to_string())];
+    }
+    ids.iter()
+        .map(|id| (id.to_string(), display_names.get(id).cloned().unwrap_or_else(|| id.to_string())))
+        .collect()
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
@@ -0,0 +1,35 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::unwrap_result_else_500;
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/profit")
+            .route("/item", web::get().to(get_all_sold_item_profits))
+            .route("/report", web::get().to(get_profit_report))
+    );
+}
+
+/// Items sold within the range of the parameters, most recently sold first. The grouping is ignored.
+async fn get_all_sold_item_profits(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ProfitReportParameters>,
+) -> HttpResponse {
+    let item_profits: Vec<ItemProfit> = unwrap_result_else_500!(
+        profit_action::get_all_sold_item_profits(&pgpool, parameters.sold_after, parameters.sold_before).await
+    );
+    item_profits.to_http_response()
+}
+
+async fn get_profit_report(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<ProfitReportParameters>,
+) -> HttpResponse {
+    let profit_report: ProfitReport = unwrap_result_else_500!(
+        profit_action::get_profit_report(&pgpool, &parameters.into_inner()).await
+    );
+    profit_report.to_http_response()
+}
@@ -0,0 +1,84 @@
+use super::*;
+use crate::error::ShopError;
+use crate::purchase::PurchaseStatus;
+use chrono::{DateTime, Utc};
+use sqlx::{query_as, PgExecutor};
+use uuid::Uuid;
+
+/// One row per fulfilled purchase of each item, with the rows of an item adjacent and its latest purchase first.
+/// Items are ordered most recently sold first. Given an item ID, only that item is included, with a single row without
+/// a purchase if it has not sold; otherwise only purchases made within the range are. Either bound may be absent.
+/// A purchase of a bundle is split across the bundle's items in proportion to their current prices, or evenly if none
+/// has a price. Shares are rounded down at cumulative boundaries, ordered by item ID, so they sum exactly to the
+/// purchase's amounts.
+pub async fn get_all_item_profit_lines(
+    pgexecutor: impl PgExecutor<'_>,
+    item_id: Option<&Uuid>,
+    sold_after: Option<DateTime<Utc>>,
+    sold_before: Option<DateTime<Utc>>,
+) -> Result<Vec<ItemProfitEntity>, ShopError> {
+    query_as!(ItemProfitEntity, r#"
+        with purchase_sale as (
+            select listing.item_id, listing.bundle_id, purchase.id as purchase_id, purchase.created, purchase.marketplace_id, purchase.quantity,
+                purchase.cost_subtotal_cents + purchase.cost_shipping_cents - purchase.cost_discount_cents as revenue_cents,
+                purchase.marketplace_fee_cents, purchase.seller_cost_total_cents as shipping_cost_cents
+            from shop.public.purchase
+                inner join shop.public.listing on purchase.listing_id = listing.id
+            where purchase.status = $1
+                and ($3::timestamptz is null or purchase.created >= $3)
+                and ($4::timestamptz is null or purchase.created < $4)
+        ),
+        bundle_share as (
+            select bundle_id, item_id,
+                case when total_price_cents > 0 then cumulative_price_cents else position end as upper_share,
+                case when total_price_cents > 0 then cumulative_price_cents - price_cents else position - 1 end as lower_share,
+                case when total_price_cents > 0 then total_price_cents else item_count end as total_share
+            from (
+                select bundle_item.bundle_id, bundle_item.item_id, item.price_cents::numeric as price_cents,
+                    sum(item.price_cents) over (partition by bundle_item.bundle_id order by bundle_item.item_id)::numeric as cumulative_price_cents,
+                    sum(item.price_cents) over (partition by bundle_item.bundle_id)::numeric as total_price_cents,
+                    row_number() over (partition by bundle_item.bundle_id order by bundle_item.item_id)::numeric as position,
+                    count(*) over (partition by bundle_item.bundle_id)::numeric as item_count
+                from shop.public.bundle_item
+                    inner join shop.public.item on bundle_item.item_id = item.id
+            ) as bundle_price
+        ),
+        sale_line as (
+            select item_id, purchase_id, created, marketplace_id, quantity, revenue_cents, marketplace_fee_cents, shipping_cost_cents
+            from purchase_sale
+            where item_id is not null
+            union all
+            select bundle_share.item_id, purchase_sale.purchase_id, purchase_sale.created, purchase_sale.marketplace_id, purchase_sale.quantity,
+                (div(purchase_sale.revenue_cents * bundle_share.upper_share, bundle_share.total_share)
+                    - div(purchase_sale.revenue_cents * bundle_share.lower_share, bundle_share.total_share))::bigint,
+                (div(purchase_sale.marketplace_fee_cents * bundle_share.upper_share, bundle_share.total_share)
+                    - div(purchase_sale.marketplace_fee_cents * bundle_share.lower_share, bundle_share.total_share))::bigint,
+                (div(purchase_sale.shipping_cost_cents * bundle_share.upper_share, bundle_share.total_share)
+                    - div(purchase_sale.shipping_cost_cents * bundle_share.lower_share, bundle_share.total_share))::bigint
+            from purchase_sale
+                inner join bundle_share on purchase_sale.bundle_id = bundle_share.bundle_id
+        )
+        select item.id as item_id, item.code as item_code, item.product_id, product.display_name as product_display_name,
+            item.acquisition_price_cents,
+            coalesce((select sum(item_refurbishment.cost_cents) from shop.public.item_refurbishment where item_refurbishment.item_id = item.id), 0)::bigint as "refurbishment_cost_cents!",
+            array(select product_category_association.category_id from shop.public.product_category_association where product_category_association.product_id = item.product_id) as "category_ids!",
+            array(select item_label_association.label_id from shop.public.item_label_association where item_label_association.item_id = item.id) as "label_ids!",
+            sale_line.purchase_id as "purchase_id?", sale_line.quantity as "quantity?", sale_line.marketplace_id as "marketplace_id?", marketplace.display_name as "marketplace_display_name?", sale_line.created as "sold?",
+            sale_line.revenue_cents as "revenue_cents?", sale_line.marketplace_fee_cents as "marketplace_fee_cents?", sale_line.shipping_cost_cents as "shipping_cost_cents?"
+        from shop.public.item
+            inner join shop.public.product on item.product_id = product.id
+            left join sale_line on sale_line.item_id = item.id
+            left join shop.public.marketplace on sale_line.marketplace_id = marketplace.id
+        where ($2::uuid is null or item.id = $2)
+            and ($2::uuid is not null or sale_line.purchase_id is not null)
+        order by max(sale_line.created) over (partition by item.id) desc, item.id, sale_line.created desc
+    "#,
+		PurchaseStatus::Fulfilled as i32,
+		item_id,
+		sold_after,
+		sold_before,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -0,0 +1,255 @@
+use crate::object::JsonHttpResponse;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ItemProfitEntity {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_id: Uuid,
+    pub product_display_name: String,
+    pub acquisition_price_cents: Option<i64>,
+    pub refurbishment_cost_cents: i64,
+    pub category_ids: Vec<Uuid>,
+    pub label_ids: Vec<Uuid>,
+    pub purchase_id: Option<Uuid>,
+    pub quantity: Option<i32>,
+    pub marketplace_id: Option<Uuid>,
+    pub marketplace_display_name: Option<String>,
+    pub sold: Option<DateTime<Utc>>,
+    pub revenue_cents: Option<i64>,
+    pub marketplace_fee_cents: Option<i64>,
+    pub shipping_cost_cents: Option<i64>,
+}
+
+/// What an item cost us and, if it has sold, what we made on it. Only fulfilled purchases count as sales.
+/// The costs of a stock record are per unit and count once for every unit sold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemProfit {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub product_id: Uuid,
+    pub product_display_name: String,
+    /// Counted as zero in the cost basis when unknown
+    pub acquisition_price_cents: Option<u32>,
+    pub refurbishment_cost_cents: u64,
+    /// Per unit
+    pub cost_basis_cents: u64,
+    /// Categories directly associated with the item's product
+    pub category_ids: Vec<Uuid>,
+    pub label_ids: Vec<Uuid>,
+    pub sale: Option<ItemSale>,
+    /// Sale proceeds less marketplace fees, shipping cost and the cost basis of the units sold. Absent until the item
+    /// sells.
+    pub profit_cents: Option<i64>,
+}
+
+/// The totals of an item's fulfilled purchases, of which a unique item has at most one, including its share of a
+/// purchase of its bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemSale {
+    pub purchase_count: u32,
+    /// Units sold across the purchases
+    pub quantity: u32,
+    /// The latest purchase, whose marketplace and time are those of the sale
+    pub purchase_id: Uuid,
+    pub marketplace_id: Uuid,
+    pub marketplace_display_name: String,
+    pub sold: DateTime<Utc>,
+    /// Subtotal plus shipping charged to the customer, less discounts. Tax is collected on behalf of others and excluded.
+    pub revenue_cents: i64,
+    pub marketplace_fee_cents: i64,
+    /// What we paid to fulfill the purchases; see [crate::purchase::Purchase::seller_cost_total_cents]
+    pub shipping_cost_cents: i64,
+}
+
+impl ItemSale {
+    /// Revenue less marketplace fees and shipping cost
+    pub fn get_net_proceeds_cents(&self) -> i64 {
+        self.revenue_cents - self.marketplace_fee_cents - self.shipping_cost_cents
+    }
+}
+
+impl ItemProfit {
+    /// Aggregates the rows of a single item, each of which holds at most one of its purchases. Absent if there are no
+    /// rows.
+    pub fn from_entities<'a>(entities: impl IntoIterator<Item = &'a ItemProfitEntity>) -> Option<ItemProfit> {
+        let mut entities = entities.into_iter().peekable();
+        let first: &ItemProfitEntity = *entities.peek()?;
+        let acquisition_price_cents: Option<u32> = first.acquisition_price_cents.map(|v| v as u32);
+        let refurbishment_cost_cents: u64 = first.refurbishment_cost_cents as u64;
+        let cost_basis_cents: u64 = u64::from(acquisition_price_cents.unwrap_or(0)) + refurbishment_cost_cents;
+
+        let mut sale: Option<ItemSale> = None;
+        for entity in entities {
+            let (Some(purchase_id), Some(marketplace_id), Some(sold)) = (entity.purchase_id, entity.marketplace_id, entity.sold) else {
+                continue;
+            };
+            let sale: &mut ItemSale = sale.get_or_insert_with(|| ItemSale {
+                purchase_count: 0,
+                quantity: 0,
+                purchase_id,
+                marketplace_id,
+                marketplace_display_name: entity.marketplace_display_name.clone().unwrap_or_default(),
+                sold,
+                revenue_cents: 0,
+                marketplace_fee_cents: 0,
+                shipping_cost_cents: 0,
+            });
+            if sold > sale.sold {
+                sale.purchase_id = purchase_id;
+                sale.marketplace_id = marketplace_id;
+                sale.marketplace_display_name = entity.marketplace_display_name.clone().unwrap_or_default();
+                sale.sold = sold;
+            }
+            sale.purchase_count += 1;
+            sale.quantity += entity.quantity.unwrap_or(0) as u32;
+            sale.revenue_cents += entity.revenue_cents.unwrap_or(0);
+            sale.marketplace_fee_cents += entity.marketplace_fee_cents.unwrap_or(0);
+            sale.shipping_cost_cents += entity.shipping_cost_cents.unwrap_or(0);
+        }
+        let profit_cents: Option<i64> = sale.as_ref()
+            .map(|sale| sale.get_net_proceeds_cents() - (cost_basis_cents * u64::from(sale.quantity)) as i64);
+
+        Some(ItemProfit {
+            item_id: first.item_id,
+            item_code: first.item_code.clone(),
+            product_id: first.product_id,
+            product_display_name: first.product_display_name.clone(),
+            acquisition_price_cents,
+            refurbishment_cost_cents,
+            cost_basis_cents,
+            category_ids: first.category_ids.clone(),
+            label_ids: first.label_ids.clone(),
+            sale,
+            profit_cents,
+        })
+    }
+}
+
+impl JsonHttpResponse for ItemProfit {}
+impl JsonHttpResponse for Vec<ItemProfit> {}
+
+/// The dimension by which sold items are aggregated into a [ProfitSummary]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ProfitGrouping {
+    #[default]
+    #[serde(rename = "product")]
+    Product,
+    /// An item counts toward every category directly associated with its product
+    #[serde(rename = "category")]
+    Category,
+    /// An item counts toward every one of its labels
+    #[serde(rename = "label")]
+    Label,
+    #[serde(rename = "marketplace")]
+    Marketplace,
+    #[serde(rename = "day")]
+    Day,
+    /// ISO 8601 weeks
+    #[serde(rename = "week")]
+    Week,
+    #[serde(rename = "month")]
+    Month,
+    #[serde(rename = "year")]
+    Year,
+}
+
+impl ProfitGrouping {
+    pub fn is_period(&self) -> bool {
+        matches!(self, ProfitGrouping::Day | ProfitGrouping::Week | ProfitGrouping::Month | ProfitGrouping::Year)
+    }
+
+    /// Whether each of an item's purchases belongs to a group of its own, rather than the item as a whole
+    pub fn is_per_purchase(&self) -> bool {
+        self.is_period() || *self == ProfitGrouping::Marketplace
+    }
+
+    /// The period containing the time of sale, e.g. "2026-10" for [ProfitGrouping::Month]. Absent for groupings which
+    /// are not periods.
+    pub fn get_period(&self, sold: &DateTime<Utc>) -> Option<String> {
+        let format: &str = match self {
+            ProfitGrouping::Day => "%Y-%m-%d",
+            ProfitGrouping::Week => "%G-W%V",
+            ProfitGrouping::Month => "%Y-%m",
+            ProfitGrouping::Year => "%Y",
+            ProfitGrouping::Product
+            | ProfitGrouping::Category
+            | ProfitGrouping::Label
+            | ProfitGrouping::Marketplace => return None,
+        };
+        Some(sold.format(format).to_string())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfitReportParameters {
+    #[serde(default)]
+    pub grouping: ProfitGrouping,
+    /// Inclusive
+    pub sold_after: Option<NaiveDate>,
+    /// Inclusive
+    pub sold_before: Option<NaiveDate>,
+}
+
+/// The totals of the items sold within a group, e.g. a product or a month
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfitSummary {
+    /// The ID of the product, category, label or marketplace, or the period
+    pub key: String,
+    pub display_name: String,
+    pub item_count: u64,
+    /// Units sold, which exceed the items sold only for stock records
+    pub unit_count: u64,
+    pub revenue_cents: i64,
+    pub marketplace_fee_cents: i64,
+    pub shipping_cost_cents: i64,
+    pub acquisition_cost_cents: u64,
+    pub refurbishment_cost_cents: u64,
+    pub profit_cents: i64,
+    /// Items whose acquisition price is unknown and counted as zero, overstating the profit
+    pub unknown_acquisition_price_count: u64,
+}
+
+impl ProfitSummary {
+    pub fn new(key: String, display_name: String) -> ProfitSummary {
+        ProfitSummary {
+            key,
+            display_name,
+            ..ProfitSummary::default()
+        }
+    }
+
+    /// Unsold items are ignored.
+    pub fn add(&mut self, item_profit: &ItemProfit) {
+        let (Some(sale), Some(profit_cents)) = (&item_profit.sale, item_profit.profit_cents) else {
+            return;
+        };
+        let quantity: u64 = u64::from(sale.quantity);
+        self.item_count += 1;
+        self.unit_count += quantity;
+        self.revenue_cents += sale.revenue_cents;
+        self.marketplace_fee_cents += sale.marketplace_fee_cents;
+        self.shipping_cost_cents += sale.shipping_cost_cents;
+        self.acquisition_cost_cents += u64::from(item_profit.acquisition_price_cents.unwrap_or(0)) * quantity;
+        self.refurbishment_cost_cents += item_profit.refurbishment_cost_cents * quantity;
+        self.profit_cents += profit_cents;
+        if item_profit.acquisition_price_cents.is_none() {
+            self.unknown_acquisition_price_count += 1;
+        }
+    }
+}
+
+/// Summaries ordered by period, or otherwise by display name, and their grand total
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfitReport {
+    pub grouping: ProfitGrouping,
+    pub sold_after: Option<NaiveDate>,
+    pub sold_before: Option<NaiveDate>,
+    pub summaries: Vec<ProfitSummary>,
+    /// Each sold item is counted once, even if it belongs to several groups
+    pub total: ProfitSummary,
+}
+
+impl JsonHttpResponse for ProfitReport {}
@@ -1,5 +1,6 @@
 use crate::category::category_api;
 use crate::product::product_api;
+use crate::trade_in::trade_in_api;
 use actix_web::web;
 
 pub fn configurer(config: &mut web::ServiceConfig) {
@@ -8,5 +9,7 @@ pub fn configurer(config: &mut web::ServiceConfig) {
             .configure(category_api::configurer_public))
         .service(web::scope(product_api::SCOPE_PATH)
             .configure(product_api::configurer_public))
+        .service(web::scope(trade_in_api::SCOPE_PATH)
+            .configure(trade_in_api::configurer_public))
     );
 }
\ No newline at end of file
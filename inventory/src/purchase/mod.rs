@@ -2,5 +2,6 @@ pub(crate) mod purchase_db;
 
 pub mod purchase_api;
 pub mod purchase_model;
+pub mod purchase_action;
 
 pub use purchase_model::*;
@@ -0,0 +1,39 @@
+use super::*;
+use crate::bundle::bundle_action;
+use crate::consignor::consignor_action;
+use crate::error::ShopError;
+use crate::item_stock::{item_stock_action, ItemStock};
+use crate::listing::Listing;
+use crate::ShopModel;
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// Records the purchase. A fulfilled purchase also removes the purchased units from the stock of the listed item, if it
+/// has a stock record, or else marks each item of the purchased bundle sold; a cancelled one never sold anything.
+/// A fulfilled purchase of a consigned item also records the payout owed to its consignor.
+/// The updated stock is returned so that marketplace quantities can be synchronized.
+pub async fn create_purchase(
+    pgpool: &PgPool,
+    purchase: &Purchase,
+    listing: &Listing,
+) -> Result<Option<ItemStock>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    let item_stock: Option<ItemStock> = match (&listing.item_id, &listing.bundle_id) {
+        (None, None) => return Err(ShopError::new(&format!("Listing is of neither an item nor a bundle; [{}]", listing.id))),
+        _ if !matches!(purchase.status, PurchaseStatus::Fulfilled) => None,
+        (Some(item_id), _) => item_stock_action::record_sale_in_transaction(
+            &mut transaction,
+            item_id,
+            purchase.quantity,
+        ).await?,
+        (None, Some(bundle_id)) => {
+            bundle_action::record_sale_in_transaction(&mut transaction, bundle_id, purchase.quantity).await?;
+            None
+        },
+    };
+    purchase_db::create_purchase(&mut *transaction, &purchase.to_entity()).await?;
+    if let Some(item_id) = &listing.item_id {
+        consignor_action::record_sale_in_transaction(&mut transaction, purchase, item_id).await?;
+    }
+    transaction.commit().await?;
+    Ok(item_stock)
+}
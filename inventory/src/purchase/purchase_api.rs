@@ -1,17 +1,25 @@
+use crate::ebay::{ebay_action, ebay_api};
+use crate::inventory_transfer::inventory_transfer_action;
+use crate::item_stock::{item_stock_action, item_stock_db, ItemStock, ItemStockMarketplaceError};
+use crate::listing::{listing_db, Listing};
 use crate::object::JsonHttpResponse;
-use crate::purchase::{purchase_db, PurchaseSerial};
+use crate::pagination::KeysetPaginationOptions;
+use crate::purchase::{purchase_action, purchase_db, Purchase, PurchaseReport, PurchaseSerial};
 use crate::{
-    unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel,
+    try_return, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel,
     ShopSerial,
 };
+use actix_web::cookie::Cookie;
 use actix_web::web::ServiceConfig;
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 pub fn configurer(config: &mut ServiceConfig) {
     config.service(
         web::scope("/purchase")
+            .route("", web::get().to(get_all_purchases_paged))
             .route("", web::post().to(create_purchase))
             .route("/{purchase_id}", web::get().to(get_purchase))
             .route(
@@ -21,15 +29,51 @@ pub fn configurer(config: &mut ServiceConfig) {
     );
 }
 
+/// A purchase of a stock record made outside of eBay updates the quantity of the item's published eBay offers, which
+/// eBay already does itself for its own sales. Offers which could not be updated are reported with the purchase.
 async fn create_purchase(
     pgpool: web::Data<PgPool>,
+    request: HttpRequest,
     purchase: web::Json<PurchaseSerial>,
 ) -> impl Responder {
-    let purchase = unwrap_result_else_400!(purchase.into_inner().try_to_model());
-    let query_result =
-        unwrap_result_else_500!(purchase_db::create_purchase(&pgpool, &purchase.to_entity()).await);
+    let purchase: Purchase = unwrap_result_else_400!(purchase.into_inner().try_to_model());
+    let listing: Listing = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        listing_db::get_listing(&pgpool, &purchase.listing_id).await
+    )).try_to_model());
 
-    HttpResponse::Ok().body(query_result.rows_affected().to_string())
+    // Require the eBay token up front so that published offers never drift from the recorded quantity
+    let stocked_item_id: Option<Uuid> = match &listing.item_id {
+        Some(item_id) => unwrap_result_else_500!(item_stock_db::get_item_stock(pgpool.get_ref(), item_id).await)
+            .map(|item_stock| item_stock.item_id),
+        None => None,
+    };
+    let published_listings: Vec<Listing> = match stocked_item_id {
+        Some(item_id) if !ebay_action::is_marketplace(&purchase.marketplace_id) => unwrap_result_else_500!(
+            inventory_transfer_action::get_published_marketplace_listings(&pgpool, &[item_id]).await
+        ),
+        _ => Vec::new(),
+    };
+    let user_access_token: Option<Cookie> = match published_listings.is_empty() {
+        true => None,
+        false => Some(try_return!(ebay_api::extract_user_token(&request))),
+    };
+
+    let item_stock: Option<ItemStock> = match purchase_action::create_purchase(&pgpool, &purchase, &listing).await {
+        Ok(item_stock) => item_stock,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    let marketplace_errors: Vec<ItemStockMarketplaceError> = match (&item_stock, &user_access_token) {
+        (Some(_), Some(user_access_token)) => {
+            item_stock_action::sync_marketplace_quantities(&pgpool, user_access_token.value(), &published_listings).await
+        },
+        _ => Vec::new(),
+    };
+
+    PurchaseReport {
+        purchase: purchase.to_serial(),
+        marketplace_errors,
+    }.to_http_response()
 }
 
 async fn get_purchase(pgpool: web::Data<PgPool>, purchase_id: web::Path<String>) -> impl Responder {
@@ -53,3 +97,19 @@ async fn get_purchase_listing(
     let listing = unwrap_result_else_500!(listing.try_to_model());
     listing.to_serial().to_http_response()
 }
+
+async fn get_all_purchases_paged(
+    pgpool: web::Data<PgPool>,
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        purchase_db::get_all_purchases_paged_created(&pgpool, &pagination_options).await
+    );
+
+    let mut purchase_serial_vec = Vec::with_capacity(entities.len());
+    for entity in entities {
+        purchase_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (purchase_serial_vec, pagination_result).to_http_response()
+}
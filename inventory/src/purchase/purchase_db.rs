@@ -1,17 +1,20 @@
 use crate::error::ShopError;
 use crate::listing::ListingEntity;
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
 use crate::purchase::PurchaseEntity;
+use chrono::{DateTime, Utc};
 use sqlx::postgres::PgQueryResult;
-use sqlx::{query, query_as, PgPool};
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 pub async fn create_purchase(
-    pgpool: &PgPool,
+    pgexecutor: impl PgExecutor<'_>,
     purchase_entity: &PurchaseEntity,
 ) -> Result<PgQueryResult, ShopError> {
     query!("\
-        insert into shop.public.purchase (id, marketplace_id, external_id, customer_id, contact_email_address, listing_id, status, cost_subtotal_cents, cost_tax_cents, cost_shipping_cents, cost_discount_cents, seller_cost_total_cents, shipping_method, payment_method, note, shipping_street_address, shipping_municipality, shipping_district, shipping_postal_area, shipping_country, billing_street_address, billing_municipality, billing_district, billing_postal_area, billing_country, created, updated) \
-        values ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18,$19,$20,$21,$22,$23,$24,$25,$26,$27) \
+        insert into shop.public.purchase (id, marketplace_id, external_id, customer_id, contact_email_address, listing_id, quantity, status, cost_subtotal_cents, cost_tax_cents, cost_shipping_cents, cost_discount_cents, seller_cost_total_cents, marketplace_fee_cents, shipping_method, payment_method, note, shipping_street_address, shipping_municipality, shipping_district, shipping_postal_area, shipping_country, billing_street_address, billing_municipality, billing_district, billing_postal_area, billing_country, created, updated) \
+        values ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,$18,$19,$20,$21,$22,$23,$24,$25,$26,$27,$28,$29) \
     ",
         purchase_entity.id,
         purchase_entity.marketplace_id,
@@ -19,12 +22,14 @@ pub async fn create_purchase(
         purchase_entity.customer_id,
         purchase_entity.contact_email_address,
         purchase_entity.listing_id,
+        purchase_entity.quantity,
         purchase_entity.status,
         purchase_entity.cost_subtotal_cents,
         purchase_entity.cost_tax_cents,
         purchase_entity.cost_shipping_cents,
         purchase_entity.cost_discount_cents,
         purchase_entity.seller_cost_total_cents,
+        purchase_entity.marketplace_fee_cents,
         purchase_entity.shipping_method,
         purchase_entity.payment_method,
         purchase_entity.note,
@@ -41,7 +46,7 @@ pub async fn create_purchase(
         purchase_entity.created,
         purchase_entity.updated,
     )
-        .execute(pgpool)
+        .execute(pgexecutor)
         .await
         .map_err(|e| ShopError::from(e))
 }
@@ -69,7 +74,7 @@ pub async fn get_purchase_listing(
     purchase_id: &Uuid,
 ) -> Result<Option<ListingEntity>, ShopError> {
     query_as!(ListingEntity, "\
-        select listing.id, listing.item_id, listing.marketplace_id, listing.status, listing.created, listing.updated \
+        select listing.id, listing.item_id, listing.bundle_id, listing.marketplace_id, listing.status, listing.published, listing.created, listing.updated \
         from shop.public.listing \
         inner join shop.public.purchase on listing.id = purchase.listing_id \
         where purchase.id = $1
@@ -80,3 +85,23 @@ pub async fn get_purchase_listing(
         .await
         .map_err(|e| ShopError::from(e))
 }
+
+/// Most recent purchases first
+pub async fn get_all_purchases_paged_created(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<DateTime<Utc>>,
+) -> Result<(Vec<PurchaseEntity>, KeysetPaginationResult<DateTime<Utc>>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, marketplace_id, external_id, customer_id, contact_email_address, listing_id, quantity, status, cost_subtotal_cents, cost_tax_cents, cost_shipping_cents, cost_discount_cents, seller_cost_total_cents, marketplace_fee_cents, shipping_method, payment_method, note, shipping_street_address, shipping_municipality, shipping_district, shipping_postal_area, shipping_country, billing_street_address, billing_municipality, billing_district, billing_postal_area, billing_country, created, updated \
+        from shop.public.purchase \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "created",
+        &Direction::Descending,
+        keyset_pagination_options,
+        |purchase: &PurchaseEntity| (purchase.created, purchase.id),
+    ).await
+}
@@ -1,4 +1,5 @@
 use crate::error::ShopError;
+use crate::item_stock::ItemStockMarketplaceError;
 use crate::object::JsonHttpResponse;
 use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
 use chrono::{DateTime, Utc};
@@ -6,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use strum::FromRepr;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, sqlx::FromRow)]
 pub struct PurchaseEntity {
     pub id: Uuid,
     pub marketplace_id: Uuid,
@@ -14,12 +15,14 @@ pub struct PurchaseEntity {
     pub customer_id: Option<Uuid>,
     pub contact_email_address: String,
     pub listing_id: Uuid,
+    pub quantity: i32,
     pub status: i32,
     pub cost_subtotal_cents: i64,
     pub cost_tax_cents: i64,
     pub cost_shipping_cents: i64,
     pub cost_discount_cents: i64,
     pub seller_cost_total_cents: i64,
+    pub marketplace_fee_cents: i64,
     pub shipping_method: i32,
     pub payment_method: i32,
     pub note: Option<String>,
@@ -49,12 +52,14 @@ pub struct Purchase {
     pub customer_id: Option<Uuid>,
     pub contact_email_address: String,
     pub listing_id: Uuid,
+    pub quantity: u32, // Represents the units sold, which may exceed one only for stock records
     pub status: PurchaseStatus,
     pub cost_subtotal_cents: i64,
     pub cost_tax_cents: i64,
     pub cost_shipping_cents: i64,
     pub cost_discount_cents: i64,
-    pub seller_cost_total_cents: i64, // Represents the total fees we paid to fulfill this transaction (e.g. marketplace, payment processor)
+    pub seller_cost_total_cents: i64, // Represents what we paid to fulfill this transaction (e.g. postage, packaging)
+    pub marketplace_fee_cents: i64, // Represents the fees the marketplace charged for this transaction (e.g. final value, payment processor)
     pub shipping_method: ShippingMethod,
     pub payment_method: PaymentMethod,
     pub note: Option<String>,
@@ -84,12 +89,14 @@ impl ShopModel for Purchase {
             customer_id: self.customer_id.clone(),
             contact_email_address: self.contact_email_address.clone(),
             listing_id: self.listing_id.clone(),
+            quantity: self.quantity,
             status: self.status.clone() as u8,
             cost_subtotal_cents: self.cost_subtotal_cents.clone(),
             cost_tax_cents: self.cost_tax_cents.clone(),
             cost_shipping_cents: self.cost_shipping_cents.clone(),
             cost_discount_cents: self.cost_discount_cents.clone(),
             seller_cost_total_cents: self.seller_cost_total_cents.clone(),
+            marketplace_fee_cents: self.marketplace_fee_cents.clone(),
             shipping_method: self.shipping_method.clone() as u8,
             payment_method: self.payment_method.clone() as u8,
             note: self.note.clone(),
@@ -109,6 +116,10 @@ impl ShopModel for Purchase {
     }
 
     fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        if serial.quantity == 0 || serial.quantity > i32::MAX as u32 {
+            return Err(ShopError::new(&format!("Invalid purchase quantity; [{}]", serial.quantity)));
+        }
+
         Ok(Purchase {
             id: object::random_uuid(),
             marketplace_id: serial.marketplace_id.clone(),
@@ -116,12 +127,14 @@ impl ShopModel for Purchase {
             customer_id: serial.customer_id.clone(),
             contact_email_address: serial.contact_email_address.clone(),
             listing_id: serial.listing_id.clone(),
+            quantity: serial.quantity,
             status: PurchaseStatus::try_from_repr(serial.status.clone())?,
             cost_subtotal_cents: serial.cost_subtotal_cents.clone(),
             cost_tax_cents: serial.cost_tax_cents.clone(),
             cost_shipping_cents: serial.cost_shipping_cents.clone(),
             cost_discount_cents: serial.cost_discount_cents.clone(),
             seller_cost_total_cents: serial.seller_cost_total_cents.clone(),
+            marketplace_fee_cents: serial.marketplace_fee_cents.clone(),
             shipping_method: ShippingMethod::try_from_repr(
                 serial.shipping_method.clone(),
             )?,
@@ -152,12 +165,14 @@ impl ShopModel for Purchase {
             customer_id: self.customer_id.clone(),
             contact_email_address: self.contact_email_address.clone(),
             listing_id: self.listing_id.clone(),
+            quantity: self.quantity as i32,
             status: i32::from(self.status.clone() as u8),
             cost_subtotal_cents: self.cost_subtotal_cents.clone(),
             cost_tax_cents: self.cost_tax_cents.clone(),
             cost_shipping_cents: self.cost_shipping_cents.clone(),
             cost_discount_cents: self.cost_discount_cents.clone(),
             seller_cost_total_cents: self.seller_cost_total_cents.clone(),
+            marketplace_fee_cents: self.marketplace_fee_cents.clone(),
             shipping_method: i32::from(self.shipping_method.clone() as u8),
             payment_method: i32::from(self.payment_method.clone() as u8),
             note: self.note.clone(),
@@ -184,12 +199,14 @@ impl ShopModel for Purchase {
             customer_id: entity.customer_id.clone(),
             contact_email_address: entity.contact_email_address.clone(),
             listing_id: entity.listing_id.clone(),
+            quantity: entity.quantity as u32,
             status: PurchaseStatus::try_from_repr(entity.status.clone() as u8)?,
             cost_subtotal_cents: entity.cost_subtotal_cents.clone(),
             cost_tax_cents: entity.cost_tax_cents.clone(),
             cost_shipping_cents: entity.cost_shipping_cents.clone(),
             cost_discount_cents: entity.cost_discount_cents.clone(),
             seller_cost_total_cents: entity.seller_cost_total_cents.clone(),
+            marketplace_fee_cents: entity.marketplace_fee_cents.clone(),
             shipping_method: ShippingMethod::try_from_repr(
                 entity.shipping_method.clone() as u8,
             )?,
@@ -251,12 +268,16 @@ pub struct PurchaseSerial {
     pub customer_id: Option<Uuid>,
     pub contact_email_address: String,
     pub listing_id: Uuid,
+    #[serde(default = "default_quantity")]
+    pub quantity: u32, // Represents the units sold, which may exceed one only for stock records
     pub status: u8,
     pub cost_subtotal_cents: i64,
     pub cost_tax_cents: i64,
     pub cost_shipping_cents: i64,
     pub cost_discount_cents: i64,
-    pub seller_cost_total_cents: i64, // Represents the total fees we paid to fulfill this transaction (e.g. marketplace, payment processor)
+    pub seller_cost_total_cents: i64, // Represents what we paid to fulfill this transaction (e.g. postage, packaging)
+    #[serde(default)]
+    pub marketplace_fee_cents: i64, // Represents the fees the marketplace charged for this transaction (e.g. final value, payment processor)
     pub shipping_method: u8,
     pub payment_method: u8,
     pub note: Option<String>,
@@ -274,9 +295,22 @@ pub struct PurchaseSerial {
     pub updated: DateTime<Utc>,
 }
 
+fn default_quantity() -> u32 {
+    1
+}
+
 impl ShopSerial for PurchaseSerial {
     type Model = Purchase;
 }
 
 impl JsonHttpResponse for PurchaseSerial {}
 impl JsonHttpResponse for Vec<PurchaseSerial> {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PurchaseReport {
+    pub purchase: PurchaseSerial,
+    /// Published eBay offers which could not be updated to the new quantity. The purchase itself is retained.
+    pub marketplace_errors: Vec<ItemStockMarketplaceError>,
+}
+
+impl JsonHttpResponse for PurchaseReport {}
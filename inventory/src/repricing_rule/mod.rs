@@ -0,0 +1,7 @@
+pub(crate) mod repricing_rule_db;
+
+pub mod repricing_rule_api;
+pub mod repricing_rule_model;
+pub mod repricing_rule_action;
+
+pub use repricing_rule_model::*;
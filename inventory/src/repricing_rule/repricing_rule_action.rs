@@ -0,0 +1,212 @@
+use super::*;
+use crate::ebay::ebay_action;
+use crate::error::ShopError;
+use crate::inventory_transfer::inventory_transfer_action;
+use crate::item::{item_db, ItemCostBasis};
+use crate::listing::Listing;
+use crate::price_history::{price_history_action, PriceChangeMarketplaceError, PriceHistory};
+use crate::{ShopEntity, ShopModel};
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Highest priority first
+pub async fn get_all_repricing_rules(pgpool: &PgPool) -> Result<Vec<RepricingRule>, ShopError> {
+    repricing_rule_db::get_all_repricing_rules(pgpool).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+pub async fn create_repricing_rule(pgpool: &PgPool, mut repricing_rule: RepricingRule) -> Result<RepricingRule, ShopError> {
+    repricing_rule.created = Utc::now();
+    repricing_rule.updated = repricing_rule.created;
+    repricing_rule_db::create_repricing_rule(pgpool, &repricing_rule.to_entity()).await?;
+    Ok(repricing_rule)
+}
+
+/// Replaces every field of the current rule except for the creation time.
+pub async fn update_repricing_rule(
+    pgpool: &PgPool,
+    current: &RepricingRule,
+    mut repricing_rule: RepricingRule,
+) -> Result<RepricingRule, ShopError> {
+    repricing_rule.id = current.id;
+    repricing_rule.created = current.created;
+    repricing_rule.updated = Utc::now();
+    repricing_rule_db::update_repricing_rule(pgpool, &repricing_rule.to_entity()).await?;
+    Ok(repricing_rule)
+}
+
+/// The markdowns the given rules would make at the given time. The rules must be ordered by priority; each item is
+/// marked down by at most the first rule whose scope includes it, even if that rule is not yet due for the item.
+pub async fn plan_repricing(
+    pgpool: &PgPool,
+    repricing_rules: &[RepricingRule],
+    now: DateTime<Utc>,
+) -> Result<RepricingPlan, ShopError> {
+    let mut plan = RepricingPlan {
+        entries: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let mut matched_item_ids: HashSet<Uuid> = HashSet::new();
+
+    for repricing_rule in repricing_rules {
+        let candidates: Vec<RepricingCandidateEntity> =
+            repricing_rule_db::get_all_repricing_candidates(pgpool, &repricing_rule.to_entity()).await?;
+        for candidate in candidates {
+            if !matched_item_ids.insert(candidate.item_id) {
+                continue;
+            }
+
+            let unchanged_since: DateTime<Utc> = candidate.last_price_change
+                .map_or(candidate.listed_since, |last_price_change| last_price_change.max(candidate.listed_since));
+            if now - unchanged_since < TimeDelta::days(i64::from(repricing_rule.listed_days)) {
+                continue;
+            }
+
+            let skip = |message: &str| RepricingSkip {
+                item_id: candidate.item_id,
+                repricing_rule_id: repricing_rule.id,
+                message: message.to_string(),
+            };
+
+            let floor_price_cents: Option<u32> = match repricing_rule.floor_markup_percent {
+                None => None,
+                Some(_) => {
+                    let item_cost_basis: Option<ItemCostBasis> = item_db::get_item_cost_basis(pgpool, &candidate.item_id).await?
+                        .map(|entity| ItemCostBasis::from(&entity));
+                    match item_cost_basis {
+                        Some(item_cost_basis) if item_cost_basis.acquisition_price_cents.is_some() =>
+                            repricing_rule.get_floor_price_cents(&item_cost_basis),
+                        _ => {
+                            plan.skipped.push(skip("Item has no acquisition price, so its floor price is unknown"));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let old_price_cents: u32 = candidate.price_cents as u32;
+            let new_price_cents: u32 = repricing_rule.get_markdown_price_cents(old_price_cents)
+                .max(floor_price_cents.unwrap_or(0));
+            if new_price_cents >= old_price_cents {
+                plan.skipped.push(skip("Item price is already at or below its floor price"));
+                continue;
+            }
+
+            plan.entries.push(RepricingPlanEntry {
+                item_id: candidate.item_id,
+                item_code: candidate.item_code,
+                repricing_rule_id: repricing_rule.id,
+                old_price_cents,
+                new_price_cents,
+                floor_price_cents,
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Applies every enabled rule which is due, recording each markdown as a [PriceHistory] and updating the prices of
+/// published eBay offers. Without a user access token, items with published eBay listings are skipped so that their
+/// offers never drift from the recorded price.
+pub async fn run_repricing(
+    pgpool: &PgPool,
+    user_access_token: Option<&str>,
+    initiated_by: Option<String>,
+) -> Result<RepricingRunReport, ShopError> {
+    let repricing_rules: Vec<RepricingRule> = get_all_repricing_rules(pgpool).await?
+        .into_iter()
+        .filter(|repricing_rule| repricing_rule.enabled)
+        .collect();
+    let plan: RepricingPlan = plan_repricing(pgpool, &repricing_rules, Utc::now()).await?;
+
+    let mut report = RepricingRunReport {
+        price_changes: Vec::new(),
+        skipped: plan.skipped,
+        marketplace_errors: Vec::new(),
+    };
+    let mut changed_listings: Vec<Listing> = Vec::new();
+
+    for entry in plan.entries {
+        let published_listings: Vec<Listing> =
+            inventory_transfer_action::get_published_marketplace_listings(pgpool, &[entry.item_id]).await?;
+        if !published_listings.is_empty() && user_access_token.is_none() {
+            report.skipped.push(RepricingSkip {
+                item_id: entry.item_id,
+                repricing_rule_id: entry.repricing_rule_id,
+                message: "Item has published eBay listings and no eBay user token is available".to_string(),
+            });
+            continue;
+        }
+
+        let display_name: &str = repricing_rules.iter()
+            .find(|repricing_rule| repricing_rule.id == entry.repricing_rule_id)
+            .map_or("", |repricing_rule| repricing_rule.display_name.as_str());
+        let reason: String = format!("Repricing rule; [{}]", display_name);
+        let price_history: Option<PriceHistory> = match price_history_action::change_item_price(
+            pgpool,
+            &entry.item_id,
+            entry.new_price_cents,
+            initiated_by.clone(),
+            Some(reason),
+        ).await {
+            Ok(price_history) => price_history,
+            Err(error) => {
+                log::error!("Failed to apply repricing rule; [{}]; [{}]; {}", entry.repricing_rule_id, entry.item_id, error);
+                report.skipped.push(RepricingSkip {
+                    item_id: entry.item_id,
+                    repricing_rule_id: entry.repricing_rule_id,
+                    message: error.message,
+                });
+                continue;
+            }
+        };
+
+        if let Some(price_history) = price_history {
+            report.price_changes.push(price_history.to_serial());
+            changed_listings.extend(published_listings);
+        }
+    }
+
+    if let Some(user_access_token) = user_access_token {
+        let marketplace_errors: Vec<PriceChangeMarketplaceError> =
+            price_history_action::sync_marketplace_prices(pgpool, user_access_token, &changed_listings).await;
+        report.marketplace_errors = marketplace_errors;
+    }
+
+    Ok(report)
+}
+
+/// Applies the enabled repricing rules every [REPRICING_INTERVAL_SECONDS], starting immediately. Should be called only
+/// once, from within the actix runtime.
+pub fn spawn_scheduler(pgpool: PgPool) {
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(Duration::from_secs(REPRICING_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+
+            let user_access_token: Option<String> = match ebay_action::get_stored_user_access_token().await {
+                Ok(user_access_token) => Some(user_access_token),
+                Err(error) => {
+                    log::warn!("Repricing without an ebay user token; items with published ebay listings are skipped; {}", error);
+                    None
+                }
+            };
+
+            match run_repricing(&pgpool, user_access_token.as_deref(), Some(REPRICING_SCHEDULER_NAME.to_string())).await {
+                Ok(report) => log::info!(
+                    "Applied repricing rules; [{} price changes]; [{} skipped]; [{} marketplace errors]",
+                    report.price_changes.len(),
+                    report.skipped.len(),
+                    report.marketplace_errors.len(),
+                ),
+                Err(error) => log::error!("Failed to apply repricing rules; {}", error),
+            }
+        }
+    });
+}
@@ -0,0 +1,139 @@
+use super::*;
+use crate::ebay::ebay_api;
+use crate::http;
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::cookie::Cookie;
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::postgres::PgQueryResult;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/repricing_rule")
+            .route("", web::get().to(get_all_repricing_rules))
+            .route("", web::post().to(create_repricing_rule))
+            .route("/preview", web::get().to(preview_repricing))
+            .route("/run", web::post().to(run_repricing))
+            .route("/{repricing_rule_id}", web::get().to(get_repricing_rule))
+            .route("/{repricing_rule_id}", web::put().to(update_repricing_rule))
+            .route("/{repricing_rule_id}", web::delete().to(delete_repricing_rule))
+            .route("/{repricing_rule_id}/preview", web::get().to(preview_repricing_rule))
+    );
+}
+
+async fn get_all_repricing_rules(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let repricing_rules: Vec<RepricingRule> = unwrap_result_else_500!(
+        repricing_rule_action::get_all_repricing_rules(&pgpool).await
+    );
+    repricing_rules.iter()
+        .map(|repricing_rule| repricing_rule.to_serial())
+        .collect::<Vec<RepricingRuleSerial>>()
+        .to_http_response()
+}
+
+async fn create_repricing_rule(
+    pgpool: web::Data<PgPool>,
+    body: web::Json<RepricingRuleSerial>,
+) -> HttpResponse {
+    let repricing_rule: RepricingRule = match body.into_inner().try_to_model() {
+        Ok(repricing_rule) => repricing_rule,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    match repricing_rule_action::create_repricing_rule(&pgpool, repricing_rule).await {
+        Ok(repricing_rule) => repricing_rule.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// The markdowns the enabled rules would make if applied now. Nothing is changed.
+async fn preview_repricing(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let repricing_rules: Vec<RepricingRule> = unwrap_result_else_500!(
+        repricing_rule_action::get_all_repricing_rules(&pgpool).await
+    )
+        .into_iter()
+        .filter(|repricing_rule| repricing_rule.enabled)
+        .collect();
+    let plan: RepricingPlan = unwrap_result_else_500!(
+        repricing_rule_action::plan_repricing(&pgpool, &repricing_rules, Utc::now()).await
+    );
+    plan.to_http_response()
+}
+
+/// Applies the enabled rules now rather than waiting for the scheduler. Items with published eBay listings are
+/// skipped unless the eBay user token is present.
+async fn run_repricing(pgpool: web::Data<PgPool>, request: HttpRequest) -> HttpResponse {
+    let user_access_token: Option<Cookie> = ebay_api::extract_user_token(&request).ok();
+    let initiated_by: Option<String> = http::extract_remote_user(&request);
+
+    let report: RepricingRunReport = unwrap_result_else_500!(repricing_rule_action::run_repricing(
+        &pgpool,
+        user_access_token.as_ref().map(|user_access_token| user_access_token.value()),
+        initiated_by,
+    ).await);
+    report.to_http_response()
+}
+
+async fn get_repricing_rule(
+    pgpool: web::Data<PgPool>,
+    repricing_rule_id: web::Path<String>,
+) -> HttpResponse {
+    let repricing_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&repricing_rule_id.into_inner()));
+    let repricing_rule: RepricingRule = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        repricing_rule_db::get_repricing_rule(pgpool.get_ref(), &repricing_rule_id).await
+    )).try_to_model());
+    repricing_rule.to_serial().to_http_response()
+}
+
+async fn update_repricing_rule(
+    pgpool: web::Data<PgPool>,
+    repricing_rule_id: web::Path<String>,
+    body: web::Json<RepricingRuleSerial>,
+) -> HttpResponse {
+    let repricing_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&repricing_rule_id.into_inner()));
+    let repricing_rule: RepricingRule = match body.into_inner().try_to_model() {
+        Ok(repricing_rule) => repricing_rule,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: RepricingRule = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        repricing_rule_db::get_repricing_rule(pgpool.get_ref(), &repricing_rule_id).await
+    )).try_to_model());
+
+    match repricing_rule_action::update_repricing_rule(&pgpool, &current, repricing_rule).await {
+        Ok(repricing_rule) => repricing_rule.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_repricing_rule(
+    pgpool: web::Data<PgPool>,
+    repricing_rule_id: web::Path<String>,
+) -> HttpResponse {
+    let repricing_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&repricing_rule_id.into_inner()));
+    let query_result: PgQueryResult = unwrap_result_else_500!(
+        repricing_rule_db::delete_repricing_rule(pgpool.get_ref(), &repricing_rule_id).await
+    );
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
+
+/// The markdowns the rule would make if it were the only rule and applied now, whether or not it is enabled.
+async fn preview_repricing_rule(
+    pgpool: web::Data<PgPool>,
+    repricing_rule_id: web::Path<String>,
+) -> HttpResponse {
+    let repricing_rule_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&repricing_rule_id.into_inner()));
+    let repricing_rule: RepricingRule = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        repricing_rule_db::get_repricing_rule(pgpool.get_ref(), &repricing_rule_id).await
+    )).try_to_model());
+    let plan: RepricingPlan = unwrap_result_else_500!(
+        repricing_rule_action::plan_repricing(&pgpool, &[repricing_rule], Utc::now()).await
+    );
+    plan.to_http_response()
+}
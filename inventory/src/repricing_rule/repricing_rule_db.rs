@@ -0,0 +1,130 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item::ItemStatus;
+use crate::listing::ListingStatus;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor};
+use uuid::Uuid;
+
+/// Highest priority first
+pub async fn get_all_repricing_rules(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<RepricingRuleEntity>, ShopError> {
+    query_as!(RepricingRuleEntity, "
+        select id, display_name, enabled, priority, category_id, label_id, condition, listed_days, markdown_percent, floor_markup_percent, created, updated
+        from shop.public.repricing_rule
+        order by priority desc, created, id
+    ")
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_repricing_rule(pgexecutor: impl PgExecutor<'_>, repricing_rule_id: &Uuid) -> Result<Option<RepricingRuleEntity>, ShopError> {
+    query_as!(RepricingRuleEntity, "
+        select id, display_name, enabled, priority, category_id, label_id, condition, listed_days, markdown_percent, floor_markup_percent, created, updated
+        from shop.public.repricing_rule
+        where id = $1
+    ",
+		repricing_rule_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_repricing_rule(pgexecutor: impl PgExecutor<'_>, repricing_rule: &RepricingRuleEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.repricing_rule (id, display_name, enabled, priority, category_id, label_id, condition, listed_days, markdown_percent, floor_markup_percent, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+    ",
+		repricing_rule.id,
+		repricing_rule.display_name,
+		repricing_rule.enabled,
+		repricing_rule.priority,
+		repricing_rule.category_id,
+		repricing_rule.label_id,
+		repricing_rule.condition,
+		repricing_rule.listed_days,
+		repricing_rule.markdown_percent,
+		repricing_rule.floor_markup_percent,
+		repricing_rule.created,
+		repricing_rule.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The creation time is never changed.
+pub async fn update_repricing_rule(pgexecutor: impl PgExecutor<'_>, repricing_rule: &RepricingRuleEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.repricing_rule
+        set (display_name, enabled, priority, category_id, label_id, condition, listed_days, markdown_percent, floor_markup_percent, updated)
+            = ($2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        where id = $1
+    ",
+		repricing_rule.id,
+		repricing_rule.display_name,
+		repricing_rule.enabled,
+		repricing_rule.priority,
+		repricing_rule.category_id,
+		repricing_rule.label_id,
+		repricing_rule.condition,
+		repricing_rule.listed_days,
+		repricing_rule.markdown_percent,
+		repricing_rule.floor_markup_percent,
+		repricing_rule.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_repricing_rule(pgexecutor: impl PgExecutor<'_>, repricing_rule_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.repricing_rule
+        where id = $1
+    ",
+		repricing_rule_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Items with a published listing, available for sale, and within the scope of the rule. Whether each item is due for
+/// a markdown is left to the caller.
+pub async fn get_all_repricing_candidates(
+    pgexecutor: impl PgExecutor<'_>,
+    repricing_rule: &RepricingRuleEntity,
+) -> Result<Vec<RepricingCandidateEntity>, ShopError> {
+    let item_statuses: Vec<i32> = vec![ItemStatus::CompleteUnlisted as i32, ItemStatus::CompleteListed as i32];
+    query_as!(RepricingCandidateEntity, r#"
+        select item.id as item_id, item.code as item_code, item.price_cents,
+            min(coalesce(listing.published, listing.updated)) as "listed_since!",
+            (select max(price_history.created) from shop.public.price_history where price_history.item_id = item.id) as last_price_change
+        from shop.public.item
+            inner join shop.public.listing on listing.item_id = item.id
+        where listing.status = $1
+            and item.status = any($2)
+            and ($3::uuid is null or exists (
+                select 1 from shop.public.product_category_association
+                where product_category_association.product_id = item.product_id and product_category_association.category_id = $3
+            ))
+            and ($4::uuid is null or exists (
+                select 1 from shop.public.item_label_association
+                where item_label_association.item_id = item.id and item_label_association.label_id = $4
+            ))
+            and ($5::int is null or item.condition = $5)
+        group by item.id
+        order by item.code
+    "#,
+		ListingStatus::Published as i32,
+		&item_statuses,
+		repricing_rule.category_id,
+		repricing_rule.label_id,
+		repricing_rule.condition,
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
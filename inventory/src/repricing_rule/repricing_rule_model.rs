@@ -0,0 +1,297 @@
+use crate::error::ShopError;
+use crate::item::{ItemCondition, ItemCostBasis};
+use crate::object::JsonHttpResponse;
+use crate::price_history::{PriceChangeMarketplaceError, PriceHistorySerial};
+use crate::{object, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Interval between scheduled applications of the enabled repricing rules
+pub const REPRICING_INTERVAL_SECONDS: u64 = 60 * 60;
+/// Recorded as the initiator of scheduled price changes
+pub const REPRICING_SCHEDULER_NAME: &str = "repricing_scheduler";
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct RepricingRuleEntity {
+    pub id: Uuid,
+    pub display_name: String,
+    pub enabled: bool,
+    pub priority: i32,
+    pub category_id: Option<Uuid>,
+    pub label_id: Option<Uuid>,
+    pub condition: Option<i32>,
+    pub listed_days: i32,
+    pub markdown_percent: i32,
+    pub floor_markup_percent: Option<i32>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for RepricingRuleEntity {
+    type Model = RepricingRule;
+}
+
+/// Marks down the price of listed items which have not sold for a while, e.g. "reduce by 10% after 60 days listed,
+/// floor at cost basis + 20%". An item is due for a markdown once it has been listed, and its price unchanged, for
+/// [RepricingRule::listed_days]; so the markdown repeats until the item sells or reaches its floor.
+#[derive(Debug, Clone)]
+pub struct RepricingRule {
+    pub id: Uuid,
+    pub display_name: String,
+    /// Disabled rules are never applied, but may still be previewed
+    pub enabled: bool,
+    /// Of all the enabled rules matching an item, only the one with the highest priority applies
+    pub priority: i32,
+    /// Limits the rule to items whose product is directly associated with the category
+    pub category_id: Option<Uuid>,
+    /// Limits the rule to items with the label
+    pub label_id: Option<Uuid>,
+    /// Limits the rule to items in the condition
+    pub condition: Option<ItemCondition>,
+    pub listed_days: u32,
+    pub markdown_percent: u8,
+    /// The price never falls below the item's cost basis plus this percentage of it
+    pub floor_markup_percent: Option<u32>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl RepricingRule {
+    /// Rounded down to the cent
+    pub fn get_markdown_price_cents(&self, price_cents: u32) -> u32 {
+        (u64::from(price_cents) * u64::from(100 - self.markdown_percent) / 100) as u32
+    }
+
+    /// Rounded up to the cent. Absent if the rule has no floor.
+    pub fn get_floor_price_cents(&self, item_cost_basis: &ItemCostBasis) -> Option<u32> {
+        self.floor_markup_percent.map(|floor_markup_percent| {
+            let floor: u64 = (item_cost_basis.cost_basis_cents * (100 + u64::from(floor_markup_percent))).div_ceil(100);
+            u32::try_from(floor).unwrap_or(u32::MAX)
+        })
+    }
+}
+
+impl ShopModel for RepricingRule {
+    type Entity = RepricingRuleEntity;
+    type Serial = RepricingRuleSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        RepricingRuleSerial {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            enabled: self.enabled.clone(),
+            priority: self.priority.clone(),
+            category_id: self.category_id.clone(),
+            label_id: self.label_id.clone(),
+            condition: self.condition.clone().map(|condition| condition as u8),
+            listed_days: self.listed_days.clone(),
+            markdown_percent: self.markdown_percent.clone(),
+            floor_markup_percent: self.floor_markup_percent.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let display_name: String = serial.display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err(ShopError::new("Repricing rule display name must not be empty"));
+        }
+        if serial.listed_days == 0 || serial.listed_days > i32::MAX as u32 {
+            return Err(ShopError::new(&format!("Listed days must be positive; [{}]", serial.listed_days)));
+        }
+        if !(1..=99).contains(&serial.markdown_percent) {
+            return Err(ShopError::new(&format!("Markdown percent must be between 1 and 99; [{}]", serial.markdown_percent)));
+        }
+        if serial.floor_markup_percent.is_some_and(|floor_markup_percent| floor_markup_percent > i32::MAX as u32) {
+            return Err(ShopError::new(&format!("Floor markup percent is too large; [{:?}]", serial.floor_markup_percent)));
+        }
+
+        Ok(RepricingRule {
+            id: object::random_uuid(),
+            display_name,
+            enabled: serial.enabled.clone(),
+            priority: serial.priority.clone(),
+            category_id: serial.category_id.clone(),
+            label_id: serial.label_id.clone(),
+            condition: serial.condition.map(ItemCondition::try_from_repr).transpose()?,
+            listed_days: serial.listed_days.clone(),
+            markdown_percent: serial.markdown_percent.clone(),
+            floor_markup_percent: serial.floor_markup_percent.clone(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        RepricingRuleEntity {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            enabled: self.enabled.clone(),
+            priority: self.priority.clone(),
+            category_id: self.category_id.clone(),
+            label_id: self.label_id.clone(),
+            condition: self.condition.clone().map(|condition| condition as i32),
+            listed_days: self.listed_days.clone() as i32,
+            markdown_percent: i32::from(self.markdown_percent),
+            floor_markup_percent: self.floor_markup_percent.map(|v| v as i32),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(RepricingRule {
+            id: entity.id.clone(),
+            display_name: entity.display_name.clone(),
+            enabled: entity.enabled.clone(),
+            priority: entity.priority.clone(),
+            category_id: entity.category_id.clone(),
+            label_id: entity.label_id.clone(),
+            condition: entity.condition.map(|condition| ItemCondition::try_from_repr(condition as u8)).transpose()?,
+            listed_days: entity.listed_days.clone() as u32,
+            markdown_percent: entity.markdown_percent.clone() as u8,
+            floor_markup_percent: entity.floor_markup_percent.map(|v| v as u32),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepricingRuleSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub display_name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub priority: i32,
+    pub category_id: Option<Uuid>,
+    pub label_id: Option<Uuid>,
+    pub condition: Option<u8>,
+    pub listed_days: u32,
+    pub markdown_percent: u8,
+    pub floor_markup_percent: Option<u32>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for RepricingRuleSerial {
+    type Model = RepricingRule;
+}
+
+impl JsonHttpResponse for RepricingRuleSerial {}
+impl JsonHttpResponse for Vec<RepricingRuleSerial> {}
+
+/// A listed item within the scope of a repricing rule
+#[derive(Debug, sqlx::FromRow)]
+pub struct RepricingCandidateEntity {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub price_cents: i64,
+    /// When the item's earliest currently published listing was published
+    pub listed_since: DateTime<Utc>,
+    pub last_price_change: Option<DateTime<Utc>>,
+}
+
+/// The price changes the enabled repricing rules would make if applied now
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepricingPlan {
+    pub entries: Vec<RepricingPlanEntry>,
+    /// Items which are due for a markdown but cannot be repriced
+    pub skipped: Vec<RepricingSkip>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepricingPlanEntry {
+    pub item_id: Uuid,
+    pub item_code: String,
+    pub repricing_rule_id: Uuid,
+    pub old_price_cents: u32,
+    pub new_price_cents: u32,
+    pub floor_price_cents: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepricingSkip {
+    pub item_id: Uuid,
+    pub repricing_rule_id: Uuid,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepricingRunReport {
+    pub price_changes: Vec<PriceHistorySerial>,
+    pub skipped: Vec<RepricingSkip>,
+    /// Published eBay offers which could not be updated to the new price. The price changes themselves are retained.
+    pub marketplace_errors: Vec<PriceChangeMarketplaceError>,
+}
+
+impl JsonHttpResponse for RepricingPlan {}
+impl JsonHttpResponse for RepricingRunReport {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repricing_rule(markdown_percent: u8, floor_markup_percent: Option<u32>) -> RepricingRule {
+        RepricingRule {
+            id: Uuid::nil(),
+            display_name: "Test".to_string(),
+            enabled: true,
+            priority: 0,
+            category_id: None,
+            label_id: None,
+            condition: None,
+            listed_days: 60,
+            markdown_percent,
+            floor_markup_percent,
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    fn item_cost_basis(cost_basis_cents: u64) -> ItemCostBasis {
+        ItemCostBasis {
+            item_id: Uuid::nil(),
+            acquisition_price_cents: None,
+            refurbishment_cost_cents: cost_basis_cents,
+            cost_basis_cents,
+        }
+    }
+
+    #[test]
+    fn get_markdown_price_cents_rounds_down() {
+        assert_eq!(repricing_rule(10, None).get_markdown_price_cents(10000), 9000);
+        assert_eq!(repricing_rule(10, None).get_markdown_price_cents(999), 899);
+        assert_eq!(repricing_rule(99, None).get_markdown_price_cents(99), 0);
+        assert_eq!(repricing_rule(1, None).get_markdown_price_cents(0), 0);
+    }
+
+    #[test]
+    fn get_markdown_price_cents_does_not_overflow() {
+        assert_eq!(repricing_rule(1, None).get_markdown_price_cents(u32::MAX), (u64::from(u32::MAX) * 99 / 100) as u32);
+    }
+
+    #[test]
+    fn get_floor_price_cents_is_absent_without_a_floor() {
+        assert_eq!(repricing_rule(10, None).get_floor_price_cents(&item_cost_basis(5000)), None);
+    }
+
+    #[test]
+    fn get_floor_price_cents_rounds_up() {
+        assert_eq!(repricing_rule(10, Some(20)).get_floor_price_cents(&item_cost_basis(5000)), Some(6000));
+        assert_eq!(repricing_rule(10, Some(20)).get_floor_price_cents(&item_cost_basis(999)), Some(1199));
+        assert_eq!(repricing_rule(10, Some(0)).get_floor_price_cents(&item_cost_basis(999)), Some(999));
+        assert_eq!(repricing_rule(10, Some(15)).get_floor_price_cents(&item_cost_basis(1)), Some(2));
+        assert_eq!(repricing_rule(10, Some(20)).get_floor_price_cents(&item_cost_basis(0)), Some(0));
+    }
+
+    #[test]
+    fn get_floor_price_cents_saturates() {
+        assert_eq!(repricing_rule(10, Some(100)).get_floor_price_cents(&item_cost_basis(u64::from(u32::MAX))), Some(u32::MAX));
+    }
+}
@@ -10,16 +10,37 @@ pub async fn open_server(pgpool: Pool<Postgres>) -> std::io::Result<()> {
             .default_service(web::route().to(HttpResponse::NotFound))
             .configure(crate::public_api::configurer)
             .configure(crate::category::category_api::configurer)
+            .configure(crate::category_attribute::category_attribute_api::configurer)
             .configure(crate::product::product_api::configurer)
             .configure(crate::inventory_location::inventory_location_api::configurer)
+            .configure(crate::inventory_transfer::inventory_transfer_api::configurer)
             .configure(crate::item::item_api::configurer)
+            .configure(crate::item_audit::item_audit_api::configurer)
+            .configure(crate::item_grading::item_grading_api::configurer)
+            .configure(crate::item_refurbishment::item_refurbishment_api::configurer)
+            .configure(crate::item_stock::item_stock_api::configurer)
+            .configure(crate::item_readiness::item_readiness_api::configurer)
+            .configure(crate::bundle::bundle_api::configurer)
             .configure(crate::label::label_api::configurer)
             .configure(crate::item_attribute::item_attribute_api::configurer)
             .configure(crate::metric_counter::metric_counter_api::configurer)
             .configure(crate::customer::customer_api::configurer)
             .configure(crate::marketplace::marketplace_api::configurer)
             .configure(crate::listing::listing_api::configurer)
+            .configure(crate::price_history::price_history_api::configurer)
             .configure(crate::purchase::purchase_api::configurer)
+            .configure(crate::profit::profit_api::configurer)
+            .configure(crate::inventory_report::inventory_report_api::configurer)
+            .configure(crate::supplier::supplier_api::configurer)
+            .configure(crate::consignor::consignor_api::configurer)
+            .configure(crate::trade_in::trade_in_api::configurer)
+            .configure(crate::acquisition_lot::acquisition_lot_api::configurer)
+            .configure(crate::repricing_rule::repricing_rule_api::configurer)
+            .configure(crate::import::import_api::configurer)
+            .configure(crate::export::export_api::configurer)
+            .configure(crate::barcode::barcode_api::configurer)
+            .configure(crate::stock_take::stock_take_api::configurer)
+            .configure(crate::storage_slot::storage_slot_api::configurer)
     })
         .bind("127.0.0.1:11001")?
         .run()
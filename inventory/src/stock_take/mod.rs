@@ -0,0 +1,7 @@
+pub(crate) mod stock_take_db;
+
+pub mod stock_take_api;
+pub mod stock_take_model;
+pub mod stock_take_action;
+
+pub use stock_take_model::*;
@@ -0,0 +1,235 @@
+use super::*;
+use crate::error::ShopError;
+use crate::inventory_transfer::{inventory_transfer_action, InventoryTransfer};
+use crate::item::{item_action, item_db, Item, ItemStatus};
+use crate::item_audit::ItemAudit;
+use crate::{object, ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// The discrepancies between the scans of a stock-take and the items assigned to its location
+pub struct StockTakeReconciliation {
+    pub scan_count: usize,
+    pub missing_items: Vec<Item>,
+    pub unexpected_items: Vec<Item>,
+    pub status_anomalies: Vec<Item>,
+}
+
+impl StockTakeReconciliation {
+    pub fn new(stock_take: &StockTake, location_items: &[Item], scanned_items: &[Item]) -> StockTakeReconciliation {
+        let missing_items: Vec<Item> = location_items.iter()
+            .filter(|item| item.status.is_on_hand())
+            .filter(|item| !scanned_items.iter().any(|scanned_item| scanned_item.id == item.id))
+            .cloned()
+            .collect();
+        let unexpected_items: Vec<Item> = scanned_items.iter()
+            .filter(|item| item.inventory_location_id != stock_take.inventory_location_id)
+            .cloned()
+            .collect();
+        let status_anomalies: Vec<Item> = scanned_items.iter()
+            .filter(|item| !item.status.is_on_hand())
+            .cloned()
+            .collect();
+
+        StockTakeReconciliation {
+            scan_count: scanned_items.len(),
+            missing_items,
+            unexpected_items,
+            status_anomalies,
+        }
+    }
+
+    pub fn to_report(
+        &self,
+        stock_take: &StockTake,
+        transfers: &[InventoryTransfer],
+        item_audits: &[ItemAudit],
+    ) -> StockTakeReport {
+        StockTakeReport {
+            stock_take: stock_take.to_serial(),
+            scan_count: self.scan_count,
+            missing_items: self.missing_items.iter().map(StockTakeReportItem::from).collect(),
+            unexpected_items: self.unexpected_items.iter().map(StockTakeReportItem::from).collect(),
+            status_anomalies: self.status_anomalies.iter().map(StockTakeReportItem::from).collect(),
+            transfers: transfers.iter().map(|transfer| transfer.to_serial()).collect(),
+            item_audits: item_audits.iter().map(|item_audit| item_audit.to_serial()).collect(),
+            marketplace_errors: Vec::new(),
+        }
+    }
+}
+
+pub async fn open_stock_take(
+    pgpool: &PgPool,
+    inventory_location_id: &Uuid,
+    initiated_by: Option<String>,
+    note: Option<String>,
+) -> Result<StockTake, ShopError> {
+    let stock_take = StockTake {
+        id: object::random_uuid(),
+        inventory_location_id: *inventory_location_id,
+        status: StockTakeStatus::Open,
+        initiated_by,
+        note,
+        created: Utc::now(),
+        closed: None,
+    };
+    stock_take_db::create_stock_take(pgpool, &stock_take.to_entity()).await?;
+    Ok(stock_take)
+}
+
+/// Records the item as physically present at the stock-take's location.
+pub async fn scan_item(pgpool: &PgPool, stock_take_id: &Uuid, item_id: &Uuid) -> Result<(), ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    get_open_stock_take_for_update(&mut *transaction, stock_take_id).await?;
+
+    let stock_take_scan = StockTakeScan {
+        id: object::random_uuid(),
+        stock_take_id: *stock_take_id,
+        item_id: *item_id,
+        created: Utc::now(),
+    };
+    stock_take_db::create_stock_take_scan(&mut *transaction, &stock_take_scan.to_entity()).await?;
+
+    transaction.commit().await?;
+    Ok(())
+}
+
+pub async fn unscan_item(pgpool: &PgPool, stock_take_id: &Uuid, item_id: &Uuid) -> Result<(), ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    get_open_stock_take_for_update(&mut *transaction, stock_take_id).await?;
+    stock_take_db::delete_stock_take_scan(&mut *transaction, stock_take_id, item_id).await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Reconciles the stock-take's current scans without closing it or applying any corrections.
+pub async fn reconcile(pgpool: &PgPool, stock_take: &StockTake) -> Result<StockTakeReconciliation, ShopError> {
+    let location_items: Vec<Item> = get_all_inventory_location_items(pgpool, &stock_take.inventory_location_id).await?;
+    let scanned_items: Vec<Item> = get_all_stock_take_scanned_items(pgpool, &stock_take.id).await?;
+    Ok(StockTakeReconciliation::new(stock_take, &location_items, &scanned_items))
+}
+
+/// Closes the stock-take and reconciles its scans within a single transaction.
+/// If corrections are applied, unexpected items are transferred to the stock-take's location, missing items are marked
+/// [ItemStatus::Missing] where their status permits it, and scanned items which were marked missing are restored as
+/// [ItemStatus::Incomplete] so that they are reviewed before being listed again.
+/// Status changes are recorded as [ItemAudit]s. Other status anomalies are only reported.
+pub async fn close_stock_take(
+    pgpool: &PgPool,
+    stock_take_id: &Uuid,
+    apply: bool,
+) -> Result<StockTakeReport, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    let mut stock_take: StockTake = get_open_stock_take_for_update(&mut *transaction, stock_take_id).await?;
+
+    let location_items: Vec<Item> = get_all_inventory_location_items(&mut *transaction, &stock_take.inventory_location_id).await?;
+    let scanned_items: Vec<Item> = get_all_stock_take_scanned_items(&mut *transaction, &stock_take.id).await?;
+    let reconciliation = StockTakeReconciliation::new(&stock_take, &location_items, &scanned_items);
+
+    let mut transfers: Vec<InventoryTransfer> = Vec::new();
+    let mut item_audits: Vec<ItemAudit> = Vec::new();
+    if apply {
+        let audit_note: String = format!("Stock-take; [{}]", stock_take.id);
+
+        let unexpected_item_ids: Vec<Uuid> = reconciliation.unexpected_items.iter().map(|item| item.id).collect();
+        transfers = inventory_transfer_action::transfer_items_in_transaction(
+            &mut transaction,
+            &unexpected_item_ids,
+            &stock_take.inventory_location_id,
+            stock_take.initiated_by.clone(),
+            Some(audit_note.clone()),
+        ).await?;
+
+        for missing_item in &reconciliation.missing_items {
+            let item: Item = get_item_for_update(&mut transaction, &missing_item.id).await?;
+            if item.status.validate_transition(&ItemStatus::Missing).is_err() {
+                continue;
+            }
+            item_audits.push(item_action::update_item_status_in_transaction(
+                &mut transaction,
+                &item,
+                ItemStatus::Missing,
+                true,
+                Some(audit_note.clone()),
+            ).await?);
+        }
+
+        for anomalous_item in &reconciliation.status_anomalies {
+            let item: Item = get_item_for_update(&mut transaction, &anomalous_item.id).await?;
+            if item.status != ItemStatus::Missing {
+                continue;
+            }
+            item_audits.push(item_action::update_item_status_in_transaction(
+                &mut transaction,
+                &item,
+                ItemStatus::Incomplete,
+                true,
+                Some(audit_note.clone()),
+            ).await?);
+        }
+    }
+
+    stock_take.status = StockTakeStatus::Closed;
+    stock_take.closed = Some(Utc::now());
+    stock_take_db::update_stock_take_status(&mut *transaction, &stock_take.id, stock_take.status.clone() as i32, stock_take.closed)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(reconciliation.to_report(&stock_take, &transfers, &item_audits))
+}
+
+/// Abandons the stock-take without reconciliation.
+pub async fn cancel_stock_take(pgpool: &PgPool, stock_take_id: &Uuid) -> Result<StockTake, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+    let mut stock_take: StockTake = get_open_stock_take_for_update(&mut *transaction, stock_take_id).await?;
+
+    stock_take.status = StockTakeStatus::Cancelled;
+    stock_take.closed = Some(Utc::now());
+    stock_take_db::update_stock_take_status(&mut *transaction, &stock_take.id, stock_take.status.clone() as i32, stock_take.closed)
+        .await?;
+
+    transaction.commit().await?;
+    Ok(stock_take)
+}
+
+async fn get_open_stock_take_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_id: &Uuid,
+) -> Result<StockTake, ShopError> {
+    let stock_take: StockTake = stock_take_db::get_stock_take_for_update(pgexecutor, stock_take_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Stock-take not found; [{}]", stock_take_id)))?
+        .try_to_model()?;
+    if stock_take.status != StockTakeStatus::Open {
+        return Err(ShopError::new(&format!("Stock-take is not open; [{}]", stock_take.status)));
+    }
+    Ok(stock_take)
+}
+
+async fn get_item_for_update(transaction: &mut Transaction<'_, Postgres>, item_id: &Uuid) -> Result<Item, ShopError> {
+    item_db::get_item_for_update(&mut **transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()
+}
+
+async fn get_all_inventory_location_items(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Vec<Item>, ShopError> {
+    item_db::get_all_inventory_location_items(pgexecutor, inventory_location_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()
+}
+
+async fn get_all_stock_take_scanned_items(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_id: &Uuid,
+) -> Result<Vec<Item>, ShopError> {
+    stock_take_db::get_all_stock_take_scanned_items(pgexecutor, stock_take_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()
+}
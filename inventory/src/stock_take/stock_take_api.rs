@@ -0,0 +1,194 @@
+use super::*;
+use crate::ebay::ebay_api;
+use crate::inventory_location::inventory_location_db;
+use crate::inventory_transfer::inventory_transfer_action;
+use crate::item::item_api;
+use crate::listing::Listing;
+use crate::object::JsonHttpResponse;
+use crate::pagination::KeysetPaginationOptions;
+use crate::{http, try_return, unwrap_option_else_400, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel};
+use actix_web::cookie::Cookie;
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/stock_take")
+            .route("", web::get().to(get_all_stock_takes_paged))
+            .route("", web::post().to(open_stock_take))
+            .route("/{stock_take_id}", web::get().to(get_stock_take))
+            .route("/{stock_take_id}/report", web::get().to(get_stock_take_report))
+            .route("/{stock_take_id}/close", web::post().to(close_stock_take))
+            .route("/{stock_take_id}/cancel", web::post().to(cancel_stock_take))
+            .route("/{stock_take_id}/scan", web::get().to(get_all_stock_take_scans))
+            .route("/{stock_take_id}/scan/{item_id}", web::post().to(scan_item))
+            .route("/{stock_take_id}/scan/{item_id}", web::delete().to(unscan_item))
+    );
+}
+
+async fn get_all_stock_takes_paged(
+    pgpool: web::Data<PgPool>,
+    query: web::Query<KeysetPaginationOptions<DateTime<Utc>>>,
+) -> impl Responder {
+    let pagination_options = unwrap_result_else_400!(query.into_inner().validated());
+    let (entities, pagination_result) = unwrap_result_else_500!(
+        stock_take_db::get_all_stock_takes_paged_created(&pgpool, &pagination_options).await
+    );
+
+    let mut stock_take_serial_vec = Vec::with_capacity(entities.len());
+    for entity in entities {
+        stock_take_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    (stock_take_serial_vec, pagination_result).to_http_response()
+}
+
+async fn open_stock_take(
+    pgpool: web::Data<PgPool>,
+    request: HttpRequest,
+    body: web::Json<StockTakeSerial>,
+) -> HttpResponse {
+    let body: StockTakeSerial = body.into_inner();
+    unwrap_option_else_400!(unwrap_result_else_500!(
+        inventory_location_db::get_inventory_location(pgpool.get_ref(), &body.inventory_location_id).await
+    ));
+    let open: Option<StockTakeEntity> = unwrap_result_else_500!(
+        stock_take_db::get_open_inventory_location_stock_take(pgpool.get_ref(), &body.inventory_location_id).await
+    );
+    if let Some(open) = open {
+        return HttpResponse::Conflict()
+            .body(format!("A stock-take is already open for this inventory location; [{}]", open.id));
+    }
+
+    let stock_take: StockTake = unwrap_result_else_500!(stock_take_action::open_stock_take(
+        &pgpool,
+        &body.inventory_location_id,
+        http::extract_remote_user(&request),
+        body.note,
+    ).await);
+    HttpResponse::Created().json(stock_take.to_serial())
+}
+
+async fn get_stock_take(pgpool: web::Data<PgPool>, stock_take_id: web::Path<String>) -> HttpResponse {
+    let stock_take: StockTake = try_return!(find_stock_take(&pgpool, &stock_take_id.into_inner()).await);
+    stock_take.to_serial().to_http_response()
+}
+
+/// Reconciles the current scans without closing the stock-take
+async fn get_stock_take_report(pgpool: web::Data<PgPool>, stock_take_id: web::Path<String>) -> HttpResponse {
+    let stock_take: StockTake = try_return!(find_stock_take(&pgpool, &stock_take_id.into_inner()).await);
+    let reconciliation = unwrap_result_else_500!(stock_take_action::reconcile(&pgpool, &stock_take).await);
+    reconciliation.to_report(&stock_take, &[], &[]).to_http_response()
+}
+
+async fn close_stock_take(
+    pgpool: web::Data<PgPool>,
+    request: HttpRequest,
+    stock_take_id: web::Path<String>,
+    parameters: web::Query<StockTakeCloseParameters>,
+) -> HttpResponse {
+    let stock_take: StockTake = try_return!(find_open_stock_take(&pgpool, &stock_take_id.into_inner()).await);
+    let parameters: StockTakeCloseParameters = parameters.into_inner();
+
+    // Transferred items with published eBay offers require the eBay token, as with any other transfer
+    let mut published_listings: Vec<Listing> = Vec::new();
+    if parameters.apply {
+        let reconciliation = unwrap_result_else_500!(stock_take_action::reconcile(&pgpool, &stock_take).await);
+        let unexpected_item_ids: Vec<Uuid> = reconciliation.unexpected_items.iter().map(|item| item.id).collect();
+        published_listings = unwrap_result_else_500!(
+            inventory_transfer_action::get_published_marketplace_listings(&pgpool, &unexpected_item_ids).await
+        );
+    }
+    let user_access_token: Option<Cookie> = match published_listings.is_empty() {
+        true => None,
+        false => Some(try_return!(ebay_api::extract_user_token(&request))),
+    };
+
+    let mut report: StockTakeReport = match stock_take_action::close_stock_take(&pgpool, &stock_take.id, parameters.apply).await {
+        Ok(report) => report,
+        Err(error) => return HttpResponse::Conflict().body(error.message),
+    };
+
+    if let Some(user_access_token) = &user_access_token {
+        let transferred_listings: Vec<Listing> = published_listings.into_iter()
+            .filter(|listing| report.transfers.iter().any(|transfer| listing.item_id == Some(transfer.item_id)))
+            .collect();
+        report.marketplace_errors = inventory_transfer_action::sync_marketplace_locations(
+            &pgpool,
+            user_access_token.value(),
+            &transferred_listings,
+        ).await;
+    }
+    report.to_http_response()
+}
+
+async fn cancel_stock_take(pgpool: web::Data<PgPool>, stock_take_id: web::Path<String>) -> HttpResponse {
+    let stock_take: StockTake = try_return!(find_open_stock_take(&pgpool, &stock_take_id.into_inner()).await);
+    match stock_take_action::cancel_stock_take(&pgpool, &stock_take.id).await {
+        Ok(stock_take) => stock_take.to_serial().to_http_response(),
+        Err(error) => HttpResponse::Conflict().body(error.message),
+    }
+}
+
+async fn get_all_stock_take_scans(pgpool: web::Data<PgPool>, stock_take_id: web::Path<String>) -> HttpResponse {
+    let stock_take: StockTake = try_return!(find_stock_take(&pgpool, &stock_take_id.into_inner()).await);
+
+    let stock_take_scan_entity_vec = unwrap_result_else_500!(
+        stock_take_db::get_all_stock_take_scans(&pgpool, &stock_take.id).await
+    );
+    let mut stock_take_scan_serial_vec: Vec<StockTakeScanSerial> = Vec::with_capacity(stock_take_scan_entity_vec.len());
+    for stock_take_scan_entity in stock_take_scan_entity_vec {
+        stock_take_scan_serial_vec.push(unwrap_result_else_500!(stock_take_scan_entity.try_to_model()).to_serial());
+    }
+    stock_take_scan_serial_vec.to_http_response()
+}
+
+/// Item IDs and item codes are both accepted. Scanning an item more than once has no effect.
+async fn scan_item(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (stock_take_id, item_id) = path.into_inner();
+    let stock_take: StockTake = try_return!(find_open_stock_take(&pgpool, &stock_take_id).await);
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &item_id).await);
+
+    match stock_take_action::scan_item(&pgpool, &stock_take.id, &item_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::Conflict().body(error.message),
+    }
+}
+
+async fn unscan_item(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (stock_take_id, item_id) = path.into_inner();
+    let stock_take: StockTake = try_return!(find_open_stock_take(&pgpool, &stock_take_id).await);
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &item_id).await);
+
+    match stock_take_action::unscan_item(&pgpool, &stock_take.id, &item_id).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::Conflict().body(error.message),
+    }
+}
+
+async fn find_stock_take(pgpool: &PgPool, stock_take_id: &str) -> Result<StockTake, HttpResponse> {
+    let stock_take_id: Uuid = Uuid::try_parse(stock_take_id)
+        .map_err(|error| HttpResponse::BadRequest().body(error.to_string()))?;
+    match stock_take_db::get_stock_take(pgpool, &stock_take_id).await {
+        Ok(Some(entity)) => entity.try_to_model()
+            .map_err(|error| {
+                log::error!("{}", error);
+                HttpResponse::InternalServerError().finish()
+            }),
+        Ok(None) => Err(HttpResponse::NotFound().finish()),
+        Err(error) => {
+            log::error!("{}", error);
+            Err(HttpResponse::InternalServerError().finish())
+        }
+    }
+}
+
+async fn find_open_stock_take(pgpool: &PgPool, stock_take_id: &str) -> Result<StockTake, HttpResponse> {
+    let stock_take: StockTake = find_stock_take(pgpool, stock_take_id).await?;
+    if stock_take.status != StockTakeStatus::Open {
+        return Err(HttpResponse::Conflict().body(format!("Stock-take is not open; [{}]", stock_take.status)));
+    }
+    Ok(stock_take)
+}
@@ -0,0 +1,191 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item::ItemEntity;
+use crate::pagination;
+use crate::pagination::{Direction, KeysetPaginationOptions, KeysetPaginationResult};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+pub async fn get_stock_take(pgpool: &PgPool, stock_take_id: &Uuid) -> Result<Option<StockTakeEntity>, ShopError> {
+    query_as!(StockTakeEntity, "
+        select id, inventory_location_id, status, initiated_by, note, created, closed
+        from shop.public.stock_take
+        where id = $1
+    ",
+		stock_take_id
+	)
+        .fetch_optional(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Locks the stock-take row until the enclosing transaction ends.
+pub async fn get_stock_take_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_id: &Uuid,
+) -> Result<Option<StockTakeEntity>, ShopError> {
+    query_as!(StockTakeEntity, "
+        select id, inventory_location_id, status, initiated_by, note, created, closed
+        from shop.public.stock_take
+        where id = $1
+        for update
+    ",
+		stock_take_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_open_inventory_location_stock_take(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Option<StockTakeEntity>, ShopError> {
+    query_as!(StockTakeEntity, "
+        select id, inventory_location_id, status, initiated_by, note, created, closed
+        from shop.public.stock_take
+        where inventory_location_id = $1 and status = $2
+    ",
+		inventory_location_id,
+		StockTakeStatus::Open as i32,
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_stock_take(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take: &StockTakeEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.stock_take (id, inventory_location_id, status, initiated_by, note, created, closed)
+        values ($1, $2, $3, $4, $5, $6, $7)
+    ",
+		stock_take.id,
+		stock_take.inventory_location_id,
+		stock_take.status,
+		stock_take.initiated_by,
+		stock_take.note,
+		stock_take.created,
+		stock_take.closed,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_stock_take_status(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_id: &Uuid,
+    status: i32,
+    closed: Option<DateTime<Utc>>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.stock_take
+        set (status, closed) = ($2, $3)
+        where id = $1
+    ",
+		stock_take_id,
+		status,
+		closed,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recent stock-takes first
+pub async fn get_all_stock_takes_paged_created(
+    pgpool: &PgPool,
+    keyset_pagination_options: &KeysetPaginationOptions<DateTime<Utc>>,
+) -> Result<(Vec<StockTakeEntity>, KeysetPaginationResult<DateTime<Utc>>), ShopError> {
+    let builder: QueryBuilder<Postgres> = QueryBuilder::new("\
+        select id, inventory_location_id, status, initiated_by, note, created, closed \
+        from shop.public.stock_take \
+        where true \
+    ");
+    pagination::fetch_page(
+        pgpool,
+        builder,
+        "created",
+        &Direction::Descending,
+        keyset_pagination_options,
+        |stock_take: &StockTakeEntity| (stock_take.created, stock_take.id),
+    ).await
+}
+
+/// Scanning an item more than once has no effect.
+pub async fn create_stock_take_scan(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_scan: &StockTakeScanEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.stock_take_scan (id, stock_take_id, item_id, created)
+        values ($1, $2, $3, $4)
+        on conflict (stock_take_id, item_id) do nothing
+    ",
+		stock_take_scan.id,
+		stock_take_scan.stock_take_id,
+		stock_take_scan.item_id,
+		stock_take_scan.created,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_stock_take_scan(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_id: &Uuid,
+    item_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.stock_take_scan
+        where stock_take_id = $1 and item_id = $2
+    ",
+		stock_take_id,
+		item_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Oldest scans first
+pub async fn get_all_stock_take_scans(
+    pgpool: &PgPool,
+    stock_take_id: &Uuid,
+) -> Result<Vec<StockTakeScanEntity>, ShopError> {
+    query_as!(StockTakeScanEntity, "
+        select id, stock_take_id, item_id, created
+        from shop.public.stock_take_scan
+        where stock_take_id = $1
+        order by created, id
+    ",
+		stock_take_id
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_stock_take_scanned_items(
+    pgexecutor: impl PgExecutor<'_>,
+    stock_take_id: &Uuid,
+) -> Result<Vec<ItemEntity>, ShopError> {
+    query_as!(ItemEntity, "
+        select item.id, item.code, item.product_id, item.inventory_location_id, item.storage_slot_id, item.condition, item.status, item.price_cents, item.priority, item.note, item.acquisition_datetime, item.acquisition_price_cents, item.acquisition_location, item.consignor_id, item.commission_basis_points, item.commission_minimum_cents, item.created, item.updated
+        from shop.public.stock_take_scan
+            inner join shop.public.item on stock_take_scan.item_id = item.id
+        where stock_take_scan.stock_take_id = $1
+        order by item.code
+    ",
+		stock_take_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -0,0 +1,248 @@
+use crate::error::ShopError;
+use crate::inventory_transfer::{InventoryTransferMarketplaceError, InventoryTransferSerial};
+use crate::item::Item;
+use crate::item_audit::ItemAuditSerial;
+use crate::object::JsonHttpResponse;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+/// A physical count of the items at an inventory location. Items are scanned into an open stock-take, which is then
+/// closed to reconcile the scans against the database.
+#[derive(Debug, sqlx::FromRow)]
+pub struct StockTakeEntity {
+    pub id: Uuid,
+    pub inventory_location_id: Uuid,
+    pub status: i32,
+    pub initiated_by: Option<String>,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub closed: Option<DateTime<Utc>>,
+}
+
+impl ShopEntity for StockTakeEntity {
+    type Model = StockTake;
+}
+
+#[derive(Debug, Clone)]
+pub struct StockTake {
+    pub id: Uuid,
+    pub inventory_location_id: Uuid,
+    pub status: StockTakeStatus,
+    /// The authenticated user who opened the stock-take, if known
+    pub initiated_by: Option<String>,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    /// Set when the stock-take is closed or cancelled
+    pub closed: Option<DateTime<Utc>>,
+}
+
+impl ShopModel for StockTake {
+    type Entity = StockTakeEntity;
+    type Serial = StockTakeSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        StockTakeSerial {
+            id: self.id.clone(),
+            inventory_location_id: self.inventory_location_id.clone(),
+            status: self.status.clone() as u8,
+            initiated_by: self.initiated_by.clone(),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            closed: self.closed.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(StockTake {
+            id: object::random_uuid(),
+            inventory_location_id: serial.inventory_location_id.clone(),
+            status: StockTakeStatus::default(),
+            initiated_by: serial.initiated_by.clone(),
+            note: serial.note.clone(),
+            created: serial.created.clone(),
+            closed: None,
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        StockTakeEntity {
+            id: self.id.clone(),
+            inventory_location_id: self.inventory_location_id.clone(),
+            status: self.status.clone() as i32,
+            initiated_by: self.initiated_by.clone(),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            closed: self.closed.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(StockTake {
+            id: entity.id.clone(),
+            inventory_location_id: entity.inventory_location_id.clone(),
+            status: StockTakeStatus::try_from_repr(entity.status.clone() as u8)?,
+            initiated_by: entity.initiated_by.clone(),
+            note: entity.note.clone(),
+            created: entity.created.clone(),
+            closed: entity.closed.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum StockTakeStatus {
+    /// Items may be scanned
+    Open = 0,
+    /// The scans have been reconciled
+    Closed,
+    /// The stock-take was abandoned without reconciliation
+    Cancelled,
+}
+
+try_from_repr!(StockTakeStatus<u8>);
+
+impl Default for StockTakeStatus {
+    fn default() -> Self {
+        StockTakeStatus::Open
+    }
+}
+
+impl Display for StockTakeStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockTakeSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub inventory_location_id: Uuid,
+    #[serde(default)]
+    pub status: u8,
+    #[serde(default)]
+    pub initiated_by: Option<String>,
+    pub note: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default)]
+    pub closed: Option<DateTime<Utc>>,
+}
+
+impl ShopSerial for StockTakeSerial {
+    type Model = StockTake;
+}
+
+impl JsonHttpResponse for StockTakeSerial {}
+impl JsonHttpResponse for Vec<StockTakeSerial> {}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StockTakeScanEntity {
+    pub id: Uuid,
+    pub stock_take_id: Uuid,
+    pub item_id: Uuid,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopEntity for StockTakeScanEntity {
+    type Model = StockTakeScan;
+}
+
+pub type StockTakeScan = StockTakeScanEntity;
+
+impl ShopModel for StockTakeScan {
+    type Entity = StockTakeScanEntity;
+    type Serial = StockTakeScanSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        StockTakeScanSerial {
+            id: self.id.clone(),
+            stock_take_id: self.stock_take_id.clone(),
+            item_id: self.item_id.clone(),
+            created: self.created.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(StockTakeScan {
+            id: object::random_uuid(),
+            stock_take_id: serial.stock_take_id.clone(),
+            item_id: serial.item_id.clone(),
+            created: serial.created.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        self.clone()
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(entity.clone())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockTakeScanSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub stock_take_id: Uuid,
+    pub item_id: Uuid,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopSerial for StockTakeScanSerial {
+    type Model = StockTakeScan;
+}
+
+impl JsonHttpResponse for StockTakeScanSerial {}
+impl JsonHttpResponse for Vec<StockTakeScanSerial> {}
+
+#[derive(Debug, Deserialize)]
+pub struct StockTakeCloseParameters {
+    /// Transfer unexpected items to the stock-take's location and mark missing items [crate::item::ItemStatus::Missing]
+    #[serde(default)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockTakeReport {
+    pub stock_take: StockTakeSerial,
+    pub scan_count: usize,
+    /// On-hand items assigned to the location which were not scanned
+    pub missing_items: Vec<StockTakeReportItem>,
+    /// Scanned items which are assigned to another location
+    pub unexpected_items: Vec<StockTakeReportItem>,
+    /// Scanned items whose status says they should not be on hand
+    pub status_anomalies: Vec<StockTakeReportItem>,
+    /// Corrections applied on close
+    pub transfers: Vec<InventoryTransferSerial>,
+    /// Corrections applied on close
+    pub item_audits: Vec<ItemAuditSerial>,
+    pub marketplace_errors: Vec<InventoryTransferMarketplaceError>,
+}
+
+impl JsonHttpResponse for StockTakeReport {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StockTakeReportItem {
+    pub item_id: Uuid,
+    pub code: String,
+    pub inventory_location_id: Uuid,
+    pub status: u8,
+}
+
+impl From<&Item> for StockTakeReportItem {
+    fn from(item: &Item) -> Self {
+        StockTakeReportItem {
+            item_id: item.id,
+            code: item.code.clone(),
+            inventory_location_id: item.inventory_location_id,
+            status: item.status.clone() as u8,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub(crate) mod storage_slot_db;
+
+pub mod storage_slot_api;
+pub mod storage_slot_model;
+pub mod storage_slot_action;
+
+pub use storage_slot_model::*;
@@ -0,0 +1,141 @@
+use super::*;
+use crate::error::ShopError;
+use crate::inventory_location::inventory_location_db;
+use crate::item::{item_db, Item};
+use crate::{ShopEntity, ShopModel};
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// The slot's parent must be of the kind given by [StorageSlotKind::get_parent_kind] and belong to the same
+/// inventory location.
+pub async fn create_storage_slot(pgpool: &PgPool, mut storage_slot: StorageSlot) -> Result<StorageSlot, ShopError> {
+    storage_slot.display_name = storage_slot.display_name.trim().to_string();
+    if storage_slot.display_name.is_empty() {
+        return Err(ShopError::new("Storage slot display name is required;"));
+    }
+
+    inventory_location_db::get_inventory_location(pgpool, &storage_slot.inventory_location_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Inventory location not found; [{}]", storage_slot.inventory_location_id)))?;
+
+    match (storage_slot.kind.get_parent_kind(), storage_slot.parent_id) {
+        (None, None) => {}
+        (None, Some(_)) => {
+            return Err(ShopError::new(&format!("Storage slot cannot have a parent; [{}]", storage_slot.kind)));
+        }
+        (Some(parent_kind), None) => {
+            return Err(ShopError::new(&format!("Storage slot requires a parent; [{}]", parent_kind)));
+        }
+        (Some(parent_kind), Some(parent_id)) => {
+            let parent: StorageSlot = storage_slot_db::get_storage_slot(pgpool, &parent_id)
+                .await?
+                .ok_or_else(|| ShopError::new(&format!("Parent storage slot not found; [{}]", parent_id)))?
+                .try_to_model()?;
+            if parent.kind != parent_kind {
+                return Err(ShopError::new(&format!("Parent storage slot must be {}; [{}]", parent_kind, parent.kind)));
+            }
+            if parent.inventory_location_id != storage_slot.inventory_location_id {
+                return Err(ShopError::new("Parent storage slot belongs to another inventory location;"));
+            }
+        }
+    }
+
+    storage_slot_db::create_storage_slot(pgpool, &storage_slot.to_entity()).await?;
+    Ok(storage_slot)
+}
+
+/// An item may only be stowed in a slot of its own inventory location.
+pub async fn validate_item_storage_slot(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+    storage_slot_id: &Option<Uuid>,
+) -> Result<(), ShopError> {
+    let Some(storage_slot_id) = storage_slot_id else {
+        return Ok(());
+    };
+
+    let storage_slot: StorageSlotEntity = storage_slot_db::get_storage_slot(pgexecutor, storage_slot_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Storage slot not found; [{}]", storage_slot_id)))?;
+    if storage_slot.inventory_location_id != *inventory_location_id {
+        return Err(ShopError::new(&format!(
+            "Storage slot belongs to another inventory location; [{}]",
+            storage_slot.inventory_location_id,
+        )));
+    }
+    Ok(())
+}
+
+/// Records that the item now sits in the given slot.
+pub async fn stow_item(pgpool: &PgPool, item_id: &Uuid, storage_slot_id: &Uuid) -> Result<Item, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let mut item: Item = item_db::get_item_for_update(&mut *transaction, item_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Item not found; [{}]", item_id)))?
+        .try_to_model()?;
+    item.storage_slot_id = Some(*storage_slot_id);
+    item.updated = Utc::now();
+    validate_item_storage_slot(&mut *transaction, &item.inventory_location_id, &item.storage_slot_id).await?;
+    item_db::update_item_storage_slot(&mut *transaction, &item.id, item.storage_slot_id, &item.updated).await?;
+
+    transaction.commit().await?;
+    Ok(item)
+}
+
+pub async fn get_all_inventory_location_storage_slots(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Vec<StorageSlot>, ShopError> {
+    storage_slot_db::get_all_inventory_location_storage_slots(pgexecutor, inventory_location_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()
+}
+
+/// Sold items awaiting retrieval, with the path to each item's storage slot.
+/// Ordered by inventory location, then storage slot path so that a picker can walk the location once.
+/// Items which have not been stowed come last within their location.
+pub async fn get_pick_list(
+    pgpool: &PgPool,
+    inventory_location_id: Option<Uuid>,
+) -> Result<Vec<PickListEntrySerial>, ShopError> {
+    let entities: Vec<PickListEntryEntity> = storage_slot_db::get_pick_list(pgpool, inventory_location_id).await?;
+
+    let mut storage_slots_by_location: HashMap<Uuid, Vec<StorageSlot>> = HashMap::new();
+    for entity in &entities {
+        if entity.storage_slot_id.is_some() && !storage_slots_by_location.contains_key(&entity.inventory_location_id) {
+            let storage_slots: Vec<StorageSlot> = get_all_inventory_location_storage_slots(pgpool, &entity.inventory_location_id).await?;
+            storage_slots_by_location.insert(entity.inventory_location_id, storage_slots);
+        }
+    }
+
+    let mut pick_list: Vec<PickListEntrySerial> = entities.into_iter()
+        .map(|entity| {
+            let storage_slot_path: Option<String> = entity.storage_slot_id.and_then(|storage_slot_id| {
+                let storage_slots: &Vec<StorageSlot> = storage_slots_by_location.get(&entity.inventory_location_id)?;
+                let storage_slot: &StorageSlot = storage_slots.iter().find(|storage_slot| storage_slot.id == storage_slot_id)?;
+                Some(storage_slot.get_path(storage_slots))
+            });
+            PickListEntrySerial {
+                item_id: entity.item_id,
+                code: entity.code,
+                product_display_name: entity.product_display_name,
+                status: entity.status as u8,
+                inventory_location_id: entity.inventory_location_id,
+                inventory_location_display_name: entity.inventory_location_display_name,
+                storage_slot_id: entity.storage_slot_id,
+                storage_slot_path,
+            }
+        })
+        .collect();
+    pick_list.sort_by(|a, b| {
+        a.inventory_location_display_name.cmp(&b.inventory_location_display_name)
+            .then_with(|| a.storage_slot_path.is_none().cmp(&b.storage_slot_path.is_none()))
+            .then_with(|| a.storage_slot_path.cmp(&b.storage_slot_path))
+            .then_with(|| a.code.cmp(&b.code))
+    });
+    Ok(pick_list)
+}
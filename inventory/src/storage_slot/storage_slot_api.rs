@@ -0,0 +1,102 @@
+use super::*;
+use crate::item::{item_api, ItemSerial};
+use crate::object::JsonHttpResponse;
+use crate::{try_return, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/storage_slot")
+            .route("", web::post().to(create_storage_slot))
+            .route("/pick_list", web::get().to(get_pick_list))
+            .route("/{storage_slot_id}", web::get().to(get_storage_slot))
+            .route("/{storage_slot_id}", web::delete().to(delete_storage_slot))
+            .route("/{storage_slot_id}/children", web::get().to(get_all_storage_slot_children))
+            .route("/{storage_slot_id}/item", web::get().to(get_all_storage_slot_items))
+            .route("/{storage_slot_id}/item/{item_id}", web::put().to(stow_item))
+    );
+}
+
+async fn create_storage_slot(pgpool: web::Data<PgPool>, body: web::Json<StorageSlotSerial>) -> HttpResponse {
+    let storage_slot: StorageSlot = unwrap_result_else_400!(body.into_inner().try_to_model());
+    match storage_slot_action::create_storage_slot(&pgpool, storage_slot).await {
+        Ok(storage_slot) => HttpResponse::Created().json(storage_slot.to_serial()),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_pick_list(pgpool: web::Data<PgPool>, parameters: web::Query<PickListParameters>) -> HttpResponse {
+    unwrap_result_else_500!(
+        storage_slot_action::get_pick_list(&pgpool, parameters.into_inner().inventory_location_id).await
+    ).to_http_response()
+}
+
+async fn get_storage_slot(pgpool: web::Data<PgPool>, storage_slot_id: web::Path<String>) -> HttpResponse {
+    let storage_slot_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(storage_slot_id.into_inner().as_str()));
+    let storage_slot: StorageSlotEntity = unwrap_option_else_404!(unwrap_result_else_500!(
+        storage_slot_db::get_storage_slot(pgpool.get_ref(), &storage_slot_id).await
+    ));
+    unwrap_result_else_500!(storage_slot.try_to_model()).to_serial().to_http_response()
+}
+
+async fn delete_storage_slot(pgpool: web::Data<PgPool>, storage_slot_id: web::Path<String>) -> HttpResponse {
+    let storage_slot_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(storage_slot_id.into_inner().as_str()));
+    unwrap_option_else_404!(unwrap_result_else_500!(storage_slot_db::get_storage_slot(pgpool.get_ref(), &storage_slot_id).await));
+
+    let children: Vec<StorageSlotEntity> = unwrap_result_else_500!(
+        storage_slot_db::get_all_storage_slot_children(&pgpool, &storage_slot_id).await
+    );
+    if !children.is_empty() {
+        return HttpResponse::Conflict().body("Storage slot contains other slots, which must be deleted first;");
+    }
+
+    unwrap_result_else_500!(storage_slot_db::delete_storage_slot(&pgpool, &storage_slot_id).await);
+    HttpResponse::NoContent().finish()
+}
+
+async fn get_all_storage_slot_children(pgpool: web::Data<PgPool>, storage_slot_id: web::Path<String>) -> HttpResponse {
+    let storage_slot_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(storage_slot_id.into_inner().as_str()));
+    let entities: Vec<StorageSlotEntity> = unwrap_result_else_500!(
+        storage_slot_db::get_all_storage_slot_children(&pgpool, &storage_slot_id).await
+    );
+
+    let mut storage_slot_serial_vec: Vec<StorageSlotSerial> = Vec::with_capacity(entities.len());
+    for entity in entities {
+        storage_slot_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    storage_slot_serial_vec.to_http_response()
+}
+
+async fn get_all_storage_slot_items(
+    pgpool: web::Data<PgPool>,
+    storage_slot_id: web::Path<String>,
+    parameters: web::Query<StorageSlotItemParameters>,
+) -> HttpResponse {
+    let storage_slot_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(storage_slot_id.into_inner().as_str()));
+    unwrap_option_else_404!(unwrap_result_else_500!(storage_slot_db::get_storage_slot(pgpool.get_ref(), &storage_slot_id).await));
+
+    let entities = unwrap_result_else_500!(
+        storage_slot_db::get_all_storage_slot_items(&pgpool, &storage_slot_id, parameters.into_inner().recursive).await
+    );
+    let mut item_serial_vec: Vec<ItemSerial> = Vec::with_capacity(entities.len());
+    for entity in entities {
+        item_serial_vec.push(unwrap_result_else_500!(entity.try_to_model()).to_serial());
+    }
+    item_serial_vec.to_http_response()
+}
+
+/// Item IDs and item codes are both accepted.
+async fn stow_item(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (storage_slot_id, item_id) = path.into_inner();
+    let storage_slot_id: Uuid = unwrap_result_else_400!(Uuid::try_parse(storage_slot_id.as_str()));
+    let item_id: Uuid = try_return!(item_api::resolve_item_id(&pgpool, &item_id).await);
+    unwrap_option_else_404!(unwrap_result_else_500!(storage_slot_db::get_storage_slot(pgpool.get_ref(), &storage_slot_id).await));
+
+    match storage_slot_action::stow_item(&pgpool, &item_id, &storage_slot_id).await {
+        Ok(item) => item.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
@@ -0,0 +1,140 @@
+use super::*;
+use crate::error::ShopError;
+use crate::item::{ItemEntity, ItemStatus};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_storage_slot(
+    pgexecutor: impl PgExecutor<'_>,
+    storage_slot_id: &Uuid,
+) -> Result<Option<StorageSlotEntity>, ShopError> {
+    query_as!(StorageSlotEntity, "
+        select id, inventory_location_id, parent_id, kind, display_name, created
+        from shop.public.storage_slot
+        where id = $1
+    ",
+		storage_slot_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_storage_slot(
+    pgexecutor: impl PgExecutor<'_>,
+    storage_slot: &StorageSlotEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.storage_slot (id, inventory_location_id, parent_id, kind, display_name, created)
+        values ($1, $2, $3, $4, $5, $6)
+    ",
+		storage_slot.id,
+		storage_slot.inventory_location_id,
+		storage_slot.parent_id,
+		storage_slot.kind,
+		storage_slot.display_name,
+		storage_slot.created,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Items stowed in the slot are left without a slot. Nested slots must be deleted beforehand.
+pub async fn delete_storage_slot(pgpool: &PgPool, storage_slot_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.storage_slot
+        where id = $1
+    ",
+		storage_slot_id,
+	)
+        .execute(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_inventory_location_storage_slots(
+    pgexecutor: impl PgExecutor<'_>,
+    inventory_location_id: &Uuid,
+) -> Result<Vec<StorageSlotEntity>, ShopError> {
+    query_as!(StorageSlotEntity, "
+        select id, inventory_location_id, parent_id, kind, display_name, created
+        from shop.public.storage_slot
+        where inventory_location_id = $1
+        order by kind, display_name
+    ",
+		inventory_location_id
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_storage_slot_children(
+    pgpool: &PgPool,
+    storage_slot_id: &Uuid,
+) -> Result<Vec<StorageSlotEntity>, ShopError> {
+    query_as!(StorageSlotEntity, "
+        select id, inventory_location_id, parent_id, kind, display_name, created
+        from shop.public.storage_slot
+        where parent_id = $1
+        order by display_name
+    ",
+		storage_slot_id
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Items stowed directly in the slot, or also in any nested slot if recursive
+pub async fn get_all_storage_slot_items(
+    pgpool: &PgPool,
+    storage_slot_id: &Uuid,
+    recursive: bool,
+) -> Result<Vec<ItemEntity>, ShopError> {
+    query_as!(ItemEntity, r#"
+        with recursive slot (id, depth) as (
+            select id, 0 from shop.public.storage_slot where id = $1
+            union all
+            select storage_slot.id, slot.depth + 1
+            from shop.public.storage_slot
+                inner join slot on storage_slot.parent_id = slot.id
+            where $2
+        )
+        select item.id, item.code, item.product_id, item.inventory_location_id, item.storage_slot_id, item.condition, item.status, item.price_cents, item.priority, item.note, item.acquisition_datetime, item.acquisition_price_cents, item.acquisition_location, item.consignor_id, item.commission_basis_points, item.commission_minimum_cents, item.created, item.updated
+        from shop.public.item
+            inner join slot on item.storage_slot_id = slot.id
+        order by item.code
+    "#,
+		storage_slot_id,
+		recursive,
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Sold items which have not yet been shipped or received, ordered by inventory location
+pub async fn get_pick_list(
+    pgpool: &PgPool,
+    inventory_location_id: Option<Uuid>,
+) -> Result<Vec<PickListEntryEntity>, ShopError> {
+    query_as!(PickListEntryEntity, r#"
+        select item.id as item_id, item.code, product.display_name as product_display_name, item.status, item.inventory_location_id,
+            inventory_location.display_name as inventory_location_display_name, item.storage_slot_id
+        from shop.public.item
+            inner join shop.public.product on item.product_id = product.id
+            inner join shop.public.inventory_location on item.inventory_location_id = inventory_location.id
+        where item.status = any($1)
+            and ($2::uuid is null or item.inventory_location_id = $2)
+        order by inventory_location.display_name, item.code
+    "#,
+		&[ItemStatus::PurchaseListed as i32, ItemStatus::PurchasedDelisted as i32],
+		inventory_location_id,
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
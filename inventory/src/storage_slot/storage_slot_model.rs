@@ -0,0 +1,187 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+/// A place within an inventory location where items are stored. Slots nest as room, shelf, then bin.
+#[derive(Debug, sqlx::FromRow)]
+pub struct StorageSlotEntity {
+    pub id: Uuid,
+    pub inventory_location_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub kind: i32,
+    pub display_name: String,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopEntity for StorageSlotEntity {
+    type Model = StorageSlot;
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageSlot {
+    pub id: Uuid,
+    pub inventory_location_id: Uuid,
+    /// None iff the slot is a room
+    pub parent_id: Option<Uuid>,
+    pub kind: StorageSlotKind,
+    /// Unique among the slot's siblings
+    pub display_name: String,
+    pub created: DateTime<Utc>,
+}
+
+impl ShopModel for StorageSlot {
+    type Entity = StorageSlotEntity;
+    type Serial = StorageSlotSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        StorageSlotSerial {
+            id: self.id.clone(),
+            inventory_location_id: self.inventory_location_id.clone(),
+            parent_id: self.parent_id.clone(),
+            kind: self.kind.clone() as u8,
+            display_name: self.display_name.clone(),
+            created: self.created.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(StorageSlot {
+            id: object::random_uuid(),
+            inventory_location_id: serial.inventory_location_id.clone(),
+            parent_id: serial.parent_id.clone(),
+            kind: StorageSlotKind::try_from_repr(serial.kind)?,
+            display_name: serial.display_name.clone(),
+            created: serial.created.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        StorageSlotEntity {
+            id: self.id.clone(),
+            inventory_location_id: self.inventory_location_id.clone(),
+            parent_id: self.parent_id.clone(),
+            kind: self.kind.clone() as i32,
+            display_name: self.display_name.clone(),
+            created: self.created.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(StorageSlot {
+            id: entity.id.clone(),
+            inventory_location_id: entity.inventory_location_id.clone(),
+            parent_id: entity.parent_id.clone(),
+            kind: StorageSlotKind::try_from_repr(entity.kind.clone() as u8)?,
+            display_name: entity.display_name.clone(),
+            created: entity.created.clone(),
+        })
+    }
+}
+
+impl StorageSlot {
+    /// The display names from the room down to this slot, e.g. "Garage / Shelf 2 / Bin C".
+    /// Ancestors are looked up among the given slots of the same inventory location.
+    pub fn get_path(&self, storage_slots: &[StorageSlot]) -> String {
+        let mut names: Vec<&str> = vec![&self.display_name];
+        let mut parent_id: Option<Uuid> = self.parent_id;
+        while let Some(id) = parent_id {
+            let Some(parent) = storage_slots.iter().find(|storage_slot| storage_slot.id == id) else {
+                break;
+            };
+            names.push(&parent.display_name);
+            parent_id = parent.parent_id;
+        }
+        names.reverse();
+        names.join(" / ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum StorageSlotKind {
+    Room = 0,
+    Shelf,
+    Bin,
+}
+
+try_from_repr!(StorageSlotKind<u8>);
+
+impl StorageSlotKind {
+    /// The kind of slot which must directly contain a slot of this kind. None if the slot has no parent.
+    pub fn get_parent_kind(&self) -> Option<StorageSlotKind> {
+        match self {
+            StorageSlotKind::Room => None,
+            StorageSlotKind::Shelf => Some(StorageSlotKind::Room),
+            StorageSlotKind::Bin => Some(StorageSlotKind::Shelf),
+        }
+    }
+}
+
+impl Display for StorageSlotKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StorageSlotSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub inventory_location_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub kind: u8,
+    pub display_name: String,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+}
+
+impl ShopSerial for StorageSlotSerial {
+    type Model = StorageSlot;
+}
+
+impl JsonHttpResponse for StorageSlotSerial {}
+impl JsonHttpResponse for Vec<StorageSlotSerial> {}
+
+#[derive(Debug, Deserialize)]
+pub struct StorageSlotItemParameters {
+    /// Include the items of nested slots
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PickListParameters {
+    pub inventory_location_id: Option<Uuid>,
+}
+
+/// A sold item which must be retrieved for shipment or pickup
+#[derive(Debug, sqlx::FromRow)]
+pub struct PickListEntryEntity {
+    pub item_id: Uuid,
+    pub code: String,
+    pub product_display_name: String,
+    pub status: i32,
+    pub inventory_location_id: Uuid,
+    pub inventory_location_display_name: String,
+    pub storage_slot_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PickListEntrySerial {
+    pub item_id: Uuid,
+    pub code: String,
+    pub product_display_name: String,
+    pub status: u8,
+    pub inventory_location_id: Uuid,
+    pub inventory_location_display_name: String,
+    pub storage_slot_id: Option<Uuid>,
+    /// See [StorageSlot::get_path]. None if the item has not been stowed.
+    pub storage_slot_path: Option<String>,
+}
+
+impl JsonHttpResponse for Vec<PickListEntrySerial> {}
@@ -0,0 +1,6 @@
+pub(crate) mod supplier_db;
+
+pub mod supplier_api;
+pub mod supplier_model;
+
+pub use supplier_model::*;
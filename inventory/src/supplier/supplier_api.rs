@@ -0,0 +1,107 @@
+use super::*;
+use crate::acquisition_lot::{acquisition_lot_db, AcquisitionLot, AcquisitionLotEntity, AcquisitionLotSerial};
+use crate::object::JsonHttpResponse;
+use crate::{unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopEntity, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use sqlx::postgres::PgQueryResult;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope("/supplier")
+            .route("", web::get().to(get_all_suppliers))
+            .route("", web::post().to(create_supplier))
+            .route("/{supplier_id}", web::get().to(get_supplier))
+            .route("/{supplier_id}", web::put().to(update_supplier))
+            .route("/{supplier_id}", web::delete().to(delete_supplier))
+            .route("/{supplier_id}/acquisition_lot", web::get().to(get_all_supplier_acquisition_lots))
+    );
+}
+
+async fn get_all_suppliers(pgpool: web::Data<PgPool>) -> HttpResponse {
+    let entities: Vec<SupplierEntity> = unwrap_result_else_500!(supplier_db::get_all_suppliers(pgpool.get_ref()).await);
+    let suppliers: Vec<Supplier> = unwrap_result_else_500!(
+        entities.iter().map(|entity| entity.try_to_model()).collect::<Result<Vec<_>, _>>()
+    );
+    suppliers.iter()
+        .map(|supplier| supplier.to_serial())
+        .collect::<Vec<SupplierSerial>>()
+        .to_http_response()
+}
+
+async fn create_supplier(pgpool: web::Data<PgPool>, body: web::Json<SupplierSerial>) -> HttpResponse {
+    let mut supplier: Supplier = match body.into_inner().try_to_model() {
+        Ok(supplier) => supplier,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    supplier.created = Utc::now();
+    supplier.updated = supplier.created;
+
+    match supplier_db::create_supplier(pgpool.get_ref(), &supplier.to_entity()).await {
+        Ok(_) => supplier.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_supplier(pgpool: web::Data<PgPool>, supplier_id: web::Path<String>) -> HttpResponse {
+    let supplier_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&supplier_id.into_inner()));
+    let supplier: Supplier = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        supplier_db::get_supplier(pgpool.get_ref(), &supplier_id).await
+    )).try_to_model());
+    supplier.to_serial().to_http_response()
+}
+
+async fn update_supplier(
+    pgpool: web::Data<PgPool>,
+    supplier_id: web::Path<String>,
+    body: web::Json<SupplierSerial>,
+) -> HttpResponse {
+    let supplier_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&supplier_id.into_inner()));
+    let mut supplier: Supplier = match body.into_inner().try_to_model() {
+        Ok(supplier) => supplier,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    let current: Supplier = unwrap_result_else_500!(unwrap_option_else_404!(unwrap_result_else_500!(
+        supplier_db::get_supplier(pgpool.get_ref(), &supplier_id).await
+    )).try_to_model());
+    supplier.id = current.id;
+    supplier.created = current.created;
+    supplier.updated = Utc::now();
+
+    match supplier_db::update_supplier(pgpool.get_ref(), &supplier.to_entity()).await {
+        Ok(_) => supplier.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Fails while any acquisition lot refers to the supplier.
+async fn delete_supplier(pgpool: web::Data<PgPool>, supplier_id: web::Path<String>) -> HttpResponse {
+    let supplier_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&supplier_id.into_inner()));
+    let query_result: PgQueryResult = match supplier_db::delete_supplier(pgpool.get_ref(), &supplier_id).await {
+        Ok(query_result) => query_result,
+        Err(error) => return HttpResponse::Conflict().body(error.message),
+    };
+    match query_result.rows_affected() {
+        0 => HttpResponse::NotFound().finish(),
+        _ => HttpResponse::NoContent().finish(),
+    }
+}
+
+async fn get_all_supplier_acquisition_lots(pgpool: web::Data<PgPool>, supplier_id: web::Path<String>) -> HttpResponse {
+    let supplier_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&supplier_id.into_inner()));
+    unwrap_option_else_404!(unwrap_result_else_500!(supplier_db::get_supplier(pgpool.get_ref(), &supplier_id).await));
+
+    let entities: Vec<AcquisitionLotEntity> = unwrap_result_else_500!(
+        acquisition_lot_db::get_all_supplier_acquisition_lots(pgpool.get_ref(), &supplier_id).await
+    );
+    let acquisition_lots: Vec<AcquisitionLot> = unwrap_result_else_500!(
+        entities.iter().map(|entity| entity.try_to_model()).collect::<Result<Vec<_>, _>>()
+    );
+    acquisition_lots.iter()
+        .map(|acquisition_lot| acquisition_lot.to_serial())
+        .collect::<Vec<AcquisitionLotSerial>>()
+        .to_http_response()
+}
@@ -0,0 +1,77 @@
+use super::*;
+use crate::error::ShopError;
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor};
+use uuid::Uuid;
+
+pub async fn get_supplier(pgexecutor: impl PgExecutor<'_>, supplier_id: &Uuid) -> Result<Option<SupplierEntity>, ShopError> {
+    query_as!(SupplierEntity, "
+        select id, display_name, contact, note, created, updated
+        from shop.public.supplier
+        where id = $1
+    ",
+		supplier_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_all_suppliers(pgexecutor: impl PgExecutor<'_>) -> Result<Vec<SupplierEntity>, ShopError> {
+    query_as!(SupplierEntity, "
+        select id, display_name, contact, note, created, updated
+        from shop.public.supplier
+        order by display_name
+    ")
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_supplier(pgexecutor: impl PgExecutor<'_>, supplier: &SupplierEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.supplier (id, display_name, contact, note, created, updated)
+        values ($1, $2, $3, $4, $5, $6)
+    ",
+		supplier.id,
+		supplier.display_name,
+		supplier.contact,
+		supplier.note,
+		supplier.created,
+		supplier.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// The creation time is never changed.
+pub async fn update_supplier(pgexecutor: impl PgExecutor<'_>, supplier: &SupplierEntity) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.supplier
+        set (display_name, contact, note, updated) = ($2, $3, $4, $5)
+        where id = $1
+    ",
+		supplier.id,
+		supplier.display_name,
+		supplier.contact,
+		supplier.note,
+		supplier.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Fails while any acquisition lot refers to the supplier.
+pub async fn delete_supplier(pgexecutor: impl PgExecutor<'_>, supplier_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.supplier
+        where id = $1
+    ",
+		supplier_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -0,0 +1,106 @@
+use crate::error::ShopError;
+use crate::object::JsonHttpResponse;
+use crate::{object, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct SupplierEntity {
+    pub id: Uuid,
+    pub display_name: String,
+    pub contact: Option<String>,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for SupplierEntity {
+    type Model = Supplier;
+}
+
+/// A person or business from which items are acquired, usually in lots; see [crate::acquisition_lot::AcquisitionLot]
+#[derive(Debug, Clone)]
+pub struct Supplier {
+    pub id: Uuid,
+    pub display_name: String,
+    /// E.g. an email address or a marketplace username
+    pub contact: Option<String>,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopModel for Supplier {
+    type Entity = SupplierEntity;
+    type Serial = SupplierSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        SupplierSerial {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            contact: self.contact.clone(),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let display_name: String = serial.display_name.trim().to_string();
+        if display_name.is_empty() {
+            return Err(ShopError::new("Supplier display name must not be empty"));
+        }
+
+        Ok(Supplier {
+            id: object::random_uuid(),
+            display_name,
+            contact: serial.contact.clone(),
+            note: serial.note.clone(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        SupplierEntity {
+            id: self.id.clone(),
+            display_name: self.display_name.clone(),
+            contact: self.contact.clone(),
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(Supplier {
+            id: entity.id.clone(),
+            display_name: entity.display_name.clone(),
+            contact: entity.contact.clone(),
+            note: entity.note.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SupplierSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub display_name: String,
+    pub contact: Option<String>,
+    pub note: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for SupplierSerial {
+    type Model = Supplier;
+}
+
+impl JsonHttpResponse for SupplierSerial {}
+impl JsonHttpResponse for Vec<SupplierSerial> {}
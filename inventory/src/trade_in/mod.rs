@@ -0,0 +1,7 @@
+pub(crate) mod trade_in_db;
+
+pub mod trade_in_api;
+pub mod trade_in_model;
+pub mod trade_in_action;
+
+pub use trade_in_model::*;
@@ -0,0 +1,444 @@
+use super::*;
+use crate::customer::customer_db;
+use crate::environment;
+use crate::error::ShopError;
+use crate::inventory_location::inventory_location_db;
+use crate::item::{item_action, Item, ItemCondition, ItemStatus};
+use crate::item_image::item_image_action;
+use crate::product::{product_db, ProductEntity};
+use crate::{object, ShopEntity, ShopModel};
+use actix_web::web::Payload;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+impl TradeInLineImage {
+    pub fn new(
+        trade_in_line_id: Uuid,
+        alt_text: String,
+        original_file_name: String,
+    ) -> TradeInLineImage {
+        TradeInLineImage {
+            id: object::random_uuid(),
+            trade_in_line_id,
+            alt_text,
+            priority: 0, // Priority mechanism is currently unused
+            original_file_name,
+        }
+    }
+
+    pub fn get_trade_in_line_image_name(&self) -> String {
+        format!("trade_in_line_{}_{}_{}", self.trade_in_line_id, self.id, self.original_file_name)
+    }
+
+    pub fn get_trade_in_line_image_path(&self) -> Result<PathBuf, ShopError> {
+        let images_directory = environment::images_directory_path()?;
+        Ok(images_directory.join(self.get_trade_in_line_image_name()))
+    }
+
+    pub fn delete_image_file(&self) -> Result<(), ShopError> {
+        fs::remove_file(self.get_trade_in_line_image_path()?)
+            .map_err(|e| ShopError::from_error_default(Box::new(e)))
+    }
+
+    /// Fails if the payload exceeds [TRADE_IN_LINE_IMAGE_SIZE_MAX] bytes.
+    /// If an error is returned, any created file will be deleted before returning.
+    pub async fn store_image_file(&self, payload: &mut Payload) -> Result<(), ShopError> {
+        item_image_action::store_image_file_limited(&self.get_trade_in_line_image_path()?, payload, TRADE_IN_LINE_IMAGE_SIZE_MAX).await
+    }
+}
+
+/// Includes the lines of the trade-in.
+pub async fn get_trade_in(pgpool: &PgPool, trade_in_id: &Uuid) -> Result<Option<TradeIn>, ShopError> {
+    let Some(entity) = trade_in_db::get_trade_in(pgpool, trade_in_id).await? else {
+        return Ok(None);
+    };
+    Ok(Some(get_trade_in_with_lines(pgpool, &entity).await?))
+}
+
+/// Includes the lines of the trade-in.
+pub async fn get_trade_in_by_access_key(pgpool: &PgPool, access_key: &Uuid) -> Result<Option<TradeIn>, ShopError> {
+    let Some(entity) = trade_in_db::get_trade_in_by_access_key(pgpool, access_key).await? else {
+        return Ok(None);
+    };
+    Ok(Some(get_trade_in_with_lines(pgpool, &entity).await?))
+}
+
+/// Includes the lines of each trade-in.
+pub async fn get_all_trade_ins(
+    pgpool: &PgPool,
+    status: Option<TradeInStatus>,
+    customer_id: Option<&Uuid>,
+) -> Result<Vec<TradeIn>, ShopError> {
+    let mut trade_ins: Vec<TradeIn> = trade_in_db::get_all_trade_ins(pgpool, status.map(|status| status as i32), customer_id).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let trade_in_ids: Vec<Uuid> = trade_ins.iter().map(|trade_in| trade_in.id).collect();
+    let lines: Vec<TradeInLine> = trade_in_db::get_all_trade_in_lines(pgpool, &trade_in_ids).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    for trade_in in &mut trade_ins {
+        trade_in.lines = lines.iter()
+            .filter(|line| line.trade_in_id == trade_in.id)
+            .cloned()
+            .collect();
+    }
+    Ok(trade_ins)
+}
+
+pub async fn create_trade_in(pgpool: &PgPool, mut trade_in: TradeIn) -> Result<TradeIn, ShopError> {
+    validate_trade_in_references(pgpool, &trade_in).await?;
+
+    trade_in.created = Utc::now();
+    trade_in.updated = trade_in.created;
+    trade_in_db::create_trade_in(pgpool, &trade_in.to_entity()).await?;
+    Ok(trade_in)
+}
+
+/// Replaces the customer, contact, inventory location, and note of the stored trade-in. Fails once the trade-in is
+/// accepted or rejected.
+pub async fn update_trade_in(pgpool: &PgPool, trade_in_id: &Uuid, mut trade_in: TradeIn) -> Result<TradeIn, ShopError> {
+    validate_trade_in_references(pgpool, &trade_in).await?;
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let current: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    if current.status.get_successors().is_empty() {
+        return Err(ShopError::new(&format!("Trade-in has already been decided; [{}]; [{}]", trade_in_id, current.status)));
+    }
+
+    trade_in.id = current.id;
+    trade_in.access_key = current.access_key;
+    trade_in.status = current.status;
+    trade_in.created = current.created;
+    trade_in.updated = Utc::now();
+    trade_in_db::update_trade_in(&mut *transaction, &trade_in.to_entity()).await?;
+
+    transaction.commit().await?;
+    get_trade_in(pgpool, trade_in_id).await?
+        .ok_or_else(|| ShopError::new(&format!("Trade-in not found; [{}]", trade_in_id)))
+}
+
+/// Deletes the trade-in with its lines and images. Image files are removed only after the transaction commits. Fails if
+/// the trade-in was accepted, since it records the acquisition of its items. False if the trade-in does not exist.
+pub async fn delete_trade_in(pgpool: &PgPool, trade_in_id: &Uuid) -> Result<bool, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let Some(entity) = trade_in_db::get_trade_in_for_update(&mut *transaction, trade_in_id).await? else {
+        return Ok(false);
+    };
+    let trade_in: TradeIn = entity.try_to_model()?;
+    if trade_in.status == TradeInStatus::Accepted {
+        return Err(ShopError::new(&format!("Accepted trade-ins must be retained; [{}]", trade_in_id)));
+    }
+
+    let trade_in_line_ids: Vec<Uuid> = trade_in_db::get_all_trade_in_lines(&mut *transaction, &[*trade_in_id]).await?
+        .iter()
+        .map(|line| line.id)
+        .collect();
+    let trade_in_line_images: Vec<TradeInLineImage> = trade_in_db::get_all_trade_in_line_images(&mut *transaction, &trade_in_line_ids).await?;
+    trade_in_db::delete_trade_in(&mut *transaction, trade_in_id).await?;
+
+    transaction.commit().await?;
+
+    for trade_in_line_image in trade_in_line_images {
+        if let Err(error) = trade_in_line_image.delete_image_file() {
+            log::warn!("Failed to delete trade-in line image file; [{}]; {}", trade_in_line_image.id, error.message);
+        }
+    }
+    Ok(true)
+}
+
+/// Adds an offered product to the trade-in, looked up by ID or, failing that, by UPC. Lines can be added only until
+/// the trade-in is quoted.
+pub async fn create_trade_in_line(
+    pgpool: &PgPool,
+    trade_in_id: &Uuid,
+    serial: &TradeInLineCreateSerial,
+) -> Result<TradeInLine, ShopError> {
+    let product: ProductEntity = match (&serial.product_id, &serial.upc) {
+        (Some(product_id), _) => product_db::get_product(pgpool, product_id).await?
+            .ok_or_else(|| ShopError::new(&format!("Product not found; [{}]", product_id)))?,
+        (None, Some(upc)) => product_db::get_product_by_upc(pgpool, upc.trim()).await?
+            .ok_or_else(|| ShopError::new(&format!("Product not found by UPC; [{}]", upc)))?,
+        (None, None) => return Err(ShopError::new("Trade-in line requires a product ID or a UPC")),
+    };
+    let line: TradeInLine = TradeInLine {
+        id: object::random_uuid(),
+        trade_in_id: *trade_in_id,
+        product_id: product.id,
+        condition: ItemCondition::try_from_repr(serial.condition)?,
+        description: serial.description.clone(),
+        quoted_price_cents: None,
+        accepted: None,
+        item_id: None,
+        created: Utc::now(),
+        updated: Utc::now(),
+    };
+
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let trade_in: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    validate_trade_in_lines_changeable(&trade_in)?;
+    trade_in_db::create_trade_in_line(&mut *transaction, &line.to_entity()).await?;
+
+    transaction.commit().await?;
+    Ok(line)
+}
+
+/// Lines can be removed only until the trade-in is quoted. False if the line does not belong to the trade-in.
+pub async fn delete_trade_in_line(pgpool: &PgPool, trade_in_id: &Uuid, trade_in_line_id: &Uuid) -> Result<bool, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let trade_in: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    let Some(line) = trade_in_db::get_trade_in_line(&mut *transaction, trade_in_line_id).await? else {
+        return Ok(false);
+    };
+    if line.trade_in_id != trade_in.id {
+        return Ok(false);
+    }
+    validate_trade_in_lines_changeable(&trade_in)?;
+
+    let trade_in_line_images: Vec<TradeInLineImage> = trade_in_db::get_all_trade_in_line_images(&mut *transaction, &[line.id]).await?;
+    trade_in_db::delete_trade_in_line(&mut *transaction, &line.id).await?;
+
+    transaction.commit().await?;
+
+    for trade_in_line_image in trade_in_line_images {
+        if let Err(error) = trade_in_line_image.delete_image_file() {
+            log::warn!("Failed to delete trade-in line image file; [{}]; {}", trade_in_line_image.id, error.message);
+        }
+    }
+    Ok(true)
+}
+
+/// Sets the price offered for the line. Lines are quoted only while the trade-in is submitted, so the quote cannot
+/// change while the customer decides. None if the line does not belong to the trade-in.
+pub async fn quote_trade_in_line(
+    pgpool: &PgPool,
+    trade_in_id: &Uuid,
+    trade_in_line_id: &Uuid,
+    quoted_price_cents: u32,
+) -> Result<Option<TradeInLine>, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let trade_in: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    let Some(entity) = trade_in_db::get_trade_in_line(&mut *transaction, trade_in_line_id).await? else {
+        return Ok(None);
+    };
+    if entity.trade_in_id != trade_in.id {
+        return Ok(None);
+    }
+    if trade_in.status != TradeInStatus::Submitted {
+        return Err(ShopError::new(&format!("Only submitted trade-ins can be quoted; [{}]; [{}]", trade_in_id, trade_in.status)));
+    }
+
+    let mut line: TradeInLine = entity.try_to_model()?;
+    line.quoted_price_cents = Some(quoted_price_cents);
+    line.updated = Utc::now();
+    trade_in_db::update_trade_in_line(&mut *transaction, &line.to_entity()).await?;
+
+    transaction.commit().await?;
+    Ok(Some(line))
+}
+
+/// Submits, quotes, or rejects the trade-in, as permitted by [TradeInStatus::validate_transition].
+/// A trade-in is submitted with at least one line and quoted once every line has a quote. Rejecting declines every
+/// line. Trade-ins are accepted only through [accept_trade_in].
+pub async fn update_trade_in_status(pgpool: &PgPool, trade_in_id: &Uuid, status: TradeInStatus) -> Result<TradeIn, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let mut trade_in: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    trade_in.status.validate_transition(&status)?;
+    let lines: Vec<TradeInLine> = trade_in_db::get_all_trade_in_lines(&mut *transaction, &[*trade_in_id]).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    trade_in.updated = Utc::now();
+    match status {
+        TradeInStatus::Submitted if lines.is_empty() => {
+            return Err(ShopError::new(&format!("Trade-in has no lines; [{}]", trade_in_id)));
+        }
+        TradeInStatus::Quoted => {
+            if let Some(line) = lines.iter().find(|line| line.quoted_price_cents.is_none()) {
+                return Err(ShopError::new(&format!("Trade-in line has not been quoted; [{}]", line.id)));
+            }
+        }
+        TradeInStatus::Accepted => {
+            return Err(ShopError::new(&format!("Trade-ins are accepted with the accepted lines; [{}]", trade_in_id)));
+        }
+        TradeInStatus::Rejected => {
+            trade_in_db::update_all_trade_in_lines_declined(&mut *transaction, trade_in_id, &trade_in.updated).await?;
+        }
+        _ => {}
+    }
+    trade_in.status = status;
+    trade_in_db::update_trade_in(&mut *transaction, &trade_in.to_entity()).await?;
+
+    transaction.commit().await?;
+    get_trade_in(pgpool, trade_in_id).await?
+        .ok_or_else(|| ShopError::new(&format!("Trade-in not found; [{}]", trade_in_id)))
+}
+
+/// Accepts the quote for the given lines, or for every line if none are given, and declines the rest. Each accepted
+/// line becomes an incomplete item in the trade-in's inventory location, acquired now at the quoted price from the
+/// trade-in. Items are priced at zero until they are priced for sale. Either every accepted line becomes an item or,
+/// if any fails, none does.
+pub async fn accept_trade_in(
+    pgpool: &PgPool,
+    trade_in_id: &Uuid,
+    trade_in_line_ids: Option<&[Uuid]>,
+) -> Result<TradeIn, ShopError> {
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let mut trade_in: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    trade_in.status.validate_transition(&TradeInStatus::Accepted)?;
+    let lines: Vec<TradeInLine> = trade_in_db::get_all_trade_in_lines(&mut *transaction, &[*trade_in_id]).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(trade_in_line_ids) = trade_in_line_ids {
+        if trade_in_line_ids.is_empty() {
+            return Err(ShopError::new(&format!("No lines were accepted; reject the trade-in instead; [{}]", trade_in_id)));
+        }
+        if let Some(trade_in_line_id) = trade_in_line_ids.iter().find(|id| !lines.iter().any(|line| line.id == **id)) {
+            return Err(ShopError::new(&format!("Trade-in line does not belong to the trade-in; [{}]; [{}]", trade_in_line_id, trade_in_id)));
+        }
+    }
+
+    let now: DateTime<Utc> = Utc::now();
+    for mut line in lines {
+        let accepted: bool = trade_in_line_ids.is_none_or(|ids| ids.contains(&line.id));
+        if accepted {
+            let quoted_price_cents: u32 = line.quoted_price_cents
+                .ok_or_else(|| ShopError::new(&format!("Trade-in line has not been quoted; [{}]", line.id)))?;
+            let item: Item = Item {
+                id: object::random_uuid(),
+                code: String::new(),
+                product_id: line.product_id,
+                inventory_location_id: trade_in.inventory_location_id,
+                storage_slot_id: None,
+                condition: line.condition.clone(),
+                status: ItemStatus::Incomplete,
+                price_cents: 0,
+                priority: 0,
+                note: line.description.clone(),
+                acquisition_datetime: now,
+                acquisition_price_cents: Some(quoted_price_cents),
+                acquisition_location: Some(trade_in.get_acquisition_location()),
+                consignor_id: None,
+                commission_basis_points: None,
+                commission_minimum_cents: None,
+                created: now,
+                updated: now,
+            };
+            let item: Item = item_action::create_item_in_transaction(&mut transaction, item).await?;
+            line.item_id = Some(item.id);
+        }
+        line.accepted = Some(accepted);
+        line.updated = now;
+        trade_in_db::update_trade_in_line(&mut *transaction, &line.to_entity()).await?;
+    }
+
+    trade_in.status = TradeInStatus::Accepted;
+    trade_in.updated = now;
+    trade_in_db::update_trade_in(&mut *transaction, &trade_in.to_entity()).await?;
+
+    transaction.commit().await?;
+    get_trade_in(pgpool, trade_in_id).await?
+        .ok_or_else(|| ShopError::new(&format!("Trade-in not found; [{}]", trade_in_id)))
+}
+
+pub async fn get_all_trade_in_line_images(pgpool: &PgPool, trade_in_line_id: &Uuid) -> Result<Vec<TradeInLineImage>, ShopError> {
+    trade_in_db::get_all_trade_in_line_images(pgpool, &[*trade_in_line_id]).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect()
+}
+
+/// Photos can be added only until the trade-in is quoted. The trade-in stays locked while the image file is stored, and
+/// the file is removed if the record cannot be created. False if the line does not belong to the trade-in.
+pub async fn create_trade_in_line_image(
+    pgpool: &PgPool,
+    trade_in_id: &Uuid,
+    trade_in_line_image: &TradeInLineImage,
+    payload: &mut Payload,
+) -> Result<bool, ShopError> {
+    validate_image_file_name(&trade_in_line_image.original_file_name)?;
+
+    let mut transaction: Transaction<Postgres> = pgpool.begin().await?;
+
+    let trade_in: TradeIn = get_trade_in_for_update(&mut transaction, trade_in_id).await?;
+    let Some(line) = trade_in_db::get_trade_in_line(&mut *transaction, &trade_in_line_image.trade_in_line_id).await? else {
+        return Ok(false);
+    };
+    if line.trade_in_id != trade_in.id {
+        return Ok(false);
+    }
+    validate_trade_in_lines_changeable(&trade_in)?;
+
+    trade_in_line_image.store_image_file(payload).await?;
+    let created: Result<(), ShopError> = match trade_in_db::create_trade_in_line_image(&mut *transaction, trade_in_line_image).await {
+        Ok(_) => transaction.commit().await.map_err(ShopError::from),
+        Err(error) => Err(error),
+    };
+    if let Err(error) = created {
+        if let Err(error) = trade_in_line_image.delete_image_file() {
+            log::warn!("Failed to delete trade-in line image file; [{}]; {}", trade_in_line_image.id, error.message);
+        }
+        return Err(error);
+    }
+    Ok(true)
+}
+
+/// The name becomes part of the image file path, so it must not reach outside the images directory.
+fn validate_image_file_name(original_file_name: &str) -> Result<(), ShopError> {
+    if original_file_name.is_empty()
+        || original_file_name.contains(['/', '\\'])
+        || original_file_name.contains("..")
+    {
+        return Err(ShopError::new(&format!("Invalid image file name; [{}]", original_file_name)));
+    }
+    Ok(())
+}
+
+async fn get_trade_in_with_lines(pgpool: &PgPool, entity: &TradeInEntity) -> Result<TradeIn, ShopError> {
+    let mut trade_in: TradeIn = entity.try_to_model()?;
+    trade_in.lines = trade_in_db::get_all_trade_in_lines(pgpool, &[trade_in.id]).await?
+        .iter()
+        .map(|entity| entity.try_to_model())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(trade_in)
+}
+
+async fn get_trade_in_for_update(transaction: &mut Transaction<'_, Postgres>, trade_in_id: &Uuid) -> Result<TradeIn, ShopError> {
+    trade_in_db::get_trade_in_for_update(&mut **transaction, trade_in_id)
+        .await?
+        .ok_or_else(|| ShopError::new(&format!("Trade-in not found; [{}]", trade_in_id)))?
+        .try_to_model()
+}
+
+async fn validate_trade_in_references(pgpool: &PgPool, trade_in: &TradeIn) -> Result<(), ShopError> {
+    if inventory_location_db::get_inventory_location(pgpool, &trade_in.inventory_location_id).await?.is_none() {
+        return Err(ShopError::new(&format!("Inventory location not found; [{}]", trade_in.inventory_location_id)));
+    }
+    if let Some(customer_id) = &trade_in.customer_id
+        && customer_db::get_customer(pgpool, customer_id).await?.is_none()
+    {
+        return Err(ShopError::new(&format!("Customer not found; [{}]", customer_id)));
+    }
+    Ok(())
+}
+
+fn validate_trade_in_lines_changeable(trade_in: &TradeIn) -> Result<(), ShopError> {
+    match trade_in.status.allows_line_changes() {
+        true => Ok(()),
+        false => Err(ShopError::new(&format!("Trade-in lines can no longer be changed; [{}]; [{}]", trade_in.id, trade_in.status))),
+    }
+}
@@ -0,0 +1,353 @@
+use super::*;
+use crate::object::JsonHttpResponse;
+use crate::{try_return, unwrap_option_else_404, unwrap_result_else_400, unwrap_result_else_500, ShopModel, ShopSerial};
+use actix_web::web::ServiceConfig;
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub const SCOPE_PATH: &str = "/trade_in";
+
+pub fn configurer(config: &mut ServiceConfig) {
+    config.service(
+        web::scope(SCOPE_PATH)
+            .route("", web::get().to(get_all_trade_ins))
+            .route("", web::post().to(create_trade_in))
+            .route("/{trade_in_id}", web::get().to(get_trade_in))
+            .route("/{trade_in_id}", web::put().to(update_trade_in))
+            .route("/{trade_in_id}", web::delete().to(delete_trade_in))
+            .route("/{trade_in_id}/acceptance", web::post().to(accept_trade_in))
+            .route("/{trade_in_id}/line", web::post().to(create_trade_in_line))
+            .route("/{trade_in_id}/line/{trade_in_line_id}", web::delete().to(delete_trade_in_line))
+            .route("/{trade_in_id}/line/{trade_in_line_id}/image", web::get().to(get_all_trade_in_line_images))
+            .route("/{trade_in_id}/line/{trade_in_line_id}/image", web::post().to(create_trade_in_line_image))
+            .route("/{trade_in_id}/line/{trade_in_line_id}/image/{trade_in_line_image_id}", web::delete().to(delete_trade_in_line_image))
+            .route("/{trade_in_id}/line/{trade_in_line_id}/quote", web::put().to(quote_trade_in_line))
+            .route("/{trade_in_id}/status", web::post().to(update_trade_in_status))
+    );
+}
+
+/// Customers reach their trade-in by its access key rather than its ID, since the public API is unauthenticated. They
+/// list lines and photos, submit the trade-in, and decide on the quote; quoting remains with admins.
+pub fn configurer_public(config: &mut ServiceConfig) {
+    config
+        .route("/{access_key}", web::get().to(get_public_trade_in))
+        .route("/{access_key}/submission", web::post().to(submit_public_trade_in))
+        .route("/{access_key}/acceptance", web::post().to(accept_public_trade_in))
+        .route("/{access_key}/rejection", web::post().to(reject_public_trade_in))
+        .route("/{access_key}/line", web::post().to(create_public_trade_in_line))
+        .route("/{access_key}/line/{trade_in_line_id}", web::delete().to(delete_public_trade_in_line))
+        .route("/{access_key}/line/{trade_in_line_id}/image", web::get().to(get_all_public_trade_in_line_images))
+        .route("/{access_key}/line/{trade_in_line_id}/image", web::post().to(create_public_trade_in_line_image))
+    ;
+}
+
+async fn get_all_trade_ins(pgpool: web::Data<PgPool>, parameters: web::Query<TradeInSearchParameters>) -> HttpResponse {
+    let parameters: TradeInSearchParameters = parameters.into_inner();
+    let status: Option<TradeInStatus> = match parameters.status {
+        Some(status) => Some(unwrap_result_else_400!(TradeInStatus::try_from_repr(status))),
+        None => None,
+    };
+
+    let trade_ins: Vec<TradeIn> = unwrap_result_else_500!(
+        trade_in_action::get_all_trade_ins(&pgpool, status, parameters.customer_id.as_ref()).await
+    );
+    trade_ins.iter()
+        .map(|trade_in| trade_in.to_serial())
+        .collect::<Vec<TradeInSerial>>()
+        .to_http_response()
+}
+
+/// Trade-ins begin as drafts, to which lines are then added.
+async fn create_trade_in(pgpool: web::Data<PgPool>, body: web::Json<TradeInSerial>) -> HttpResponse {
+    let trade_in: TradeIn = match body.into_inner().try_to_model() {
+        Ok(trade_in) => trade_in,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+
+    match trade_in_action::create_trade_in(&pgpool, trade_in).await {
+        Ok(trade_in) => trade_in.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_trade_in(pgpool: web::Data<PgPool>, trade_in_id: web::Path<String>) -> HttpResponse {
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id.into_inner()));
+    let trade_in: TradeIn = unwrap_option_else_404!(unwrap_result_else_500!(trade_in_action::get_trade_in(&pgpool, &trade_in_id).await));
+    trade_in.to_serial().to_http_response()
+}
+
+async fn update_trade_in(
+    pgpool: web::Data<PgPool>,
+    trade_in_id: web::Path<String>,
+    body: web::Json<TradeInSerial>,
+) -> HttpResponse {
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id.into_inner()));
+    let trade_in: TradeIn = match body.into_inner().try_to_model() {
+        Ok(trade_in) => trade_in,
+        Err(error) => return HttpResponse::BadRequest().body(error.message),
+    };
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+
+    match trade_in_action::update_trade_in(&pgpool, &trade_in_id, trade_in).await {
+        Ok(trade_in) => trade_in.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Accepted trade-ins are never deleted.
+async fn delete_trade_in(pgpool: web::Data<PgPool>, trade_in_id: web::Path<String>) -> HttpResponse {
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id.into_inner()));
+    match trade_in_action::delete_trade_in(&pgpool, &trade_in_id).await {
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// Responds with the trade-in, including the item created for each accepted line. An empty body accepts every line.
+async fn accept_trade_in(
+    pgpool: web::Data<PgPool>,
+    trade_in_id: web::Path<String>,
+    body: Option<web::Json<TradeInAcceptanceSerial>>,
+) -> HttpResponse {
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id.into_inner()));
+    let body: TradeInAcceptanceSerial = body.map(|body| body.into_inner()).unwrap_or_default();
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+
+    match trade_in_action::accept_trade_in(&pgpool, &trade_in_id, body.trade_in_line_ids.as_deref()).await {
+        Ok(trade_in) => trade_in.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn update_trade_in_status(
+    pgpool: web::Data<PgPool>,
+    trade_in_id: web::Path<String>,
+    body: web::Json<TradeInStatusChangeSerial>,
+) -> HttpResponse {
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id.into_inner()));
+    let status: TradeInStatus = unwrap_result_else_400!(TradeInStatus::try_from_repr(body.into_inner().status));
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+
+    match trade_in_action::update_trade_in_status(&pgpool, &trade_in_id, status).await {
+        Ok(trade_in) => trade_in.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn create_trade_in_line(
+    pgpool: web::Data<PgPool>,
+    trade_in_id: web::Path<String>,
+    body: web::Json<TradeInLineCreateSerial>,
+) -> HttpResponse {
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id.into_inner()));
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+
+    match trade_in_action::create_trade_in_line(&pgpool, &trade_in_id, &body.into_inner()).await {
+        Ok(line) => line.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_trade_in_line(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (trade_in_id, trade_in_line_id) = path.into_inner();
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id));
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+
+    match trade_in_action::delete_trade_in_line(&pgpool, &trade_in_id, &trade_in_line_id).await {
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn quote_trade_in_line(
+    pgpool: web::Data<PgPool>,
+    path: web::Path<(String, String)>,
+    body: web::Json<TradeInLineQuoteSerial>,
+) -> HttpResponse {
+    let (trade_in_id, trade_in_line_id) = path.into_inner();
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id));
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+
+    match trade_in_action::quote_trade_in_line(&pgpool, &trade_in_id, &trade_in_line_id, body.quoted_price_cents).await {
+        Ok(Some(line)) => line.to_serial().to_http_response(),
+        Ok(None) => HttpResponse::NotFound().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_all_trade_in_line_images(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (_, trade_in_line_id) = path.into_inner();
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+    let trade_in_line_images: Vec<TradeInLineImage> = unwrap_result_else_500!(
+        trade_in_action::get_all_trade_in_line_images(&pgpool, &trade_in_line_id).await
+    );
+    trade_in_line_images.iter()
+        .map(|trade_in_line_image| trade_in_line_image.to_serial())
+        .collect::<Vec<TradeInLineImageSerial>>()
+        .to_http_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateTradeInLineImageParameters {
+    pub alt_text: String,
+    pub original_file_name: String,
+}
+
+async fn create_trade_in_line_image(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<CreateTradeInLineImageParameters>,
+    path: web::Path<(String, String)>,
+    mut payload: web::Payload,
+) -> HttpResponse {
+    let (trade_in_id, trade_in_line_id) = path.into_inner();
+    let trade_in_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_id));
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+    unwrap_option_else_404!(unwrap_result_else_500!(trade_in_db::get_trade_in(pgpool.get_ref(), &trade_in_id).await));
+    let trade_in_line_image: TradeInLineImage = TradeInLineImage::new(
+        trade_in_line_id,
+        parameters.alt_text.clone(),
+        parameters.original_file_name.clone(),
+    );
+
+    match trade_in_action::create_trade_in_line_image(&pgpool, &trade_in_id, &trade_in_line_image, &mut payload).await {
+        Ok(true) => trade_in_line_image.to_serial().to_http_response(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_trade_in_line_image(pgpool: web::Data<PgPool>, path: web::Path<(String, String, String)>) -> HttpResponse {
+    let (_, _, trade_in_line_image_id): (String, String, String) = path.into_inner();
+    let trade_in_line_image_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_image_id));
+    let trade_in_line_image: TradeInLineImage = unwrap_option_else_404!(unwrap_result_else_500!(
+        trade_in_db::get_trade_in_line_image(&pgpool, &trade_in_line_image_id).await
+    ));
+
+    unwrap_result_else_500!(trade_in_db::delete_trade_in_line_image(pgpool.get_ref(), &trade_in_line_image.id).await);
+    if let Err(error) = trade_in_line_image.delete_image_file() {
+        log::warn!("Trade-in line image DB record was deleted, but the image file was not; [{}]; {}", trade_in_line_image.id, error.message);
+    }
+    HttpResponse::NoContent().finish()
+}
+
+/// The trade-in, with its lines, whose access key is given
+async fn resolve_public_trade_in(pgpool: &PgPool, access_key: &str) -> Result<TradeIn, HttpResponse> {
+    let access_key: Uuid = Uuid::parse_str(access_key)
+        .map_err(|_| HttpResponse::BadRequest().finish())?;
+    match trade_in_action::get_trade_in_by_access_key(pgpool, &access_key).await {
+        Ok(Some(trade_in)) => Ok(trade_in),
+        Ok(None) => Err(HttpResponse::NotFound().finish()),
+        Err(error) => {
+            log::error!("{}", error);
+            Err(HttpResponse::InternalServerError().finish())
+        }
+    }
+}
+
+async fn get_public_trade_in(pgpool: web::Data<PgPool>, access_key: web::Path<String>) -> HttpResponse {
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key.into_inner()).await);
+    trade_in.to_public_serial().to_http_response()
+}
+
+async fn submit_public_trade_in(pgpool: web::Data<PgPool>, access_key: web::Path<String>) -> HttpResponse {
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key.into_inner()).await);
+
+    match trade_in_action::update_trade_in_status(&pgpool, &trade_in.id, TradeInStatus::Submitted).await {
+        Ok(trade_in) => trade_in.to_public_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+/// An empty body accepts every line; see [trade_in_action::accept_trade_in].
+async fn accept_public_trade_in(
+    pgpool: web::Data<PgPool>,
+    access_key: web::Path<String>,
+    body: Option<web::Json<TradeInAcceptanceSerial>>,
+) -> HttpResponse {
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key.into_inner()).await);
+    let body: TradeInAcceptanceSerial = body.map(|body| body.into_inner()).unwrap_or_default();
+
+    match trade_in_action::accept_trade_in(&pgpool, &trade_in.id, body.trade_in_line_ids.as_deref()).await {
+        Ok(trade_in) => trade_in.to_public_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn reject_public_trade_in(pgpool: web::Data<PgPool>, access_key: web::Path<String>) -> HttpResponse {
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key.into_inner()).await);
+
+    match trade_in_action::update_trade_in_status(&pgpool, &trade_in.id, TradeInStatus::Rejected).await {
+        Ok(trade_in) => trade_in.to_public_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn create_public_trade_in_line(
+    pgpool: web::Data<PgPool>,
+    access_key: web::Path<String>,
+    body: web::Json<TradeInLineCreateSerial>,
+) -> HttpResponse {
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key.into_inner()).await);
+
+    match trade_in_action::create_trade_in_line(&pgpool, &trade_in.id, &body.into_inner()).await {
+        Ok(line) => line.to_serial().to_http_response(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn delete_public_trade_in_line(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (access_key, trade_in_line_id) = path.into_inner();
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key).await);
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+
+    match trade_in_action::delete_trade_in_line(&pgpool, &trade_in.id, &trade_in_line_id).await {
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
+
+async fn get_all_public_trade_in_line_images(pgpool: web::Data<PgPool>, path: web::Path<(String, String)>) -> HttpResponse {
+    let (access_key, trade_in_line_id) = path.into_inner();
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key).await);
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+    if !trade_in.lines.iter().any(|line| line.id == trade_in_line_id) {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let trade_in_line_images: Vec<TradeInLineImage> = unwrap_result_else_500!(
+        trade_in_action::get_all_trade_in_line_images(&pgpool, &trade_in_line_id).await
+    );
+    trade_in_line_images.iter()
+        .map(|trade_in_line_image| trade_in_line_image.to_serial())
+        .collect::<Vec<TradeInLineImageSerial>>()
+        .to_http_response()
+}
+
+async fn create_public_trade_in_line_image(
+    pgpool: web::Data<PgPool>,
+    parameters: web::Query<CreateTradeInLineImageParameters>,
+    path: web::Path<(String, String)>,
+    mut payload: web::Payload,
+) -> HttpResponse {
+    let (access_key, trade_in_line_id) = path.into_inner();
+    let trade_in: TradeIn = try_return!(resolve_public_trade_in(&pgpool, &access_key).await);
+    let trade_in_line_id: Uuid = unwrap_result_else_400!(Uuid::parse_str(&trade_in_line_id));
+    let trade_in_line_image: TradeInLineImage = TradeInLineImage::new(
+        trade_in_line_id,
+        parameters.alt_text.clone(),
+        parameters.original_file_name.clone(),
+    );
+
+    match trade_in_action::create_trade_in_line_image(&pgpool, &trade_in.id, &trade_in_line_image, &mut payload).await {
+        Ok(true) => trade_in_line_image.to_serial().to_http_response(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(error) => HttpResponse::BadRequest().body(error.message),
+    }
+}
@@ -0,0 +1,318 @@
+use super::*;
+use crate::error::ShopError;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgQueryResult;
+use sqlx::{query, query_as, PgExecutor, PgPool};
+use uuid::Uuid;
+
+pub async fn get_trade_in(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_id: &Uuid,
+) -> Result<Option<TradeInEntity>, ShopError> {
+    query_as!(TradeInEntity, "
+        select id, access_key, customer_id, contact, inventory_location_id, status, note, created, updated
+        from shop.public.trade_in
+        where id = $1
+    ",
+		trade_in_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_trade_in_for_update(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_id: &Uuid,
+) -> Result<Option<TradeInEntity>, ShopError> {
+    query_as!(TradeInEntity, "
+        select id, access_key, customer_id, contact, inventory_location_id, status, note, created, updated
+        from shop.public.trade_in
+        where id = $1
+        for update
+    ",
+		trade_in_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_trade_in_by_access_key(
+    pgexecutor: impl PgExecutor<'_>,
+    access_key: &Uuid,
+) -> Result<Option<TradeInEntity>, ShopError> {
+    query_as!(TradeInEntity, "
+        select id, access_key, customer_id, contact, inventory_location_id, status, note, created, updated
+        from shop.public.trade_in
+        where access_key = $1
+    ",
+		access_key
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Most recently created trade-ins first, optionally only those in the status or of the customer
+pub async fn get_all_trade_ins(
+    pgpool: &PgPool,
+    status: Option<i32>,
+    customer_id: Option<&Uuid>,
+) -> Result<Vec<TradeInEntity>, ShopError> {
+    query_as!(TradeInEntity, "
+        select id, access_key, customer_id, contact, inventory_location_id, status, note, created, updated
+        from shop.public.trade_in
+        where ($1::int is null or status = $1)
+            and ($2::uuid is null or customer_id = $2)
+        order by created desc, id
+    ",
+		status,
+		customer_id,
+	)
+        .fetch_all(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_trade_in(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in: &TradeInEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.trade_in (id, access_key, customer_id, contact, inventory_location_id, status, note, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    ",
+		trade_in.id,
+		trade_in.access_key,
+		trade_in.customer_id,
+		trade_in.contact,
+		trade_in.inventory_location_id,
+		trade_in.status,
+		trade_in.note,
+		trade_in.created,
+		trade_in.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_trade_in(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in: &TradeInEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.trade_in
+        set (customer_id, contact, inventory_location_id, status, note, updated) = ($2, $3, $4, $5, $6, $7)
+        where id = $1
+    ",
+		trade_in.id,
+		trade_in.customer_id,
+		trade_in.contact,
+		trade_in.inventory_location_id,
+		trade_in.status,
+		trade_in.note,
+		trade_in.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Lines and their images are deleted by cascade.
+pub async fn delete_trade_in(pgexecutor: impl PgExecutor<'_>, trade_in_id: &Uuid) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.trade_in
+        where id = $1
+    ",
+		trade_in_id
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_trade_in_line(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line_id: &Uuid,
+) -> Result<Option<TradeInLineEntity>, ShopError> {
+    query_as!(TradeInLineEntity, "
+        select id, trade_in_id, product_id, condition, description, quoted_price_cents, accepted, item_id, created, updated
+        from shop.public.trade_in_line
+        where id = $1
+    ",
+		trade_in_line_id
+	)
+        .fetch_optional(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Ordered by trade-in, then by the time each line was added
+pub async fn get_all_trade_in_lines(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_ids: &[Uuid],
+) -> Result<Vec<TradeInLineEntity>, ShopError> {
+    query_as!(TradeInLineEntity, "
+        select id, trade_in_id, product_id, condition, description, quoted_price_cents, accepted, item_id, created, updated
+        from shop.public.trade_in_line
+        where trade_in_id = any($1)
+        order by trade_in_id, created, id
+    ",
+		trade_in_ids
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_trade_in_line(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line: &TradeInLineEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.trade_in_line (id, trade_in_id, product_id, condition, description, quoted_price_cents, accepted, item_id, created, updated)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+    ",
+		trade_in_line.id,
+		trade_in_line.trade_in_id,
+		trade_in_line.product_id,
+		trade_in_line.condition,
+		trade_in_line.description,
+		trade_in_line.quoted_price_cents,
+		trade_in_line.accepted,
+		trade_in_line.item_id,
+		trade_in_line.created,
+		trade_in_line.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn update_trade_in_line(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line: &TradeInLineEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.trade_in_line
+        set (product_id, condition, description, quoted_price_cents, accepted, item_id, updated) = ($2, $3, $4, $5, $6, $7, $8)
+        where id = $1
+    ",
+		trade_in_line.id,
+		trade_in_line.product_id,
+		trade_in_line.condition,
+		trade_in_line.description,
+		trade_in_line.quoted_price_cents,
+		trade_in_line.accepted,
+		trade_in_line.item_id,
+		trade_in_line.updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Declines every line of the trade-in.
+pub async fn update_all_trade_in_lines_declined(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_id: &Uuid,
+    updated: &DateTime<Utc>,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        update shop.public.trade_in_line
+        set (accepted, updated) = (false, $2)
+        where trade_in_id = $1
+    ",
+		trade_in_id,
+		updated,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Images are deleted by cascade.
+pub async fn delete_trade_in_line(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.trade_in_line
+        where id = $1
+    ",
+		trade_in_line_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn get_trade_in_line_image(
+    pgpool: &PgPool,
+    trade_in_line_image_id: &Uuid,
+) -> Result<Option<TradeInLineImageEntity>, ShopError> {
+    query_as!(TradeInLineImageEntity, "
+        select id, trade_in_line_id, alt_text, priority, original_file_name
+        from shop.public.trade_in_line_image
+        where id = $1
+    ",
+		trade_in_line_image_id
+	)
+        .fetch_optional(pgpool)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+/// Ordered by line, then by priority
+pub async fn get_all_trade_in_line_images(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line_ids: &[Uuid],
+) -> Result<Vec<TradeInLineImageEntity>, ShopError> {
+    query_as!(TradeInLineImageEntity, "
+        select id, trade_in_line_id, alt_text, priority, original_file_name
+        from shop.public.trade_in_line_image
+        where trade_in_line_id = any($1)
+        order by trade_in_line_id, priority, id
+    ",
+		trade_in_line_ids
+	)
+        .fetch_all(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn create_trade_in_line_image(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line_image: &TradeInLineImageEntity,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        insert into shop.public.trade_in_line_image (id, trade_in_line_id, alt_text, priority, original_file_name)
+        values ($1, $2, $3, $4, $5)
+    ",
+		trade_in_line_image.id,
+		trade_in_line_image.trade_in_line_id,
+		trade_in_line_image.alt_text,
+		trade_in_line_image.priority,
+		trade_in_line_image.original_file_name,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
+
+pub async fn delete_trade_in_line_image(
+    pgexecutor: impl PgExecutor<'_>,
+    trade_in_line_image_id: &Uuid,
+) -> Result<PgQueryResult, ShopError> {
+    query!("
+        delete from shop.public.trade_in_line_image
+        where id = $1
+    ",
+		trade_in_line_image_id,
+	)
+        .execute(pgexecutor)
+        .await
+        .map_err(|e| ShopError::from(e))
+}
@@ -0,0 +1,450 @@
+use crate::error::ShopError;
+use crate::item::ItemCondition;
+use crate::object::JsonHttpResponse;
+use crate::{object, try_from_repr, ShopEntity, ShopModel, ShopSerial};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use strum::{FromRepr, IntoStaticStr, VariantArray};
+use uuid::Uuid;
+
+/// Largest photo accepted for a trade-in line, which customers may upload through the public API
+pub const TRADE_IN_LINE_IMAGE_SIZE_MAX: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct TradeInEntity {
+    pub id: Uuid,
+    pub access_key: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub contact: Option<String>,
+    pub inventory_location_id: Uuid,
+    pub status: i32,
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for TradeInEntity {
+    type Model = TradeIn;
+}
+
+/// An offer by a customer to sell us products, e.g. a collection brought to the shop. The customer lists the offered
+/// products, each of which is quoted separately, and then accepts or rejects the quote. Each accepted line becomes an
+/// item acquired at its quoted price.
+#[derive(Debug, Clone)]
+pub struct TradeIn {
+    pub id: Uuid,
+    /// Grants the customer access to the trade-in through the public API; see
+    /// [crate::trade_in::trade_in_api::configurer_public]
+    pub access_key: Uuid,
+    /// Absent for walk-in customers without an account, who are reached through the contact instead
+    pub customer_id: Option<Uuid>,
+    pub contact: Option<String>,
+    /// Where the items of accepted lines are received
+    pub inventory_location_id: Uuid,
+    pub status: TradeInStatus,
+    pub note: Option<String>,
+    /// Stored separately from the trade-in entity; empty when converted from an entity alone
+    pub lines: Vec<TradeInLine>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl TradeIn {
+    /// The serial shown to the customer, which omits our note
+    pub fn to_public_serial(&self) -> TradeInSerial {
+        TradeInSerial {
+            note: None,
+            ..self.to_serial()
+        }
+    }
+
+    /// The source recorded as the acquisition location of the items created from the trade-in
+    pub fn get_acquisition_location(&self) -> String {
+        format!("Trade-in {}", self.id)
+    }
+}
+
+impl ShopModel for TradeIn {
+    type Entity = TradeInEntity;
+    type Serial = TradeInSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        TradeInSerial {
+            id: self.id.clone(),
+            access_key: self.access_key.clone(),
+            customer_id: self.customer_id.clone(),
+            contact: self.contact.clone(),
+            inventory_location_id: self.inventory_location_id.clone(),
+            status: self.status.clone() as u8,
+            note: self.note.clone(),
+            lines: self.lines.iter().map(|line| line.to_serial()).collect(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    /// Trade-ins always begin as drafts with a new access key, and lines are added individually, so the access key,
+    /// status, and lines of the serial are ignored.
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        let contact: Option<String> = serial.contact.as_ref()
+            .map(|contact| contact.trim().to_string())
+            .filter(|contact| !contact.is_empty());
+        if serial.customer_id.is_none() && contact.is_none() {
+            return Err(ShopError::new("Trade-in requires a customer or a contact"));
+        }
+
+        Ok(TradeIn {
+            id: object::random_uuid(),
+            access_key: object::random_uuid(),
+            customer_id: serial.customer_id.clone(),
+            contact,
+            inventory_location_id: serial.inventory_location_id.clone(),
+            status: TradeInStatus::Draft,
+            note: serial.note.clone(),
+            lines: Vec::new(),
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        TradeInEntity {
+            id: self.id.clone(),
+            access_key: self.access_key.clone(),
+            customer_id: self.customer_id.clone(),
+            contact: self.contact.clone(),
+            inventory_location_id: self.inventory_location_id.clone(),
+            status: self.status.clone() as i32,
+            note: self.note.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(TradeIn {
+            id: entity.id.clone(),
+            access_key: entity.access_key.clone(),
+            customer_id: entity.customer_id.clone(),
+            contact: entity.contact.clone(),
+            inventory_location_id: entity.inventory_location_id.clone(),
+            status: TradeInStatus::try_from_repr(entity.status as u8)?,
+            note: entity.note.clone(),
+            lines: Vec::new(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, FromRepr, VariantArray, IntoStaticStr)]
+#[repr(u8)]
+pub enum TradeInStatus {
+    /// Lines are being listed by the customer or an admin
+    Draft = 0,
+    /// Awaiting quotes; lines can still be corrected
+    Submitted,
+    /// Every line is quoted, awaiting the customer's decision
+    Quoted,
+    /// Items were created for the accepted lines
+    Accepted,
+    /// Rejected by the customer or withdrawn before a decision
+    Rejected,
+}
+
+try_from_repr!(TradeInStatus<u8>);
+
+impl TradeInStatus {
+    pub fn get_successors(&self) -> &'static [TradeInStatus] {
+        match self {
+            TradeInStatus::Draft => &[TradeInStatus::Submitted, TradeInStatus::Rejected],
+            TradeInStatus::Submitted => &[TradeInStatus::Quoted, TradeInStatus::Rejected],
+            TradeInStatus::Quoted => &[TradeInStatus::Accepted, TradeInStatus::Rejected],
+            TradeInStatus::Accepted => &[],
+            TradeInStatus::Rejected => &[],
+        }
+    }
+
+    pub fn validate_transition(&self, next: &TradeInStatus) -> Result<(), ShopError> {
+        if self.get_successors().contains(next) {
+            Ok(())
+        } else {
+            Err(ShopError::new(&format!("Illegal trade-in status transition; [{} -> {}]", self, next)))
+        }
+    }
+
+    /// Lines can be added, removed, and photographed only until the trade-in is quoted.
+    pub fn allows_line_changes(&self) -> bool {
+        matches!(self, TradeInStatus::Draft | TradeInStatus::Submitted)
+    }
+}
+
+impl Display for TradeInStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", Into::<&'static str>::into(self), self.clone() as u8)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeInSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    /// Ignored on input
+    #[serde(default)]
+    pub access_key: Uuid,
+    pub customer_id: Option<Uuid>,
+    pub contact: Option<String>,
+    pub inventory_location_id: Uuid,
+    /// Ignored on input
+    #[serde(default)]
+    pub status: u8,
+    pub note: Option<String>,
+    /// Ignored on input
+    #[serde(default)]
+    pub lines: Vec<TradeInLineSerial>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for TradeInSerial {
+    type Model = TradeIn;
+}
+
+impl JsonHttpResponse for TradeInSerial {}
+impl JsonHttpResponse for Vec<TradeInSerial> {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TradeInSearchParameters {
+    pub status: Option<u8>,
+    pub customer_id: Option<Uuid>,
+}
+
+/// Submits, quotes, or rejects a trade-in; see [TradeInStatus::get_successors]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeInStatusChangeSerial {
+    pub status: u8,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct TradeInLineEntity {
+    pub id: Uuid,
+    pub trade_in_id: Uuid,
+    pub product_id: Uuid,
+    pub condition: i32,
+    pub description: Option<String>,
+    pub quoted_price_cents: Option<i64>,
+    pub accepted: Option<bool>,
+    pub item_id: Option<Uuid>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopEntity for TradeInLineEntity {
+    type Model = TradeInLine;
+}
+
+/// A single offered product of a trade-in
+#[derive(Debug, Clone)]
+pub struct TradeInLine {
+    pub id: Uuid,
+    pub trade_in_id: Uuid,
+    pub product_id: Uuid,
+    pub condition: ItemCondition,
+    /// As described by the customer, e.g. missing accessories
+    pub description: Option<String>,
+    /// What we offer to pay; becomes the acquisition price of the line's item
+    pub quoted_price_cents: Option<u32>,
+    /// None until the customer decides
+    pub accepted: Option<bool>,
+    /// The item created when the line was accepted
+    pub item_id: Option<Uuid>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopModel for TradeInLine {
+    type Entity = TradeInLineEntity;
+    type Serial = TradeInLineSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        TradeInLineSerial {
+            id: self.id.clone(),
+            trade_in_id: self.trade_in_id.clone(),
+            product_id: self.product_id.clone(),
+            condition: self.condition.clone() as u8,
+            description: self.description.clone(),
+            quoted_price_cents: self.quoted_price_cents.clone(),
+            accepted: self.accepted.clone(),
+            item_id: self.item_id.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    /// Lines are quoted and decided through their own requests, so the quote, decision, and item of the serial are
+    /// ignored.
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(TradeInLine {
+            id: object::random_uuid(),
+            trade_in_id: serial.trade_in_id.clone(),
+            product_id: serial.product_id.clone(),
+            condition: ItemCondition::try_from_repr(serial.condition)?,
+            description: serial.description.clone(),
+            quoted_price_cents: None,
+            accepted: None,
+            item_id: None,
+            created: serial.created.clone(),
+            updated: serial.updated.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        TradeInLineEntity {
+            id: self.id.clone(),
+            trade_in_id: self.trade_in_id.clone(),
+            product_id: self.product_id.clone(),
+            condition: self.condition.clone() as u8 as i32,
+            description: self.description.clone(),
+            quoted_price_cents: self.quoted_price_cents.map(|v| i64::from(v)),
+            accepted: self.accepted.clone(),
+            item_id: self.item_id.clone(),
+            created: self.created.clone(),
+            updated: self.updated.clone(),
+        }
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(TradeInLine {
+            id: entity.id.clone(),
+            trade_in_id: entity.trade_in_id.clone(),
+            product_id: entity.product_id.clone(),
+            condition: ItemCondition::try_from_repr(entity.condition as u8)?,
+            description: entity.description.clone(),
+            quoted_price_cents: entity.quoted_price_cents.map(|v| v as u32),
+            accepted: entity.accepted.clone(),
+            item_id: entity.item_id.clone(),
+            created: entity.created.clone(),
+            updated: entity.updated.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeInLineSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    #[serde(default)]
+    pub trade_in_id: Uuid,
+    pub product_id: Uuid,
+    pub condition: u8,
+    pub description: Option<String>,
+    /// Ignored on input
+    #[serde(default)]
+    pub quoted_price_cents: Option<u32>,
+    /// Ignored on input
+    #[serde(default)]
+    pub accepted: Option<bool>,
+    /// Ignored on input
+    #[serde(default)]
+    pub item_id: Option<Uuid>,
+    #[serde(default = "Utc::now")]
+    pub created: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub updated: DateTime<Utc>,
+}
+
+impl ShopSerial for TradeInLineSerial {
+    type Model = TradeInLine;
+}
+
+impl JsonHttpResponse for TradeInLineSerial {}
+impl JsonHttpResponse for Vec<TradeInLineSerial> {}
+
+/// Identifies the offered product of a new line by ID or, failing that, by UPC.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeInLineCreateSerial {
+    pub product_id: Option<Uuid>,
+    pub upc: Option<String>,
+    pub condition: u8,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeInLineQuoteSerial {
+    pub quoted_price_cents: u32,
+}
+
+/// The lines accepted by the customer; the remaining lines are declined. All lines are accepted if absent.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TradeInAcceptanceSerial {
+    pub trade_in_line_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeInLineImageEntity {
+    pub id: Uuid,
+    pub trade_in_line_id: Uuid,
+    pub alt_text: String,
+    pub priority: i32,
+    pub original_file_name: String,
+}
+
+impl ShopEntity for TradeInLineImageEntity {
+    type Model = TradeInLineImage;
+}
+
+/// Stored alongside item images; see [crate::item_image::ItemImage]
+pub type TradeInLineImage = TradeInLineImageEntity;
+
+impl ShopModel for TradeInLineImage {
+    type Entity = TradeInLineImageEntity;
+    type Serial = TradeInLineImageSerial;
+
+    fn to_serial(&self) -> Self::Serial {
+        TradeInLineImageSerial {
+            id: self.id.clone(),
+            trade_in_line_id: self.trade_in_line_id.clone(),
+            alt_text: self.alt_text.clone(),
+            priority: self.priority.clone(),
+            original_file_name: self.original_file_name.clone(),
+        }
+    }
+
+    fn try_from_serial(serial: &Self::Serial) -> Result<Self, ShopError> {
+        Ok(TradeInLineImageEntity {
+            id: object::random_uuid(),
+            trade_in_line_id: serial.trade_in_line_id.clone(),
+            alt_text: serial.alt_text.clone(),
+            priority: serial.priority.clone(),
+            original_file_name: serial.original_file_name.clone(),
+        })
+    }
+
+    fn to_entity(&self) -> Self::Entity {
+        self.clone()
+    }
+
+    fn try_from_entity(entity: &Self::Entity) -> Result<Self, ShopError> {
+        Ok(entity.clone())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeInLineImageSerial {
+    #[serde(default)]
+    pub id: Uuid,
+    pub trade_in_line_id: Uuid,
+    pub alt_text: String,
+    pub priority: i32,
+    pub original_file_name: String,
+}
+
+impl ShopSerial for TradeInLineImageSerial {
+    type Model = TradeInLineImage;
+}
+
+impl JsonHttpResponse for TradeInLineImageSerial {}
+impl JsonHttpResponse for Vec<TradeInLineImageSerial> {}
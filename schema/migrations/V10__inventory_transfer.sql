@@ -0,0 +1,12 @@
+create table if not exists shop.public.inventory_transfer
+(
+    id                         uuid primary key,
+    item_id                    uuid                     not null, -- no foreign key constraint in order to retain this record after potential item deletion
+    from_inventory_location_id uuid references shop.public.inventory_location (id) on delete restrict not null,
+    to_inventory_location_id   uuid references shop.public.inventory_location (id) on delete restrict not null,
+    initiated_by               text,                              -- the authenticated user, if known
+    reason                     text,
+    created                    timestamp with time zone not null
+);
+create index if not exists idx_inventory_transfer_item_id on shop.public.inventory_transfer (item_id);
+create index if not exists idx_inventory_transfer_created on shop.public.inventory_transfer (created);
@@ -0,0 +1,22 @@
+create table if not exists shop.public.stock_take
+(
+    id                    uuid primary key,
+    inventory_location_id uuid references shop.public.inventory_location (id) on delete restrict not null,
+    status                int                      not null,
+    initiated_by          text,                              -- the authenticated user, if known
+    note                  text,
+    created               timestamp with time zone not null,
+    closed                timestamp with time zone
+);
+create index if not exists idx_stock_take_created on shop.public.stock_take (created);
+-- at most one open stock-take per inventory location
+create unique index if not exists idx_stock_take_open_inventory_location_id on shop.public.stock_take (inventory_location_id) where status = 0;
+
+create table if not exists shop.public.stock_take_scan
+(
+    id            uuid primary key,
+    stock_take_id uuid references shop.public.stock_take (id) on delete cascade not null,
+    item_id       uuid references shop.public.item (id) on delete cascade     not null,
+    created       timestamp with time zone not null,
+    unique (stock_take_id, item_id)
+);
@@ -0,0 +1,16 @@
+create table if not exists shop.public.storage_slot
+(
+    id                    uuid primary key,
+    inventory_location_id uuid references shop.public.inventory_location (id) on delete restrict not null,
+    parent_id             uuid references shop.public.storage_slot (id) on delete restrict, -- null iff the slot is a room
+    kind                  int                      not null,                                -- corresponds to enum
+    display_name          text                     not null,
+    created               timestamp with time zone not null
+);
+create unique index if not exists idx_storage_slot_parent_id_display_name
+    on shop.public.storage_slot (inventory_location_id, parent_id, display_name) nulls not distinct;
+
+alter table if exists shop.public.item
+    add column if not exists storage_slot_id uuid references shop.public.storage_slot (id) on delete set null -- must belong to the item's inventory location
+;
+create index if not exists idx_item_storage_slot_id on shop.public.item (storage_slot_id);
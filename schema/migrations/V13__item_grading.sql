@@ -0,0 +1,19 @@
+create table if not exists shop.public.item_grading
+(
+    item_id            uuid primary key references shop.public.item (id) on delete cascade,
+    company            int                      not null, -- corresponds to enum
+    grade_tenths       int                      not null, -- e.g. 98 for CGC 9.8 or 850 for AFA 85
+    certificate_number text                     not null,
+    grade_date         date,
+    created            timestamp with time zone not null,
+    updated            timestamp with time zone not null
+);
+create unique index if not exists idx_item_grading_company_certificate_number on shop.public.item_grading (company, certificate_number);
+
+create table if not exists shop.public.item_grading_subgrade
+(
+    item_id      uuid references shop.public.item_grading (item_id) on delete cascade not null,
+    name         text                                                                not null, -- defined per grading company
+    grade_tenths int                                                                 not null,
+    constraint pk_item_grading_subgrade primary key (item_id, name)
+);
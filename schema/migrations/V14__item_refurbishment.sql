@@ -0,0 +1,20 @@
+create table if not exists shop.public.item_refurbishment
+(
+    id             uuid primary key,
+    item_id        uuid references shop.public.item (id) on delete cascade not null,
+    kind           int                      not null, -- corresponds to enum
+    performed_date date                     not null,
+    description    text                     not null,
+    cost_cents     bigint                   not null,
+    disclose       boolean                  not null default false, -- include in marketplace descriptions
+    created        timestamp with time zone not null,
+    updated        timestamp with time zone not null
+);
+create index if not exists idx_item_refurbishment_item_id on shop.public.item_refurbishment (item_id);
+
+create table if not exists shop.public.item_refurbishment_image
+(
+    item_refurbishment_id uuid references shop.public.item_refurbishment (id) on delete cascade not null,
+    item_image_id         uuid references shop.public.item_image (id) on delete cascade         not null, -- must belong to the same item
+    constraint pk_item_refurbishment_image primary key (item_refurbishment_id, item_image_id)
+);
@@ -0,0 +1,12 @@
+create table if not exists shop.public.price_history
+(
+    id              uuid primary key,
+    item_id         uuid                     not null, -- no foreign key constraint in order to retain this record after potential item deletion
+    old_price_cents bigint                   not null,
+    new_price_cents bigint                   not null,
+    initiated_by    text,                              -- the authenticated user, if known
+    reason          text,
+    created         timestamp with time zone not null
+);
+create index if not exists idx_price_history_item_id on shop.public.price_history (item_id);
+create index if not exists idx_price_history_created on shop.public.price_history (created);
@@ -0,0 +1,26 @@
+create table if not exists shop.public.repricing_rule
+(
+    id                   uuid primary key,
+    display_name         text unique              not null,
+    enabled              boolean                  not null,
+    priority             int                      not null, -- the highest priority matching rule applies to each item
+    category_id          uuid references shop.public.category (id) on delete cascade, -- the item's product must be directly associated
+    label_id             uuid references shop.public.label (id) on delete cascade,
+    condition            int,                               -- corresponds to enum
+    listed_days          int                      not null, -- days listed without a price change before each markdown
+    markdown_percent     int                      not null,
+    floor_markup_percent int,                               -- the price never falls below the item's cost basis plus this markup
+    created              timestamp with time zone not null,
+    updated              timestamp with time zone not null
+);
+
+-- The time the listing last became visible to buyers; null if it never has been
+alter table shop.public.listing
+    add column if not exists published timestamp with time zone;
+
+-- Active listings published before this column existed were last updated when published, at the latest
+update shop.public.listing
+set published = updated
+where published is null
+  and status in (1, 2) -- published or held
+;
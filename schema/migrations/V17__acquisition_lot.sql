@@ -0,0 +1,36 @@
+create table if not exists shop.public.supplier
+(
+    id           uuid primary key,
+    display_name text unique              not null,
+    contact      text,                    -- e.g. an email address or a marketplace username
+    note         text,
+    created      timestamp with time zone not null,
+    updated      timestamp with time zone not null
+);
+
+create table if not exists shop.public.acquisition_lot
+(
+    id                uuid primary key,
+    supplier_id       uuid references shop.public.supplier (id) on delete restrict not null,
+    display_name      text                     not null,
+    acquisition_date  date                     not null,
+    total_cost_cents  bigint                   not null,
+    fees_cents        bigint                   not null, -- e.g. shipping and buyer's premium; allocated with the total cost
+    allocation_method int                      not null, -- corresponds to enum
+    note              text,
+    created           timestamp with time zone not null,
+    updated           timestamp with time zone not null
+);
+create index if not exists idx_acquisition_lot_supplier_id on shop.public.acquisition_lot (supplier_id);
+
+-- Each item belongs to at most one lot. The lot's cost is allocated to item.acquisition_price_cents.
+create table if not exists shop.public.acquisition_lot_item
+(
+    item_id               uuid primary key references shop.public.item (id) on delete cascade,
+    acquisition_lot_id    uuid references shop.public.acquisition_lot (id) on delete cascade not null,
+    weight_grams          int,    -- required by weight allocation
+    estimated_value_cents bigint, -- required by estimated value allocation
+    created               timestamp with time zone not null,
+    updated               timestamp with time zone not null
+);
+create index if not exists idx_acquisition_lot_item_acquisition_lot_id on shop.public.acquisition_lot_item (acquisition_lot_id);
@@ -0,0 +1,4 @@
+-- Fees charged by the marketplace for the sale, e.g. final value and payment processing fees. Previously these were
+-- included in seller_cost_total_cents, which now holds only what we paid to fulfill the purchase, e.g. postage.
+alter table shop.public.purchase
+    add column if not exists marketplace_fee_cents bigint not null default 0;
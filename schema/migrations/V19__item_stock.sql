@@ -0,0 +1,19 @@
+-- A stock record is an item representing many identical units of one product and condition, e.g. sealed stock.
+-- Items without a stock record are unique units, as before.
+create table if not exists shop.public.item_stock
+(
+    item_id             uuid primary key references shop.public.item (id) on delete cascade,
+    quantity            int                      not null check (quantity >= 0), -- units available for sale
+    low_stock_threshold int check (low_stock_threshold >= 0),                    -- low when the quantity is at or below
+    created             timestamp with time zone not null,
+    updated             timestamp with time zone not null
+);
+
+-- Units sold by the purchase; greater than one only for stock records
+alter table shop.public.purchase
+    add column if not exists quantity int not null default 1 check (quantity > 0);
+
+-- A listing of a stock record is purchased repeatedly
+alter table shop.public.purchase
+    drop constraint if exists purchase_listing_id_key;
+create index if not exists idx_purchase_listing_id on shop.public.purchase (listing_id);
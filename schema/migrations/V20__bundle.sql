@@ -0,0 +1,40 @@
+-- A bundle groups items into one sellable unit, e.g. a figure lot, with its own title, price and images
+create table if not exists shop.public.bundle
+(
+    id           uuid primary key,
+    display_name text                     not null,
+    description  text,
+    condition    int                      not null, -- corresponds to enum; the condition of the lot as a whole
+    price_cents  bigint                   not null,
+    note         text,
+    created      timestamp with time zone not null,
+    updated      timestamp with time zone not null
+);
+
+-- Each item belongs to at most one bundle. An item must be removed from its bundle before it can be deleted.
+create table if not exists shop.public.bundle_item
+(
+    item_id   uuid primary key references shop.public.item (id) on delete restrict,
+    bundle_id uuid references shop.public.bundle (id) on delete cascade not null,
+    created   timestamp with time zone not null
+);
+create index if not exists idx_bundle_item_bundle_id on shop.public.bundle_item (bundle_id);
+
+create table if not exists shop.public.bundle_image
+(
+    id                 uuid primary key,
+    bundle_id          uuid references shop.public.bundle (id) on delete cascade not null,
+    alt_text           text not null,
+    priority           int  not null,
+    original_file_name text not null
+);
+create index if not exists idx_bundle_image_bundle_id on shop.public.bundle_image (bundle_id);
+
+-- A listing is of exactly one item or exactly one bundle
+alter table if exists shop.public.listing
+    alter column item_id drop not null,
+    add column if not exists bundle_id uuid references shop.public.bundle (id),
+    add constraint listing_item_or_bundle check ((item_id is null) <> (bundle_id is null)),
+    add constraint bundle_marketplace_unique unique (bundle_id, marketplace_id)
+;
+create index if not exists idx_listing_bundle_id on shop.public.listing (bundle_id);
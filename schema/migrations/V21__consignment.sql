@@ -0,0 +1,42 @@
+-- A person or business whose items are sold on their behalf in exchange for a commission
+create table if not exists shop.public.consignor
+(
+    id           uuid primary key,
+    display_name text unique              not null,
+    contact      text,                    -- e.g. an email address or a phone number
+    note         text,
+    created      timestamp with time zone not null,
+    updated      timestamp with time zone not null
+);
+
+alter table if exists shop.public.item
+    add column if not exists consignor_id             uuid references shop.public.consignor (id) on delete restrict,
+    add column if not exists commission_basis_points  int,    -- share of the sale price kept as commission; 10000 is the whole
+    add column if not exists commission_minimum_cents bigint, -- least commission kept on a sale
+    add constraint item_consignment_terms check (
+        (consignor_id is null) = (commission_basis_points is null)
+        and (consignor_id is not null or commission_minimum_cents is null)
+        and commission_basis_points between 0 and 10000
+    )
+;
+create index if not exists idx_item_consignor_id on shop.public.item (consignor_id);
+
+-- What is owed to the consignor for a consigned item sold in a fulfilled purchase, fixed at the time of the sale
+create table if not exists shop.public.consignment_payout
+(
+    id                uuid primary key,
+    consignor_id      uuid references shop.public.consignor (id) on delete restrict not null,
+    item_id           uuid references shop.public.item (id) on delete restrict      not null,
+    purchase_id       uuid references shop.public.purchase (id) on delete restrict  not null,
+    sale_price_cents  bigint                   not null, -- purchase subtotal less discounts
+    fee_cents         bigint                   not null, -- marketplace fees
+    commission_cents  bigint                   not null,
+    payout_cents      bigint                   not null, -- never negative
+    paid              timestamp with time zone,          -- null while unpaid
+    payment_reference text,                              -- e.g. a check number
+    created           timestamp with time zone not null,
+    updated           timestamp with time zone not null,
+    constraint consignment_payout_purchase_item_unique unique (purchase_id, item_id)
+);
+create index if not exists idx_consignment_payout_consignor_id on shop.public.consignment_payout (consignor_id);
+create index if not exists idx_consignment_payout_item_id on shop.public.consignment_payout (item_id);
@@ -0,0 +1,42 @@
+-- An offer by a customer to sell us products, e.g. a collection brought to the shop, which is quoted per line
+create table if not exists shop.public.trade_in
+(
+    id                    uuid primary key,
+    customer_id           uuid references shop.public.customer (id) on delete restrict,
+    contact               text, -- e.g. an email address or a phone number of a walk-in customer
+    -- where the items of accepted lines are received
+    inventory_location_id uuid references shop.public.inventory_location (id) on delete restrict not null,
+    status                int                      not null, -- corresponds to enum
+    note                  text,
+    -- shared with the customer in place of an account, granting access to the trade-in through the public API
+    access_key            uuid unique              not null default gen_random_uuid(),
+    created               timestamp with time zone not null,
+    updated               timestamp with time zone not null,
+    constraint trade_in_customer_or_contact check (customer_id is not null or contact is not null)
+);
+create index if not exists idx_trade_in_customer_id on shop.public.trade_in (customer_id);
+
+create table if not exists shop.public.trade_in_line
+(
+    id                 uuid primary key,
+    trade_in_id        uuid references shop.public.trade_in (id) on delete cascade not null,
+    product_id         uuid references shop.public.product (id) on delete restrict not null,
+    condition          int                      not null, -- corresponds to enum
+    description        text,                              -- as described by the customer
+    quoted_price_cents bigint,                            -- null until quoted
+    accepted           boolean,                           -- null until the customer decides
+    item_id            uuid references shop.public.item (id) on delete set null, -- created when the line is accepted
+    created            timestamp with time zone not null,
+    updated            timestamp with time zone not null
+);
+create index if not exists idx_trade_in_line_trade_in_id on shop.public.trade_in_line (trade_in_id);
+
+create table if not exists shop.public.trade_in_line_image
+(
+    id                 uuid primary key,
+    trade_in_line_id   uuid references shop.public.trade_in_line (id) on delete cascade not null,
+    alt_text           text not null,
+    priority           int  not null,
+    original_file_name text not null
+);
+create index if not exists idx_trade_in_line_image_trade_in_line_id on shop.public.trade_in_line_image (trade_in_line_id);
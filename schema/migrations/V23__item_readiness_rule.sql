@@ -0,0 +1,24 @@
+-- A check an item must pass before it is complete, e.g. "at least 3 images" or "figures need a Year attribute"
+create table if not exists shop.public.item_readiness_rule
+(
+    id            uuid primary key,
+    display_name  text unique              not null,
+    enabled       boolean                  not null,
+    kind          int                      not null, -- corresponds to enum
+    blocking      boolean                  not null, -- failures block completion; others only lower the score
+    category_id   uuid references shop.public.category (id) on delete cascade, -- the item's product must be directly associated
+    minimum_count int,                               -- required by the image count kind
+    attribute_key text,                              -- required by the attribute kind
+    created       timestamp with time zone not null,
+    updated       timestamp with time zone not null,
+    constraint item_readiness_rule_minimum_count check (minimum_count is null or minimum_count > 0)
+);
+
+insert into shop.public.item_readiness_rule
+(id, display_name, enabled, kind, blocking, category_id, minimum_count, attribute_key, created, updated)
+values (gen_random_uuid(), 'At least one image', true, 0, true, null, 1, null, now(), now()),
+       (gen_random_uuid(), 'Price set', true, 2, true, null, null, null, now(), now()),
+       (gen_random_uuid(), 'Product has a UPC or an eBay category', true, 3, true, null, null, null, now(), now()),
+       (gen_random_uuid(), 'Images have alt text', true, 4, false, null, null, null, now(), now())
+on conflict do nothing
+;
@@ -0,0 +1,27 @@
+-- An attribute definition which items of products directly associated with the category should follow,
+-- e.g. "Year" is a number for action figures
+create table if not exists shop.public.category_attribute
+(
+    id             uuid primary key,
+    category_id    uuid references shop.public.category (id) on delete cascade not null,
+    key            text                     not null,
+    kind           int                      not null, -- corresponds to enum
+    allowed_values text[]                   not null, -- required by the enum kind; empty otherwise
+    unit           text,
+    required       boolean                  not null, -- missing values block completion
+    created        timestamp with time zone not null,
+    updated        timestamp with time zone not null
+);
+
+-- Keys are matched case-insensitively
+create unique index if not exists category_attribute_category_id_key on shop.public.category_attribute (category_id, lower(key));
+
+-- Typed values of attributes matching a definition; null for free-form attributes
+alter table shop.public.item_attribute
+    add column if not exists value_number  double precision,
+    add column if not exists value_date    date,
+    add column if not exists value_boolean boolean
+;
+
+create index if not exists item_attribute_key_value_number on shop.public.item_attribute (lower(key), value_number) where value_number is not null;
+create index if not exists item_attribute_key_value_date on shop.public.item_attribute (lower(key), value_date) where value_date is not null;
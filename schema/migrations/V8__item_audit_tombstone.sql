@@ -0,0 +1,3 @@
+alter table if exists shop.public.item_audit
+    add column if not exists deleted bool not null default false -- true iff this row records the deletion of its item
+;
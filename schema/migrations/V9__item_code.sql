@@ -0,0 +1,46 @@
+create sequence if not exists shop.public.item_code_sequence;
+
+alter table if exists shop.public.item
+    add column if not exists code text -- short, check-digited identifier; see inventory::item::create_item_code
+;
+
+-- Backfill existing items in creation order using the same encoding as inventory::item::create_item_code
+do
+$$
+    declare
+        alphabet  constant text := '0123456789ABCDEFGHJKMNPQRSTVWXYZ';
+        checksums constant text := '0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U';
+        row       record;
+        number    bigint;
+        remainder bigint;
+        body      text;
+        prefix    text;
+    begin
+        for row in
+            select item.id, inventory_location.internal_name
+            from shop.public.item
+            inner join shop.public.inventory_location on item.inventory_location_id = inventory_location.id
+            where item.code is null
+            order by item.created, item.id
+            loop
+                number := nextval('shop.public.item_code_sequence');
+                remainder := number;
+                body := '';
+                while remainder > 0
+                    loop
+                        body := substr(alphabet, (remainder % 32)::int + 1, 1) || body;
+                        remainder := remainder / 32;
+                    end loop;
+                body := lpad(body, 4, '0');
+                prefix := coalesce(nullif(upper(left(regexp_replace(row.internal_name, '[^A-Za-z]', '', 'g'), 3)), ''), 'X');
+                update shop.public.item
+                set code = prefix || '-' || body || substr(checksums, (number % 37)::int + 1, 1)
+                where id = row.id;
+            end loop;
+    end
+$$;
+
+alter table if exists shop.public.item
+    alter column code set not null
+;
+create unique index if not exists idx_item_code on shop.public.item (code);
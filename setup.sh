@@ -24,6 +24,8 @@ function sqlx_setup {
 }
 sqlx_setup
 
+# The repricing scheduler (REPRICING_SCHEDULER_ENABLED in .env) decrypts the "ebay__zach.sandbox.refresh_token" secret
+# at runtime rather than during setup; it must be added to ./crypt/src/data.rs for eBay listings to be repriced
 function generate_env_from_template {
 	echo "Generating .env"
 	local postgres__user_shop_password_local_key="postgres__user.shop.password.local"